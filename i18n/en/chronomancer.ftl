@@ -6,14 +6,143 @@ welcome = Welcome to COSMIC! ✨
 page-id = Page { $num }
 git-description = Git commit {$hash} on {$date}
 seconds = Seconds
+percent = Percent
 minutes = Minutes
 hours = Hours
 days = Days
 operation-suspend = Suspend
+operation-hibernate = Hibernate
 operation-shutdown = Shutdown
 operation-reboot = Reboot
 operation-restart = Restart
 operation-logout = Logout
+operation-notify = Notification
 set-time-label = Set { $operation } Time
 unit-label = Unit
 set-button-label = Set
+condition-at-time = When the timer ends
+condition-on-ac = When the timer ends, on AC power only
+condition-battery-below = When battery drops below
+condition-system-quiet = When CPU, disk and network are quiet for
+battery-percent-label = Battery percentage
+condition-file-appears = When a file appears
+condition-file-disappears = When a file disappears
+condition-file-stable = When a file stops changing for
+condition-media-ends = When the current track or episode ends
+watch-path-label = File or folder path
+stay-awake-media = Stay awake while media is playing
+active-timers = Active timers
+timer-remaining = in { $time }
+timer-remaining-on-ac = in { $time }, on AC power only
+timer-battery-below = when battery drops below { $percent }%
+timer-system-quiet = after { $time } of quiet
+timer-file-appears = when { $path } appears
+timer-file-disappears = when { $path } disappears
+timer-file-stable = when { $path } is unchanged for { $time }
+timer-media-ends = when the current track ends
+timer-chain-next = then { $names }
+timer-export = Export
+systemd-timers = Run by systemd
+systemd-timer-unit = { $unit }.timer
+page-power = Power
+page-sequences = Sequences
+sequence-pomodoro = Pomodoro
+sequence-work = Work
+sequence-break = Break
+sequence-long-break = Long break
+sequence-running = Running sequences
+sequence-new = New sequence
+sequence-name = Sequence name
+sequence-default-name = Sequence
+sequence-phase-name = Phase name
+sequence-phase-default = Phase { $number }
+sequence-phase-round = { $phase }, round { $round } of { $rounds }
+sequence-final = After last round
+sequence-add-phase = Add phase
+sequence-rounds = Rounds
+sequence-start = Start
+page-chains = Chains
+chain-new = New chain
+chain-hint = Each step runs its minutes after the step before it
+chain-save-work = Save your work
+chain-note = Notification text
+chain-step-default = Step { $number }
+chain-add-step = Add step
+chain-start = Start chain
+page-stopwatch = Stopwatch
+stopwatch-start = Start
+stopwatch-stop = Stop
+stopwatch-lap = Lap
+stopwatch-reset = Reset
+stopwatch-lap-number = Lap { $number }
+page-settings = Settings
+quick-timers = Quick timers
+preset-new = New quick timer
+preset-edit = Edit quick timer
+preset-label = Button label
+preset-description = Notification text
+preset-save = Save
+preset-cancel = Cancel
+preset-default-tea = 15 min tea
+preset-default-tea-ready = Your tea is ready
+preset-default-shutdown = Shutdown in 1h
+settings-general = General
+settings-default-unit = Default time unit
+settings-grace-period = Grace period before power actions
+settings-confirm = Ask before running power actions
+settings-stay-awake-battery = Stop staying awake below battery percentage (0 never stops)
+settings-notification-timeout = Notification timeout
+settings-persistent-notifications = Keep notifications until dismissed
+settings-panel-countdown = Countdown in the panel
+countdown-off = Off
+countdown-beside-icon = Beside the icon
+countdown-instead-of-icon = Instead of the icon
+settings-progress-icon = Show timer progress on the panel icon
+settings-missed-timers = Timers missed while not running
+missed-discard = Discard
+missed-notify = Discard and notify
+missed-run = Run them
+settings-log-level = Log level (applies after a restart)
+log-error = Errors only
+log-warn = Warnings
+log-info = Info
+log-debug = Debug
+log-trace = Everything
+pending-action = { $operation } in { $time }
+pending-confirm = Run { $operation }?
+pending-cancel = Cancel
+pending-run-now = Run now
+error-dismiss = Dismiss
+error-database = Couldn't open the timer database
+error-create-timer = Couldn't start the timer
+error-load = Couldn't load your timers
+error-save = Couldn't save your changes
+error-export = Couldn't move the timer to systemd
+error-stay-awake = Couldn't keep the system awake
+error-power-action = Couldn't run { $operation }
+error-permission-denied = The system didn't allow it. Check who may do this in your system settings, or ask an administrator.
+page-timers = Timers
+page-reminders = Reminders
+page-history = History
+timers-active = Active
+timers-none = No timers running
+reminder-new = New reminder
+reminder-text = Remind me to…
+reminder-create = Remind me
+reminder-pending = Upcoming reminders
+reminder-none = No reminders set
+history-title = Recent timers
+history-clear = Clear
+history-empty = Nothing here yet
+outcome-finished = Finished
+outcome-cancelled = Cancelled
+outcome-skipped = Skipped on battery
+outcome-missed = Missed
+quick-entry-title = New timer
+quick-entry-placeholder = 25m standup
+quick-entry-hint = Type a duration or a time and what to do, e.g. 25m standup or 23:00 shutdown
+quick-entry-preview = { $name } in { $time }
+quick-entry-preview-at = { $name } at { $time }
+quick-entry-default = Timer finished
+timer-paused = Paused, { $time } left
+timer-repeating = in { $time }, then every { $period }
@@ -0,0 +1,2 @@
+-- Remove timer conditions
+ALTER TABLE timers DROP COLUMN condition;
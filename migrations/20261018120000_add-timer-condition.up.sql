@@ -0,0 +1,2 @@
+-- Conditions gate or trigger a timer beyond its ends_at deadline (see models::condition)
+ALTER TABLE timers ADD COLUMN condition TEXT NOT NULL DEFAULT '';
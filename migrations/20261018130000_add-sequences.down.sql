@@ -0,0 +1,4 @@
+-- Remove sequence timers
+DROP INDEX IF EXISTS sequence_phases_sequence_id_idx;
+DROP TABLE IF EXISTS sequence_phases;
+DROP TABLE IF EXISTS sequences;
@@ -0,0 +1,22 @@
+-- Sequence timers: an ordered list of phases that advance automatically (e.g. pomodoro)
+CREATE TABLE sequences (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL DEFAULT '',
+    rounds INTEGER NOT NULL DEFAULT 1,
+    current_step INTEGER NOT NULL DEFAULT 0,
+    step_ends_at INTEGER NOT NULL DEFAULT 0,
+    paused_at INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL
+);
+
+-- Phases repeat once per round, final phases only run once after the last round
+CREATE TABLE sequence_phases (
+    id INTEGER PRIMARY KEY,
+    sequence_id INTEGER NOT NULL REFERENCES sequences (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    is_final BOOLEAN NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS sequence_phases_sequence_id_idx ON sequence_phases (sequence_id, position);
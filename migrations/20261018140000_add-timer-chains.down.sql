@@ -0,0 +1,5 @@
+-- Remove timer chains, dropping links that were still waiting on a parent
+DELETE FROM timers WHERE parent_id != 0;
+DROP INDEX IF EXISTS timers_parent_id_idx;
+ALTER TABLE timers DROP COLUMN delay_seconds;
+ALTER TABLE timers DROP COLUMN parent_id;
@@ -0,0 +1,5 @@
+-- Chained timers: a link waits on its parent (0 = none) and runs delay_seconds after it finishes
+ALTER TABLE timers ADD COLUMN parent_id INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE timers ADD COLUMN delay_seconds INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS timers_parent_id_idx ON timers (parent_id);
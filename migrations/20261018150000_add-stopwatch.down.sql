@@ -0,0 +1,4 @@
+-- Remove the stopwatch
+DROP INDEX IF EXISTS stopwatch_laps_stopwatch_id_idx;
+DROP TABLE IF EXISTS stopwatch_laps;
+DROP TABLE IF EXISTS stopwatches;
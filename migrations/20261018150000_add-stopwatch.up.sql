@@ -0,0 +1,17 @@
+-- Stopwatch: elapsed time is kept as the milliseconds banked before started_at (0 while stopped)
+CREATE TABLE stopwatches (
+    id INTEGER PRIMARY KEY,
+    started_at INTEGER NOT NULL DEFAULT 0,
+    elapsed_ms INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL
+);
+
+-- Laps store the total elapsed time when they were taken
+CREATE TABLE stopwatch_laps (
+    id INTEGER PRIMARY KEY,
+    stopwatch_id INTEGER NOT NULL REFERENCES stopwatches (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    elapsed_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS stopwatch_laps_stopwatch_id_idx ON stopwatch_laps (stopwatch_id, position);
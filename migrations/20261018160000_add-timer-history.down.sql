@@ -0,0 +1,3 @@
+-- Remove the timer history
+DROP INDEX IF EXISTS timer_history_finished_at_idx;
+DROP TABLE IF EXISTS timer_history;
@@ -0,0 +1,9 @@
+-- History of timers that finished, were cancelled, skipped or missed; newest entries are shown first
+CREATE TABLE timer_history (
+    id INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    finished_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS timer_history_finished_at_idx ON timer_history (finished_at);
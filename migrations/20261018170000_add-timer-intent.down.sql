@@ -0,0 +1,2 @@
+-- Remove timer intents, wall clock timers become plain countdowns to their last deadline
+ALTER TABLE timers DROP COLUMN intent;
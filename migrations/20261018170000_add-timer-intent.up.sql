@@ -0,0 +1,2 @@
+-- Whether a timer counts down or ends at a wall clock time in a time zone (see models::intent)
+ALTER TABLE timers ADD COLUMN intent TEXT NOT NULL DEFAULT '';
@@ -1,33 +1,80 @@
 // SPDX-License-Identifier: MIT
 
+use chrono::NaiveTime;
 use cosmic::{
     Action, Application, Core, Element, Task, applet,
     cosmic_config::{self, CosmicConfigEntry},
     cosmic_theme::Spacing,
     iced::{
-        Alignment, Length, Limits, Subscription, platform_specific::shell::commands::popup,
-        stream::channel, widget::column, window,
+        Alignment, Length, Limits, Subscription, keyboard,
+        platform_specific::shell::commands::popup,
+        stream::channel,
+        widget::{column, focus_next, focus_previous, row},
+        window,
     },
     iced_runtime::Appearance,
     theme,
-    widget::text,
+    widget::{
+        button, icon,
+        menu::{action::MenuAction as _, key_bind::KeyBind},
+        text,
+    },
+};
+use futures_util::{SinkExt, StreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    str::FromStr,
+    sync::Arc,
 };
-use futures_util::SinkExt;
-use notify_rust::{Hint, Notification};
-use std::{fs::File, str::FromStr, sync::Arc};
 
 use crate::{
-    app_messages::{AppMessage as Message, DatabaseMessage, PowerMessage, TimerMessage},
-    config::Config,
-    models::{Timer, timer::TimerType},
-    pages::{PowerControls, power_controls},
+    app_messages::{
+        AppMessage as Message, DatabaseMessage, HistoryMessage, PowerMessage, SequenceMessage,
+        ServiceMessage, StopwatchMessage, TimerMessage,
+    },
+    components::{panel_button, quick_timers, timer_list},
+    config::{Config, MissedTimerPolicy, PanelCountdown, QuickTimerPreset, Setting},
+    error::AppError,
+    fl,
+    key_binds::{self, MenuAction},
+    models::{
+        HistoryEntry, Sequence, Stopwatch, Timer,
+        condition::{FileEvent, MediaTrack, TimerCondition},
+        history::{HISTORY_LIMIT, Outcome},
+        timer::TimerType,
+    },
+    pages::{
+        Chains, History, PopupPage, PowerControls, QuickEntry, Reminders, Sequences, Settings,
+        TimerPage, chains, power_controls, quick_entry, reminders, sequences, settings, stopwatch,
+    },
+    runner::{self, Effect, Notice, TimerRunner},
+    service::{self, PendingAction, Request, Role, ServiceEvent, Signal, Update},
     utils::{
+        activation,
+        clock::{self, ClockChange, ClockWatch, SharedClock},
         database::{Repository, SQLiteDatabase},
-        format_duration, resources,
+        file_watch, format_duration,
+        mpris::{self, PlayerState},
+        progress_ring::{self, Glyph},
+        resources,
+        scheduler::{self, Deadlines},
+        system_load::{self, ActivitySample},
+        systemd,
+        time::format_countdown,
+        time_zone,
+        ui::{Gaps, Padding},
+        upower,
     },
 };
 
 const APP_ID: &str = "io.vulpapps.Chronomancer";
+/// How often `/proc` is sampled while a "system quiet" trigger is waiting
+const ACTIVITY_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+/// How often MPRIS players are polled while their playback state is needed
+const MEDIA_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often countdowns are redrawn while they are on screen
+const COUNTDOWN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 // const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
 // const APP_ICON: &[u8] = include_bytes!("../resources/icons/hicolor/scalable/apps/hourglass.svg");
 
@@ -37,24 +84,76 @@ const APP_ID: &str = "io.vulpapps.Chronomancer";
 pub struct AppModel {
     /// Application state which is managed by the COSMIC runtime.
     core: Core,
-    /// Key bindings for the application
-    // will look into this for more keyboard friendly UX as most cosmic apps are very lacking
-    // key_binds: HashMap<menu::KeyBind, ()>,
+    /// Key bindings for the popup, see [`key_binds`]
+    key_binds: HashMap<KeyBind, MenuAction>,
     /// The icon button displayed in the system tray.
     icon_name: String,
     // Configuration data that persists between application runs.
     config: Config,
+    /// Handle used to write configuration changes, `None` if the config store is unavailable
+    config_handler: Option<cosmic_config::Config>,
     /// Popup window
     popup: Option<window::Id>,
     /// Database connection
     // clone when passing to async tasks to add to the pool's reference count
     database: Option<SQLiteDatabase>,
+    /// Connection of the D-Bus timer service, `None` until it is published
+    service: Option<zbus::Connection>,
+    /// Whether the applet runs the timers, `false` while `chronomancerd` owns the timer service
+    scheduling: bool,
+    /// Where the current time comes from, a manual clock in tests
+    clock: SharedClock,
     /// Suspend inhibitor file descriptor. Keep this alive to prevent system sleep.
     suspend_inhibitor: Option<File>,
-    /// Active timers
-    active_timers: Vec<Timer>,
+    /// Whether `chronomancerd` holds stay-awake, while it runs the timers
+    daemon_stay_awake: bool,
+    /// Suspend inhibitor held only while media is playing, separate from stay-awake
+    media_inhibitor: Option<File>,
+    /// Whether "stay awake while media plays" is turned on
+    media_stay_awake: bool,
+    /// Whether a media inhibitor request is in flight, so playback updates don't stack requests
+    media_inhibit_pending: bool,
+    /// Running timers, what their triggers wait on and the pending power action.
+    /// While `chronomancerd` runs the timers, only its pending action is mirrored here.
+    runner: TimerRunner,
+    /// Chain links waiting for the timer before them to finish
+    chained_timers: Vec<Timer>,
+    /// The user's systemd timers by unit name, listed read-only while the popup is open
+    systemd_timers: Vec<(String, Timer)>,
+    /// Timers that left the active list but may still be in the database, so reloads skip them
+    removing_timers: HashSet<i64>,
+    /// Counts writes to the timers the applet saw land, so reloads started before one are dropped
+    timers_version: u64,
+    /// Running and paused sequence timers
+    sequences: Vec<Sequence>,
+    /// The stopwatch, `id` 0 until it is first started and stored
+    stopwatch: Stopwatch,
+    /// Whether the stopwatch is being stored for the first time, so it has no `id` yet
+    stopwatch_inserting: bool,
+    /// Timers that are no longer running, newest first
+    history: Vec<HistoryEntry>,
+    /// Page currently shown in the popup
+    page: PopupPage,
+    /// Id of the timer or sequence focused with the arrow keys on the current page
+    focused_item: Option<i64>,
     /// Power control component
     power_controls: PowerControls,
+    /// Sequence timers page
+    sequences_page: Sequences,
+    /// Chained timers page
+    chains_page: Chains,
+    /// Stopwatch page
+    stopwatch_page: stopwatch::Page,
+    /// Reminders page
+    reminders_page: Reminders,
+    /// History page
+    history_page: History,
+    /// Settings page
+    settings_page: Settings,
+    /// Quick entry prompt, shown instead of the pages while it is open
+    quick_entry: Option<QuickEntry>,
+    /// Last failure, shown as a banner until dismissed: what failed and why
+    error: Option<(String, AppError)>,
 }
 
 /// Create a COSMIC application from the app model
@@ -82,34 +181,69 @@ impl Application for AppModel {
     /// We initialize the app model with default state, load configuration, and start the database connection here.
     /// It's also where keybinds will go if/when implemented.
     fn init(core: cosmic::Core, _flags: Self::Flags) -> (Self, Task<cosmic::Action<Message>>) {
+        // Optional configuration file for an application.
+        let config_handler = cosmic_config::Config::new(Self::APP_ID, Config::VERSION).ok();
+        let config = config_handler
+            .as_ref()
+            .map(|context| match Config::get_entry(context) {
+                Ok(config) => config,
+                Err((errors, config)) => {
+                    for why in errors {
+                        tracing::error!(%why, "error loading app config");
+                    }
+
+                    config
+                }
+            })
+            .unwrap_or_default();
+
+        let mut power_controls = PowerControls::default();
+        power_controls
+            .power_form
+            .set_default_unit(config.default_unit);
+
         let app = AppModel {
             core,
-            // key_binds: HashMap::new(),
+            key_binds: key_binds::key_binds(),
             icon_name: "io.vulpapps.Chronomancer".to_string(),
-            // Optional configuration file for an application.
-            config: cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
-                .map(|context| match Config::get_entry(&context) {
-                    Ok(config) => config,
-                    Err((_errors, config)) => {
-                        // for why in errors {
-                        //     tracing::error!(%why, "error loading app config");
-                        // }
-
-                        config
-                    }
-                })
-                .unwrap_or_default(),
+            config,
+            config_handler,
             popup: None,
+            service: None,
+            scheduling: true,
+            clock: clock::system(),
             database: None,
             suspend_inhibitor: None,
-            active_timers: vec![],
-            power_controls: PowerControls::default(),
+            daemon_stay_awake: false,
+            media_inhibitor: None,
+            media_stay_awake: false,
+            media_inhibit_pending: false,
+            runner: TimerRunner::default(),
+            chained_timers: vec![],
+            systemd_timers: vec![],
+            removing_timers: HashSet::new(),
+            timers_version: 0,
+            sequences: vec![],
+            stopwatch: Stopwatch::default(),
+            stopwatch_inserting: false,
+            history: vec![],
+            page: config.last_page,
+            focused_item: None,
+            power_controls,
+            sequences_page: Sequences::default(),
+            chains_page: Chains::default(),
+            stopwatch_page: stopwatch::Page,
+            reminders_page: Reminders::default(),
+            history_page: History,
+            settings_page: Settings::default(),
+            quick_entry: None,
+            error: None,
         };
 
         (
             app,
             Task::perform(
-                async move { SQLiteDatabase::new().await.map_err(|e| e.to_string()) },
+                async move { SQLiteDatabase::new().await.map_err(AppError::from) },
                 |result| match result {
                     Ok(db) => Action::App(Message::DatabaseMessage(DatabaseMessage::Initialized(
                         Ok(db),
@@ -124,7 +258,8 @@ impl Application for AppModel {
 
     /// Define the view window for the application.
     ///
-    /// This method constructs the popup window content when it is open.
+    /// This method constructs the popup window content when it is open: the page
+    /// tabs followed by the page that is currently selected.
     ///
     /// # Arguments
     ///
@@ -135,13 +270,118 @@ impl Application for AppModel {
     /// An `Element` representing the window content.
     fn view_window(&self, id: window::Id) -> Element<'_, Message> {
         if matches!(self.popup, Some(p) if p == id) {
-            let Spacing { space_m, .. } = theme::active().cosmic().spacing;
+            let Spacing {
+                space_xs, space_m, ..
+            } = theme::active().cosmic().spacing;
+            let now = self.clock.now();
+
+            if let Some(quick_entry) = &self.quick_entry {
+                let content =
+                    column![quick_entry.view().map(Message::QuickEntryMessage)].width(Length::Fill);
+                return self
+                    .core
+                    .applet
+                    .popup_container(content)
+                    .max_width(800.)
+                    .into();
+            }
 
-            let power = self
-                .power_controls
-                .view()
-                .map(Message::PowerControlsMessage);
-            let content = column![power]
+            let tabs = row![
+                self.page_tab(fl!("page-power"), PopupPage::PowerControls),
+                self.page_tab(fl!("page-timers"), PopupPage::Timers(TimerPage::Active)),
+                self.page_tab(fl!("page-reminders"), PopupPage::Reminders),
+                self.page_tab(fl!("page-history"), PopupPage::History),
+                self.page_tab(fl!("page-settings"), PopupPage::Settings),
+            ]
+            .spacing(space_xs)
+            .padding(Padding::horizontal(24));
+
+            let mut content = column![tabs];
+            if let PopupPage::Timers(_) = self.page {
+                content = content.push(
+                    row![
+                        self.timer_page_tab(fl!("timers-active"), TimerPage::Active),
+                        self.timer_page_tab(fl!("page-sequences"), TimerPage::Sequences),
+                        self.timer_page_tab(fl!("page-chains"), TimerPage::Chains),
+                        self.timer_page_tab(fl!("page-stopwatch"), TimerPage::Stopwatch),
+                    ]
+                    .spacing(space_xs)
+                    .padding(Padding::horizontal(24)),
+                );
+            }
+            if let Some((summary, error)) = &self.error {
+                content = content.push(Self::error_banner(summary, error));
+            }
+            if let Some(pending) = &self.runner.pending_action {
+                content = content.push(Self::pending_action_banner(pending, now));
+            }
+            match self.page {
+                PopupPage::PowerControls => {
+                    if !self.config.quick_timers.is_empty() {
+                        content = content.push(quick_timers::quick_timers(
+                            &self.config.quick_timers,
+                            Message::QuickTimer,
+                        ));
+                    }
+                    content = content.push(
+                        self.power_controls
+                            .view()
+                            .map(Message::PowerControlsMessage),
+                    );
+                    if self.lists_timers() {
+                        content = content.push(self.active_timer_list(now));
+                    }
+                }
+                PopupPage::Timers(TimerPage::Active) => {
+                    content = content.push(if self.lists_timers() {
+                        self.active_timer_list(now)
+                    } else {
+                        text::caption(fl!("timers-none")).into()
+                    });
+                }
+                PopupPage::Timers(TimerPage::Sequences) => {
+                    content = content.push(
+                        self.sequences_page
+                            .view(&self.sequences, now, self.focused_item)
+                            .map(Message::SequencesMessage),
+                    );
+                }
+                PopupPage::Timers(TimerPage::Chains) => {
+                    content = content.push(self.chains_page.view().map(Message::ChainsMessage));
+                    if self.lists_timers() {
+                        content = content.push(self.active_timer_list(now));
+                    }
+                }
+                PopupPage::Timers(TimerPage::Stopwatch) => {
+                    content = content.push(
+                        self.stopwatch_page
+                            .view(&self.stopwatch, self.clock.now_millis())
+                            .map(|msg| Message::StopwatchMessage(msg.into())),
+                    );
+                }
+                PopupPage::Reminders => {
+                    content = content.push(
+                        self.reminders_page
+                            .view(&self.runner.active_timers, now, self.focused_item)
+                            .map(Message::RemindersMessage),
+                    );
+                }
+                PopupPage::History => {
+                    content = content.push(
+                        self.history_page
+                            .view(&self.history)
+                            .map(|msg| Message::HistoryMessage(msg.into())),
+                    );
+                }
+                PopupPage::Settings => {
+                    content = content.push(
+                        self.settings_page
+                            .view(&self.config)
+                            .map(Message::SettingsMessage),
+                    );
+                }
+            }
+            let content = content
                 .spacing(space_m)
                 .align_x(Alignment::Center)
                 .width(Length::Fill);
@@ -160,19 +400,33 @@ impl Application for AppModel {
     /// Describes the interface based on the current state of the application model.
     ///
     /// This method constructs the icon button displayed in the system tray, NOT the applet popup window.
+    ///
+    /// While something runs the icon becomes a progress ring, see [`AppModel::panel_icon`].
+    /// With the panel countdown turned on, the time left on the next timer is shown
+    /// beside or instead of the icon, see [`panel_button`].
     fn view(&'_ self) -> Element<'_, Message> {
-        self.core
-            .applet
-            .icon_button(&self.icon_name)
-            .class(
-                if self.suspend_inhibitor.is_some() || !self.active_timers.is_empty() {
-                    theme::Button::Suggested
-                } else {
-                    theme::Button::AppletIcon
-                },
-            )
-            .on_press_down(Message::TogglePopup)
-            .into()
+        let now = self.clock.now();
+        panel_button::panel_button(
+            &self.core.applet,
+            self.panel_icon(now),
+            self.next_countdown(now),
+            self.config.panel_countdown,
+        )
+        .class(
+            if self.stays_awake()
+                || self.media_inhibitor.is_some()
+                || !self.runner.active_timers.is_empty()
+                || !self.sequences.is_empty()
+                || self.stopwatch.is_running()
+                || self.runner.pending_action.is_some()
+            {
+                theme::Button::Suggested
+            } else {
+                theme::Button::AppletIcon
+            },
+        )
+        .on_press_down(Message::TogglePopup)
+        .into()
     }
 
     fn style(&self) -> Option<Appearance> {
@@ -194,21 +448,100 @@ impl Application for AppModel {
         let task: Task<Action<Message>> = match message {
             Message::TogglePopup => {
                 let t = self.toggle_popup();
-                t.map(|_| Action::<Message>::None)
+                let t = t.map(|_| Action::<Message>::None);
+                if self.popup.is_some() {
+                    Task::batch([t, self.fetch_systemd_timers()])
+                } else {
+                    t
+                }
+            }
+
+            Message::ShowPage(page) => {
+                self.page = page;
+                self.focused_item = None;
+                self.write_config(|config| config.last_page = page);
+                Task::none()
             }
 
+            Message::KeyPressed(key, modifiers) => {
+                let action = self
+                    .key_binds
+                    .iter()
+                    .find(|(bind, _)| bind.matches(modifiers, &key))
+                    .map(|(_, action)| *action);
+                match action {
+                    Some(action) => self.update(action.message()),
+                    None => Task::none(),
+                }
+            }
+
+            Message::Shortcut(action) => self.handle_shortcut(action),
+
             Message::PowerControlsMessage(msg) => self.handle_power_controls_message(msg),
 
+            Message::SettingsMessage(msg) => self.handle_settings_message(msg),
+
+            Message::QuickTimer(index) => {
+                let Some(preset) = self.config.quick_timers.get(index).cloned() else {
+                    return Task::none();
+                };
+                self.create_power_timer(
+                    i32::try_from(preset.duration_seconds).unwrap_or(i32::MAX),
+                    &preset.timer_type(),
+                    &preset.label,
+                    "Runs in",
+                    "alarm-symbolic",
+                    TimerCondition::None,
+                )
+            }
+
+            Message::SequencesMessage(msg) => self.handle_sequences_message(msg),
+
+            Message::ChainsMessage(msg) => self.handle_chains_message(msg),
+
+            Message::RemindersMessage(msg) => self.handle_reminders_message(msg),
+
+            Message::QuickEntryMessage(msg) => self.handle_quick_entry_message(msg),
+
             Message::DatabaseMessage(msg) => self.handle_database_message(msg),
 
             Message::TimerMessage(msg) => self.handle_timer_message(msg),
 
             Message::PowerMessage(msg) => self.handle_power_message(msg),
 
+            Message::SequenceMessage(msg) => self.handle_sequence_message(msg),
+
+            Message::StopwatchMessage(msg) => self.handle_stopwatch_message(msg),
+
+            Message::HistoryMessage(msg) => self.handle_history_message(msg),
+
+            Message::ServiceMessage(msg) => self.handle_service_message(msg),
+
             Message::Tick => self.handle_tick(),
 
+            // Triggers waiting on activity or files are checked as soon as it changes
+            Message::ActivitySampled(sample) => {
+                self.record_activity(sample);
+                self.handle_tick()
+            }
+
+            Message::FileChanged(path) => {
+                self.runner.observe_file(&path, self.clock.now());
+                self.handle_tick()
+            }
+
+            Message::ClockChanged(change) => self.handle_clock_change(&change),
+
+            Message::DismissError => {
+                self.error = None;
+                Task::none()
+            }
+
             Message::UpdateConfig(config) => {
                 self.config = config;
+                self.power_controls
+                    .power_form
+                    .set_default_unit(self.config.default_unit);
                 Task::none()
             }
         };
@@ -216,24 +549,95 @@ impl Application for AppModel {
         task
     }
 
+    /// Handle D-Bus activation of the running applet.
+    ///
+    /// Another process (usually `chronomancer --quick-entry` bound to a global
+    /// shortcut, or another `chronomancer` command, see [`activation`]) asks the
+    /// applet to do something instead of starting a second instance. Activating
+    /// without an action opens the popup.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The activation request
+    ///
+    /// # Returns
+    ///
+    /// The task to be executed.
+    fn dbus_activation(
+        &mut self,
+        msg: cosmic::dbus_activation::Message,
+    ) -> Task<Action<Self::Message>> {
+        match msg.msg {
+            cosmic::dbus_activation::Details::ActivateAction { action, args } => {
+                match action.as_str() {
+                    activation::QUICK_ENTRY_ACTION => self.open_quick_entry(),
+                    activation::RELOAD_ACTION => self.fetch_timers(false),
+                    activation::STAY_AWAKE_ACTION => {
+                        let Some(enabled) = args.first().and_then(|arg| bool::try_from(arg).ok())
+                        else {
+                            return Task::none();
+                        };
+                        self.set_stay_awake(enabled)
+                    }
+                    _ => Task::none(),
+                }
+            }
+            cosmic::dbus_activation::Details::Activate if self.popup.is_none() => {
+                self.toggle_popup().map(|_| Action::None)
+            }
+            _ => Task::none(),
+        }
+    }
+
     /// Register subscriptions for this application.
     ///
     /// Subscriptions are long-running async tasks running in the background which
     /// emit messages to the application through a channel. They are started at the
     /// beginning of the application, and persist through its lifetime.
     /// Good example uses are to watch for configuration file changes or keyboard events.
+    /// The timer subscription sleeps until the next deadline (see [`scheduler`]) and only
+    /// ticks every second while a countdown is on screen; it is keyed on both, so it
+    /// restarts whenever a timer is added, finished or paused or the popup opens or closes.
+    /// The activity sampler, file watcher and media player poller are the exception: they
+    /// only run while a trigger (or the open popup, for media) needs them, so idle applets
+    /// don't poll `/proc` or D-Bus or hold inotify watches.
+    /// The file watcher is keyed on the watched paths so it restarts when they change.
+    /// Power state, database writes and clock changes are waited for as events (UPower's
+    /// and timedated's signals, inotify on the database, a wall clock sleep), not polled.
     fn subscription(&self) -> Subscription<Self::Message> {
         struct TimerSubscription;
-
-        Subscription::batch(vec![
-            // Timer tick subscription - fires every second
+        struct PowerStateSubscription;
+        struct ActivitySubscription;
+        struct FileWatchSubscription;
+        struct MediaSubscription;
+        struct ServiceSubscription;
+        struct DatabaseWatchSubscription;
+        struct ClockSubscription;
+
+        let deadlines = self.deadlines();
+        let shows_countdown = self.shows_countdown();
+        let clock = self.clock.clone();
+
+        let mut subscriptions = vec![
+            // Timer tick subscription - fires at each deadline, or every second while a countdown is shown
             Subscription::run_with_id(
-                std::any::TypeId::of::<TimerSubscription>(),
+                (
+                    std::any::TypeId::of::<TimerSubscription>(),
+                    deadlines.next(),
+                    shows_countdown,
+                ),
                 channel(4, move |mut channel| async move {
-                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+                    let mut deadlines = deadlines;
+                    let mut interval = tokio::time::interval(COUNTDOWN_INTERVAL);
 
                     loop {
-                        interval.tick().await;
+                        if shows_countdown {
+                            interval.tick().await;
+                        } else {
+                            scheduler::sleep_until(deadlines.next(), &*clock).await;
+                            // Timers ending together are finished by the same tick
+                            deadlines.pop_due(clock.now());
+                        }
                         if channel.send(Message::Tick).await.is_err() {
                             // Channel closed, exit the subscription
                             break;
@@ -241,45 +645,533 @@ impl Application for AppModel {
                     }
                 }),
             ),
+            // UPower subscription - reports battery and AC state, and each change of them
+            Subscription::run_with_id(
+                std::any::TypeId::of::<PowerStateSubscription>(),
+                channel(4, move |mut channel| async move {
+                    let mut states = match upower::watch_power_state().await {
+                        Ok(states) => states,
+                        Err(e) => {
+                            tracing::warn!("Failed to follow power state: {e}");
+                            return;
+                        }
+                    };
+                    // Only report the first failure in a row so desktops without UPower don't spam stderr
+                    let mut reported_failure = false;
+
+                    while let Some(state) = states.next().await {
+                        match state {
+                            Ok(state) => {
+                                reported_failure = false;
+                                if channel
+                                    .send(Message::PowerMessage(PowerMessage::PowerStateChanged(
+                                        state,
+                                    )))
+                                    .await
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                if !reported_failure {
+                                    tracing::warn!("Failed to read power state: {e}");
+                                    reported_failure = true;
+                                }
+                            }
+                        }
+                    }
+                }),
+            ),
             // Watch for application configuration changes.
             self.core()
                 .watch_config::<Config>(Self::APP_ID)
                 .map(|update| Message::UpdateConfig(update.config)),
-        ])
+        ];
+
+        // Clock watch subscription - notices the wall clock being set and time zone changes,
+        // waking up when the clock is set, timedated announces a zone or DST starts or ends
+        let clock = self.clock.clone();
+        subscriptions.push(Subscription::run_with_id(
+            std::any::TypeId::of::<ClockSubscription>(),
+            channel(4, move |mut channel| async move {
+                let mut zone_changes = time_zone::changes().await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to follow time zone changes: {e}");
+                    futures_util::stream::pending().boxed()
+                });
+                let mut watch = ClockWatch::new(&*clock);
+
+                loop {
+                    let next_offset_change =
+                        time_zone::next_offset_change(&clock.time_zone(), clock.now());
+                    // The sleep ends early when the clock is set
+                    tokio::select! {
+                        () = scheduler::sleep_until(next_offset_change, &*clock) => {}
+                        Some(()) = zone_changes.next() => {}
+                    }
+                    if let Some(change) = watch.check(&*clock)
+                        && channel.send(Message::ClockChanged(change)).await.is_err()
+                    {
+                        break;
+                    }
+                }
+            }),
+        ));
+
+        if let Some(database) = &self.database {
+            // Timer service subscription - publishes the D-Bus API and forwards its requests
+            let pool = database.pool().clone();
+            let clock = self.clock.clone();
+            subscriptions.push(Subscription::run_with_id(
+                std::any::TypeId::of::<ServiceSubscription>(),
+                channel(4, move |mut channel| async move {
+                    let (connection, mut events) =
+                        match service::start(pool, clock, Role::Applet).await {
+                            Ok(service) => service,
+                            Err(e) => {
+                                tracing::error!("Failed to start the D-Bus timer service: {e}");
+                                return;
+                            }
+                        };
+                    // Follow who owns the service, so the applet steps back while chronomancerd runs
+                    let mut updates = service::updates(&connection).await.unwrap_or_else(|e| {
+                        tracing::error!("Failed to follow the D-Bus timer service: {e}");
+                        futures_util::stream::empty().boxed()
+                    });
+                    if channel
+                        .send(Message::ServiceMessage(ServiceMessage::Started(connection)))
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+
+                    loop {
+                        let message = tokio::select! {
+                            event = events.recv() => match event {
+                                Some(event) => ServiceMessage::Event(event),
+                                None => break,
+                            },
+                            Some(update) = updates.next() => ServiceMessage::Update(update),
+                        };
+                        if channel
+                            .send(Message::ServiceMessage(message))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }),
+            ));
+
+            // Database watch subscription - notices timers changed by other processes
+            let database = database.clone();
+            subscriptions.push(Subscription::run_with_id(
+                std::any::TypeId::of::<DatabaseWatchSubscription>(),
+                channel(4, move |mut channel| async move {
+                    let mut changes = match database.watch_changes().await {
+                        Ok(changes) => changes,
+                        Err(e) => {
+                            tracing::warn!("Failed to watch the database: {e}");
+                            return;
+                        }
+                    };
+                    let mut reported_failure = false;
+
+                    loop {
+                        changes.written().await;
+                        match changes.has_changed().await {
+                            Ok(changed) => {
+                                reported_failure = false;
+                                if changed
+                                    && channel
+                                        .send(Message::DatabaseMessage(DatabaseMessage::Changed))
+                                        .await
+                                        .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                if !reported_failure {
+                                    tracing::warn!("Failed to check the database for changes: {e}");
+                                    reported_failure = true;
+                                }
+                            }
+                        }
+                    }
+                }),
+            ));
+        }
+
+        if self.popup.is_some() {
+            // Keyboard shortcuts, only for key presses no widget in the popup handled
+            subscriptions.push(keyboard::on_key_press(|key, modifiers| {
+                Some(Message::KeyPressed(key, modifiers))
+            }));
+        }
+
+        if self.runner.longest_quiet_window().is_some() {
+            let clock = self.clock.clone();
+            // Activity sampling subscription - turns /proc counters into per-second rates
+            subscriptions.push(Subscription::run_with_id(
+                std::any::TypeId::of::<ActivitySubscription>(),
+                channel(4, move |mut channel| async move {
+                    let mut interval = tokio::time::interval(ACTIVITY_SAMPLE_INTERVAL);
+                    let mut previous = None;
+                    let mut reported_failure = false;
+
+                    loop {
+                        interval.tick().await;
+                        match system_load::read_counters(clock.now()) {
+                            Ok(counters) => {
+                                reported_failure = false;
+                                // Rates need two readings, so the first one only primes `previous`
+                                if let Some(earlier) = previous.replace(counters)
+                                    && channel
+                                        .send(Message::ActivitySampled(
+                                            counters.rate_since(&earlier),
+                                        ))
+                                        .await
+                                        .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                if !reported_failure {
+                                    tracing::warn!("Failed to sample system activity: {e}");
+                                    reported_failure = true;
+                                }
+                            }
+                        }
+                    }
+                }),
+            ));
+        }
+
+        let watched_paths = self.runner.watched_paths();
+        if !watched_paths.is_empty() {
+            // File watch subscription - reports inotify activity on watched paths
+            subscriptions.push(Subscription::run_with_id(
+                (
+                    std::any::TypeId::of::<FileWatchSubscription>(),
+                    watched_paths.clone(),
+                ),
+                channel(4, move |mut channel| async move {
+                    let (_watcher, mut changes) = match file_watch::watch_paths(&watched_paths) {
+                        Ok(watch) => watch,
+                        Err(e) => {
+                            tracing::warn!("Failed to watch files: {e}");
+                            return;
+                        }
+                    };
+
+                    while let Some(path) = changes.recv().await {
+                        if channel.send(Message::FileChanged(path)).await.is_err() {
+                            break;
+                        }
+                    }
+                }),
+            ));
+        }
+
+        if self.needs_media_state() {
+            // MPRIS polling subscription - reports playback state of all media players
+            subscriptions.push(Subscription::run_with_id(
+                std::any::TypeId::of::<MediaSubscription>(),
+                channel(4, move |mut channel| async move {
+                    let mut interval = tokio::time::interval(MEDIA_POLL_INTERVAL);
+                    let mut reported_failure = false;
+
+                    loop {
+                        interval.tick().await;
+                        match mpris::get_players().await {
+                            Ok(players) => {
+                                reported_failure = false;
+                                if channel
+                                    .send(Message::PowerMessage(PowerMessage::MediaStateChanged(
+                                        players,
+                                    )))
+                                    .await
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                if !reported_failure {
+                                    tracing::warn!("Failed to read media players: {e}");
+                                    reported_failure = true;
+                                }
+                            }
+                        }
+                    }
+                }),
+            ));
+        }
+
+        Subscription::batch(subscriptions)
     }
 }
 
 /// Helper functions for the application model.
 impl AppModel {
-    /// Sends a desktop notification with a 5-second timeout.
+    /// Sends a desktop notification with the configured timeout.
     ///
     /// Creates and displays a notification using the system notification daemon.
-    /// The notification is categorized as "device" and will automatically dismiss
-    /// after 5 seconds. Errors are logged to stderr but do not propagate.
+    /// The notification is categorized as "device" and dismisses itself after the
+    /// timeout from the settings (5 seconds by default), or stays up until dismissed
+    /// when persistent notifications are turned on. Errors are logged to stderr but
+    /// do not propagate.
     ///
     /// # Arguments
     ///
     /// - `summary`: Notification title
     /// - `body`: Notification body text
     /// - `icon`: Icon name from the freedesktop icon theme (e.g., "alarm", "battery")
-    fn send_notification(summary: &str, body: &str, icon: &str) {
-        if let Err(e) = Notification::new()
-            .summary(summary)
-            .body(body)
-            .icon(icon)
-            .hint(Hint::Category("device".to_owned()))
-            .timeout(5000) // 5 seconds
-            .show()
-        {
-            eprintln!("Failed to send notification: {e}");
+    fn send_notification(&self, summary: &str, body: &str, icon: &str) {
+        Notice::new(summary, body, icon).show(&self.config);
+    }
+
+    /// Shows a failure in the popup until it is dismissed.
+    ///
+    /// While the popup is closed the failure is sent as a notification as well,
+    /// unless it is the one already shown, so a failed shutdown doesn't go
+    /// unnoticed and a failure that keeps repeating doesn't flood the desktop.
+    ///
+    /// # Arguments
+    ///
+    /// - `summary`: Localized description of what failed
+    /// - `error`: Why it failed
+    fn report_error(&mut self, summary: String, error: AppError) {
+        tracing::error!("{summary}: {error}");
+        let repeated = self
+            .error
+            .as_ref()
+            .is_some_and(|shown| shown.0 == summary && shown.1 == error);
+        if self.popup.is_none() && !repeated {
+            self.send_notification(
+                &summary,
+                &Self::error_details(&error),
+                "dialog-error-symbolic",
+            );
+        }
+        self.error = Some((summary, error));
+    }
+
+    /// Gets the text explaining a failure to the user.
+    ///
+    /// Permission problems get a hint on what to do instead of the raw D-Bus error.
+    fn error_details(error: &AppError) -> String {
+        if error.is_permission_denied() {
+            fl!("error-permission-denied")
+        } else {
+            error.to_string()
+        }
+    }
+
+    /// Gets the panel icon.
+    ///
+    /// With the progress icon turned on, the nearest timer draws a ring that drains
+    /// until it ends, around a power or reminder glyph. Without a timer, stay-awake
+    /// shows its glyph in an empty ring. Otherwise it's the regular applet icon.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: Current Unix timestamp in seconds
+    fn panel_icon(&self, now: i64) -> icon::Handle {
+        let ring = match self.nearest_timer() {
+            Some(timer) => Some((
+                Some(progress_ring::remaining_fraction(
+                    timer.started_at(),
+                    timer.ends_at,
+                    now,
+                )),
+                match TimerType::from_str(&timer.description) {
+                    Ok(TimerType::UserDefined(_)) | Err(_) => Glyph::Reminder,
+                    Ok(_) => Glyph::Power,
+                },
+            )),
+            None if self.stays_awake() || self.media_inhibitor.is_some() => {
+                Some((None, Glyph::StayAwake))
+            }
+            None => None,
+        };
+
+        match ring.filter(|_| self.config.progress_icon) {
+            Some((remaining, glyph)) => icon::from_svg_bytes(
+                progress_ring::progress_ring_svg(remaining, glyph).into_bytes(),
+            )
+            .symbolic(true),
+            None => icon::from_name(self.icon_name.clone()).handle(),
+        }
+    }
+
+    /// Gets the timer that ends next, for the panel countdown.
+    ///
+    /// Trigger timers have no deadline and are left out.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: Current Unix timestamp in seconds
+    fn next_countdown(&self, now: i64) -> Option<panel_button::Countdown> {
+        self.nearest_timer().map(|timer| panel_button::Countdown {
+            icon_name: timer_list::timer_icon(timer),
+            remaining: timer.ends_at - now,
+        })
+    }
+
+    /// Gets the running timer with the nearest deadline, the one the panel button counts down.
+    fn nearest_timer(&self) -> Option<&Timer> {
+        self.runner
+            .active_timers
+            .iter()
+            .filter(|timer| timer.ends_at != Timer::NO_DEADLINE && !timer.is_paused())
+            .min_by_key(|timer| timer.ends_at)
+    }
+
+    /// Renders the banner for a power action that is about to run, with cancel and run now buttons.
+    ///
+    /// # Arguments
+    ///
+    /// - `pending`: The waiting action
+    /// - `now`: Current Unix timestamp in seconds
+    fn pending_action_banner(pending: &PendingAction, now: i64) -> Element<'_, Message> {
+        let operation = timer_list::operation_name(&pending.timer_type);
+        let label = match pending.runs_at {
+            Some(runs_at) => fl!(
+                "pending-action",
+                operation = operation,
+                time = format_countdown(runs_at - now)
+            ),
+            None => fl!("pending-confirm", operation = operation),
+        };
+
+        row![
+            text::heading(label).width(Length::Fill),
+            button::text(fl!("pending-cancel"))
+                .on_press(Message::PowerMessage(PowerMessage::CancelPendingAction)),
+            button::suggested(fl!("pending-run-now"))
+                .on_press(Message::PowerMessage(PowerMessage::RunPendingAction)),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .into()
+    }
+
+    /// Renders the banner for the last failure, with a button to dismiss it.
+    ///
+    /// # Arguments
+    ///
+    /// - `summary`: Localized description of what failed
+    /// - `error`: Why it failed
+    fn error_banner<'a>(summary: &'a str, error: &AppError) -> Element<'a, Message> {
+        row![
+            resources::system_icon("dialog-error-symbolic", 24),
+            column![
+                text::heading(summary),
+                text::caption(Self::error_details(error))
+            ]
+            .width(Length::Fill),
+            button::text(fl!("error-dismiss")).on_press(Message::DismissError),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .into()
+    }
+
+    /// Renders a top-level tab button that switches the popup to the given page.
+    ///
+    /// The tab stays selected on every page under it. Pressing the selected tab
+    /// keeps the current page, so the timers tab doesn't jump back to the active list.
+    ///
+    /// # Arguments
+    ///
+    /// - `label`: Localized tab label
+    /// - `page`: The page the tab switches to
+    fn page_tab(&self, label: String, page: PopupPage) -> Element<'_, Message> {
+        let selected = self.page.same_tab(page);
+        Self::tab_button(label, selected, if selected { self.page } else { page })
+    }
+
+    /// Renders a tab button in the second row under the timers tab.
+    ///
+    /// # Arguments
+    ///
+    /// - `label`: Localized tab label
+    /// - `page`: The timer page the tab switches to
+    fn timer_page_tab(&self, label: String, page: TimerPage) -> Element<'_, Message> {
+        let page = PopupPage::Timers(page);
+        Self::tab_button(label, self.page == page, page)
+    }
+
+    /// Renders a tab button, highlighted when selected.
+    fn tab_button<'a>(label: String, selected: bool, page: PopupPage) -> Element<'a, Message> {
+        button::text(label)
+            .class(if selected {
+                theme::Button::Suggested
+            } else {
+                theme::Button::Text
+            })
+            .on_press(Message::ShowPage(page))
+            .into()
+    }
+
+    /// Renders the active timer list with cancel and export buttons, and the systemd timers under it.
+    fn active_timer_list(&self, now: i64) -> Element<'_, Message> {
+        let mut list = column![].spacing(Gaps::s());
+        if !self.runner.active_timers.is_empty() {
+            list = list.push(timer_list::timer_list(
+                &self.runner.active_timers,
+                &self.chained_timers,
+                now,
+                self.focused_item,
+                |id| Message::TimerMessage(TimerMessage::Cancel(id)),
+                |id| Message::TimerMessage(TimerMessage::Export(id)),
+            ));
+        }
+        if !self.systemd_timers.is_empty() {
+            list = list.push(timer_list::systemd_timer_list(&self.systemd_timers, now));
+        }
+        list.into()
+    }
+
+    /// Whether the popup has timers to list, its own or systemd's.
+    fn lists_timers(&self) -> bool {
+        !self.runner.active_timers.is_empty() || !self.systemd_timers.is_empty()
+    }
+
+    /// Builds the body of the notification shown when a notification-only timer fires.
+    ///
+    /// # Arguments
+    ///
+    /// - `condition`: The condition the timer waits for
+    fn notification_text(condition: &TimerCondition) -> String {
+        match condition {
+            TimerCondition::None | TimerCondition::OnAcPower => "Timer finished".to_string(),
+            TimerCondition::BatteryBelow(percent) => format!("Battery dropped below {percent}%"),
+            TimerCondition::SystemQuiet(_) => "The system has gone quiet".to_string(),
+            TimerCondition::File(trigger) => {
+                let path = trigger.path.display();
+                match trigger.event {
+                    FileEvent::Appears => format!("{path} appeared"),
+                    FileEvent::Disappears => format!("{path} disappeared"),
+                    FileEvent::StopsChanging(_) => format!("{path} stopped changing"),
+                }
+            }
+            TimerCondition::MediaEnds(_) => "The track has ended".to_string(),
         }
     }
 
     /// Creates a power management timer and performs related UI/database operations.
     ///
     /// This is a high-level orchestration function that:
-    /// 1. Sends a desktop notification with the formatted timer duration
-    /// 2. Creates a `Timer` instance with the specified Arguments
+    /// 1. Sends a desktop notification with the formatted timer duration (or battery threshold)
+    /// 2. Creates a `Timer` instance with the specified Arguments and condition
     /// 3. Closes the popup window
     /// 4. Clears the power controls form
     /// 5. Asynchronously inserts the timer into the database
@@ -294,6 +1186,9 @@ impl AppModel {
     /// - `notification_title`: Title for the desktop notification
     /// - `notification_body_prefix`: Text prefix before the duration (e.g., "Suspending in")
     /// - `icon`: Icon name for the notification
+    /// - `condition`: Condition gating or replacing the deadline. An unbound media
+    ///   condition is bound to the playing track first, or nothing is created if
+    ///   nothing is playing.
     fn create_power_timer(
         &mut self,
         time: i32,
@@ -301,22 +1196,67 @@ impl AppModel {
         notification_title: &str,
         notification_body_prefix: &str,
         icon: &str,
+        condition: TimerCondition,
     ) -> Task<Action<Message>> {
         let Some(database) = self.database.clone() else {
-            eprintln!("Database not yet available");
+            tracing::warn!("Database not yet available");
+            return Task::none();
+        };
+        let Some(condition) = self.bind_media_track(condition) else {
+            self.send_notification(
+                "Nothing Playing",
+                "Start the track or episode to wait for, then set the timer again",
+                "audio-x-generic-symbolic",
+            );
             return Task::none();
         };
 
         // Send notification
         let display_time = format_duration(time);
-        AppModel::send_notification(
-            notification_title,
-            &format!("{notification_body_prefix} {display_time}"),
-            icon,
-        );
+        let body = match &condition {
+            TimerCondition::None => format!("{notification_body_prefix} {display_time}"),
+            TimerCondition::OnAcPower => {
+                format!("{notification_body_prefix} {display_time} if on AC power")
+            }
+            TimerCondition::BatteryBelow(percent) => {
+                format!("Runs when the battery drops below {percent}%")
+            }
+            TimerCondition::SystemQuiet(_) => {
+                format!("Runs once the system has been quiet for {display_time}")
+            }
+            TimerCondition::File(trigger) => {
+                let path = trigger.path.display();
+                match trigger.event {
+                    FileEvent::Appears => format!("Runs when {path} appears"),
+                    FileEvent::Disappears => format!("Runs when {path} disappears"),
+                    FileEvent::StopsChanging(_) => {
+                        format!("Runs once {path} hasn't changed for {display_time}")
+                    }
+                }
+            }
+            TimerCondition::MediaEnds(target) => {
+                let remaining = self
+                    .runner
+                    .media_players
+                    .iter()
+                    .flatten()
+                    .find(|player| player.bus_name == target.player)
+                    .and_then(PlayerState::remaining_secs)
+                    .and_then(|secs| i32::try_from(secs).ok());
+                match remaining {
+                    Some(secs) => format!(
+                        "Runs after the current track, in about {}",
+                        format_duration(secs)
+                    ),
+                    None => "Runs after the current track ends".to_string(),
+                }
+            }
+        };
+        self.send_notification(notification_title, &body, icon);
 
         // Create the timer
-        let timer = Timer::new(time, false, timer_type);
+        let timer =
+            Timer::new(time, false, timer_type, self.clock.now()).with_condition(&condition);
 
         // Close the popup
         let close_task = self.toggle_popup();
@@ -331,35 +1271,135 @@ impl AppModel {
                 async move {
                     Timer::insert(database.pool(), &timer)
                         .await
-                        .map_err(|e| e.to_string())
+                        .map_err(AppError::from)
                 },
                 |result| Action::App(Message::TimerMessage(TimerMessage::Created(result))),
             ),
         ])
     }
 
-    /// Toggles the applet popup window open or closed.
+    /// Creates a timer that ends the next time the local clock shows a time of day.
     ///
-    /// If a popup is currently open, it will be closed. If no popup exists,
-    /// a new one will be created with the configured size (500×500 default)
-    /// and minimum dimensions (300×150).
-    ///
-    /// The popup position is automatically determined by the panel location
-    /// (top, bottom, left, or right) via `get_popup_settings()`.
+    /// The timer keeps the local time and the current time zone, so it still
+    /// ends at that time on the local clock after the time zone changes.
     ///
-    /// # Test Context Behavior
+    /// # Arguments
     ///
-    /// In test environments where no main window ID exists, the popup state
-    /// is still tracked in `self.popup`, but no actual window task is generated.
-    /// This allows testing popup logic without requiring a full GUI context.
+    /// - `time`: The time of day to end at
+    /// - `timer_type`: What happens when it ends
     ///
     /// # Returns
     ///
-    /// A `Task` that will create or destroy the popup window, or `Task::none()`
-    /// if running in a test context without a main window.
-    fn toggle_popup(&mut self) -> Task<Message> {
-        if let Some(p) = self.popup.take() {
-            // Close the popup if it is open.
+    /// The task storing the timer.
+    fn create_wall_clock_timer(
+        &mut self,
+        time: NaiveTime,
+        timer_type: &TimerType,
+    ) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone() else {
+            tracing::warn!("Database not yet available");
+            return Task::none();
+        };
+        let now = self.clock.now();
+        let zone = self.clock.time_zone();
+        let Some(timer) = time_zone::next_occurrence(time, &zone, now)
+            .and_then(|at| Timer::at(at, &zone, timer_type, now))
+        else {
+            tracing::warn!("Failed to resolve {time} in time zone {zone}");
+            return Task::none();
+        };
+
+        self.send_notification(
+            "Timer Set",
+            &format!(
+                "Runs at {}, in {}",
+                time.format("%H:%M"),
+                format_duration(i32::try_from(timer.ends_at - now).unwrap_or(i32::MAX))
+            ),
+            "alarm-symbolic",
+        );
+        Task::perform(
+            async move {
+                Timer::insert(database.pool(), &timer)
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| Action::App(Message::TimerMessage(TimerMessage::Created(result))),
+        )
+    }
+
+    /// Stores a chain of timers and starts its first timer.
+    ///
+    /// Sends a notification naming the first step and the steps that follow,
+    /// closes the popup and clears the chain editor.
+    ///
+    /// # Arguments
+    ///
+    /// - `timers`: The chain's timers in order, as built by the chains page
+    fn start_chain(&mut self, timers: Vec<Timer>) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone() else {
+            tracing::warn!("Database not yet available");
+            return Task::none();
+        };
+        let Some((head, links)) = timers.split_first() else {
+            return Task::none();
+        };
+
+        let next: Vec<String> = links.iter().map(timer_list::timer_name).collect();
+        self.send_notification(
+            "Timer Chain Started",
+            &format!(
+                "{} {}, then {}",
+                timer_list::timer_name(head),
+                timer_list::timer_summary(head, self.clock.now()),
+                next.join(", ")
+            ),
+            "alarm-symbolic",
+        );
+
+        let head = head.clone();
+        let links = links.to_vec();
+        let close_task = self.toggle_popup();
+
+        Task::batch(vec![
+            close_task.map(|_| Action::None),
+            Task::done(Action::App(Message::ChainsMessage(
+                chains::Message::ClearForm,
+            ))),
+            Task::perform(
+                async move {
+                    Timer::insert_chain(database.pool(), &head, &links)
+                        .await
+                        .map_err(AppError::from)
+                },
+                |result| Action::App(Message::TimerMessage(TimerMessage::ChainCreated(result))),
+            ),
+        ])
+    }
+
+    /// Toggles the applet popup window open or closed.
+    ///
+    /// If a popup is currently open, it will be closed. If no popup exists,
+    /// a new one will be created with the configured size (500×500 default)
+    /// and minimum dimensions (300×150).
+    ///
+    /// The popup position is automatically determined by the panel location
+    /// (top, bottom, left, or right) via `get_popup_settings()`.
+    ///
+    /// # Test Context Behavior
+    ///
+    /// In test environments where no main window ID exists, the popup state
+    /// is still tracked in `self.popup`, but no actual window task is generated.
+    /// This allows testing popup logic without requiring a full GUI context.
+    ///
+    /// # Returns
+    ///
+    /// A `Task` that will create or destroy the popup window, or `Task::none()`
+    /// if running in a test context without a main window.
+    fn toggle_popup(&mut self) -> Task<Message> {
+        if let Some(p) = self.popup.take() {
+            // Close the popup if it is open.
+            self.quick_entry = None;
             popup::destroy_popup::<Message>(p)
         } else {
             // create new popup
@@ -384,103 +1424,463 @@ impl AppModel {
         }
     }
 
-    /// Processes expired timers on each tick of the subscription interval.
+    /// Binds an unbound "media ends" condition to the track that is playing right now.
+    ///
+    /// The power controls page doesn't know about media players, so it submits an
+    /// empty [`MediaTrack`] that is filled in here from the latest player snapshots.
+    ///
+    /// # Arguments
+    ///
+    /// - `condition`: The condition submitted by the page
+    ///
+    /// # Returns
+    ///
+    /// The condition to store, or `None` for a media condition while nothing is playing.
+    /// Other conditions are returned unchanged.
+    fn bind_media_track(&self, condition: TimerCondition) -> Option<TimerCondition> {
+        match condition {
+            TimerCondition::MediaEnds(target) if target.player.is_empty() => self
+                .runner
+                .media_players
+                .as_deref()
+                .and_then(mpris::playing_player)
+                .map(|player| TimerCondition::MediaEnds(MediaTrack::current(player))),
+            condition => Some(condition),
+        }
+    }
+
+    /// Whether stay-awake is on, held by the applet or by `chronomancerd`.
+    fn stays_awake(&self) -> bool {
+        self.suspend_inhibitor.is_some() || self.daemon_stay_awake
+    }
+
+    /// Turns stay-awake on or off, doing nothing if it already is.
+    ///
+    /// While `chronomancerd` runs the timers it holds the lock, so the request goes to it.
+    ///
+    /// # Arguments
+    ///
+    /// - `enabled`: Whether the system should stay awake
+    fn set_stay_awake(&mut self, enabled: bool) -> Task<Action<Message>> {
+        if !self.scheduling {
+            return self.request(Request::SetStayAwake(enabled));
+        }
+        if enabled == self.suspend_inhibitor.is_some() {
+            Task::none()
+        } else {
+            self.handle_power_message(PowerMessage::ToggleStayAwake)
+        }
+    }
+
+    /// Whether media player state is needed right now.
+    ///
+    /// That's the case while "stay awake while media plays" is on, while a
+    /// "media ends" timer waits, and while the popup is open so a new "media ends"
+    /// timer can be bound to the playing track.
+    fn needs_media_state(&self) -> bool {
+        self.media_stay_awake || self.popup.is_some() || self.runner.waits_on_media()
+    }
+
+    /// Collects the deadlines the tick subscription sleeps until.
+    ///
+    /// Sequence steps always count, timers and the pending power action only
+    /// while this applet runs them rather than chronomancerd.
+    fn deadlines(&self) -> Deadlines {
+        let sequences = self
+            .sequences
+            .iter()
+            .filter(|sequence| !sequence.is_paused())
+            .map(|sequence| sequence.step_ends_at);
+        // The progress ring is redrawn when it moves, which needs no tick in between
+        let ring_step = self
+            .nearest_timer()
+            .filter(|_| self.config.progress_icon)
+            .and_then(|timer| {
+                progress_ring::next_step_at(timer.started_at(), timer.ends_at, self.clock.now())
+            });
+        if !self.scheduling {
+            return sequences.chain(ring_step).collect();
+        }
+
+        sequences
+            .chain(ring_step)
+            .chain(self.runner.wake_times())
+            .collect()
+    }
+
+    /// Whether a countdown is on screen and has to be redrawn every second.
+    ///
+    /// That's the case while the popup is open, and while the panel button shows
+    /// the countdown text of a running timer. The progress ring only moves once
+    /// per step, which [`Self::deadlines`] wakes the applet for.
+    fn shows_countdown(&self) -> bool {
+        self.popup.is_some()
+            || (self.config.panel_countdown != PanelCountdown::Off
+                && self.nearest_timer().is_some())
+    }
+
+    /// Whether the media inhibitor should be held given the latest player and power state.
+    ///
+    /// Like stay-awake, the lock is let go below the configured minimum battery level.
+    fn wants_media_inhibitor(&self) -> bool {
+        let threshold = self.config.stay_awake_min_battery;
+        let battery_low = threshold > 0
+            && self
+                .runner
+                .power_state
+                .is_some_and(|state| state.is_battery_below(threshold));
+
+        self.media_stay_awake
+            && !battery_low
+            && self
+                .runner
+                .media_players
+                .as_deref()
+                .and_then(mpris::playing_player)
+                .is_some()
+    }
+
+    /// Acquires or releases the media inhibitor to match [`Self::wants_media_inhibitor`].
+    ///
+    /// # Returns
+    ///
+    /// A task acquiring the inhibitor when one is needed and not already held or requested.
+    fn sync_media_inhibitor(&mut self) -> Task<Action<Message>> {
+        if self.wants_media_inhibitor() {
+            if self.media_inhibitor.is_none() && !self.media_inhibit_pending {
+                self.media_inhibit_pending = true;
+                return AppModel::get_media_inhibitor();
+            }
+        } else if let Some(inhibitor) = self.media_inhibitor.take() {
+            resources::release_suspend_inhibit(inhibitor);
+        }
+        Task::none()
+    }
+
+    /// Appends an activity sample to the history used by "system quiet" triggers.
     ///
-    /// Called every second by the tick subscription to check for completed timers.
-    /// For each expired timer, this function:
-    /// 1. Determines the timer type (power operation or user-defined)
-    /// 2. Executes the appropriate action (system command or notification)
-    /// 3. Removes the timer from the active list
-    /// 4. Schedules database deletion
+    /// The history is trimmed to the longest active window (keeping one older
+    /// sample so the window stays fully covered). A gap between samples, e.g. after
+    /// the sampler was stopped or the system slept, throws the history away since
+    /// nobody watched what happened in between.
+    ///
+    /// # Arguments
+    ///
+    /// - `sample`: The newest activity sample
+    fn record_activity(&mut self, sample: ActivitySample) {
+        let max_gap = 2 * i64::try_from(ACTIVITY_SAMPLE_INTERVAL.as_secs()).unwrap_or(i64::MAX);
+        let window = self.runner.longest_quiet_window().unwrap_or_default();
+        system_load::record_sample(&mut self.runner.activity_history, sample, window, max_gap);
+    }
+
+    /// Moves timers, sequences and the pending power action along with a change of the clock.
     ///
-    /// Power operation timers trigger system actions (suspend, shutdown, logout, reboot)
-    /// via the power management message flow. User-defined timers show a desktop
-    /// notification with the timer description.
+    /// Countdowns keep their remaining time when the wall clock is set, timers
+    /// set to a wall clock time are resolved again in a new time zone, see
+    /// [`Timer::follow_clock`]. While `chronomancerd` runs the timers it moves
+    /// them itself, and the applet picks them up from the database.
     ///
-    /// # Implementation Note
+    /// # Arguments
     ///
-    /// Database deletions are processed one per tick to avoid concurrent deletion
-    /// conflicts. Since ticks occur every second and sqlite database operations are fast,
-    /// this trade-off is prefereable to batching multiple deletions at once.
-    ///  How often will we be processing multiple timers expiring simultaneously anyway?
+    /// - `change`: What changed about the clock
     ///
     /// # Returns
     ///
-    /// A batched `Task` containing all scheduled operations for this tick.
-    fn handle_tick(&mut self) -> Task<Action<Message>> {
+    /// The task to be executed, followed by a tick for timers that are now due.
+    fn handle_clock_change(&mut self, change: &ClockChange) -> Task<Action<Message>> {
         let mut tasks: Vec<Task<Action<Message>>> = vec![];
 
-        for timer in self.active_timers.clone() {
-            if !timer.is_active() {
-                match TimerType::from_str(&timer.description) {
-                    Ok(TimerType::Suspend) => {
-                        // Execute system suspend
-                        tasks.push(Task::done(Action::App(Message::PowerMessage(
-                            PowerMessage::ExecuteSuspend,
-                        ))));
-                    }
-                    Ok(TimerType::Logout) => {
-                        // Execute system logout
-                        tasks.push(Task::done(Action::App(Message::PowerMessage(
-                            PowerMessage::ExecuteLogout,
-                        ))));
-                    }
-                    Ok(TimerType::Shutdown) => {
-                        tasks.push(Task::done(Action::App(Message::PowerMessage(
-                            PowerMessage::ExecuteShutdown,
-                        ))));
-                    }
-                    Ok(TimerType::Reboot) => {
-                        tasks.push(Task::done(Action::App(Message::PowerMessage(
-                            PowerMessage::ExecuteReboot,
-                        ))));
-                    }
-                    Ok(TimerType::UserDefined(ref description)) => {
-                        if let Err(e) = Notification::new()
-                            .summary("Timer Finished")
-                            .body(description.as_str())
-                            .icon("alarm")
-                            .hint(Hint::Category("alarm".to_owned()))
-                            .hint(Hint::Resident(true))
-                            // Uncomment for persistent notifications
-                            // .timeout(0)
-                            .show()
-                        {
-                            eprintln!("Failed to send notification: {e}");
+        let moved: Vec<i64> = self
+            .sequences
+            .iter_mut()
+            .filter_map(|sequence| sequence.follow_clock(change).then_some(sequence.id))
+            .collect();
+        for id in moved {
+            tasks.push(self.persist_sequence(id));
+        }
+
+        if self.scheduling {
+            let effects = self.runner.follow_clock(change);
+            tasks.push(self.apply(effects));
+            if let Some(database) = self.database.clone() {
+                let change = change.clone();
+                tasks.push(Task::perform(
+                    async move { Timer::follow_clock_all(database.pool(), &change).await },
+                    |result| {
+                        if let Err(e) = result {
+                            tracing::error!("Failed to move timers along with the clock: {e}");
                         }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse timer type: {e}");
-                    }
-                }
+                        Action::None
+                    },
+                ));
+            }
+        }
+
+        tasks.push(self.handle_tick());
+        Task::batch(tasks)
+    }
 
-                // Capture the id before mutating the vector
-                let timer_id = timer.id;
+    /// Processes expired timers when a deadline passes or the system state changes.
+    ///
+    /// Called by the tick subscription at each deadline (every second while a
+    /// countdown is on screen) and after power, activity, file and media updates,
+    /// to check for completed timers and to move sequences whose current phase
+    /// ran out on to their next phase.
+    ///
+    /// Timers are finished by the [`TimerRunner`], see [`TimerRunner::tick`];
+    /// the tasks carrying out its effects are built by [`Self::apply`]. Power
+    /// operation timers trigger system actions (suspend, shutdown, logout,
+    /// reboot) via the power management message flow. User-defined timers show
+    /// a desktop notification with the timer description.
+    ///
+    /// # Returns
+    ///
+    /// A batched `Task` containing all scheduled operations for this tick.
+    fn handle_tick(&mut self) -> Task<Action<Message>> {
+        let mut tasks: Vec<Task<Action<Message>>> = vec![];
+        let now = self.clock.now();
+
+        let due: Vec<i64> = self
+            .sequences
+            .iter()
+            .filter(|sequence| sequence.is_due(now))
+            .map(|sequence| sequence.id)
+            .collect();
+        for id in due {
+            tasks.push(self.advance_sequence(id, now, true));
+        }
 
-                // Remove finished timer from active timers
-                self.active_timers.retain(|t| t.id != timer_id);
+        // chronomancerd runs the timers and their power actions while it owns the service
+        if self.scheduling {
+            let effects = self.runner.tick(&self.config, now);
+            tasks.push(self.apply(effects));
+        }
+        Task::batch(tasks)
+    }
 
-                if let Some(database) = self.database.clone() {
-                    // Schedule an async task to delete the timer from the DB.
+    /// Builds the tasks carrying out the effects of the timer runner.
+    ///
+    /// Each finished timer is deleted by its own [`Timer::complete`] task, which
+    /// only touches that timer and its chain, and reports back through
+    /// [`TimerMessage::Completed`]. Timers being deleted are kept out of reloads
+    /// until then.
+    ///
+    /// # Arguments
+    ///
+    /// - `effects`: What the runner needs done
+    fn apply(&mut self, effects: Vec<Effect>) -> Task<Action<Message>> {
+        let mut tasks: Vec<Task<Action<Message>>> = vec![];
+        for effect in effects {
+            match effect {
+                Effect::Notify(notice) => notice.show(&self.config),
+                Effect::Record(entry) => tasks.push(self.record_history(entry)),
+                Effect::Emit(signal) => tasks.push(self.emit_signal(signal)),
+                Effect::Complete { id, now } => {
+                    let Some(database) = self.database.clone() else {
+                        continue;
+                    };
+                    self.removing_timers.insert(id);
                     tasks.push(Task::perform(
                         async move {
-                            Timer::delete_by_id(database.pool(), &timer_id)
+                            Timer::complete(database.pool(), id, now)
                                 .await
-                                .map_err(|e| e.to_string())
+                                .map_err(AppError::from)
+                        },
+                        move |result| {
+                            Action::App(Message::TimerMessage(TimerMessage::Completed(id, result)))
+                        },
+                    ));
+                }
+                Effect::Discard { timers, now } => {
+                    let Some(database) = self.database.clone() else {
+                        continue;
+                    };
+                    let ids: Vec<i64> = timers.iter().map(|timer| timer.id).collect();
+                    self.removing_timers.extend(ids.iter().copied());
+                    tasks.push(Task::perform(
+                        async move {
+                            let result = runner::discard_missed(database.pool(), &timers, now)
+                                .await
+                                .map_err(AppError::from);
+                            (ids, result)
+                        },
+                        |(ids, result)| {
+                            Action::App(Message::TimerMessage(TimerMessage::Removed(ids, result)))
                         },
-                        |_result| Action::<Message>::None,
                     ));
-                    // We only schedule one deletion per tick; break to avoid multiple concurrent deletes
-                    // We could easily batch them, but 1 second and some local db calls aren't going to be a big deal here
-                    // More noting this to acknowledge potential optimizations XP
-                    break;
                 }
+                Effect::Execute(timer_type) => tasks.push(Self::execute_power_action(&timer_type)),
             }
         }
-
         Task::batch(tasks)
     }
 
+    /// Builds the task that immediately executes a power operation.
+    ///
+    /// User-defined timer types have no power operation and give `Task::none()`.
+    fn execute_power_action(timer_type: &TimerType) -> Task<Action<Message>> {
+        let message = match timer_type {
+            TimerType::Suspend => PowerMessage::ExecuteSuspend,
+            TimerType::Hibernate => PowerMessage::ExecuteHibernate,
+            TimerType::Logout => PowerMessage::ExecuteLogout,
+            TimerType::Shutdown => PowerMessage::ExecuteShutdown,
+            TimerType::Reboot => PowerMessage::ExecuteReboot,
+            TimerType::UserDefined(_) => return Task::none(),
+        };
+        Task::done(Action::App(Message::PowerMessage(message)))
+    }
+
+    /// Runs a keyboard shortcut's action on the page that is showing.
+    ///
+    /// Shortcuts that don't apply to the current page (e.g. pausing on a page
+    /// without sequences) do nothing. Picking a power operation switches to the
+    /// power page first, so the number keys work from anywhere in the popup.
+    ///
+    /// # Arguments
+    ///
+    /// - `action`: The shortcut's action, see [`key_binds::key_binds`]
+    fn handle_shortcut(&mut self, action: MenuAction) -> Task<Action<Message>> {
+        match action {
+            MenuAction::SelectOperation(index) => {
+                let show_page = if self.page == PopupPage::PowerControls {
+                    Task::none()
+                } else {
+                    self.update(Message::ShowPage(PopupPage::PowerControls))
+                };
+                Task::batch([
+                    show_page,
+                    self.handle_power_controls_message(
+                        power_controls::Message::RadioOptionSelected(index),
+                    ),
+                ])
+            }
+            MenuAction::NextOperation | MenuAction::PreviousOperation => {
+                let forward = action == MenuAction::NextOperation;
+                match self.power_controls.adjacent_operation(forward) {
+                    Some(index) if self.page == PopupPage::PowerControls => self
+                        .handle_power_controls_message(
+                            power_controls::Message::RadioOptionSelected(index),
+                        ),
+                    _ => Task::none(),
+                }
+            }
+            MenuAction::Submit if self.quick_entry.is_some() => {
+                self.handle_quick_entry_message(quick_entry::Message::SubmitPressed)
+            }
+            MenuAction::Submit => match self.page {
+                PopupPage::PowerControls => {
+                    self.handle_power_controls_message(power_controls::Message::FormSubmitPressed)
+                }
+                PopupPage::Timers(TimerPage::Sequences) => {
+                    self.handle_sequences_message(sequences::Message::StartPressed)
+                }
+                PopupPage::Timers(TimerPage::Chains) => {
+                    self.handle_chains_message(chains::Message::StartPressed)
+                }
+                PopupPage::Reminders => {
+                    self.handle_reminders_message(reminders::Message::SubmitPressed)
+                }
+                _ => Task::none(),
+            },
+            MenuAction::ClosePopup => {
+                if self.popup.is_some() {
+                    self.toggle_popup().map(|_| Action::None)
+                } else {
+                    Task::none()
+                }
+            }
+            MenuAction::FocusNextItem | MenuAction::FocusPreviousItem => {
+                let forward = action == MenuAction::FocusNextItem;
+                self.focused_item =
+                    key_binds::step_focus(&self.focusable_items(), self.focused_item, forward);
+                Task::none()
+            }
+            MenuAction::CancelFocused => {
+                let Some(id) = self
+                    .focused_item
+                    .filter(|id| self.focusable_items().contains(id))
+                else {
+                    return Task::none();
+                };
+                // Keep the focus in the list, on the row that takes the cancelled one's place
+                let items = self.focusable_items();
+                self.focused_item =
+                    key_binds::step_focus(&items, Some(id), true).filter(|next| *next != id);
+                if self.page == PopupPage::Timers(TimerPage::Sequences) {
+                    self.handle_sequence_message(SequenceMessage::Stop(id))
+                } else {
+                    self.handle_timer_message(TimerMessage::Cancel(id))
+                }
+            }
+            MenuAction::PauseFocused => {
+                let Some(id) = self
+                    .focused_item
+                    .filter(|id| self.focusable_items().contains(id))
+                else {
+                    return Task::none();
+                };
+                if self.page == PopupPage::Timers(TimerPage::Sequences) {
+                    match self.sequences.iter().find(|sequence| sequence.id == id) {
+                        Some(sequence) if sequence.is_paused() => {
+                            self.handle_sequence_message(SequenceMessage::Resume(id))
+                        }
+                        Some(_) => self.handle_sequence_message(SequenceMessage::Pause(id)),
+                        None => Task::none(),
+                    }
+                } else {
+                    match self
+                        .runner
+                        .active_timers
+                        .iter()
+                        .find(|timer| timer.id == id)
+                    {
+                        Some(timer) if timer.is_paused() => {
+                            self.handle_timer_message(TimerMessage::Resume(id))
+                        }
+                        Some(_) => self.handle_timer_message(TimerMessage::Pause(id)),
+                        None => Task::none(),
+                    }
+                }
+            }
+            MenuAction::NextPage | MenuAction::PreviousPage => {
+                let page = self.page.adjacent(action == MenuAction::NextPage);
+                self.update(Message::ShowPage(page))
+            }
+            MenuAction::FocusNextWidget => focus_next(),
+            MenuAction::FocusPreviousWidget => focus_previous(),
+        }
+    }
+
+    /// Gets the ids the arrow keys move the focus through on the current page.
+    ///
+    /// Pages listing timers focus timers (only reminders on the reminders page),
+    /// the sequences page focuses running sequences. Other pages list nothing
+    /// that can be focused.
+    fn focusable_items(&self) -> Vec<i64> {
+        match self.page {
+            PopupPage::PowerControls | PopupPage::Timers(TimerPage::Active | TimerPage::Chains) => {
+                self.runner
+                    .active_timers
+                    .iter()
+                    .map(|timer| timer.id)
+                    .collect()
+            }
+            PopupPage::Reminders => self
+                .runner
+                .active_timers
+                .iter()
+                .filter(|timer| Reminders::is_reminder(timer))
+                .map(|timer| timer.id)
+                .collect(),
+            PopupPage::Timers(TimerPage::Sequences) => {
+                self.sequences.iter().map(|sequence| sequence.id).collect()
+            }
+            PopupPage::Timers(TimerPage::Stopwatch) | PopupPage::History | PopupPage::Settings => {
+                vec![]
+            }
+        }
+    }
+
     /// Routes power controls page messages to the appropriate handler.
     ///
     /// This function translates page-level messages from the power controls UI
@@ -490,8 +1890,9 @@ impl AppModel {
     ///
     /// # Current Routing
     ///
-    /// - Timer creation messages → `handle_power_message()`
-    /// - Stay awake toggle → `handle_power_message()`
+    /// - Power timer creation messages → `handle_power_message()`
+    /// - Notification timer creation → `handle_timer_message()`
+    /// - Stay awake and media stay awake toggles → `handle_power_message()`
     /// - Component messages → `power_controls.update()`
     ///
     /// # Future Expansion
@@ -506,17 +1907,26 @@ impl AppModel {
             power_controls::Message::ToggleStayAwake => {
                 self.handle_power_message(PowerMessage::ToggleStayAwake)
             }
-            power_controls::Message::SetSuspendTime(time) => {
-                self.handle_power_message(PowerMessage::SetSuspendTime(time))
+            power_controls::Message::SetMediaStayAwake(enabled) => {
+                self.handle_power_message(PowerMessage::SetMediaStayAwake(enabled))
             }
-            power_controls::Message::SetShutdownTime(time) => {
-                self.handle_power_message(PowerMessage::SetShutdownTime(time))
+            power_controls::Message::SetSuspendTime(time, condition) => {
+                self.handle_power_message(PowerMessage::SetSuspendTime(time, condition))
             }
-            power_controls::Message::SetLogoutTime(time) => {
-                self.handle_power_message(PowerMessage::SetLogoutTime(time))
+            power_controls::Message::SetHibernateTime(time, condition) => {
+                self.handle_power_message(PowerMessage::SetHibernateTime(time, condition))
             }
-            power_controls::Message::SetRebootTime(time) => {
-                self.handle_power_message(PowerMessage::SetRebootTime(time))
+            power_controls::Message::SetShutdownTime(time, condition) => {
+                self.handle_power_message(PowerMessage::SetShutdownTime(time, condition))
+            }
+            power_controls::Message::SetLogoutTime(time, condition) => {
+                self.handle_power_message(PowerMessage::SetLogoutTime(time, condition))
+            }
+            power_controls::Message::SetRebootTime(time, condition) => {
+                self.handle_power_message(PowerMessage::SetRebootTime(time, condition))
+            }
+            power_controls::Message::SetNotifyTime(time, condition) => {
+                self.handle_timer_message(TimerMessage::SetNotifyTime(time, condition))
             }
             power_controls::Message::ClosePopup => {
                 let close_task = self.toggle_popup();
@@ -532,81 +1942,1196 @@ impl AppModel {
         }
     }
 
-    /// Handles database-related messages.
+    /// Routes sequences page messages to the appropriate handler.
+    ///
+    /// Start, pause, resume, skip and stop requests go to `handle_sequence_message()`,
+    /// editor messages are passed to the page's update method.
+    fn handle_sequences_message(&mut self, msg: sequences::Message) -> Task<Action<Message>> {
+        match msg {
+            sequences::Message::StartPressed => {
+                match self.sequences_page.to_sequence(self.clock.now()) {
+                    Some(sequence) => {
+                        self.handle_sequence_message(SequenceMessage::Start(sequence))
+                    }
+                    // Keep the draft around so it can be fixed instead of retyped
+                    None => Task::none(),
+                }
+            }
+            sequences::Message::Start(sequence) => {
+                self.handle_sequence_message(SequenceMessage::Start(sequence))
+            }
+            sequences::Message::Pause(id) => {
+                self.handle_sequence_message(SequenceMessage::Pause(id))
+            }
+            sequences::Message::Resume(id) => {
+                self.handle_sequence_message(SequenceMessage::Resume(id))
+            }
+            sequences::Message::Skip(id) => self.handle_sequence_message(SequenceMessage::Skip(id)),
+            sequences::Message::Stop(id) => self.handle_sequence_message(SequenceMessage::Stop(id)),
+            // Let the page handle its own state updates
+            _ => self.sequences_page.update(msg).map(|action| match action {
+                Action::App(page_msg) => Action::App(Message::SequencesMessage(page_msg)),
+                Action::None => Action::None,
+                Action::Cosmic(cosmic_action) => Action::Cosmic(cosmic_action),
+                Action::DbusActivation(dbus_action) => Action::DbusActivation(dbus_action),
+            }),
+        }
+    }
+
+    /// Routes settings page messages to the appropriate handler.
+    ///
+    /// Changing a setting and saving or removing presets updates the config, which
+    /// is written to disk right away. Form messages are passed to the page's update method.
+    fn handle_settings_message(&mut self, msg: settings::Message) -> Task<Action<Message>> {
+        match msg {
+            settings::Message::Save(index, preset) => {
+                let mut presets = self.config.quick_timers.clone();
+                match index.and_then(|index| presets.get_mut(index)) {
+                    Some(existing) => *existing = preset,
+                    None => presets.push(preset),
+                }
+                self.set_quick_timers(presets);
+                self.settings_page
+                    .update(settings::Message::ClearForm)
+                    .map(|_| Action::None)
+            }
+            settings::Message::Remove(index) => {
+                let mut presets = self.config.quick_timers.clone();
+                if index < presets.len() {
+                    presets.remove(index);
+                }
+                self.set_quick_timers(presets);
+                // The form may point at a preset that moved or no longer exists
+                self.settings_page
+                    .update(settings::Message::ClearForm)
+                    .map(|_| Action::None)
+            }
+            settings::Message::Set(setting) => {
+                self.write_config(|config| setting.apply(config));
+                if let Setting::DefaultUnit(unit) = setting {
+                    self.power_controls.power_form.set_default_unit(unit);
+                }
+                Task::none()
+            }
+            // Let the page handle its own state updates
+            _ => self.settings_page.update(msg).map(|action| match action {
+                Action::App(page_msg) => Action::App(Message::SettingsMessage(page_msg)),
+                Action::None => Action::None,
+                Action::Cosmic(cosmic_action) => Action::Cosmic(cosmic_action),
+                Action::DbusActivation(dbus_action) => Action::DbusActivation(dbus_action),
+            }),
+        }
+    }
+
+    /// Replaces the quick timer presets and writes them to the config store.
+    fn set_quick_timers(&mut self, presets: Vec<QuickTimerPreset>) {
+        self.write_config(|config| config.quick_timers = presets);
+    }
+
+    /// Changes the config and writes it to the config store.
+    ///
+    /// The in-memory config is updated even when writing fails, so the change
+    /// holds for this session.
+    ///
+    /// # Arguments
+    ///
+    /// - `change`: Applies the change to the config
+    fn write_config(&mut self, change: impl FnOnce(&mut Config)) {
+        change(&mut self.config);
+        if let Some(handler) = &self.config_handler
+            && let Err(e) = self.config.write_entry(handler)
+        {
+            tracing::error!("Failed to save settings: {e}");
+        }
+    }
+
+    /// Routes reminders page messages to the appropriate handler.
+    ///
+    /// Creating a reminder makes a user-defined timer with the reminder's text and
+    /// clears the form; cancelling one works like cancelling any timer. Form
+    /// messages are passed to the page's update method.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The reminders page message to handle.
+    fn handle_reminders_message(&mut self, msg: reminders::Message) -> Task<Action<Message>> {
+        match msg {
+            reminders::Message::Create(time, text) => {
+                let _ = self.reminders_page.update(reminders::Message::ClearForm);
+                self.create_power_timer(
+                    time,
+                    &TimerType::UserDefined(text),
+                    "Reminder Set",
+                    "You will be reminded in",
+                    "alarm-symbolic",
+                    TimerCondition::None,
+                )
+            }
+            reminders::Message::Cancel(id) => self.handle_timer_message(TimerMessage::Cancel(id)),
+            // Let the page handle its own state updates
+            _ => self.reminders_page.update(msg).map(|action| match action {
+                Action::App(page_msg) => Action::App(Message::RemindersMessage(page_msg)),
+                Action::None => Action::None,
+                Action::Cosmic(cosmic_action) => Action::Cosmic(cosmic_action),
+                Action::DbusActivation(dbus_action) => Action::DbusActivation(dbus_action),
+            }),
+        }
+    }
+
+    /// Opens the popup with the quick entry prompt, focusing its input.
+    ///
+    /// An open popup switches to the prompt in place, and a half typed entry is kept.
+    fn open_quick_entry(&mut self) -> Task<Action<Message>> {
+        self.quick_entry.get_or_insert_with(QuickEntry::default);
+        let open_popup = if self.popup.is_none() {
+            self.toggle_popup().map(|_| Action::None)
+        } else {
+            Task::none()
+        };
+        Task::batch([
+            open_popup,
+            cosmic::widget::text_input::focus(quick_entry::input_id()),
+        ])
+    }
+
+    /// Routes quick entry prompt messages to the appropriate handler.
+    ///
+    /// Creating a timer closes the prompt and goes through `create_power_timer()`,
+    /// like the power form and reminders do, or `create_wall_clock_timer()` for a
+    /// time of day. Typing is passed to the prompt's update method.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The quick entry message to handle.
+    fn handle_quick_entry_message(&mut self, msg: quick_entry::Message) -> Task<Action<Message>> {
+        let Some(page) = self.quick_entry.as_mut() else {
+            return Task::none();
+        };
+        match msg {
+            quick_entry::Message::Create(when, timer_type) => {
+                self.quick_entry = None;
+                let close_popup = if self.popup.is_some() {
+                    self.toggle_popup().map(|_| Action::None)
+                } else {
+                    Task::none()
+                };
+                let create = match when {
+                    quick_entry::When::In(time) => self.create_power_timer(
+                        time,
+                        &timer_type,
+                        "Timer Set",
+                        "Runs in",
+                        "alarm-symbolic",
+                        TimerCondition::None,
+                    ),
+                    quick_entry::When::At(time) => self.create_wall_clock_timer(time, &timer_type),
+                };
+                Task::batch([close_popup, create])
+            }
+            // Let the prompt handle its own state updates
+            _ => page.update(msg).map(|action| match action {
+                Action::App(page_msg) => Action::App(Message::QuickEntryMessage(page_msg)),
+                Action::None => Action::None,
+                Action::Cosmic(cosmic_action) => Action::Cosmic(cosmic_action),
+                Action::DbusActivation(dbus_action) => Action::DbusActivation(dbus_action),
+            }),
+        }
+    }
+
+    /// Routes chains page messages to the appropriate handler.
+    ///
+    /// Starting a chain goes to `handle_timer_message()`, editor messages are
+    /// passed to the page's update method.
+    fn handle_chains_message(&mut self, msg: chains::Message) -> Task<Action<Message>> {
+        match msg {
+            chains::Message::StartPressed => match self.chains_page.to_timers(self.clock.now()) {
+                Some(timers) => self.handle_timer_message(TimerMessage::StartChain(timers)),
+                // Keep the draft around so it can be fixed instead of retyped
+                None => Task::none(),
+            },
+            chains::Message::Start(timers) => {
+                self.handle_timer_message(TimerMessage::StartChain(timers))
+            }
+            // Let the page handle its own state updates
+            _ => self.chains_page.update(msg).map(|action| match action {
+                Action::App(page_msg) => Action::App(Message::ChainsMessage(page_msg)),
+                Action::None => Action::None,
+                Action::Cosmic(cosmic_action) => Action::Cosmic(cosmic_action),
+                Action::DbusActivation(dbus_action) => Action::DbusActivation(dbus_action),
+            }),
+        }
+    }
+
+    /// Handles sequence timer messages.
+    ///
+    /// Starting stores the sequence before it shows up as running, every other
+    /// change is applied in memory right away and then saved.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The sequence message to handle.
+    ///
+    /// # Returns
+    ///
+    /// Task representing the action to be performed.
+    fn handle_sequence_message(&mut self, msg: SequenceMessage) -> Task<Action<Message>> {
+        let now = self.clock.now();
+
+        match msg {
+            SequenceMessage::Start(sequence) => {
+                let Some(database) = self.database.clone() else {
+                    tracing::warn!("Database not yet available");
+                    return Task::none();
+                };
+                if let Some(phase) = sequence.current_phase() {
+                    self.send_notification(
+                        &format!("{} Started", sequence.name),
+                        &format!(
+                            "{} for {}",
+                            phase.name,
+                            format_duration(
+                                i32::try_from(phase.duration_seconds).unwrap_or(i32::MAX)
+                            )
+                        ),
+                        "alarm-symbolic",
+                    );
+                }
+
+                return Task::batch(vec![
+                    Task::done(Action::App(Message::SequencesMessage(
+                        sequences::Message::ClearForm,
+                    ))),
+                    Task::perform(
+                        async move {
+                            Sequence::insert(database.pool(), &sequence)
+                                .await
+                                .map_err(AppError::from)
+                        },
+                        |result| {
+                            Action::App(Message::SequenceMessage(SequenceMessage::Created(result)))
+                        },
+                    ),
+                ]);
+            }
+            SequenceMessage::Created(result) => match result {
+                Ok(sequence) => self.sequences.push(sequence),
+                Err(err) => self.report_error(fl!("error-create-timer"), err),
+            },
+            SequenceMessage::ActiveFetched(result) => match result {
+                Ok(sequences) => self.sequences = sequences,
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+            SequenceMessage::Pause(id) => {
+                if let Some(sequence) = self.sequences.iter_mut().find(|s| s.id == id) {
+                    sequence.pause(now);
+                }
+                return self.persist_sequence(id);
+            }
+            SequenceMessage::Resume(id) => {
+                if let Some(sequence) = self.sequences.iter_mut().find(|s| s.id == id) {
+                    sequence.resume(now);
+                }
+                return self.persist_sequence(id);
+            }
+            SequenceMessage::Skip(id) => return self.advance_sequence(id, now, false),
+            SequenceMessage::Stop(id) => {
+                self.sequences.retain(|s| s.id != id);
+                return self.delete_sequence(id);
+            }
+        }
+        Task::none()
+    }
+
+    /// Moves a sequence on to its next phase, removing it once all phases are done.
+    ///
+    /// # Arguments
+    ///
+    /// - `id`: The sequence to advance
+    /// - `now`: Current Unix timestamp the next phase is timed from
+    /// - `notify`: Whether to announce the phase boundary (not wanted for manual skips)
+    ///
+    /// # Returns
+    ///
+    /// A task saving the new progress, or deleting the finished sequence.
+    fn advance_sequence(&mut self, id: i64, now: i64, notify: bool) -> Task<Action<Message>> {
+        let Some(index) = self.sequences.iter().position(|s| s.id == id) else {
+            return Task::none();
+        };
+        let sequence = &mut self.sequences[index];
+        let finished = sequence
+            .current_phase()
+            .map(|phase| phase.name.clone())
+            .unwrap_or_default();
+
+        match sequence
+            .advance(now)
+            .map(|phase| (phase.name.clone(), phase.duration_seconds))
+        {
+            Some((next, duration)) => {
+                if notify {
+                    self.send_notification(
+                        &format!("{finished} Finished"),
+                        &format!(
+                            "{next} for {}",
+                            format_duration(i32::try_from(duration).unwrap_or(i32::MAX))
+                        ),
+                        "alarm-symbolic",
+                    );
+                }
+                self.persist_sequence(id)
+            }
+            None => {
+                let sequence = self.sequences.remove(index);
+                if notify {
+                    self.send_notification(
+                        &format!("{} Finished", sequence.name),
+                        "All phases are done",
+                        "alarm-symbolic",
+                    );
+                }
+                self.delete_sequence(id)
+            }
+        }
+    }
+
+    /// Saves a sequence's progress in the background.
+    fn persist_sequence(&self, id: i64) -> Task<Action<Message>> {
+        let (Some(database), Some(sequence)) = (
+            self.database.clone(),
+            self.sequences.iter().find(|s| s.id == id).cloned(),
+        ) else {
+            return Task::none();
+        };
+
+        Task::perform(
+            async move { Sequence::update_progress(database.pool(), &sequence).await },
+            |result| {
+                if let Err(e) = result {
+                    tracing::error!("Failed to save sequence: {e}");
+                }
+                Action::None
+            },
+        )
+    }
+
+    /// Deletes a sequence and its phases in the background.
+    fn delete_sequence(&self, id: i64) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone() else {
+            return Task::none();
+        };
+
+        Task::perform(
+            async move { Sequence::delete_by_id(database.pool(), &id).await },
+            |result| {
+                if let Err(e) = result {
+                    tracing::error!("Failed to delete sequence: {e}");
+                }
+                Action::None
+            },
+        )
+    }
+
+    /// Handles stopwatch messages.
+    ///
+    /// The stopwatch is stored the first time it starts. After that every control
+    /// is applied in memory right away and then saved, and reset deletes it again.
+    /// Start and reset wait for the first store to finish, since without an `id`
+    /// they would store a second stopwatch or leave the first one behind.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The stopwatch message to handle.
+    ///
+    /// # Returns
+    ///
+    /// Task representing the action to be performed.
+    fn handle_stopwatch_message(&mut self, msg: StopwatchMessage) -> Task<Action<Message>> {
+        let now_ms = self.clock.now_millis();
+
+        match msg {
+            StopwatchMessage::Start => {
+                if self.stopwatch_inserting {
+                    return Task::none();
+                }
+                let Some(database) = self.database.clone() else {
+                    tracing::warn!("Database not yet available");
+                    return Task::none();
+                };
+                self.stopwatch.start(now_ms);
+                if self.stopwatch.id == 0 {
+                    self.stopwatch_inserting = true;
+                    let stopwatch = self.stopwatch.clone();
+                    return Task::perform(
+                        async move {
+                            Stopwatch::insert(database.pool(), &stopwatch)
+                                .await
+                                .map_err(AppError::from)
+                        },
+                        |result| {
+                            Action::App(Message::StopwatchMessage(StopwatchMessage::Created(
+                                result,
+                            )))
+                        },
+                    );
+                }
+                return self.save_stopwatch(0);
+            }
+            StopwatchMessage::Stop => {
+                self.stopwatch.stop(now_ms);
+                return self.save_stopwatch(0);
+            }
+            StopwatchMessage::Lap => {
+                let laps = self.stopwatch.laps.len();
+                if self.stopwatch.lap(now_ms).is_some() {
+                    return self.save_stopwatch(laps);
+                }
+            }
+            StopwatchMessage::Reset => {
+                if self.stopwatch_inserting {
+                    return Task::none();
+                }
+                let id = self.stopwatch.id;
+                self.stopwatch = Stopwatch::default();
+                if let Some(database) = self.database.clone()
+                    && id != 0
+                {
+                    return Task::perform(
+                        async move { Stopwatch::delete_by_id(database.pool(), &id).await },
+                        |result| {
+                            if let Err(e) = result {
+                                tracing::error!("Failed to reset stopwatch: {e}");
+                            }
+                            Action::None
+                        },
+                    );
+                }
+            }
+            StopwatchMessage::Created(result) => {
+                self.stopwatch_inserting = false;
+                match result {
+                    Ok(stored) => {
+                        self.stopwatch.id = stored.id;
+                        // Catch up with anything pressed while the stopwatch was being stored
+                        return self.save_stopwatch(stored.laps.len());
+                    }
+                    Err(err) => self.report_error(fl!("error-save"), err),
+                }
+            }
+            StopwatchMessage::Fetched(result) => match result {
+                Ok(stopwatches) => {
+                    if let Some(stopwatch) = stopwatches.into_iter().next() {
+                        self.stopwatch = stopwatch;
+                    }
+                }
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+        }
+        Task::none()
+    }
+
+    /// Saves the stopwatch state and any laps from `first_lap` on in the background.
+    ///
+    /// Does nothing until the stopwatch has been stored for the first time.
+    fn save_stopwatch(&self, first_lap: usize) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone().filter(|_| self.stopwatch.id != 0) else {
+            return Task::none();
+        };
+        let stopwatch = self.stopwatch.clone();
+
+        Task::perform(
+            async move {
+                Stopwatch::update_state(database.pool(), &stopwatch).await?;
+                for lap in stopwatch.laps.iter().skip(first_lap) {
+                    Stopwatch::insert_lap(database.pool(), stopwatch.id, lap).await?;
+                }
+                anyhow::Ok(())
+            },
+            |result| {
+                if let Err(e) = result {
+                    tracing::error!("Failed to save stopwatch: {e}");
+                }
+                Action::None
+            },
+        )
+    }
+
+    /// Handles database-related messages.
+    ///
+    /// This function processes messages related to database initialization, CRUD operations, and error handling.
+    /// These are app level messages exclusive to the database layer.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The database message to handle.
+    ///
+    /// # Returns
+    ///
+    /// A task representing the action to be performed.
+    fn handle_database_message(&mut self, msg: DatabaseMessage) -> Task<Action<Message>> {
+        match msg {
+            DatabaseMessage::Initialized(result) => {
+                if let Ok(db) = result {
+                    tracing::debug!(?db, "Database initialized");
+                    self.database = Some(db);
+
+                    // Fetch timers, chain links and the history, then sequences and the stopwatch
+                    if let Some(database) = self.database.clone() {
+                        let sequences_database = database.clone();
+                        let stopwatch_database = database;
+                        let now = self.clock.now();
+                        return Task::batch(vec![
+                            self.fetch_timers(true),
+                            Task::perform(
+                                async move {
+                                    Sequence::get_all_active(sequences_database.pool(), now)
+                                        .await
+                                        .map_err(AppError::from)
+                                },
+                                |result| {
+                                    Action::App(Message::SequenceMessage(
+                                        SequenceMessage::ActiveFetched(result),
+                                    ))
+                                },
+                            ),
+                            Task::perform(
+                                async move {
+                                    Stopwatch::get_all_active(stopwatch_database.pool(), now)
+                                        .await
+                                        .map_err(AppError::from)
+                                },
+                                |result| {
+                                    Action::App(Message::StopwatchMessage(
+                                        StopwatchMessage::Fetched(result),
+                                    ))
+                                },
+                            ),
+                        ]);
+                    }
+                }
+            }
+            DatabaseMessage::FailedToInitialize(err) => {
+                self.report_error(fl!("error-database"), err);
+            }
+            DatabaseMessage::Changed => return self.fetch_timers(false),
+        }
+        Task::none()
+    }
+
+    /// Handles messages from the D-Bus timer service.
+    ///
+    /// The service changes the database itself, so timer changes only need a
+    /// reload. Stay-awake lives in the app and is toggled here.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The service message to handle.
+    fn handle_service_message(&mut self, msg: ServiceMessage) -> Task<Action<Message>> {
+        match msg {
+            ServiceMessage::Started(connection) => {
+                self.service = Some(connection);
+                Task::none()
+            }
+            ServiceMessage::Event(ServiceEvent::TimersChanged) => self.fetch_timers(false),
+            ServiceMessage::Event(ServiceEvent::ToggleStayAwake) => {
+                self.handle_power_message(PowerMessage::ToggleStayAwake)
+            }
+            ServiceMessage::Event(ServiceEvent::SetStayAwake(enabled)) => {
+                self.set_stay_awake(enabled)
+            }
+            ServiceMessage::Event(ServiceEvent::RunPendingAction) => {
+                self.handle_power_message(PowerMessage::RunPendingAction)
+            }
+            ServiceMessage::Event(ServiceEvent::CancelPendingAction) => {
+                self.handle_power_message(PowerMessage::CancelPendingAction)
+            }
+            ServiceMessage::Event(ServiceEvent::AdoptPendingAction(pending)) => {
+                let effects = self.runner.adopt_pending_action(pending);
+                self.apply(effects)
+            }
+            ServiceMessage::Update(Update::Owner(owner)) => {
+                if owner == self.scheduling {
+                    return Task::none();
+                }
+                self.scheduling = owner;
+                if owner {
+                    // chronomancerd stopped, its stay-awake lock and pending action went with it
+                    self.daemon_stay_awake = false;
+                    self.runner.pending_action = None;
+                    return self.fetch_timers(false);
+                }
+                self.hand_over_to_daemon()
+            }
+            ServiceMessage::Update(Update::StayAwakeChanged(enabled)) => {
+                if !self.scheduling {
+                    self.daemon_stay_awake = enabled;
+                }
+                Task::none()
+            }
+            ServiceMessage::Update(Update::PendingActionChanged(pending)) => {
+                if !self.scheduling {
+                    self.runner.pending_action = pending;
+                }
+                Task::none()
+            }
+            ServiceMessage::StateFetched(result) => {
+                match result {
+                    Ok((stay_awake, pending)) if !self.scheduling => {
+                        self.daemon_stay_awake = stay_awake;
+                        self.runner.pending_action = pending;
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        tracing::warn!("Failed to fetch the state of chronomancerd: {err}");
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Steps back after `chronomancerd` took over the timer service.
+    ///
+    /// The daemon runs the timers from now on, so stay-awake and a power action
+    /// still waiting here move over to it. Its own stay-awake and pending action
+    /// are fetched to show them.
+    fn hand_over_to_daemon(&mut self) -> Task<Action<Message>> {
+        let stay_awake = match self.suspend_inhibitor.take() {
+            Some(inhibitor) => {
+                resources::release_suspend_inhibit(inhibitor);
+                self.request(Request::SetStayAwake(true))
+            }
+            None => Task::none(),
+        };
+        // Its timer is already gone, so the daemon is the only one left to run it
+        let pending = match self.runner.pending_action.clone() {
+            Some(pending) => self.request(Request::AdoptPendingAction(pending)),
+            None => Task::none(),
+        };
+
+        let Some(connection) = self.service.clone() else {
+            return Task::batch([stay_awake, pending]);
+        };
+        Task::batch([
+            stay_awake,
+            pending,
+            Task::perform(
+                async move {
+                    service::fetch_state(&connection)
+                        .await
+                        .map_err(AppError::from)
+                },
+                |result| {
+                    Action::App(Message::ServiceMessage(ServiceMessage::StateFetched(
+                        result,
+                    )))
+                },
+            ),
+        ])
+    }
+
+    /// Sends a request to `chronomancerd` while it runs the timers.
+    ///
+    /// # Arguments
+    ///
+    /// - `request`: What the daemon should do
+    fn request(&self, request: Request) -> Task<Action<Message>> {
+        let Some(connection) = self.service.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                service::request(&connection, request)
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| {
+                if let Err(e) = result {
+                    tracing::error!("Failed to reach chronomancerd: {e}");
+                }
+                Action::None
+            },
+        )
+    }
+
+    /// Sends a signal from the D-Bus timer service, if it is published.
+    ///
+    /// # Arguments
+    ///
+    /// - `signal`: What happened
+    fn emit_signal(&self, signal: Signal) -> Task<Action<Message>> {
+        let Some(connection) = self.service.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                service::emit(&connection, signal)
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| {
+                if let Err(e) = result {
+                    tracing::warn!("Failed to send D-Bus signal: {e}");
+                }
+                Action::None
+            },
+        )
+    }
+
+    /// Reads the user's systemd timers for the popup's timer list.
     ///
-    /// This function processes messages related to database initialization, CRUD operations, and error handling.
-    /// These are app level messages exclusive to the database layer.
+    /// Timers whose schedule can't be translated are left out, see
+    /// [`systemd::ImportedTimer::to_timer`].
+    fn fetch_systemd_timers(&self) -> Task<Action<Message>> {
+        let now = self.clock.now();
+        Task::perform(
+            async move {
+                let dir = systemd::user_unit_dir()?;
+                let timers = systemd::read_timers(&dir, now)?
+                    .into_iter()
+                    .filter_map(|unit| Some((unit.name.clone(), unit.to_timer(now)?)))
+                    .collect();
+                Ok(timers)
+            },
+            |result: anyhow::Result<Vec<(String, Timer)>>| {
+                Action::App(Message::TimerMessage(TimerMessage::SystemdFetched(
+                    result.map_err(AppError::from),
+                )))
+            },
+        )
+    }
+
+    /// Fetches the running timers, waiting chain links and the history from the database.
+    ///
+    /// Runs once the database is open, leaving timers that ended in the
+    /// meantime to the missed timer policy. It runs again whenever the database
+    /// changed, e.g. through the `chronomancer` CLI or the D-Bus service; those
+    /// reloads fetch every running timer and leave overdue ones to the tick,
+    /// since they ended while the applet was running. The fetched lists
+    /// replace the ones in memory, minus timers the applet is still removing.
+    /// A reload that started before one of the applet's own writes landed is
+    /// fetched again, since it may still list timers that finished meanwhile.
     ///
     /// # Arguments
     ///
-    /// - `msg`: The database message to handle.
+    /// - `startup`: Whether this is the first load after the database opened
     ///
     /// # Returns
     ///
-    /// A task representing the action to be performed.
-    fn handle_database_message(&mut self, msg: DatabaseMessage) -> Task<Action<Message>> {
+    /// The fetch tasks, or `Task::none()` while the database isn't open yet.
+    fn fetch_timers(&self, startup: bool) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone() else {
+            return Task::none();
+        };
+        let chained_database = database.clone();
+        let history_database = database.clone();
+        let now = self.clock.now();
+        let version = self.timers_version;
+        let running = if startup {
+            Task::perform(
+                async move {
+                    Timer::get_all_active(database.pool(), now)
+                        .await
+                        .map_err(AppError::from)
+                },
+                |result| Action::App(Message::TimerMessage(TimerMessage::ActiveFetched(result))),
+            )
+        } else {
+            Task::perform(
+                async move {
+                    Timer::get_all_running(database.pool())
+                        .await
+                        .map_err(AppError::from)
+                },
+                move |result| {
+                    Action::App(Message::TimerMessage(TimerMessage::Reloaded(
+                        version, result,
+                    )))
+                },
+            )
+        };
+        Task::batch([
+            running,
+            Task::perform(
+                async move {
+                    Timer::get_all_chained(chained_database.pool())
+                        .await
+                        .map_err(AppError::from)
+                },
+                |result| Action::App(Message::TimerMessage(TimerMessage::ChainedFetched(result))),
+            ),
+            Task::perform(
+                async move {
+                    HistoryEntry::get_all_active(history_database.pool(), now)
+                        .await
+                        .map_err(AppError::from)
+                },
+                |result| Action::App(Message::HistoryMessage(HistoryMessage::Fetched(result))),
+            ),
+        ])
+    }
+
+    /// Handles timer-related messages.
+    ///
+    /// This function processes messages related to timer creation and fetching active timers.
+    /// This is app level messages exclusive to timer creation and management.
+    /// This is not where the timer ticks are handled as that is done with a subscription, not message.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The timer message to handle.
+    ///
+    /// # Returns
+    ///
+    /// Task representing the action to be performed.
+    fn handle_timer_message(&mut self, msg: TimerMessage) -> Task<Action<Message>> {
         match msg {
-            DatabaseMessage::Initialized(result) => {
-                if let Ok(db) = result {
-                    println!("Database initialized successfully: {db:?}");
-                    self.database = Some(db);
+            TimerMessage::Created(result) => match result {
+                Ok(timer) => {
+                    self.timers_version += 1;
+                    tracing::info!(
+                        id = timer.id,
+                        description = %timer.description,
+                        ends_at = timer.ends_at,
+                        "Timer created"
+                    );
+                    self.runner.add_timers([timer.clone()], self.clock.now());
+                    return self.emit_signal(Signal::TimerCreated(timer));
+                }
+                Err(err) => self.report_error(fl!("error-create-timer"), err),
+            },
+            TimerMessage::ActiveFetched(result) => match result {
+                Ok(timers) => {
+                    self.runner.set_timers(timers, self.clock.now());
 
-                    // Fetch active timers from the database
+                    // Fetch missed timers only now, so running them isn't undone by this list
                     if let Some(database) = self.database.clone() {
+                        let now = self.clock.now();
                         return Task::perform(
                             async move {
-                                Timer::get_all_active(database.pool())
+                                Timer::get_all_missed(database.pool(), now)
                                     .await
-                                    .map_err(|e| e.to_string())
+                                    .map_err(AppError::from)
                             },
                             |result| {
-                                Action::App(Message::TimerMessage(TimerMessage::ActiveFetched(
+                                Action::App(Message::TimerMessage(TimerMessage::MissedFetched(
                                     result,
                                 )))
                             },
                         );
                     }
                 }
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+            // A write landed since the fetch started, so the list may bring back
+            // timers that finished in between; fetch again instead
+            TimerMessage::Reloaded(version, _) if version != self.timers_version => {
+                return self.fetch_timers(false);
             }
-            DatabaseMessage::FailedToInitialize(err) => {
-                eprintln!("Failed to initialize database: {err}");
-                // todo: figure out how tf to notify user appropriately in applets
+            TimerMessage::Reloaded(_, result) => match result {
+                Ok(timers) => {
+                    // Keep timers that are already on their way out from coming back
+                    let timers = timers
+                        .into_iter()
+                        .filter(|t| !self.removing_timers.contains(&t.id))
+                        .collect();
+                    self.runner.set_timers(timers, self.clock.now());
+                }
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+            TimerMessage::ChainedFetched(result) => match result {
+                Ok(mut timers) => {
+                    // A link armed since the fetch started is already running
+                    timers
+                        .retain(|link| !self.runner.active_timers.iter().any(|t| t.id == link.id));
+                    self.chained_timers = timers;
+                }
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+            TimerMessage::MissedFetched(result) => match result {
+                Ok(timers) if !timers.is_empty() => return self.handle_missed_timers(timers),
+                Ok(_) => {}
+                Err(err) => self.report_error(fl!("error-load"), err),
+            },
+            TimerMessage::StartChain(timers) => return self.start_chain(timers),
+            TimerMessage::ChainCreated(result) => match result {
+                Ok(mut timers) if !timers.is_empty() => {
+                    self.timers_version += 1;
+                    let head = timers.remove(0);
+                    self.chained_timers.extend(timers);
+                    self.runner.add_timers([head.clone()], self.clock.now());
+                    return self.emit_signal(Signal::TimerCreated(head));
+                }
+                Ok(_) => {}
+                Err(err) => self.report_error(fl!("error-create-timer"), err),
+            },
+            TimerMessage::Completed(id, result) => match result {
+                Ok(next) => {
+                    self.timers_version += 1;
+                    self.removing_timers.remove(&id);
+                    if let Some(next) = next {
+                        self.chained_timers.retain(|t| t.id != next.id);
+                        let effects = self.runner.armed(next, self.clock.now());
+                        return self.apply(effects);
+                    }
+                }
+                // The timer is still stored, so reloads keep skipping it rather than running it again
+                Err(err) => self.report_error(fl!("error-save"), err),
+            },
+            TimerMessage::Removed(ids, result) => match result {
+                Ok(()) => {
+                    self.timers_version += 1;
+                    for id in &ids {
+                        self.removing_timers.remove(id);
+                    }
+                }
+                Err(err) => self.report_error(fl!("error-save"), err),
+            },
+            TimerMessage::Cancel(id) => {
+                let timer = self
+                    .runner
+                    .active_timers
+                    .iter()
+                    .find(|t| t.id == id)
+                    .cloned();
+                // Drop the rest of the chain in memory too, so the list updates right away
+                let links: Vec<i64> = timer
+                    .as_ref()
+                    .map(|timer| {
+                        timer_list::chain_after(timer, &self.chained_timers)
+                            .iter()
+                            .map(|link| link.id)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                self.runner.remove_timers(&[id], self.clock.now());
+                self.chained_timers.retain(|t| !links.contains(&t.id));
+
+                if let Some(database) = self.database.clone() {
+                    let now = self.clock.now();
+                    self.removing_timers.insert(id);
+                    return Task::batch([
+                        Task::perform(
+                            async move {
+                                Timer::delete_chain(database.pool(), id)
+                                    .await
+                                    .map_err(AppError::from)
+                            },
+                            move |result| {
+                                Action::App(Message::TimerMessage(TimerMessage::Removed(
+                                    vec![id],
+                                    result,
+                                )))
+                            },
+                        ),
+                        timer.map_or_else(Task::none, |timer| {
+                            tracing::info!(
+                                id = timer.id,
+                                description = %timer.description,
+                                "Timer cancelled"
+                            );
+                            Task::batch([
+                                self.record_history(HistoryEntry::new(
+                                    &timer,
+                                    Outcome::Cancelled,
+                                    now,
+                                )),
+                                self.emit_signal(Signal::TimerCancelled(timer)),
+                            ])
+                        }),
+                    ]);
+                }
+            }
+            TimerMessage::Export(id) => {
+                let Some(database) = self.database.clone() else {
+                    return Task::none();
+                };
+                let now = self.clock.now();
+                return Task::perform(
+                    async move {
+                        systemd::export(database.pool(), id, now)
+                            .await
+                            .map(|_| ())
+                            .map_err(AppError::from)
+                    },
+                    move |result| {
+                        Action::App(Message::TimerMessage(TimerMessage::Exported(id, result)))
+                    },
+                );
+            }
+            TimerMessage::Exported(id, result) => match result {
+                Ok(()) => {
+                    // The timer was deleted, systemd runs it from here on
+                    tracing::info!(id, "Timer exported to systemd");
+                    self.timers_version += 1;
+                    self.runner.remove_timers(&[id], self.clock.now());
+                    return self.fetch_systemd_timers();
+                }
+                Err(err) => self.report_error(fl!("error-export"), err),
+            },
+            TimerMessage::SystemdFetched(result) => match result {
+                Ok(timers) => self.systemd_timers = timers,
+                Err(err) => tracing::warn!("Failed to read systemd timers: {err}"),
+            },
+            TimerMessage::Pause(id) => return self.set_timer_paused(id, true),
+            TimerMessage::Resume(id) => return self.set_timer_paused(id, false),
+            TimerMessage::PauseStored(result) => match result {
+                Ok(stored) => {
+                    self.timers_version += 1;
+                    if let Some(timer) = self
+                        .runner
+                        .active_timers
+                        .iter_mut()
+                        .find(|t| t.id == stored.id)
+                    {
+                        *timer = stored;
+                    }
+                }
+                Err(err) => {
+                    self.report_error(fl!("error-save"), err);
+                    // Put the list back in line with what is stored
+                    return self.fetch_timers(false);
+                }
+            },
+            TimerMessage::SetNotifyTime(time, condition) => {
+                let description = AppModel::notification_text(&condition);
+                return self.create_power_timer(
+                    time,
+                    &TimerType::UserDefined(description),
+                    "Notification Set",
+                    "You will be notified in",
+                    "alarm-symbolic",
+                    condition,
+                );
             }
         }
         Task::none()
     }
 
-    /// Handles timer-related messages.
+    /// Pauses or resumes a running timer right away, then stores the change.
     ///
-    /// This function processes messages related to timer creation and fetching active timers.
-    /// This is app level messages exclusive to timer creation and management.
-    /// This is not where the timer ticks are handled as that is done with a subscription, not message.
+    /// # Arguments
+    ///
+    /// - `id`: The timer to change
+    /// - `paused`: Pause the timer instead of resuming it
+    ///
+    /// # Returns
+    ///
+    /// Task storing the change, reporting back with [`TimerMessage::PauseStored`].
+    fn set_timer_paused(&mut self, id: i64, paused: bool) -> Task<Action<Message>> {
+        let now = self.clock.now();
+        let Some(timer) = self.runner.active_timers.iter_mut().find(|t| t.id == id) else {
+            return Task::none();
+        };
+        if paused {
+            timer.pause(now);
+        } else {
+            timer.resume(now);
+        }
+
+        let Some(database) = self.database.clone() else {
+            tracing::warn!("Database not yet available");
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                Timer::set_paused(database.pool(), id, paused, now)
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| Action::App(Message::TimerMessage(TimerMessage::PauseStored(result))),
+        )
+    }
+
+    /// Applies the missed timer policy to timers that ended while the applet wasn't running.
+    ///
+    /// See [`TimerRunner::missed`]. Dropped timers take the chain links
+    /// waiting on them along; recurring timers come back with the next reload.
     ///
     /// # Arguments
     ///
-    /// - `msg`: The timer message to handle.
+    /// - `timers`: The missed timers, as fetched from the database
+    fn handle_missed_timers(&mut self, mut timers: Vec<Timer>) -> Task<Action<Message>> {
+        // A reload may have handed some of them to the tick already
+        timers.retain(|t| !self.removing_timers.contains(&t.id));
+
+        if self.config.missed_timer_policy != MissedTimerPolicy::Run {
+            for timer in &timers {
+                let links: Vec<i64> = timer_list::chain_after(timer, &self.chained_timers)
+                    .iter()
+                    .map(|link| link.id)
+                    .collect();
+                self.chained_timers.retain(|t| !links.contains(&t.id));
+            }
+        }
+
+        let effects = self.runner.missed(timers, &self.config, self.clock.now());
+        self.apply(effects)
+    }
+
+    /// Stores a history entry for a timer that left the active list.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry`: How and when the timer stopped running
     ///
     /// # Returns
     ///
-    /// Task representing the action to be performed.
-    fn handle_timer_message(&mut self, msg: TimerMessage) -> Task<Action<Message>> {
+    /// A task storing the entry, or no task without a database.
+    fn record_history(&self, entry: HistoryEntry) -> Task<Action<Message>> {
+        let Some(database) = self.database.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                HistoryEntry::insert(database.pool(), &entry)
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| Action::App(Message::HistoryMessage(HistoryMessage::Recorded(result))),
+        )
+    }
+
+    /// Handles timer history messages.
+    ///
+    /// Recorded entries are added to the top of the list, which is trimmed to the
+    /// same length the database keeps.
+    ///
+    /// # Arguments
+    ///
+    /// - `msg`: The history message to handle.
+    fn handle_history_message(&mut self, msg: HistoryMessage) -> Task<Action<Message>> {
         match msg {
-            TimerMessage::Created(result) => match result {
-                Ok(timer) => {
-                    self.active_timers.push(timer);
-                    println!("Created timer: {:#?}", &self.active_timers.last());
+            HistoryMessage::Clear => {
+                self.history.clear();
+                if let Some(database) = self.database.clone() {
+                    return Task::perform(
+                        async move {
+                            HistoryEntry::clear(database.pool())
+                                .await
+                                .map_err(AppError::from)
+                        },
+                        |result| {
+                            Action::App(Message::HistoryMessage(HistoryMessage::Cleared(result)))
+                        },
+                    );
                 }
-                Err(err) => {
-                    eprintln!("Failed to create timer: {err}");
+            }
+            HistoryMessage::Cleared(result) => {
+                if let Err(err) = result {
+                    self.report_error(fl!("error-save"), err);
                 }
+            }
+            HistoryMessage::Fetched(result) => match result {
+                Ok(entries) => self.history = entries,
+                Err(err) => self.report_error(fl!("error-load"), err),
             },
-            TimerMessage::ActiveFetched(result) => match result {
-                Ok(timers) => {
-                    self.active_timers = timers;
-                }
-                Err(err) => {
-                    eprintln!("Failed to fetch active timers: {err}");
+            HistoryMessage::Recorded(result) => match result {
+                Ok(entry) => {
+                    self.history.insert(0, entry);
+                    self.history
+                        .truncate(usize::try_from(HISTORY_LIMIT).unwrap_or(usize::MAX));
                 }
+                Err(err) => self.report_error(fl!("error-save"), err),
             },
         }
         Task::none()
@@ -627,15 +3152,21 @@ impl AppModel {
         // let _ = self.power_controls.update(&msg);
         match msg {
             PowerMessage::ToggleStayAwake => {
-                if let Some(inhibitor) = self.suspend_inhibitor.take() {
+                let toggled = if !self.scheduling {
+                    self.request(Request::ToggleStayAwake)
+                } else if let Some(inhibitor) = self.suspend_inhibitor.take() {
                     resources::release_suspend_inhibit(inhibitor);
+                    self.emit_signal(Signal::StayAwakeChanged(false))
                 } else {
                     return AppModel::get_suspend_inhibitor();
-                }
+                };
 
-                // Close the popup after toggling
-                let close_task = self.toggle_popup();
-                return close_task.map(|_| Action::None);
+                // Close the popup after toggling (stay-awake can also be toggled over D-Bus)
+                if self.popup.is_none() {
+                    return toggled;
+                }
+                let close_task = self.toggle_popup().map(|_| Action::None);
+                return Task::batch([toggled, close_task]);
             }
             PowerMessage::InhibitAcquired(result) => {
                 match Arc::try_unwrap(result) {
@@ -644,21 +3175,20 @@ impl AppModel {
                         // Double okay is a bit silly but matches the async task return type
                         // Also makes the arc unwrap safe
                         self.suspend_inhibitor = Some(file);
+                        return self.emit_signal(Signal::StayAwakeChanged(true));
                     }
-                    Ok(Err(err)) => {
-                        eprintln!("Failed to acquire inhibit: {err}");
-                    }
+                    Ok(Err(err)) => self.report_error(fl!("error-stay-awake"), err),
                     Err(arc) => {
                         // Multiple Arc references exist - this shouldn't happen in normal flow
                         // but handle it gracefully anyway as things that shouldn't happen have a habit of happening
-                        eprintln!(
+                        tracing::error!(
                             "Cannot take ownership: Arc has multiple references (count: {})",
                             Arc::strong_count(&arc)
                         );
                     }
                 }
             }
-            PowerMessage::SetSuspendTime(time) => {
+            PowerMessage::SetSuspendTime(time, condition) => {
                 let _inhibitor_task = AppModel::get_suspend_inhibitor();
 
                 return self.create_power_timer(
@@ -667,9 +3197,22 @@ impl AppModel {
                     "Suspend Timer Set",
                     "System will suspend in",
                     "system-suspend-symbolic",
+                    condition,
+                );
+            }
+            PowerMessage::SetHibernateTime(time, condition) => {
+                let _inhibitor_task = AppModel::get_suspend_inhibitor();
+
+                return self.create_power_timer(
+                    time,
+                    &TimerType::Hibernate,
+                    "Hibernate Timer Set",
+                    "System will hibernate in",
+                    "io.vulpapps.Chronomancer-hibernate",
+                    condition,
                 );
             }
-            PowerMessage::SetShutdownTime(time) => {
+            PowerMessage::SetShutdownTime(time, condition) => {
                 // We create a suspend inhibitor when setting a shutdown timer so the timer overrides system settings
                 // Otherwise the system might suspend before shutting down and never complete until it wakes up and immedately shuts down
                 let _inhibitor_task = AppModel::get_suspend_inhibitor();
@@ -680,9 +3223,10 @@ impl AppModel {
                     "Shutdown Timer Set",
                     "System will shutdown in",
                     "system-shutdown-symbolic",
+                    condition,
                 );
             }
-            PowerMessage::SetLogoutTime(time) => {
+            PowerMessage::SetLogoutTime(time, condition) => {
                 // We create a suspend inhibitor when setting a logout timer so the timer overrides system settings
                 // Otherwise the system might suspend before logging out and never complete until it wakes up and immedately logs out
                 let _inhibitor_task = AppModel::get_suspend_inhibitor();
@@ -693,9 +3237,10 @@ impl AppModel {
                     "Logout Timer Set",
                     "System will logout in",
                     "system-log-out-symbolic",
+                    condition,
                 );
             }
-            PowerMessage::SetRebootTime(time) => {
+            PowerMessage::SetRebootTime(time, condition) => {
                 // We create a suspend inhibitor when setting a reboot timer so the timer overrides system settings
                 // Otherwise the system might suspend before rebooting and never complete until it wakes up and immediately reboots
                 let _inhibitor_task = AppModel::get_suspend_inhibitor();
@@ -706,54 +3251,146 @@ impl AppModel {
                     "Reboot Timer Set",
                     "System will reboot in",
                     "system-reboot-symbolic",
+                    condition,
                 );
             }
+            PowerMessage::PowerStateChanged(state) => {
+                // Let stay-awake go before it drains the battery to zero
+                if let Some(notice) = self.runner.set_power_state(state, &self.config)
+                    && let Some(inhibitor) = self.suspend_inhibitor.take()
+                {
+                    resources::release_suspend_inhibit(inhibitor);
+                    notice.show(&self.config);
+                }
+                // Battery triggers and AC guards may have been waiting on this
+                return Task::batch([self.sync_media_inhibitor(), self.handle_tick()]);
+            }
+            PowerMessage::SetMediaStayAwake(enabled) => {
+                self.media_stay_awake = enabled;
+                return self.sync_media_inhibitor();
+            }
+            PowerMessage::MediaStateChanged(players) => {
+                self.runner.media_players = Some(players);
+                return Task::batch([self.sync_media_inhibitor(), self.handle_tick()]);
+            }
+            PowerMessage::MediaInhibitAcquired(result) => {
+                self.media_inhibit_pending = false;
+                match Arc::try_unwrap(result) {
+                    Ok(Ok(file)) if self.wants_media_inhibitor() => {
+                        self.media_inhibitor = Some(file);
+                    }
+                    Ok(Ok(file)) => {
+                        // Playback stopped while the lock was being acquired
+                        resources::release_suspend_inhibit(file);
+                    }
+                    Ok(Err(err)) => self.report_error(fl!("error-stay-awake"), err),
+                    Err(arc) => {
+                        tracing::error!(
+                            "Cannot take ownership: Arc has multiple references (count: {})",
+                            Arc::strong_count(&arc)
+                        );
+                    }
+                }
+            }
+            PowerMessage::RunPendingAction => {
+                if !self.scheduling {
+                    self.runner.pending_action = None;
+                    return self.request(Request::RunPendingAction);
+                }
+                let effects = self.runner.run_pending_action();
+                return self.apply(effects);
+            }
+            PowerMessage::CancelPendingAction => {
+                if !self.scheduling {
+                    self.runner.pending_action = None;
+                    return self.request(Request::CancelPendingAction);
+                }
+                let effects = self.runner.cancel_pending_action();
+                return self.apply(effects);
+            }
             PowerMessage::ExecuteSuspend => {
                 return Task::perform(
-                    async move { resources::execute_system_suspend().await },
+                    async move {
+                        resources::execute_system_suspend()
+                            .await
+                            .map_err(AppError::from)
+                    },
                     |result| {
-                        if let Err(e) = result {
-                            eprintln!("Failed to suspend system: {e}");
-                        }
-                        Action::None
+                        Action::App(Message::PowerMessage(PowerMessage::Executed(
+                            TimerType::Suspend,
+                            result,
+                        )))
+                    },
+                );
+            }
+            PowerMessage::ExecuteHibernate => {
+                return Task::perform(
+                    async move {
+                        resources::execute_system_hibernate()
+                            .await
+                            .map_err(AppError::from)
+                    },
+                    |result| {
+                        Action::App(Message::PowerMessage(PowerMessage::Executed(
+                            TimerType::Hibernate,
+                            result,
+                        )))
                     },
                 );
             }
             PowerMessage::ExecuteShutdown => {
                 return Task::perform(
-                    async move { resources::execute_system_shutdown().await },
+                    async move {
+                        resources::execute_system_shutdown()
+                            .await
+                            .map_err(AppError::from)
+                    },
                     |result| {
-                        if let Err(e) = result {
-                            eprintln!("Failed to shutdown system: {e}");
-                        }
-                        Action::None
+                        Action::App(Message::PowerMessage(PowerMessage::Executed(
+                            TimerType::Shutdown,
+                            result,
+                        )))
                     },
                 );
             }
             PowerMessage::ExecuteLogout => {
-                println!("Executing system logout");
+                tracing::info!("Logging out");
                 return Task::perform(
-                    async move { resources::execute_system_logout().await },
+                    async move {
+                        resources::execute_system_logout()
+                            .await
+                            .map_err(AppError::from)
+                    },
                     |result| {
-                        if let Err(e) = result {
-                            eprintln!("Failed to logout: {e}");
-                        }
-                        Action::None
+                        Action::App(Message::PowerMessage(PowerMessage::Executed(
+                            TimerType::Logout,
+                            result,
+                        )))
                     },
                 );
             }
             PowerMessage::ExecuteReboot => {
-                println!("Executing system reboot");
+                tracing::info!("Rebooting");
                 return Task::perform(
-                    async move { resources::execute_system_reboot().await },
+                    async move {
+                        resources::execute_system_reboot()
+                            .await
+                            .map_err(AppError::from)
+                    },
                     |result| {
-                        if let Err(e) = result {
-                            eprintln!("Failed to reboot: {e}");
-                        }
-                        Action::None
+                        Action::App(Message::PowerMessage(PowerMessage::Executed(
+                            TimerType::Reboot,
+                            result,
+                        )))
                     },
                 );
             }
+            PowerMessage::Executed(timer_type, result) => {
+                if let Err(err) = result {
+                    let operation = timer_list::operation_name(&timer_type);
+                    self.report_error(fl!("error-power-action", operation = operation), err);
+                }
+            }
         }
         Task::none()
     }
@@ -776,7 +3413,7 @@ impl AppModel {
                     "block",
                 )
                 .await
-                .map_err(|e| e.to_string())
+                .map_err(AppError::from)
             },
             |result| {
                 Action::<Message>::App(Message::PowerMessage(PowerMessage::InhibitAcquired(
@@ -785,24 +3422,60 @@ impl AppModel {
             },
         )
     }
-}
 
-// TODO: Implement menu actions as needed. Might try to use this for keyboard shortcuts
-// impl menu::action::MenuAction for MenuAction {
-//     type Message = Message;
-
-//     fn message(&self) -> Self::Message {
-//         match self {
-//         }
-//     }
-// }
+    /// Acquires the media inhibitor asynchronously.
+    ///
+    /// Works like [`Self::get_suspend_inhibitor`], but the lock is tracked separately
+    /// so playback starting and stopping never touches the user's stay-awake toggle.
+    ///
+    /// # Returns
+    ///
+    /// A Task that resolves to an Action containing the result of the inhibitor acquisition.
+    fn get_media_inhibitor() -> Task<Action<Message>> {
+        Task::perform(
+            async move {
+                resources::acquire_suspend_inhibit("Chronomancer", "Media is playing", "block")
+                    .await
+                    .map_err(AppError::from)
+            },
+            |result| {
+                Action::<Message>::App(Message::PowerMessage(PowerMessage::MediaInhibitAcquired(
+                    Arc::new(result),
+                )))
+            },
+        )
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        models::{
+            condition::{FileTrigger, QuietThresholds},
+            sequence::Phase,
+        },
+        pages::history,
+        utils::{
+            clock::{Clock, ManualClock, SystemClock},
+            mpris::PlaybackStatus,
+            upower::PowerState,
+        },
+    };
+    use notify_rust::Timeout;
+
+    const NOW: i64 = 1_767_600_000;
 
     fn get_test_app() -> AppModel {
-        AppModel::init(Core::default(), ()).0
+        get_test_app_with_clock().0
+    }
+
+    /// Gets an app whose clock stands at `NOW` until the test moves it.
+    fn get_test_app_with_clock() -> (AppModel, ManualClock) {
+        let clock = ManualClock::new(NOW);
+        let mut app = AppModel::init(Core::default(), ()).0;
+        app.clock = std::sync::Arc::new(clock.clone());
+        (app, clock)
     }
 
     #[test]
@@ -822,7 +3495,7 @@ mod tests {
             "Suspend inhibitor should be None on initialization"
         );
         assert!(
-            app.active_timers.is_empty(),
+            app.runner.active_timers.is_empty(),
             "Active timers should be empty on initialization"
         );
     }
@@ -848,28 +3521,29 @@ mod tests {
 
     #[test]
     fn test_tick_with_active_timer() {
-        let mut app = get_test_app();
+        let (mut app, clock) = get_test_app_with_clock();
 
-        // To ensure handle_tick removes it immediately, set ends_at to a past second value.
-        let now_sec = chrono::Utc::now().timestamp();
-        let expired_timer = Timer {
-            id: 1,
-            is_recurring: false,
-            description: TimerType::UserDefined("Renamon exists reminder".to_string())
-                .as_str()
-                .to_string(),
-            paused_at: 0,
-            created_at: now_sec - 5, // created slightly in the past
-            ends_at: now_sec - 1,    // already expired
-        };
-        app.active_timers.push(expired_timer);
+        let mut timer = Timer::new(
+            60,
+            false,
+            &TimerType::UserDefined("Renamon exists reminder".to_string()),
+            NOW,
+        );
+        timer.id = 1;
+        app.runner.active_timers.push(timer);
+
+        // One second before the deadline the timer keeps running
+        clock.advance(59);
+        let _task = app.handle_tick();
+        assert_eq!(app.runner.active_timers.len(), 1);
 
-        // Handle tick; should remove the expired timer
+        // Handle tick on the deadline; should remove the expired timer
+        clock.advance(1);
         let task = app.handle_tick();
 
         // Active timers list should now be empty
         assert!(
-            app.active_timers.is_empty(),
+            app.runner.active_timers.is_empty(),
             "Expired timer should be removed after tick"
         );
 
@@ -882,14 +3556,14 @@ mod tests {
         let mut app = get_test_app();
 
         // Ensure no active timers
-        app.active_timers.clear();
+        app.runner.active_timers.clear();
 
         // Handle tick; should not schedule any follow-up actions since there are no timers.
         let _task = app.handle_tick();
 
         // State should remain unchanged (still no active timers).
         assert!(
-            app.active_timers.is_empty(),
+            app.runner.active_timers.is_empty(),
             "Active timers should remain empty after tick"
         );
     }
@@ -910,19 +3584,24 @@ mod tests {
         });
     }
 
-    // Verify that the tick subscription produces Tick messages at 1-second intervals.
-    // We spawn the stream and verify it sends messages, then cancel it.
+    // Verify that the tick subscription sleeps until each deadline, ticking once
+    // for deadlines that fall together. We spawn the stream the same way the
+    // subscription does and check when it sends messages.
     #[tokio::test]
-    async fn test_tick_subscription_interval() {
+    async fn test_tick_subscription_wakes_at_deadlines() {
         use cosmic::iced::futures::StreamExt;
         use cosmic::iced::stream::channel;
         use tokio::time::{Duration, timeout};
 
+        let now = SystemClock.now();
+        let deadlines: Deadlines = [now + 2, now + 1, now + 1].into_iter().collect();
+
         // Use the same channel function signature as in the actual subscription
-        let stream = channel(4, |mut output| async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        let stream = channel(4, move |mut output| async move {
+            let mut deadlines = deadlines;
             loop {
-                interval.tick().await;
+                scheduler::sleep_until(deadlines.next(), &SystemClock).await;
+                deadlines.pop_due(SystemClock.now());
                 if output.send(Message::Tick).await.is_err() {
                     break;
                 }
@@ -931,21 +3610,64 @@ mod tests {
 
         tokio::pin!(stream);
 
-        // Verify we can receive multiple Tick messages
-        for i in 1..=3 {
-            let result = timeout(Duration::from_secs(2), stream.next()).await;
+        // One tick for both timers ending at now + 1, one for now + 2
+        for i in 1..=2 {
+            let result = timeout(Duration::from_secs(3), stream.next()).await;
             match result {
-                Ok(Some(msg)) => {
-                    assert!(
-                        matches!(msg, Message::Tick),
-                        "Expected Tick message, got something else"
-                    );
-                    println!("Successfully received tick {i}");
-                }
+                Ok(Some(msg)) => assert!(
+                    matches!(msg, Message::Tick),
+                    "Expected Tick message, got something else"
+                ),
                 Ok(None) => panic!("Stream closed unexpectedly after {i} ticks"),
                 Err(err) => panic!("Timeout waiting for tick {i}. Error: {err}"),
             }
         }
+        assert!(SystemClock.now() >= now + 2);
+
+        // Nothing left to wake up for
+        assert!(
+            timeout(Duration::from_millis(1500), stream.next())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_deadlines_follow_running_timers() {
+        let mut app = get_test_app();
+        assert!(app.deadlines().is_empty());
+
+        let mut reminder = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        reminder.id = 1;
+        reminder.ends_at = NOW + 60;
+        let mut paused = Timer::new(30, false, &TimerType::Suspend, NOW);
+        paused.id = 2;
+        paused.ends_at = NOW + 30;
+        paused.paused_at = NOW;
+        let mut battery = Timer::new(0, false, &TimerType::Hibernate, NOW)
+            .with_condition(&TimerCondition::BatteryBelow(10));
+        battery.id = 3;
+        app.runner.active_timers = vec![reminder, paused, battery];
+
+        // Paused timers and triggers waiting on updates don't wake the applet
+        app.config.panel_countdown = PanelCountdown::Off;
+        app.config.progress_icon = false;
+        assert_eq!(app.deadlines().next(), Some(NOW + 60));
+
+        // Without a countdown on screen the applet sleeps in between
+        assert!(!app.shows_countdown());
+        // The progress ring only wakes it when it moves, once a second for a 60 second timer
+        app.config.progress_icon = true;
+        assert!(!app.shows_countdown());
+        assert_eq!(app.deadlines().next(), Some(NOW + 1));
+        app.config.panel_countdown = PanelCountdown::BesideIcon;
+        assert!(app.shows_countdown());
+
+        // chronomancerd runs the timers while it owns the service, the ring still moves
+        app.scheduling = false;
+        assert_eq!(app.deadlines().next(), Some(NOW + 1));
+        app.config.progress_icon = false;
+        assert!(app.deadlines().is_empty());
     }
 
     #[test]
@@ -986,7 +3708,6 @@ mod tests {
         let mut app = get_test_app();
 
         // Create an expired timer
-        let now_sec = chrono::Utc::now().timestamp();
         let expired_timer = Timer {
             id: 1,
             is_recurring: false,
@@ -994,16 +3715,20 @@ mod tests {
                 .as_str()
                 .to_string(),
             paused_at: 0,
-            created_at: now_sec - 5,
-            ends_at: now_sec - 1,
+            created_at: NOW - 5,
+            ends_at: NOW - 1,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         };
-        app.active_timers.push(expired_timer);
+        app.runner.active_timers.push(expired_timer);
 
         // Send Tick message
         let _task = app.update(Message::Tick);
 
         // Timer should be removed
-        assert!(app.active_timers.is_empty());
+        assert!(app.runner.active_timers.is_empty());
     }
 
     #[test]
@@ -1015,27 +3740,31 @@ mod tests {
             is_recurring: false,
             description: "Test Timer".to_string(),
             paused_at: 0,
-            created_at: chrono::Utc::now().timestamp(),
-            ends_at: chrono::Utc::now().timestamp() + 3600,
+            created_at: NOW,
+            ends_at: NOW + 3600,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         };
 
         let msg = TimerMessage::Created(Ok(timer.clone()));
         let _task = app.update(Message::TimerMessage(msg));
 
         // Timer should be added to active timers
-        assert_eq!(app.active_timers.len(), 1);
-        assert_eq!(app.active_timers[0].id, timer.id);
+        assert_eq!(app.runner.active_timers.len(), 1);
+        assert_eq!(app.runner.active_timers[0].id, timer.id);
     }
 
     #[test]
     fn test_handle_timer_message_created_failure() {
         let mut app = get_test_app();
 
-        let msg = TimerMessage::Created(Err("Database error".to_string()));
+        let msg = TimerMessage::Created(Err(AppError::Database("Database error".to_string())));
         let _task = app.update(Message::TimerMessage(msg));
 
         // No timers should be added
-        assert!(app.active_timers.is_empty());
+        assert!(app.runner.active_timers.is_empty());
     }
 
     #[test]
@@ -1047,8 +3776,12 @@ mod tests {
             is_recurring: false,
             description: "Timer 1".to_string(),
             paused_at: 0,
-            created_at: chrono::Utc::now().timestamp(),
-            ends_at: chrono::Utc::now().timestamp() + 3600,
+            created_at: NOW,
+            ends_at: NOW + 3600,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         };
 
         let second_timer = Timer {
@@ -1056,8 +3789,12 @@ mod tests {
             is_recurring: false,
             description: "Timer 2".to_string(),
             paused_at: 0,
-            created_at: chrono::Utc::now().timestamp(),
-            ends_at: chrono::Utc::now().timestamp() + 7200,
+            created_at: NOW,
+            ends_at: NOW + 7200,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         };
 
         let timers = vec![first_timer.clone(), second_timer.clone()];
@@ -1065,9 +3802,9 @@ mod tests {
         let _task = app.update(Message::TimerMessage(msg));
 
         // Active timers should be populated
-        assert_eq!(app.active_timers.len(), 2);
-        assert_eq!(app.active_timers[0].id, first_timer.id);
-        assert_eq!(app.active_timers[1].id, second_timer.id);
+        assert_eq!(app.runner.active_timers.len(), 2);
+        assert_eq!(app.runner.active_timers[0].id, first_timer.id);
+        assert_eq!(app.runner.active_timers[1].id, second_timer.id);
     }
 
     #[test]
@@ -1075,31 +3812,68 @@ mod tests {
         let mut app = get_test_app();
 
         // Add a timer first
-        app.active_timers.push(Timer {
+        app.runner.active_timers.push(Timer {
             id: 1,
             is_recurring: false,
             description: "Existing Timer".to_string(),
             paused_at: 0,
-            created_at: chrono::Utc::now().timestamp(),
-            ends_at: chrono::Utc::now().timestamp() + 3600,
+            created_at: NOW,
+            ends_at: NOW + 3600,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         });
 
-        let msg = TimerMessage::ActiveFetched(Err("Fetch failed".to_string()));
+        let msg = TimerMessage::ActiveFetched(Err(AppError::Database("Fetch failed".to_string())));
         let _task = app.update(Message::TimerMessage(msg));
 
         // Existing timers should remain unchanged
-        assert_eq!(app.active_timers.len(), 1);
+        assert_eq!(app.runner.active_timers.len(), 1);
     }
 
     #[test]
     fn test_handle_database_message_failed_to_initialize() {
         let mut app = get_test_app();
 
-        let msg = DatabaseMessage::FailedToInitialize("Connection error".to_string());
+        let msg =
+            DatabaseMessage::FailedToInitialize(AppError::Database("Connection error".to_string()));
         let _task = app.update(Message::DatabaseMessage(msg));
 
-        // Database should remain None
+        // Database should remain None, and the user is told why
         assert!(app.database.is_none());
+        assert_eq!(
+            app.error.as_ref().map(|(_, error)| error),
+            Some(&AppError::Database("Connection error".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_denied_power_action_shows_banner_until_dismissed() {
+        let mut app = get_test_app();
+
+        let denied = AppError::PermissionDenied("Interactive authentication required.".to_string());
+        let _task = app.update(Message::PowerMessage(PowerMessage::Executed(
+            TimerType::Shutdown,
+            Err(denied.clone()),
+        )));
+        let (summary, error) = app.error.clone().expect("banner for the failed shutdown");
+        assert!(summary.contains(&timer_list::operation_name(&TimerType::Shutdown)));
+        assert_eq!(error, denied);
+        assert_eq!(
+            AppModel::error_details(&error),
+            fl!("error-permission-denied")
+        );
+
+        // Succeeding leaves the banner of the earlier failure alone
+        let _task = app.update(Message::PowerMessage(PowerMessage::Executed(
+            TimerType::Reboot,
+            Ok(()),
+        )));
+        assert!(app.error.is_some());
+
+        let _task = app.update(Message::DismissError);
+        assert!(app.error.is_none());
     }
 
     #[test]
@@ -1163,7 +3937,9 @@ mod tests {
     fn test_handle_power_message_inhibit_acquired_failure() {
         let mut app = get_test_app();
 
-        let msg = PowerMessage::InhibitAcquired(Arc::new(Err("Failed to acquire".to_string())));
+        let msg = PowerMessage::InhibitAcquired(Arc::new(Err(AppError::DBus(
+            "Failed to acquire".to_string(),
+        ))));
         let _task = app.update(Message::PowerMessage(msg));
 
         // Inhibitor should remain None
@@ -1186,7 +3962,6 @@ mod tests {
     #[test]
     fn test_multiple_timer_creation_and_removal() {
         let mut app = get_test_app();
-        let now = chrono::Utc::now().timestamp();
 
         // Create multiple timers
         for i in 1..=3 {
@@ -1195,14 +3970,18 @@ mod tests {
                 is_recurring: false,
                 description: format!("Timer {i}"),
                 paused_at: 0,
-                created_at: now,
-                ends_at: now + 3600 + (i * 100),
+                created_at: NOW,
+                ends_at: NOW + 3600 + (i * 100),
+                condition: String::new(),
+                parent_id: 0,
+                delay_seconds: 0,
+                intent: String::new(),
             };
             let msg = TimerMessage::Created(Ok(timer));
             let _task = app.update(Message::TimerMessage(msg));
         }
 
-        assert_eq!(app.active_timers.len(), 3);
+        assert_eq!(app.runner.active_timers.len(), 3);
 
         // Create an expired timer
         let expired = Timer {
@@ -1210,17 +3989,849 @@ mod tests {
             is_recurring: false,
             description: "Expired".to_string(),
             paused_at: 0,
-            created_at: now - 10,
-            ends_at: now - 1,
+            created_at: NOW - 10,
+            ends_at: NOW - 1,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         };
         let msg = TimerMessage::Created(Ok(expired));
         let _task = app.update(Message::TimerMessage(msg));
 
-        assert_eq!(app.active_timers.len(), 4);
+        assert_eq!(app.runner.active_timers.len(), 4);
 
         // Tick should remove expired timer
         let _task = app.update(Message::Tick);
 
-        assert_eq!(app.active_timers.len(), 3);
+        assert_eq!(app.runner.active_timers.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_tick_finishes_simultaneous_timers_together() -> anyhow::Result<()> {
+        let mut app = get_test_app();
+        app.database = Some(SQLiteDatabase::new_in_memory().await?);
+
+        for id in 1..=3 {
+            let mut timer =
+                Timer::new(60, false, &TimerType::UserDefined(format!("Tea {id}")), NOW);
+            timer.id = id;
+            timer.ends_at = NOW - 1;
+            app.runner.active_timers.push(timer);
+        }
+
+        let _task = app.update(Message::Tick);
+        assert!(app.runner.active_timers.is_empty());
+        assert_eq!(app.removing_timers, HashSet::from([1, 2, 3]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tick_respects_power_conditions() {
+        let mut app = get_test_app();
+        app.runner.power_state = Some(PowerState {
+            on_battery: true,
+            battery_percentage: Some(50.0),
+        });
+
+        // Expired but AC-only, so it is dropped without running
+        let mut ac_only = Timer::new(0, false, &TimerType::Shutdown, NOW)
+            .with_condition(&TimerCondition::OnAcPower);
+        ac_only.id = 1;
+        ac_only.ends_at = NOW - 1;
+
+        // Battery trigger that hasn't reached its threshold yet
+        let mut battery_trigger = Timer::new(0, false, &TimerType::Hibernate, NOW)
+            .with_condition(&TimerCondition::BatteryBelow(10));
+        battery_trigger.id = 2;
+
+        app.runner.active_timers = vec![ac_only, battery_trigger];
+        let _task = app.update(Message::Tick);
+
+        assert_eq!(app.runner.active_timers.len(), 1);
+        assert_eq!(app.runner.active_timers[0].id, 2);
+
+        // Battery drains past the threshold, the trigger fires on the next tick
+        app.runner.power_state = Some(PowerState {
+            on_battery: true,
+            battery_percentage: Some(9.0),
+        });
+        let _task = app.update(Message::Tick);
+        assert!(app.runner.active_timers.is_empty());
+    }
+
+    #[test]
+    fn test_quiet_trigger_fires_after_sustained_idle() {
+        let mut app = get_test_app();
+        let sample = |at, net_bytes_per_sec| ActivitySample {
+            at,
+            load_percent: 2.0,
+            disk_bytes_per_sec: 0.0,
+            net_bytes_per_sec,
+        };
+
+        let mut quiet = Timer::new(60, false, &TimerType::Shutdown, NOW).with_condition(
+            &TimerCondition::SystemQuiet(QuietThresholds {
+                window_secs: 60,
+                ..QuietThresholds::default()
+            }),
+        );
+        quiet.id = 1;
+        app.runner.active_timers.push(quiet);
+        assert_eq!(app.runner.longest_quiet_window(), Some(60));
+
+        // The download finished half a minute ago, so the window isn't quiet yet
+        for at in (NOW - 120..=NOW).step_by(10) {
+            let net = if at <= NOW - 30 { 10_000_000.0 } else { 0.0 };
+            let _task = app.update(Message::ActivitySampled(sample(at, net)));
+        }
+        // Trimmed to the window plus one older sample
+        assert_eq!(
+            app.runner.activity_history.first().map(|s| s.at),
+            Some(NOW - 60)
+        );
+
+        let _task = app.update(Message::Tick);
+        assert_eq!(app.runner.active_timers.len(), 1);
+
+        // A full quiet minute fires the trigger
+        app.runner.activity_history = (NOW - 70..=NOW)
+            .step_by(10)
+            .map(|at| sample(at, 0.0))
+            .collect();
+        let _task = app.update(Message::Tick);
+        assert!(app.runner.active_timers.is_empty());
+    }
+
+    #[test]
+    fn test_activity_gap_resets_history() {
+        let mut app = get_test_app();
+        let sample = |at| ActivitySample {
+            at,
+            load_percent: 0.0,
+            disk_bytes_per_sec: 0.0,
+            net_bytes_per_sec: 0.0,
+        };
+
+        let mut quiet = Timer::new(7200, false, &TimerType::Suspend, NOW).with_condition(
+            &TimerCondition::SystemQuiet(QuietThresholds {
+                window_secs: 7200,
+                ..QuietThresholds::default()
+            }),
+        );
+        quiet.id = 1;
+        app.runner.active_timers.push(quiet);
+
+        app.record_activity(sample(100));
+        app.record_activity(sample(110));
+        assert_eq!(app.runner.activity_history.len(), 2);
+        // The system slept for an hour, nothing before it can be trusted
+        app.record_activity(sample(3710));
+
+        assert_eq!(app.runner.activity_history, vec![sample(3710)]);
+    }
+
+    #[test]
+    fn test_file_trigger_fires_when_file_appears() {
+        let mut app = get_test_app();
+        let dir = std::env::temp_dir().join(format!("chronomancer-app-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("render.done");
+        let _ = std::fs::remove_file(&path);
+
+        let mut watcher = Timer::new(0, false, &TimerType::UserDefined("Done".to_string()), NOW)
+            .with_condition(&TimerCondition::File(FileTrigger {
+                path: path.clone(),
+                event: FileEvent::Appears,
+            }));
+        watcher.id = 1;
+        let _task = app.update(Message::TimerMessage(TimerMessage::Created(Ok(watcher))));
+        assert_eq!(app.runner.watched_paths(), vec![path.clone()]);
+
+        let _task = app.update(Message::Tick);
+        assert_eq!(app.runner.active_timers.len(), 1);
+
+        // The watcher reports the new file, which fires the trigger without waiting for a tick
+        std::fs::write(&path, b"").unwrap();
+        let _task = app.update(Message::FileChanged(path.clone()));
+        assert!(app.runner.active_timers.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_power_state_releases_stay_awake_on_low_battery() {
+        let mut app = get_test_app();
+        app.config.stay_awake_min_battery = 15;
+
+        let temp_file = std::env::temp_dir().join("chronomancer_test_inhibitor3");
+        app.suspend_inhibitor = Some(std::fs::File::create(&temp_file).unwrap());
+
+        // Plenty of charge left, stay-awake is kept
+        let _task = app.update(Message::PowerMessage(PowerMessage::PowerStateChanged(
+            PowerState {
+                on_battery: true,
+                battery_percentage: Some(40.0),
+            },
+        )));
+        assert!(app.suspend_inhibitor.is_some());
+
+        // Below the configured threshold, stay-awake is released
+        let _task = app.update(Message::PowerMessage(PowerMessage::PowerStateChanged(
+            PowerState {
+                on_battery: true,
+                battery_percentage: Some(12.0),
+            },
+        )));
+        assert!(app.suspend_inhibitor.is_none());
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    fn podcast_player(status: PlaybackStatus, track: &str) -> PlayerState {
+        PlayerState {
+            bus_name: "org.mpris.MediaPlayer2.podcasts".to_string(),
+            status,
+            track: track.to_string(),
+            position_us: 0,
+            length_us: Some(1_800_000_000),
+        }
+    }
+
+    #[test]
+    fn test_media_stay_awake_follows_playback() {
+        let mut app = get_test_app();
+        let temp_file = std::env::temp_dir().join("chronomancer_test_media_inhibitor");
+
+        // Turned on while nothing is known about the players yet, nothing to hold
+        let _task = app.update(Message::PowerMessage(PowerMessage::SetMediaStayAwake(true)));
+        assert!(!app.media_inhibit_pending);
+
+        let _task = app.update(Message::PowerMessage(PowerMessage::MediaStateChanged(
+            vec![podcast_player(PlaybackStatus::Playing, "/episode/42")],
+        )));
+        assert!(app.media_inhibit_pending);
+
+        let file = std::fs::File::create(&temp_file).unwrap();
+        let _task = app.update(Message::PowerMessage(PowerMessage::MediaInhibitAcquired(
+            Arc::new(Ok(file)),
+        )));
+        assert!(!app.media_inhibit_pending);
+        assert!(app.media_inhibitor.is_some());
+        // The user's own stay-awake toggle is untouched
+        assert!(app.suspend_inhibitor.is_none());
+
+        // Pausing lets the system sleep again
+        let _task = app.update(Message::PowerMessage(PowerMessage::MediaStateChanged(
+            vec![podcast_player(PlaybackStatus::Paused, "/episode/42")],
+        )));
+        assert!(app.media_inhibitor.is_none());
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    #[test]
+    fn test_media_inhibitor_dropped_when_playback_stops_early() {
+        let mut app = get_test_app();
+        let temp_file = std::env::temp_dir().join("chronomancer_test_media_inhibitor2");
+        app.media_stay_awake = true;
+        app.runner.media_players = Some(vec![]);
+
+        let file = std::fs::File::create(&temp_file).unwrap();
+        let _task = app.update(Message::PowerMessage(PowerMessage::MediaInhibitAcquired(
+            Arc::new(Ok(file)),
+        )));
+        assert!(app.media_inhibitor.is_none());
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    #[test]
+    fn test_media_end_timer_binds_and_fires_after_track() {
+        let mut app = get_test_app();
+        let unbound = TimerCondition::MediaEnds(MediaTrack::default());
+
+        // Nothing playing, nothing to wait for
+        assert_eq!(app.bind_media_track(unbound.clone()), None);
+
+        app.runner.media_players =
+            Some(vec![podcast_player(PlaybackStatus::Playing, "/episode/42")]);
+        let bound = app.bind_media_track(unbound).unwrap();
+        assert_eq!(
+            bound,
+            TimerCondition::MediaEnds(MediaTrack {
+                player: "org.mpris.MediaPlayer2.podcasts".to_string(),
+                track: "/episode/42".to_string(),
+            })
+        );
+
+        let mut sleep_timer = Timer::new(0, false, &TimerType::Suspend, NOW).with_condition(&bound);
+        sleep_timer.id = 1;
+        app.runner.active_timers.push(sleep_timer);
+        assert!(app.needs_media_state());
+
+        let _task = app.update(Message::Tick);
+        assert_eq!(app.runner.active_timers.len(), 1);
+
+        // The next episode started, so the one we waited for is over
+        let _task = app.update(Message::PowerMessage(PowerMessage::MediaStateChanged(
+            vec![podcast_player(PlaybackStatus::Playing, "/episode/43")],
+        )));
+        let _task = app.update(Message::Tick);
+        assert!(app.runner.active_timers.is_empty());
+    }
+
+    #[test]
+    fn test_tick_advances_and_finishes_sequences() {
+        let (mut app, clock) = get_test_app_with_clock();
+
+        let mut sequence = Sequence::new(
+            "Pomodoro",
+            1,
+            vec![
+                Phase::new("Work", 1500, false),
+                Phase::new("Break", 300, false),
+            ],
+            NOW,
+        );
+        sequence.id = 1;
+        let _task = app.update(Message::SequenceMessage(SequenceMessage::Created(Ok(
+            sequence,
+        ))));
+
+        // Work ran out, the break starts
+        clock.advance(1500);
+        let _task = app.update(Message::Tick);
+        assert_eq!(app.sequences.len(), 1);
+        assert_eq!(
+            app.sequences[0].current_phase().map(|p| p.name.as_str()),
+            Some("Break")
+        );
+        assert_eq!(app.sequences[0].step_ends_at, NOW + 1800);
+
+        // A paused break never runs out, and picks up where it left off
+        clock.advance(100);
+        let _task = app.update(Message::SequenceMessage(SequenceMessage::Pause(1)));
+        clock.advance(3600);
+        let _task = app.update(Message::Tick);
+        assert_eq!(app.sequences.len(), 1);
+        let _task = app.update(Message::SequenceMessage(SequenceMessage::Resume(1)));
+        assert_eq!(app.sequences[0].step_ends_at, NOW + 5400);
+
+        // Skipping the last phase finishes the sequence
+        let _task = app.update(Message::SequencesMessage(sequences::Message::Skip(1)));
+        assert!(app.sequences.is_empty());
+    }
+
+    #[test]
+    fn test_show_page() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+
+        let _task = app.update(Message::ShowPage(PopupPage::Timers(TimerPage::Sequences)));
+        assert_eq!(app.page, PopupPage::Timers(TimerPage::Sequences));
+        // The page is remembered for the next time the applet starts
+        assert_eq!(
+            app.config.last_page,
+            PopupPage::Timers(TimerPage::Sequences)
+        );
+    }
+
+    #[test]
+    fn test_shortcuts_pick_operation_from_any_page() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+        app.page = PopupPage::History;
+
+        let _task = app.update(Message::Shortcut(MenuAction::SelectOperation(4)));
+        assert_eq!(app.page, PopupPage::PowerControls);
+        assert_eq!(app.power_controls.power_buttons.selected, Some(4));
+
+        let _task = app.update(Message::Shortcut(MenuAction::NextOperation));
+        assert_eq!(app.power_controls.power_buttons.selected, Some(5));
+
+        let _task = app.update(Message::Shortcut(MenuAction::PreviousPage));
+        assert_eq!(app.page, PopupPage::Settings);
+    }
+
+    #[test]
+    fn test_shortcuts_cancel_focused_timer() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+        for id in 1..=3 {
+            let mut timer = Timer::new(60, false, &TimerType::Suspend, NOW);
+            timer.id = id;
+            app.runner.active_timers.push(timer);
+        }
+
+        // Nothing is focused yet, so nothing is cancelled
+        let _task = app.update(Message::Shortcut(MenuAction::CancelFocused));
+        assert_eq!(app.runner.active_timers.len(), 3);
+
+        let _task = app.update(Message::Shortcut(MenuAction::FocusNextItem));
+        let _task = app.update(Message::Shortcut(MenuAction::FocusNextItem));
+        assert_eq!(app.focused_item, Some(2));
+
+        let _task = app.update(Message::Shortcut(MenuAction::CancelFocused));
+        let ids: Vec<i64> = app.runner.active_timers.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        // The focus moves on to the next row
+        assert_eq!(app.focused_item, Some(3));
+
+        // Pausing toggles the focused timer
+        let _task = app.update(Message::Shortcut(MenuAction::PauseFocused));
+        assert!(app.runner.active_timers[1].is_paused());
+        let _task = app.update(Message::Shortcut(MenuAction::PauseFocused));
+        assert!(!app.runner.active_timers[1].is_paused());
+
+        // Switching pages drops the focus
+        let _task = app.update(Message::Shortcut(MenuAction::NextPage));
+        assert_eq!(app.page, PopupPage::Timers(TimerPage::Active));
+        assert_eq!(app.focused_item, None);
+    }
+
+    #[test]
+    fn test_history_keeps_newest_first() {
+        let mut app = get_test_app();
+        let timer = Timer::new(60, false, &TimerType::Suspend, NOW);
+        let mut older = HistoryEntry::new(&timer, Outcome::Finished, 100);
+        older.id = 1;
+        let mut newer = HistoryEntry::new(&timer, Outcome::Cancelled, 200);
+        newer.id = 2;
+
+        let _task = app.update(Message::HistoryMessage(HistoryMessage::Fetched(Ok(vec![
+            older,
+        ]))));
+        let _task = app.update(Message::HistoryMessage(HistoryMessage::Recorded(Ok(
+            newer.clone()
+        ))));
+        assert_eq!(app.history.len(), 2);
+        assert_eq!(app.history[0], newer);
+
+        let _task = app.update(Message::HistoryMessage(history::Message::Clear.into()));
+        assert!(app.history.is_empty());
+    }
+
+    #[test]
+    fn test_reminders_page_cancels_timer() {
+        let mut app = get_test_app();
+        let mut timer = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        timer.id = 1;
+        app.runner.active_timers.push(timer);
+
+        let _task = app.update(Message::RemindersMessage(reminders::Message::Cancel(1)));
+        assert!(app.runner.active_timers.is_empty());
+    }
+
+    #[test]
+    fn test_quick_entry_opens_popup_and_closes_with_it() {
+        let mut app = get_test_app();
+        let _task = app.open_quick_entry();
+        assert!(app.popup.is_some());
+        assert!(app.quick_entry.is_some());
+
+        let _task = app.update(Message::QuickEntryMessage(
+            quick_entry::Message::InputChanged("25m standup".to_string()),
+        ));
+        // Opening it again keeps what was typed
+        let _task = app.open_quick_entry();
+        assert_eq!(
+            app.quick_entry.as_ref().map(|page| page.input.as_str()),
+            Some("25m standup")
+        );
+
+        let _task = app.update(Message::TogglePopup);
+        assert!(app.popup.is_none());
+        assert!(app.quick_entry.is_none());
+    }
+
+    #[test]
+    fn test_quick_entry_create_closes_prompt() {
+        let mut app = get_test_app();
+        let _task = app.open_quick_entry();
+
+        let _task = app.update(Message::QuickEntryMessage(quick_entry::Message::Create(
+            quick_entry::When::In(1500),
+            TimerType::UserDefined("standup".to_string()),
+        )));
+        assert!(app.quick_entry.is_none());
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn test_completed_timer_arms_next_link() {
+        let mut app = get_test_app();
+        let mut head = Timer::new(60, false, &TimerType::UserDefined("Save".to_string()), NOW);
+        head.id = 1;
+        let mut logout = Timer::chained(300, &TimerType::Logout, NOW);
+        logout.id = 2;
+        logout.parent_id = 1;
+
+        let _task = app.update(Message::TimerMessage(TimerMessage::ChainCreated(Ok(vec![
+            head,
+            logout.clone(),
+        ]))));
+        assert_eq!(app.runner.active_timers.len(), 1);
+        assert_eq!(app.chained_timers.len(), 1);
+
+        // The database armed the logout once the reminder finished
+        app.runner.active_timers.clear();
+        logout.parent_id = 0;
+        let _task = app.update(Message::TimerMessage(TimerMessage::Completed(
+            1,
+            Ok(Some(logout)),
+        )));
+        assert_eq!(app.runner.active_timers.len(), 1);
+        assert_eq!(app.runner.active_timers[0].id, 2);
+        assert!(app.chained_timers.is_empty());
+    }
+
+    #[test]
+    fn test_reload_does_not_bring_back_removed_timers() {
+        let mut app = get_test_app();
+        let mut finished = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        finished.id = 1;
+        let mut logout = Timer::new(300, false, &TimerType::Logout, NOW);
+        logout.id = 2;
+        let mut added = Timer::new(600, false, &TimerType::Shutdown, NOW);
+        added.id = 3;
+
+        // The tick fired the reminder, but the database hasn't deleted it yet
+        app.removing_timers.insert(1);
+        let _task = app.update(Message::TimerMessage(TimerMessage::Reloaded(
+            0,
+            Ok(vec![finished.clone(), logout.clone(), added]),
+        )));
+        let ids: Vec<i64> = app.runner.active_timers.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        // Messages for timers the reload already picked up don't add them twice
+        let _task = app.update(Message::TimerMessage(TimerMessage::Completed(
+            1,
+            Ok(Some(logout.clone())),
+        )));
+        let _task = app.update(Message::TimerMessage(TimerMessage::Created(Ok(logout))));
+        assert_eq!(app.runner.active_timers.len(), 2);
+        assert!(app.removing_timers.is_empty());
+
+        // A reload fetched before the delete landed arrives after it
+        let _task = app.update(Message::TimerMessage(TimerMessage::Reloaded(
+            0,
+            Ok(vec![finished]),
+        )));
+        assert_eq!(app.runner.active_timers.len(), 2);
+    }
+
+    #[test]
+    fn test_cancel_removes_whole_chain() {
+        let mut app = get_test_app();
+        let mut head = Timer::new(60, false, &TimerType::Suspend, NOW);
+        head.id = 1;
+        let mut logout = Timer::chained(60, &TimerType::Logout, NOW);
+        logout.id = 2;
+        logout.parent_id = 1;
+        let mut shutdown = Timer::chained(60, &TimerType::Shutdown, NOW);
+        shutdown.id = 3;
+        shutdown.parent_id = 2;
+        let mut other = Timer::chained(60, &TimerType::Reboot, NOW);
+        other.id = 5;
+        other.parent_id = 4;
+
+        app.runner.active_timers = vec![head];
+        app.chained_timers = vec![logout, shutdown, other];
+
+        let _task = app.update(Message::TimerMessage(TimerMessage::Cancel(1)));
+        assert!(app.runner.active_timers.is_empty());
+        assert_eq!(app.chained_timers.len(), 1);
+        assert_eq!(app.chained_timers[0].id, 5);
+    }
+
+    #[test]
+    fn test_exported_timer_leaves_for_systemd() {
+        let mut app = get_test_app();
+        let mut tea = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        tea.id = 1;
+        app.runner.active_timers = vec![tea.clone()];
+
+        let _task = app.update(Message::TimerMessage(TimerMessage::Exported(1, Ok(()))));
+        assert!(app.runner.active_timers.is_empty());
+
+        // systemd's timers are listed next to the applet's own
+        let _task = app.update(Message::TimerMessage(TimerMessage::SystemdFetched(Ok(
+            vec![("chronomancer-1".to_string(), tea)],
+        ))));
+        assert!(app.lists_timers());
+        assert_eq!(app.systemd_timers[0].0, "chronomancer-1");
+    }
+
+    #[test]
+    fn test_stopwatch_controls_without_database() {
+        let mut app = get_test_app();
+        let _task = app.update(Message::StopwatchMessage(StopwatchMessage::Start));
+
+        // Nothing can be stored yet, so the controls are ignored
+        assert!(!app.stopwatch.is_running());
+
+        let mut stored = Stopwatch::default();
+        stored.id = 3;
+        stored.start(1_000);
+        let _task = app.update(Message::StopwatchMessage(StopwatchMessage::Fetched(Ok(
+            vec![stored],
+        ))));
+        assert_eq!(app.stopwatch.id, 3);
+        assert!(app.stopwatch.is_running());
+    }
+
+    #[test]
+    fn test_stopwatch_waits_for_first_store() {
+        let mut app = get_test_app();
+        app.stopwatch.start(1_000);
+        app.stopwatch_inserting = true;
+
+        // Without an id, a reset would leave the stored stopwatch behind
+        let _task = app.update(Message::StopwatchMessage(StopwatchMessage::Reset));
+        assert!(app.stopwatch.is_running());
+
+        let mut stored = app.stopwatch.clone();
+        stored.id = 4;
+        let _task = app.update(Message::StopwatchMessage(StopwatchMessage::Created(Ok(
+            stored,
+        ))));
+        assert!(!app.stopwatch_inserting);
+        assert_eq!(app.stopwatch.id, 4);
+
+        let _task = app.update(Message::StopwatchMessage(StopwatchMessage::Reset));
+        assert!(!app.stopwatch.is_running());
+        assert_eq!(app.stopwatch.id, 0);
+    }
+
+    #[test]
+    fn test_settings_save_and_remove_presets() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+        let defaults = app.config.quick_timers.len();
+        let preset = QuickTimerPreset {
+            label: "Laundry".to_string(),
+            duration_seconds: 45 * 60,
+            action: crate::config::PresetAction::Notify,
+            description: String::new(),
+        };
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Save(
+            None,
+            preset.clone(),
+        )));
+        assert_eq!(app.config.quick_timers.len(), defaults + 1);
+        assert_eq!(app.config.quick_timers.last(), Some(&preset));
+
+        let renamed = QuickTimerPreset {
+            label: "Dryer".to_string(),
+            ..preset
+        };
+        let _task = app.update(Message::SettingsMessage(settings::Message::Save(
+            Some(defaults),
+            renamed,
+        )));
+        assert_eq!(app.config.quick_timers.len(), defaults + 1);
+        assert_eq!(app.config.quick_timers[defaults].label, "Dryer");
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Remove(0)));
+        assert_eq!(app.config.quick_timers.len(), defaults);
+        assert_eq!(app.settings_page.editing, None);
+    }
+
+    #[test]
+    fn test_pending_action_follows_ticks_and_cancel() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+        app.config.grace_period_seconds = 30;
+        app.config.confirm_before_execute = false;
+
+        let mut shutdown = Timer::new(60, false, &TimerType::Shutdown, NOW);
+        shutdown.id = 1;
+        shutdown.ends_at = NOW - 1;
+        app.runner.active_timers.push(shutdown);
+
+        // The tick hands the timer to the runner, which holds it for the grace period
+        let _task = app.handle_tick();
+        assert!(app.runner.active_timers.is_empty());
+        assert!(app.runner.pending_action.is_some());
+        assert_eq!(app.deadlines().next(), Some(NOW + 30));
+
+        let _task = app.update(Message::PowerMessage(PowerMessage::CancelPendingAction));
+        assert!(app.runner.pending_action.is_none());
+    }
+
+    #[test]
+    fn test_clock_changes_move_timers_and_sequences() {
+        let (mut app, clock) = get_test_app_with_clock();
+        app.config_handler = None;
+
+        let mut tea = Timer::new(600, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        tea.id = 1;
+        app.runner.active_timers = vec![tea];
+        let mut sequence = Sequence::new("Focus", 1, vec![Phase::new("Work", 1500, false)], NOW);
+        sequence.id = 1;
+        app.sequences.push(sequence);
+
+        // The clock is set back an hour, countdowns keep their time left
+        clock.set(NOW - 3600);
+        let _task = app.update(Message::ClockChanged(ClockChange {
+            jumped_by: -3600,
+            time_zone: None,
+        }));
+        let now = clock.now();
+        assert_eq!(app.runner.active_timers[0].remaining(now), 600);
+        assert_eq!(app.sequences[0].remaining(now), 1500);
+    }
+
+    #[test]
+    fn test_confirmation_waits_for_run_now() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+        app.config.confirm_before_execute = true;
+
+        let mut suspend = Timer::new(60, false, &TimerType::Suspend, NOW);
+        suspend.id = 1;
+        suspend.ends_at = NOW - 1;
+        app.runner.active_timers.push(suspend);
+
+        let _task = app.handle_tick();
+        assert_eq!(
+            app.runner.pending_action,
+            Some(PendingAction {
+                timer_type: TimerType::Suspend,
+                runs_at: None,
+            })
+        );
+
+        // Waiting for confirmation never runs on its own
+        let _task = app.handle_tick();
+        assert!(app.runner.pending_action.is_some());
+
+        let _task = app.update(Message::PowerMessage(PowerMessage::RunPendingAction));
+        assert!(app.runner.pending_action.is_none());
+    }
+
+    #[test]
+    fn test_discarded_missed_timers_take_their_chain() {
+        let mut app = get_test_app();
+        let mut missed = Timer::new(60, false, &TimerType::Reboot, NOW);
+        missed.id = 4;
+        missed.ends_at = NOW - 600;
+        let mut link = Timer::chained(300, &TimerType::UserDefined("Log in".to_string()), NOW);
+        link.id = 5;
+        link.parent_id = 4;
+        app.chained_timers.push(link);
+
+        app.config.missed_timer_policy = MissedTimerPolicy::Discard;
+        let _task = app.update(Message::TimerMessage(TimerMessage::MissedFetched(Ok(
+            vec![missed],
+        ))));
+        assert!(app.runner.active_timers.is_empty());
+        assert!(app.chained_timers.is_empty());
+    }
+
+    #[test]
+    fn test_settings_change_updates_config_and_form() {
+        let mut app = get_test_app();
+        app.config_handler = None;
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Set(
+            Setting::DefaultUnit(crate::utils::TimeUnit::Hours),
+        )));
+        assert_eq!(app.config.default_unit, crate::utils::TimeUnit::Hours);
+        assert_eq!(
+            app.power_controls.power_form.time_unit,
+            crate::utils::TimeUnit::Hours
+        );
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Set(
+            Setting::PersistentNotifications(true),
+        )));
+        assert!(matches!(
+            runner::notification_timeout(&app.config),
+            Timeout::Never
+        ));
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Set(
+            Setting::StayAwakeMinBattery(25),
+        )));
+        assert_eq!(app.config.stay_awake_min_battery, 25);
+
+        let _task = app.update(Message::SettingsMessage(settings::Message::Set(
+            Setting::LogLevel(crate::config::LogLevel::Debug),
+        )));
+        assert_eq!(app.config.log_level, crate::config::LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_panel_countdown_picks_soonest_deadline() {
+        let mut app = get_test_app();
+        assert_eq!(app.next_countdown(NOW), None);
+
+        let trigger = Timer::new(0, false, &TimerType::Hibernate, NOW)
+            .with_condition(&TimerCondition::BatteryBelow(10));
+        let mut shutdown = Timer::new(3600, false, &TimerType::Shutdown, NOW);
+        shutdown.ends_at = NOW + 3600;
+        let mut tea = Timer::new(300, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        tea.ends_at = NOW + 300;
+        app.runner.active_timers = vec![trigger, shutdown, tea];
+
+        assert_eq!(
+            app.next_countdown(NOW),
+            Some(panel_button::Countdown {
+                icon_name: "alarm-symbolic",
+                remaining: 300,
+            })
+        );
+
+        // Paused timers don't count down
+        app.runner.active_timers[2].pause(NOW);
+        assert_eq!(
+            app.next_countdown(NOW).map(|countdown| countdown.remaining),
+            Some(3600)
+        );
+    }
+
+    #[test]
+    fn test_daemon_runs_timers_while_it_owns_the_service() {
+        let mut app = get_test_app();
+        let mut shutdown = Timer::new(60, false, &TimerType::Shutdown, NOW);
+        shutdown.id = 1;
+        shutdown.ends_at = NOW - 1;
+        app.runner.active_timers.push(shutdown);
+
+        let _task = app.update(Message::ServiceMessage(ServiceMessage::Update(
+            Update::Owner(false),
+        )));
+        assert!(!app.scheduling);
+
+        // The daemon fires the timer, the applet only shows it
+        let _task = app.handle_tick();
+        assert_eq!(app.runner.active_timers.len(), 1);
+        assert!(app.runner.pending_action.is_none());
+
+        let pending = PendingAction {
+            timer_type: TimerType::Shutdown,
+            runs_at: None,
+        };
+        let _task = app.update(Message::ServiceMessage(ServiceMessage::Update(
+            Update::PendingActionChanged(Some(pending.clone())),
+        )));
+        let _task = app.update(Message::ServiceMessage(ServiceMessage::Update(
+            Update::StayAwakeChanged(true),
+        )));
+        assert_eq!(app.runner.pending_action, Some(pending));
+        assert!(app.stays_awake());
+        assert!(app.suspend_inhibitor.is_none());
+
+        // Taking the service back drops the daemon's state
+        let _task = app.update(Message::ServiceMessage(ServiceMessage::Update(
+            Update::Owner(true),
+        )));
+        assert!(app.scheduling);
+        assert!(app.runner.pending_action.is_none());
+        assert!(!app.stays_awake());
     }
 }
@@ -7,7 +7,7 @@
 //! adapting it tho have a clear separation between UI pages and service layer operations.
 //! Messages flow from UI interactions down to service layer operations and back
 //! up as results. The main `AppMessage` enum dispatches to specialized message
-//! types for logically divided subsystems (database, power management, timers, pages).
+//! types for logically divided subsystems (database, power management, timers, sequences, history, pages).
 //!
 //! ## Message Flow Pattern
 //!
@@ -37,10 +37,26 @@
 //! }
 //! ```
 
-use std::{fs::File, sync::Arc};
+use std::{fs::File, path::PathBuf, sync::Arc};
+
+use cosmic::iced::keyboard::{Key, Modifiers};
 
 use crate::{
-    config::Config, models::Timer, pages::power_controls, utils::database::SQLiteDatabase,
+    config::Config,
+    error::AppError,
+    key_binds::MenuAction,
+    models::{
+        HistoryEntry, Sequence, Stopwatch, Timer, condition::TimerCondition, timer::TimerType,
+    },
+    pages::{
+        PopupPage, chains, history, power_controls, quick_entry, reminders, sequences, settings,
+        stopwatch,
+    },
+    service::{PendingAction, ServiceEvent, Update},
+    utils::{
+        clock::ClockChange, database::SQLiteDatabase, mpris::PlayerState,
+        system_load::ActivitySample, upower::PowerState,
+    },
 };
 
 /// Messages related to database operations.
@@ -50,50 +66,177 @@ use crate::{
 #[derive(Debug, Clone)]
 pub enum DatabaseMessage {
     /// Database successfully initialized with the given connection
-    Initialized(Result<SQLiteDatabase, String>),
-    /// Database initialization failed with an error message
-    FailedToInitialize(String),
+    Initialized(Result<SQLiteDatabase, AppError>),
+    /// Database initialization failed, shown until the applet restarts
+    FailedToInitialize(AppError),
+    /// Another connection, like the CLI, committed changes to the database
+    Changed,
 }
 
 /// Messages related to power management operations.
 ///
-/// Handles stay-awake inhibit locks, timed power operations (suspend, logout,
-/// shutdown, reboot), and immediate execution of those operations. Inhibit
+/// Handles stay-awake inhibit locks, timed power operations (suspend, hibernate,
+/// logout, shutdown, reboot), and immediate execution of those operations. Inhibit
 /// locks prevent the system from sleeping while active without overriding user settings.
+/// Timed operations carry a [`TimerCondition`] that can gate or replace their deadline.
+/// The media inhibit lock is separate from stay-awake and only held while a media
+/// player reports that it is playing. Finished power timers may leave their action
+/// pending for a grace period or until confirmed, see the pending action variants.
 #[derive(Debug, Clone)]
 pub enum PowerMessage {
     /// Toggle the stay-awake inhibit lock on/off
     ToggleStayAwake,
     /// Result of acquiring a systemd inhibit lock (wrapped in Arc for cheap cloning)
-    InhibitAcquired(Arc<Result<File, String>>),
+    InhibitAcquired(Arc<Result<File, AppError>>),
     /// Schedule a suspend operation after the given number of seconds
-    SetSuspendTime(i32),
+    SetSuspendTime(i32, TimerCondition),
+    /// Schedule a hibernate operation after the given number of seconds
+    SetHibernateTime(i32, TimerCondition),
     /// Schedule a logout operation after the given number of seconds
-    SetLogoutTime(i32),
+    SetLogoutTime(i32, TimerCondition),
     /// Schedule a shutdown operation after the given number of seconds
-    SetShutdownTime(i32),
+    SetShutdownTime(i32, TimerCondition),
     /// Schedule a reboot operation after the given number of seconds
-    SetRebootTime(i32),
+    SetRebootTime(i32, TimerCondition),
+    /// Battery/AC state reported by `UPower`
+    PowerStateChanged(PowerState),
+    /// Turn "stay awake while media plays" on or off
+    SetMediaStayAwake(bool),
+    /// Playback state of all MPRIS media players
+    MediaStateChanged(Vec<PlayerState>),
+    /// Result of acquiring the media inhibit lock (wrapped in Arc for cheap cloning)
+    MediaInhibitAcquired(Arc<Result<File, AppError>>),
+    /// Run the power action waiting out its grace period or for confirmation now
+    RunPendingAction,
+    /// Drop the power action waiting out its grace period or for confirmation
+    CancelPendingAction,
     /// Immediately execute a system suspend
     ExecuteSuspend,
+    /// Immediately execute a system hibernate
+    ExecuteHibernate,
     /// Immediately execute a user logout
     ExecuteLogout,
     /// Immediately execute a system shutdown
     ExecuteShutdown,
     /// Immediately execute a system reboot
     ExecuteReboot,
+    /// Result of executing the power operation of the given type
+    Executed(TimerType, Result<(), AppError>),
 }
 
 /// Messages related to timer operations.
 ///
-/// Represents results from timer creation and retrieval operations. Timers
-/// are stored in the database and tracked for countdown display and notifications.
+/// Represents notification-only timer requests and results from timer creation and
+/// retrieval operations. Timers are stored in the database and tracked for countdown
+/// display and notifications.
 #[derive(Debug, Clone)]
 pub enum TimerMessage {
     /// Result of creating a new timer (contains the created Timer on success)
-    Created(Result<Timer, String>),
+    Created(Result<Timer, AppError>),
     /// Result of fetching all active timers from the database
-    ActiveFetched(Result<Vec<Timer>, String>),
+    ActiveFetched(Result<Vec<Timer>, AppError>),
+    /// Schedule a notification-only timer after the given number of seconds
+    SetNotifyTime(i32, TimerCondition),
+    /// Store and start a chain of timers, first timer first
+    StartChain(Vec<Timer>),
+    /// Result of storing a chain (contains the stored timers on success)
+    ChainCreated(Result<Vec<Timer>, AppError>),
+    /// Result of fetching chain links still waiting on their parent
+    ChainedFetched(Result<Vec<Timer>, AppError>),
+    /// Result of fetching timers that ended while the applet wasn't running
+    MissedFetched(Result<Vec<Timer>, AppError>),
+    /// Result of resyncing running timers with the database after it changed,
+    /// tagged with the timers version the fetch started at
+    Reloaded(u64, Result<Vec<Timer>, AppError>),
+    /// Result of completing the timer with the given id (contains the armed next link, if any)
+    Completed(i64, Result<Option<Timer>, AppError>),
+    /// Result of deleting timers that already left the active list, by id
+    Removed(Vec<i64>, Result<(), AppError>),
+    /// Cancel the timer with the given id, along with the rest of its chain
+    Cancel(i64),
+    /// Move the timer with the given id to a systemd user timer
+    Export(i64),
+    /// Result of exporting the timer with the given id
+    Exported(i64, Result<(), AppError>),
+    /// Result of reading the user's systemd timers (unit names with the timer each would be)
+    SystemdFetched(Result<Vec<(String, Timer)>, AppError>),
+    /// Pause the timer with the given id
+    Pause(i64),
+    /// Resume the timer with the given id
+    Resume(i64),
+    /// Result of storing a paused or resumed timer (contains the stored Timer on success)
+    PauseStored(Result<Timer, AppError>),
+}
+
+/// Messages related to sequence timers.
+///
+/// Sequences advance on the regular tick; these messages cover starting them,
+/// loading them on startup, and the pause, skip and stop controls.
+#[derive(Debug, Clone)]
+pub enum SequenceMessage {
+    /// Store and start a new sequence
+    Start(Sequence),
+    /// Result of storing a new sequence (contains the stored Sequence on success)
+    Created(Result<Sequence, AppError>),
+    /// Result of fetching all stored sequences from the database
+    ActiveFetched(Result<Vec<Sequence>, AppError>),
+    /// Pause the sequence with the given id
+    Pause(i64),
+    /// Resume the sequence with the given id
+    Resume(i64),
+    /// Skip to the next phase of the sequence with the given id
+    Skip(i64),
+    /// Stop and delete the sequence with the given id
+    Stop(i64),
+}
+
+/// Messages related to the stopwatch.
+///
+/// The controls arrive from the stopwatch page (converted via `From`), the
+/// results come back from saving and loading the stopwatch.
+#[derive(Debug, Clone)]
+pub enum StopwatchMessage {
+    /// Start or continue counting
+    Start,
+    /// Stop counting, keeping the elapsed time
+    Stop,
+    /// Record a lap
+    Lap,
+    /// Clear the elapsed time and laps
+    Reset,
+    /// Result of storing a newly started stopwatch
+    Created(Result<Stopwatch, AppError>),
+    /// Result of fetching the stored stopwatch from the database
+    Fetched(Result<Vec<Stopwatch>, AppError>),
+}
+
+/// Messages related to the timer history.
+///
+/// Entries are recorded by the app whenever a timer leaves the active list; the
+/// clear control arrives from the history page (converted via `From`).
+#[derive(Debug, Clone)]
+pub enum HistoryMessage {
+    /// Delete every history entry
+    Clear,
+    /// Result of deleting the history
+    Cleared(Result<(), AppError>),
+    /// Result of fetching the stored history from the database, newest first
+    Fetched(Result<Vec<HistoryEntry>, AppError>),
+    /// Result of storing a new history entry
+    Recorded(Result<HistoryEntry, AppError>),
+}
+
+/// Messages from the session D-Bus timer service (see [`crate::service`]).
+#[derive(Debug, Clone)]
+pub enum ServiceMessage {
+    /// The service is published, keep its connection for sending signals
+    Started(zbus::Connection),
+    /// The service received a request the app has to act on
+    Event(ServiceEvent),
+    /// The service's owner changed, or `chronomancerd` reported a change while it owns it
+    Update(Update),
+    /// Result of fetching `chronomancerd`'s stay-awake state and pending power action
+    StateFetched(Result<(bool, Option<PendingAction>), AppError>),
 }
 
 /// Top-level application messages that coordinate all subsystems.
@@ -105,18 +248,52 @@ pub enum TimerMessage {
 pub enum AppMessage {
     /// Toggle the applet's popup window open/closed
     TogglePopup,
+    /// Switch the popup to another page
+    ShowPage(PopupPage),
+    /// A key press no widget in the popup handled, looked up in the key bindings
+    KeyPressed(Key, Modifiers),
+    /// Run a keyboard shortcut's action
+    Shortcut(MenuAction),
     /// Update the app configuration (triggers save to disk)
     UpdateConfig(Config),
-    /// Regular tick for timer countdown updates (fires every second)
+    /// Tick at the next deadline, and every second while a countdown is on screen
     Tick,
+    /// CPU, disk and network activity sampled while a "system quiet" trigger is waiting
+    ActivitySampled(ActivitySample),
+    /// The file watcher saw activity on a path watched by a file trigger
+    FileChanged(PathBuf),
+    /// The wall clock was set or the time zone changed, see [`ClockWatch`](crate::utils::clock::ClockWatch)
+    ClockChanged(ClockChange),
     /// Message from the power controls page (auto-converted via From trait)
     PowerControlsMessage(power_controls::Message),
+    /// Message from the sequences page (auto-converted via From trait)
+    SequencesMessage(sequences::Message),
+    /// Message from the chains page (auto-converted via From trait)
+    ChainsMessage(chains::Message),
+    /// Message from the reminders page (auto-converted via From trait)
+    RemindersMessage(reminders::Message),
+    /// Message from the quick entry prompt (auto-converted via From trait)
+    QuickEntryMessage(quick_entry::Message),
+    /// Message from the settings page (auto-converted via From trait)
+    SettingsMessage(settings::Message),
+    /// Start the quick timer preset at the given index
+    QuickTimer(usize),
     /// Message from database operations
     DatabaseMessage(DatabaseMessage),
     /// Message from timer operations
     TimerMessage(TimerMessage),
     /// Message from power management operations
     PowerMessage(PowerMessage),
+    /// Message from sequence timer operations
+    SequenceMessage(SequenceMessage),
+    /// Message from stopwatch operations
+    StopwatchMessage(StopwatchMessage),
+    /// Message from timer history operations
+    HistoryMessage(HistoryMessage),
+    /// Message from the D-Bus timer service
+    ServiceMessage(ServiceMessage),
+    /// Hide the banner of the last failure
+    DismissError,
 }
 
 /// Automatic conversion from power controls page messages to app messages.
@@ -129,3 +306,64 @@ impl From<power_controls::Message> for AppMessage {
         AppMessage::PowerControlsMessage(msg)
     }
 }
+
+/// Automatic conversion from sequences page messages to app messages.
+impl From<sequences::Message> for AppMessage {
+    fn from(msg: sequences::Message) -> Self {
+        AppMessage::SequencesMessage(msg)
+    }
+}
+
+/// Automatic conversion from chains page messages to app messages.
+impl From<chains::Message> for AppMessage {
+    fn from(msg: chains::Message) -> Self {
+        AppMessage::ChainsMessage(msg)
+    }
+}
+
+/// Automatic conversion from reminders page messages to app messages.
+impl From<reminders::Message> for AppMessage {
+    fn from(msg: reminders::Message) -> Self {
+        AppMessage::RemindersMessage(msg)
+    }
+}
+
+/// Automatic conversion from quick entry prompt messages to app messages.
+impl From<quick_entry::Message> for AppMessage {
+    fn from(msg: quick_entry::Message) -> Self {
+        AppMessage::QuickEntryMessage(msg)
+    }
+}
+
+/// Conversion from stopwatch page messages to the app's stopwatch messages.
+///
+/// The page has no state to update, so its buttons map straight onto app-level controls.
+impl From<stopwatch::Message> for StopwatchMessage {
+    fn from(msg: stopwatch::Message) -> Self {
+        match msg {
+            stopwatch::Message::Start => StopwatchMessage::Start,
+            stopwatch::Message::Stop => StopwatchMessage::Stop,
+            stopwatch::Message::Lap => StopwatchMessage::Lap,
+            stopwatch::Message::Reset => StopwatchMessage::Reset,
+        }
+    }
+}
+
+/// Automatic conversion from settings page messages to app messages.
+impl From<settings::Message> for AppMessage {
+    fn from(msg: settings::Message) -> Self {
+        AppMessage::SettingsMessage(msg)
+    }
+}
+
+/// Conversion from history page messages to the app's history messages.
+///
+/// Like the stopwatch page, the history page has no state and its only control
+/// maps straight onto an app-level message.
+impl From<history::Message> for HistoryMessage {
+    fn from(msg: history::Message) -> Self {
+        match msg {
+            history::Message::Clear => HistoryMessage::Clear,
+        }
+    }
+}
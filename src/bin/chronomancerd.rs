@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT
+
+//! `chronomancerd` runs Chronomancer's timers without the panel applet.
+//!
+//! See [`chronomancer::daemon`] for what it covers. It's meant to run as a
+//! systemd user service, installed from `resources/chronomancerd.service`.
+
+use chronomancer::{config::Config, daemon, i18n, utils::logging};
+use i18n_embed::DesktopLanguageRequester;
+
+#[tokio::main]
+async fn main() {
+    // Log to the journal (through stderr) and the log file
+    let log_guard = logging::init("chronomancerd", Config::load().log_level);
+
+    // Power action names in notifications are localized
+    i18n::init(&DesktopLanguageRequester::requested_languages());
+
+    if let Err(e) = daemon::run().await {
+        tracing::error!("{e:#}");
+        // Exiting skips destructors, so flush the log file first
+        drop(log_guard);
+        std::process::exit(1);
+    }
+}
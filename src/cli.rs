@@ -0,0 +1,753 @@
+// SPDX-License-Identifier: MIT
+
+//! Command-line interface.
+//!
+//! `chronomancer` without arguments runs the panel applet. With a command it
+//! works on the same `SQLite` database instead, so scripts and SSH sessions can
+//! schedule timers without the GUI:
+//!
+//! ```text
+//! chronomancer add 1h reboot
+//! chronomancer list --json
+//! chronomancer cancel 12
+//! ```
+//!
+//! After changing timers the CLI asks a running applet to reload them (see
+//! [`activation::RELOAD_ACTION`]), so the panel shows the change right away
+//! instead of on its next database check.
+//! Stay-awake lives in the applet, so `stay-awake on|off` needs it running,
+//! while `stay-awake for <duration>` holds its own inhibitor lock until the
+//! time is up or the command is interrupted.
+//!
+//! `export` moves a timer to a systemd user timer and `import` adds the user's
+//! systemd timers to the list (see [`systemd`]), so scheduled actions can run
+//! from the user's service manager without the desktop.
+//!
+//! `--json` prints machine-readable output instead of text: an array for
+//! `list` and `history`, an object for the other commands.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use zbus::zvariant::Value;
+
+use crate::{
+    components::timer_list::{timer_name, timer_summary},
+    fl,
+    models::{HistoryEntry, Timer, timer::TimerType},
+    pages::{
+        history,
+        quick_entry::{When, parse_quick_entry},
+    },
+    utils::{
+        activation,
+        clock::{Clock, SystemClock},
+        database::{Repository, SQLiteDatabase},
+        format_duration, resources,
+        systemd::{self, ImportedTimer},
+        time::parse_duration,
+    },
+};
+
+/// Usage text printed for `chronomancer help` and after parse errors.
+pub const USAGE: &str = "\
+Usage: chronomancer [COMMAND] [--json]
+
+Without a command, runs the panel applet.
+
+Commands:
+  add <duration> [what]      Start a timer, e.g. `add 25m standup` or `add 1h reboot`
+  list                       List running timers
+  cancel <id>                Cancel a timer and the rest of its chain
+  pause <id>                 Pause a timer
+  resume <id>                Resume a paused timer
+  stay-awake on|off          Turn the applet's stay-awake on or off
+  stay-awake for <duration>  Keep the system awake until the time is up
+  history                    List timers that are no longer running
+  export <id>                Move a timer to a systemd user timer
+  import                     Take exported timers back from systemd, list the rest
+  help                       Show this help
+
+Options:
+  --json                     Print JSON instead of text
+  --quick-entry              Open the applet's quick entry prompt";
+
+/// A parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub command: Command,
+    /// Print JSON instead of text
+    pub json: bool,
+}
+
+/// Commands the CLI can run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Work on the timers in the database
+    Timers(TimerCommand),
+    /// Change the stay-awake mode
+    StayAwake(StayAwake),
+    /// Move the timer with the given id to systemd user units
+    Export(i64),
+    /// Add the systemd user timers to the database
+    Import,
+    /// Open the running applet's quick entry prompt
+    QuickEntry,
+    /// Print the usage text
+    Help,
+}
+
+/// Commands that read or change the timers in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimerCommand {
+    /// Start a timer after the given number of seconds
+    Add(i32, TimerType),
+    /// List running timers
+    List,
+    /// Cancel the timer with the given id
+    Cancel(i64),
+    /// Pause the timer with the given id
+    Pause(i64),
+    /// Resume the timer with the given id
+    Resume(i64),
+    /// List the history
+    History,
+}
+
+/// What `stay-awake` should do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StayAwake {
+    /// Turn the applet's stay-awake on
+    On,
+    /// Turn the applet's stay-awake off
+    Off,
+    /// Keep the system awake for the given number of seconds
+    For(i32),
+}
+
+/// Result of a timer command, printed as text or JSON.
+#[derive(Debug, Clone)]
+pub enum Output {
+    /// Timers listed by `list`
+    Timers(Vec<Timer>),
+    /// The timer that was added, paused or resumed
+    Timer(Timer),
+    /// The timer that was cancelled
+    Cancelled(Timer),
+    /// Entries listed by `history`
+    History(Vec<HistoryEntry>),
+}
+
+/// JSON form of a timer.
+#[derive(Debug, Clone, Serialize)]
+struct TimerJson {
+    id: i64,
+    kind: &'static str,
+    name: String,
+    /// Unix timestamp of the deadline, `None` for trigger timers
+    ends_at: Option<i64>,
+    /// Seconds left, frozen while paused, `None` for trigger timers
+    remaining_seconds: Option<i64>,
+    paused: bool,
+    /// Serialized condition, empty for plain countdowns
+    condition: String,
+}
+
+impl TimerJson {
+    fn new(timer: &Timer, now: i64) -> Self {
+        let has_deadline = timer.ends_at != Timer::NO_DEADLINE;
+        Self {
+            id: timer.id,
+            kind: TimerType::from_str(&timer.description)
+                .map_or("reminder", |timer_type| timer_type.kind()),
+            name: timer_name(timer),
+            ends_at: has_deadline.then_some(timer.ends_at),
+            remaining_seconds: has_deadline.then(|| timer.remaining(now)),
+            paused: timer.is_paused(),
+            condition: timer.condition.clone(),
+        }
+    }
+}
+
+/// Timers added by `import`.
+#[derive(Debug, Clone, Default)]
+pub struct Import {
+    /// The timers added to the database
+    pub timers: Vec<Timer>,
+    /// Units exported by Chronomancer that came back, to be removed from systemd
+    pub exported: Vec<String>,
+    /// Other user timers by unit name, with the timer they would be; systemd
+    /// keeps running them, so they are only listed
+    pub foreign: Vec<(String, Timer)>,
+    /// Units whose schedule Chronomancer can't follow
+    pub skipped: Vec<String>,
+}
+
+/// JSON form of a timer and its systemd unit, for `export` and `import`.
+#[derive(Debug, Clone, Serialize)]
+struct ExportJson {
+    unit: String,
+    timer: TimerJson,
+}
+
+/// JSON form of an import.
+#[derive(Debug, Clone, Serialize)]
+struct ImportJson {
+    imported: Vec<TimerJson>,
+    foreign: Vec<ExportJson>,
+    skipped: Vec<String>,
+}
+
+/// JSON form of a history entry.
+#[derive(Debug, Clone, Serialize)]
+struct HistoryJson {
+    id: i64,
+    name: String,
+    outcome: String,
+    finished_at: i64,
+}
+
+/// JSON form of a stay-awake change.
+#[derive(Debug, Clone, Serialize)]
+struct StayAwakeJson {
+    enabled: bool,
+    seconds: Option<i32>,
+}
+
+/// Parses the command line.
+///
+/// # Arguments
+///
+/// - `args` - The arguments, without the program name
+///
+/// # Returns
+///
+/// The command to run, or `None` without a command, meaning the applet should run.
+///
+/// # Errors
+///
+/// Returns an error for unknown commands or missing and malformed arguments.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chronomancer::cli::{Command, TimerCommand, parse};
+///
+/// let args = ["cancel", "12", "--json"].map(String::from);
+/// let invocation = parse(&args).unwrap().unwrap();
+/// assert_eq!(invocation.command, Command::Timers(TimerCommand::Cancel(12)));
+/// assert!(invocation.json);
+///
+/// assert!(parse(&[]).unwrap().is_none());
+/// ```
+pub fn parse(args: &[String]) -> Result<Option<Invocation>> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let mut args = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--json");
+
+    let command = match args.next() {
+        None if json => bail!("--json needs a command"),
+        None => return Ok(None),
+        Some("--quick-entry") => Command::QuickEntry,
+        Some("help" | "--help" | "-h") => Command::Help,
+        Some("add") => {
+            let entry = args.by_ref().collect::<Vec<_>>().join(" ");
+            let (when, timer_type) = parse_quick_entry(&entry)
+                .ok_or_else(|| anyhow!("Expected a duration like 25m or 1h30m, got `{entry}`"))?;
+            // The timer service only takes countdowns, times of day go through the quick entry prompt
+            let When::In(seconds) = when else {
+                bail!(
+                    "Expected a duration like 25m or 1h30m, use the quick entry prompt for `{entry}`"
+                );
+            };
+            Command::Timers(TimerCommand::Add(seconds, timer_type))
+        }
+        Some("list") => Command::Timers(TimerCommand::List),
+        Some("history") => Command::Timers(TimerCommand::History),
+        Some("cancel") => Command::Timers(TimerCommand::Cancel(parse_id(args.next())?)),
+        Some("pause") => Command::Timers(TimerCommand::Pause(parse_id(args.next())?)),
+        Some("resume") => Command::Timers(TimerCommand::Resume(parse_id(args.next())?)),
+        Some("export") => Command::Export(parse_id(args.next())?),
+        Some("import") => Command::Import,
+        Some("stay-awake") => Command::StayAwake(match args.next() {
+            Some("on") => StayAwake::On,
+            Some("off") => StayAwake::Off,
+            Some("for") => {
+                let duration = args.next().unwrap_or_default();
+                StayAwake::For(
+                    parse_duration(duration)
+                        .ok_or_else(|| anyhow!("Expected a duration like 2h, got `{duration}`"))?,
+                )
+            }
+            _ => bail!("Expected `stay-awake on`, `stay-awake off` or `stay-awake for <duration>`"),
+        }),
+        Some(other) => bail!("Unknown command `{other}`"),
+    };
+
+    if let Some(extra) = args.next() {
+        bail!("Unexpected argument `{extra}`");
+    }
+    Ok(Some(Invocation { command, json }))
+}
+
+/// Parses a timer id argument.
+fn parse_id(arg: Option<&str>) -> Result<i64> {
+    let arg = arg.ok_or_else(|| anyhow!("Expected a timer id, see `chronomancer list`"))?;
+    arg.parse()
+        .with_context(|| format!("Expected a timer id, got `{arg}`"))
+}
+
+/// Runs a parsed command and prints its output.
+///
+/// # Arguments
+///
+/// - `invocation` - The command from [`parse`]
+///
+/// # Errors
+///
+/// Returns an error if the database can't be opened, a timer doesn't exist,
+/// or the applet can't be reached for commands that need it.
+pub async fn run(invocation: Invocation) -> Result<()> {
+    let Invocation { command, json } = invocation;
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::QuickEntry => activation::activate_action(activation::QUICK_ENTRY_ACTION).await?,
+        Command::StayAwake(stay_awake) => run_stay_awake(stay_awake, json).await?,
+        Command::Export(id) => {
+            run_export(id, json).await?;
+            let _ = activation::activate_action(activation::RELOAD_ACTION).await;
+        }
+        Command::Import => {
+            run_import(json).await?;
+            let _ = activation::activate_action(activation::RELOAD_ACTION).await;
+        }
+        Command::Timers(command) => {
+            let database = SQLiteDatabase::new().await?;
+            let now = SystemClock.now();
+            let output = execute(database.pool(), &command, now).await?;
+            println!("{}", render(&output, json, now)?);
+
+            if !matches!(command, TimerCommand::List | TimerCommand::History) {
+                // The applet may not be running, then it loads the change on startup
+                let _ = activation::activate_action(activation::RELOAD_ACTION).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs a timer command against the database.
+///
+/// Works like the applet does: cancelling records the timer in the history and
+/// drops the rest of its chain (see [`Timer::cancel`]).
+///
+/// # Arguments
+///
+/// - `pool` - The database connection pool
+/// - `command` - The command to run
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Errors
+///
+/// Returns an error if a query fails, or the timer to change doesn't exist or is
+/// a chain link still waiting on the timer before it.
+pub async fn execute(pool: &SqlitePool, command: &TimerCommand, now: i64) -> Result<Output> {
+    match command {
+        TimerCommand::Add(seconds, timer_type) => {
+            let timer = Timer::insert(pool, &Timer::new(*seconds, false, timer_type, now)).await?;
+            Ok(Output::Timer(timer))
+        }
+        TimerCommand::List => Ok(Output::Timers(Timer::get_all_active(pool, now).await?)),
+        TimerCommand::History => Ok(Output::History(
+            HistoryEntry::get_all_active(pool, now).await?,
+        )),
+        TimerCommand::Cancel(id) => Ok(Output::Cancelled(Timer::cancel(pool, *id, now).await?)),
+        TimerCommand::Pause(id) => Ok(Output::Timer(
+            Timer::set_paused(pool, *id, true, now).await?,
+        )),
+        TimerCommand::Resume(id) => Ok(Output::Timer(
+            Timer::set_paused(pool, *id, false, now).await?,
+        )),
+    }
+}
+
+/// Formats a command's output.
+///
+/// # Arguments
+///
+/// - `output` - The output of [`execute`]
+/// - `json` - Format as JSON instead of text
+/// - `now` - Current Unix timestamp in seconds, for remaining times
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+pub fn render(output: &Output, json: bool, now: i64) -> Result<String> {
+    if json {
+        let value = match output {
+            Output::Timers(timers) => serde_json::to_value(
+                timers
+                    .iter()
+                    .map(|timer| TimerJson::new(timer, now))
+                    .collect::<Vec<_>>(),
+            )?,
+            Output::Timer(timer) | Output::Cancelled(timer) => {
+                serde_json::to_value(TimerJson::new(timer, now))?
+            }
+            Output::History(entries) => serde_json::to_value(
+                entries
+                    .iter()
+                    .map(|entry| HistoryJson {
+                        id: entry.id,
+                        name: history::entry_name(entry),
+                        outcome: entry.outcome().as_str().to_string(),
+                        finished_at: entry.finished_at,
+                    })
+                    .collect::<Vec<_>>(),
+            )?,
+        };
+        return Ok(serde_json::to_string_pretty(&value)?);
+    }
+
+    let timer_line = |timer: &Timer| {
+        format!(
+            "{:>4}  {}  {}",
+            timer.id,
+            timer_name(timer),
+            timer_summary(timer, now)
+        )
+    };
+    Ok(match output {
+        Output::Timers(timers) if timers.is_empty() => fl!("timers-none"),
+        Output::Timers(timers) => timers.iter().map(timer_line).collect::<Vec<_>>().join("\n"),
+        Output::Timer(timer) => timer_line(timer),
+        Output::Cancelled(timer) => format!("{}: {}", fl!("outcome-cancelled"), timer_name(timer)),
+        Output::History(entries) if entries.is_empty() => fl!("history-empty"),
+        Output::History(entries) => entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}  {}  {}",
+                    history::format_finished_at(entry.finished_at),
+                    history::entry_name(entry),
+                    history::outcome_name(entry.outcome())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    })
+}
+
+/// Runs `export`, moving a timer from the database to systemd user units.
+///
+/// See [`systemd::export`].
+async fn run_export(id: i64, json: bool) -> Result<()> {
+    let database = SQLiteDatabase::new().await?;
+    let now = SystemClock.now();
+    let (timer, units) = systemd::export(database.pool(), id, now).await?;
+    let dir = systemd::user_unit_dir()?;
+
+    let unit = format!("{}.timer", units.name);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ExportJson {
+                unit,
+                timer: TimerJson::new(&timer, now),
+            })?
+        );
+    } else {
+        println!("{}: {}", timer_name(&timer), dir.join(unit).display());
+    }
+    Ok(())
+}
+
+/// Runs `import`, adding the user's systemd timers to the database.
+async fn run_import(json: bool) -> Result<()> {
+    let database = SQLiteDatabase::new().await?;
+    let now = SystemClock.now();
+    let dir = systemd::user_unit_dir()?;
+    let units = systemd::read_timers(&dir, now)?;
+    let import = import(database.pool(), &units, now).await?;
+    // Only after the timers are stored, so none gets lost on the way
+    for name in &import.exported {
+        systemd::remove(&dir, name).await?;
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ImportJson {
+                imported: import
+                    .timers
+                    .iter()
+                    .map(|timer| TimerJson::new(timer, now))
+                    .collect(),
+                foreign: import
+                    .foreign
+                    .iter()
+                    .map(|(name, timer)| ExportJson {
+                        unit: format!("{name}.timer"),
+                        timer: TimerJson::new(timer, now),
+                    })
+                    .collect(),
+                skipped: import.skipped,
+            })?
+        );
+        return Ok(());
+    }
+    if import.timers.is_empty() && import.foreign.is_empty() && import.skipped.is_empty() {
+        println!("{}", fl!("timers-none"));
+    }
+    for timer in &import.timers {
+        println!(
+            "{:>4}  {}  {}",
+            timer.id,
+            timer_name(timer),
+            timer_summary(timer, now)
+        );
+    }
+    for (name, timer) in &import.foreign {
+        println!(
+            "   -  {}  {}  ({name}.timer, run by systemd)",
+            timer_name(timer),
+            timer_summary(timer, now)
+        );
+    }
+    for name in &import.skipped {
+        println!("Skipped {name}.timer, its schedule isn't supported");
+    }
+    Ok(())
+}
+
+/// Brings timers exported to systemd back into the database.
+///
+/// Units exported by Chronomancer come back as the timer they were, and are
+/// listed so they can be removed from systemd; otherwise the action would run
+/// twice. Other timers stay with systemd and are only listed, read-only:
+/// imported as reminders, every run would also pop up a notification.
+///
+/// # Arguments
+///
+/// - `pool` - The database connection pool
+/// - `units` - The timers from [`systemd::read_timers`]
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn import(pool: &SqlitePool, units: &[ImportedTimer], now: i64) -> Result<Import> {
+    let mut import = Import::default();
+    for unit in units {
+        let Some(timer) = unit.to_timer(now) else {
+            import.skipped.push(unit.name.clone());
+            continue;
+        };
+
+        if unit.exported_id().is_none() {
+            import.foreign.push((unit.name.clone(), timer));
+            continue;
+        }
+        import.exported.push(unit.name.clone());
+        import.timers.push(Timer::insert(pool, &timer).await?);
+    }
+    Ok(import)
+}
+
+/// Runs `stay-awake`, in the applet for `on` and `off` or in this process for a duration.
+async fn run_stay_awake(stay_awake: StayAwake, json: bool) -> Result<()> {
+    let print = |enabled: bool, seconds: Option<i32>, text: String| -> Result<()> {
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&StayAwakeJson { enabled, seconds })?
+            );
+        } else {
+            println!("{text}");
+        }
+        Ok(())
+    };
+
+    match stay_awake {
+        StayAwake::On | StayAwake::Off => {
+            let enabled = stay_awake == StayAwake::On;
+            activation::activate_action_with(
+                activation::STAY_AWAKE_ACTION,
+                vec![Value::from(enabled)],
+            )
+            .await?;
+            print(
+                enabled,
+                None,
+                if enabled {
+                    "Staying awake".to_string()
+                } else {
+                    "No longer staying awake".to_string()
+                },
+            )
+        }
+        StayAwake::For(seconds) => {
+            let inhibitor = resources::acquire_suspend_inhibit(
+                "Chronomancer",
+                "User requested stay-awake mode",
+                "block",
+            )
+            .await?;
+            print(
+                true,
+                Some(seconds),
+                format!(
+                    "Staying awake for {}, interrupt to stop early",
+                    format_duration(seconds)
+                ),
+            )?;
+            tokio::time::sleep(std::time::Duration::from_secs(
+                seconds.unsigned_abs().into(),
+            ))
+            .await;
+            resources::release_suspend_inhibit(inhibitor);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::history::Outcome, utils::systemd::Units};
+
+    fn parse_args(args: &[&str]) -> Result<Option<Invocation>> {
+        parse(&args.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let invocation = parse_args(&["add", "1h", "reboot", "--json"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            invocation.command,
+            Command::Timers(TimerCommand::Add(3600, TimerType::Reboot))
+        );
+        assert!(invocation.json);
+
+        assert_eq!(
+            parse_args(&["stay-awake", "for", "2h"])
+                .unwrap()
+                .unwrap()
+                .command,
+            Command::StayAwake(StayAwake::For(7200))
+        );
+        assert_eq!(
+            parse_args(&["--quick-entry"]).unwrap().unwrap().command,
+            Command::QuickEntry
+        );
+        assert!(parse_args(&[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_arguments() {
+        assert!(parse_args(&["add", "standup"]).is_err());
+        assert!(parse_args(&["add", "at", "8:00", "standup"]).is_err());
+        assert!(parse_args(&["cancel"]).is_err());
+        assert!(parse_args(&["cancel", "twelve"]).is_err());
+        assert!(parse_args(&["list", "everything"]).is_err());
+        assert!(parse_args(&["stay-awake", "maybe"]).is_err());
+        assert!(parse_args(&["frobnicate"]).is_err());
+        assert!(parse_args(&["--json"]).is_err());
+        assert!(parse_args(&["export"]).is_err());
+        assert!(parse_args(&["import", "everything"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_only_adds_exported_timers() -> Result<()> {
+        let database = SQLiteDatabase::new_in_memory().await?;
+        let pool = database.pool();
+        let now = 1_700_000_000;
+
+        let mut reboot = Timer::new(3600, false, &TimerType::Reboot, now);
+        reboot.id = 12;
+        let exported = Units::for_timer(&reboot, now)?;
+        let units = vec![
+            ImportedTimer::parse(&exported.name, &exported.timer, now),
+            ImportedTimer::parse(
+                "backup",
+                "[Unit]\nDescription=Backup\n[Timer]\nOnUnitActiveSec=1d\n",
+                now,
+            ),
+            ImportedTimer::parse("monthly", "[Timer]\nOnCalendar=monthly\n", now),
+        ];
+
+        let imported = import(pool, &units, now).await?;
+        assert_eq!(imported.timers.len(), 1);
+        assert_eq!(imported.timers[0].description, "System Reboot");
+        assert_eq!(imported.exported, vec!["chronomancer-12".to_string()]);
+        assert_eq!(imported.skipped, vec!["monthly".to_string()]);
+
+        // systemd keeps running the backup, it is only listed
+        assert_eq!(imported.foreign.len(), 1);
+        assert_eq!(imported.foreign[0].0, "backup");
+        assert_eq!(imported.foreign[0].1.period(), Some(86_400));
+        assert_eq!(Timer::get_all_running(pool).await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_timer_commands_share_the_database() -> Result<()> {
+        let database = SQLiteDatabase::new_in_memory().await?;
+        let pool = database.pool();
+        let now = 1_700_000_000;
+
+        let Output::Timer(timer) =
+            execute(pool, &TimerCommand::Add(3600, TimerType::Reboot), now).await?
+        else {
+            panic!("add should return the new timer");
+        };
+
+        let Output::Timer(paused) = execute(pool, &TimerCommand::Pause(timer.id), now).await?
+        else {
+            panic!("pause should return the timer");
+        };
+        assert!(paused.is_paused());
+
+        let Output::Timer(resumed) =
+            execute(pool, &TimerCommand::Resume(timer.id), now + 60).await?
+        else {
+            panic!("resume should return the timer");
+        };
+        assert_eq!(resumed.ends_at, timer.ends_at + 60);
+
+        execute(pool, &TimerCommand::Cancel(timer.id), now).await?;
+        assert!(Timer::get_all_active(pool, now).await?.is_empty());
+        assert!(
+            execute(pool, &TimerCommand::Cancel(timer.id), now)
+                .await
+                .is_err()
+        );
+
+        let Output::History(entries) = execute(pool, &TimerCommand::History, now).await? else {
+            panic!("history should return entries");
+        };
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].outcome(), Outcome::Cancelled);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_json() {
+        let now = 1_700_000_000;
+        let mut timer = Timer::new(60, false, &TimerType::Shutdown, now);
+        timer.id = 3;
+
+        let json = render(&Output::Timers(vec![timer]), true, now).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["id"], 3);
+        assert_eq!(value[0]["kind"], "shutdown");
+        assert_eq!(value[0]["remaining_seconds"], 60);
+        assert_eq!(value[0]["paused"], false);
+    }
+}
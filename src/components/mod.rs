@@ -18,6 +18,12 @@
 //! - [`ToggleIconRadio`] - Icon-based radio button option
 //! - [`RadioComponent`](radio_components::RadioComponent) - Trait for implementing custom radio options
 //!
+//! ## Display Components
+//!
+//! - [`timer_list`](timer_list::timer_list) - List of active timers with their conditions
+//! - [`quick_timers`](quick_timers::quick_timers) - One-click buttons for the configured quick timer presets
+//! - [`panel_button`](panel_button::panel_button) - The applet's panel button, with an optional countdown
+//!
 //! ## Power Management
 //!
 //! - [`PowerOperation`](power_form::PowerOperation) - System power operation types (suspend, shutdown, etc.)
@@ -32,8 +38,11 @@
 //! 4. **Documented** - Each component and method includes arguments, return values, and possible errors. Jury is still out on whether or not these will end up being doctests
 
 pub mod icon_button;
+pub mod panel_button;
 pub mod power_form;
+pub mod quick_timers;
 pub mod radio_components;
+pub mod timer_list;
 
 pub use icon_button::ToggleIconRadio;
 pub use power_form::PowerForm;
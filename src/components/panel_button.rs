@@ -0,0 +1,143 @@
+//! Panel button component.
+//!
+//! Builds the applet's button in the COSMIC panel. Besides the applet icon
+//! (which may be the progress ring, see [`crate::utils::progress_ring`]) it can show the time left on the timer that ends next, with an icon
+//! for what that timer does, either after the applet icon or in its place.
+//! Horizontal panels get a clock (`12:34`) in a row, vertical panels a short
+//! single unit (`12m`) stacked under the icon so the panel keeps its width.
+//! Icon and text sizes follow the panel size from the applet context.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::components::panel_button::{Countdown, panel_button};
+//! use chronomancer::config::PanelCountdown;
+//! use cosmic::{Element, applet, widget::icon};
+//!
+//! #[derive(Clone, Debug)]
+//! enum Message {
+//!     TogglePopup,
+//! }
+//!
+//! fn view(applet: &applet::Context) -> Element<'_, Message> {
+//!     let countdown = Countdown {
+//!         icon_name: "system-shutdown-symbolic",
+//!         remaining: 754,
+//!     };
+//!     let app_icon = icon::from_name("io.vulpapps.Chronomancer").handle();
+//!     panel_button(applet, app_icon, Some(countdown), PanelCountdown::BesideIcon)
+//!         .on_press_down(Message::TogglePopup)
+//!         .into()
+//! }
+//! ```
+
+use cosmic::{
+    Element, applet,
+    iced::Alignment,
+    iced_widget::{column, row},
+    widget::{Button, icon},
+};
+
+use crate::{
+    config::PanelCountdown,
+    utils::time::{format_compact, format_countdown},
+};
+
+/// The timer shown on the panel button.
+///
+/// # Fields
+///
+/// - `icon_name` - Icon for what the timer does, e.g. `system-shutdown-symbolic`
+/// - `remaining` - Seconds until the timer ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Countdown {
+    pub icon_name: &'static str,
+    pub remaining: i64,
+}
+
+/// Builds the panel button, with the countdown if one is given and turned on.
+///
+/// # Arguments
+///
+/// - `applet` - The applet context, for the panel's orientation and size
+/// - `app_icon` - The applet icon
+/// - `countdown` - The timer that ends next, `None` if there is none
+/// - `mode` - Whether and where the countdown is shown
+///
+/// # Returns
+///
+/// A [`Button`] without a press handler or class, so the caller can add them.
+pub fn panel_button<'a, Message: Clone + 'static>(
+    applet: &applet::Context,
+    app_icon: icon::Handle,
+    countdown: Option<Countdown>,
+    mode: PanelCountdown,
+) -> Button<'a, Message> {
+    let Some(countdown) = countdown.filter(|_| mode != PanelCountdown::Off) else {
+        return applet.icon_button_from_handle(app_icon);
+    };
+
+    let horizontal = applet.is_horizontal();
+    let time = if horizontal {
+        format_countdown(countdown.remaining)
+    } else {
+        format_compact(countdown.remaining)
+    };
+
+    let mut items: Vec<Element<'a, Message>> = vec![];
+    if mode == PanelCountdown::BesideIcon {
+        items.push(icon(app_icon).size(applet.suggested_size(false).0).into());
+    }
+    items.push(
+        icon::from_name(countdown.icon_name)
+            .size(applet.suggested_size(true).0)
+            .into(),
+    );
+    items.push(applet.text(time).into());
+
+    let content: Element<'a, Message> = if horizontal {
+        row(items)
+            .align_y(Alignment::Center)
+            .spacing(applet.suggested_padding(true))
+            .into()
+    } else {
+        column(items)
+            .align_x(Alignment::Center)
+            .spacing(applet.suggested_padding(true))
+            .into()
+    };
+
+    applet.button_from_element(content, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    enum TestMessage {}
+
+    #[test]
+    fn test_view_compiles() {
+        let core = cosmic::Core::default();
+        let countdown = Countdown {
+            icon_name: "system-shutdown-symbolic",
+            remaining: 754,
+        };
+
+        for mode in PanelCountdown::ALL {
+            let _button: Button<'_, TestMessage> = panel_button(
+                &core.applet,
+                icon::from_name("io.vulpapps.Chronomancer").handle(),
+                Some(countdown),
+                mode,
+            );
+        }
+        let _button: Button<'_, TestMessage> = panel_button(
+            &core.applet,
+            icon::from_name("io.vulpapps.Chronomancer").handle(),
+            None,
+            PanelCountdown::BesideIcon,
+        );
+    }
+}
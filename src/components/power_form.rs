@@ -31,9 +31,11 @@ use crate::{
 ///
 /// - `StayAwake` - Prevent system from sleeping (keep awake mode)
 /// - `Suspend` - Suspend system to RAM (sleep mode)
+/// - `Hibernate` - Suspend system to disk
 /// - `Shutdown` - Power off the system
 /// - `Reboot` - Restart the system
 /// - `Logout` - Log out current user session
+/// - `Notify` - Only show a desktop notification
 ///
 /// # Examples
 ///
@@ -52,9 +54,11 @@ use crate::{
 pub enum PowerOperation {
     StayAwake,
     Suspend,
+    Hibernate,
     Shutdown,
     Reboot,
     Logout,
+    Notify,
 }
 
 impl PowerOperation {
@@ -81,6 +85,8 @@ impl PowerOperation {
     /// assert_eq!(PowerOperation::from_index(2), PowerOperation::Logout);
     /// assert_eq!(PowerOperation::from_index(3), PowerOperation::Reboot);
     /// assert_eq!(PowerOperation::from_index(4), PowerOperation::Shutdown);
+    /// assert_eq!(PowerOperation::from_index(5), PowerOperation::Hibernate);
+    /// assert_eq!(PowerOperation::from_index(6), PowerOperation::Notify);
     ///
     /// // Invalid index defaults to Suspend
     /// assert_eq!(PowerOperation::from_index(999), PowerOperation::Suspend);
@@ -92,6 +98,8 @@ impl PowerOperation {
             2 => Self::Logout,
             3 => Self::Reboot,
             4 => Self::Shutdown,
+            5 => Self::Hibernate,
+            6 => Self::Notify,
             _ => Self::Suspend, // Default fallback (includes index 1)
         }
     }
@@ -102,7 +110,7 @@ impl PowerOperation {
     ///
     /// # Returns
     ///
-    /// The index corresponding to this operation (0-4).
+    /// The index corresponding to this operation (0-6).
     ///
     /// # Examples
     ///
@@ -114,6 +122,8 @@ impl PowerOperation {
     /// assert_eq!(PowerOperation::Logout.index(), 2);
     /// assert_eq!(PowerOperation::Reboot.index(), 3);
     /// assert_eq!(PowerOperation::Shutdown.index(), 4);
+    /// assert_eq!(PowerOperation::Hibernate.index(), 5);
+    /// assert_eq!(PowerOperation::Notify.index(), 6);
     /// ```
     #[must_use]
     pub const fn index(self) -> usize {
@@ -123,6 +133,8 @@ impl PowerOperation {
             Self::Logout => 2,
             Self::Reboot => 3,
             Self::Shutdown => 4,
+            Self::Hibernate => 5,
+            Self::Notify => 6,
         }
     }
 
@@ -142,6 +154,8 @@ impl PowerOperation {
     /// - Logout: `"system-log-out-symbolic"`
     /// - Reboot: `"system-reboot-symbolic"`
     /// - Shutdown: `"system-shutdown-symbolic"`
+    /// - Hibernate: `"io.vulpapps.Chronomancer-hibernate"` (custom)
+    /// - Notify: `"alarm-symbolic"`
     ///
     /// # Examples
     ///
@@ -165,6 +179,8 @@ impl PowerOperation {
             Self::Logout => "system-log-out-symbolic",
             Self::Reboot => "system-reboot-symbolic",
             Self::Shutdown => "system-shutdown-symbolic",
+            Self::Hibernate => "io.vulpapps.Chronomancer-hibernate",
+            Self::Notify => "alarm-symbolic",
         }
     }
 
@@ -195,9 +211,11 @@ impl PowerOperation {
         match self {
             Self::StayAwake => String::new(), // No placeholder needed for stay awake
             Self::Suspend => fl!("set-time-label", operation = fl!("operation-suspend")),
+            Self::Hibernate => fl!("set-time-label", operation = fl!("operation-hibernate")),
             Self::Shutdown => fl!("set-time-label", operation = fl!("operation-shutdown")),
             Self::Reboot => fl!("set-time-label", operation = fl!("operation-reboot")),
             Self::Logout => fl!("set-time-label", operation = fl!("operation-logout")),
+            Self::Notify => fl!("set-time-label", operation = fl!("operation-notify")),
         }
     }
 }
@@ -212,8 +230,11 @@ impl PowerOperation {
 ///
 /// - `input_value` - Current numeric value as a string
 /// - `time_unit` - Selected time unit (seconds, minutes, hours, days)
+/// - `default_unit` - Unit selected when the form is cleared
 /// - `time_unit_options` - Combo box state for unit selection
 /// - `placeholder_text` - Placeholder text shown when input is empty
+/// - `show_time_unit` - Whether the unit combo box is shown (hide it for unitless input)
+/// - `show_value_input` - Whether the numeric input is shown (hide it when only the button is needed)
 ///
 /// # Validation
 ///
@@ -250,11 +271,24 @@ pub struct PowerForm {
     /// The currently selected time unit.
     pub time_unit: TimeUnit,
 
+    /// The unit the form starts with and returns to when cleared.
+    pub default_unit: TimeUnit,
+
     /// State for the time unit combo box.
     pub time_unit_options: combo_box::State<TimeUnit>,
 
     /// Placeholder text displayed in the input field.
     pub placeholder_text: String,
+
+    /// Whether the time unit combo box is displayed.
+    ///
+    /// Pages hide it when the input is not a duration (e.g. a battery percentage).
+    pub show_time_unit: bool,
+
+    /// Whether the numeric text input is displayed.
+    ///
+    /// Pages hide it when the submission needs no number (e.g. "when a file appears").
+    pub show_value_input: bool,
 }
 
 impl PowerForm {
@@ -265,6 +299,7 @@ impl PowerForm {
     /// - Default time unit (Seconds)
     /// - All time unit options available
     /// - Custom placeholder text
+    /// - Visible time unit selection and numeric input
     ///
     /// # Arguments
     ///
@@ -287,21 +322,19 @@ impl PowerForm {
         Self {
             input_value: String::new(),
             time_unit: TimeUnit::Seconds, // Default to seconds
-            time_unit_options: combo_box::State::new(vec![
-                TimeUnit::Seconds,
-                TimeUnit::Minutes,
-                TimeUnit::Hours,
-                TimeUnit::Days,
-            ]),
+            default_unit: TimeUnit::Seconds,
+            time_unit_options: combo_box::State::new(TimeUnit::ALL.to_vec()),
             placeholder_text: placeholder_text.into(),
+            show_time_unit: true,
+            show_value_input: true,
         }
     }
 
     /// Renders the power form as an [`Element`].
     ///
     /// Creates a vertical layout containing:
-    /// 1. Text input field for duration
-    /// 2. Combo box for time unit selection
+    /// 1. Text input field for duration (when `show_value_input` is set)
+    /// 2. Combo box for time unit selection (when `show_time_unit` is set)
     /// 3. Submit button
     ///
     /// # Arguments
@@ -346,22 +379,34 @@ impl PowerForm {
         Message: Clone + 'static,
     {
         let on_submit_clone = on_submit.clone();
-        column![
-            TextInput::new(&self.placeholder_text, &self.input_value)
-                .on_input(on_text_input)
-                .on_submit(move |_| on_submit_clone.clone())
+        let mut form = column![];
+
+        if self.show_value_input {
+            form = form.push(
+                TextInput::new(&self.placeholder_text, &self.input_value)
+                    .on_input(on_text_input)
+                    .on_submit(move |_| on_submit_clone.clone())
+                    .width(Fill),
+            );
+        }
+
+        if self.show_value_input && self.show_time_unit {
+            form = form.push(
+                ComboBox::new(
+                    &self.time_unit_options,
+                    &fl!("unit-label"),
+                    Some(&self.time_unit),
+                    on_time_unit,
+                )
                 .width(Fill),
-            ComboBox::new(
-                &self.time_unit_options,
-                &fl!("unit-label"),
-                Some(&self.time_unit),
-                on_time_unit,
-            )
-            .width(Fill),
+            );
+        }
+
+        form.push(
             button::text(fl!("set-button-label"))
                 .on_press(on_submit)
-                .class(Button::Suggested)
-        ]
+                .class(Button::Suggested),
+        )
         .align_x(Alignment::Center)
         .spacing(Gaps::s())
         .padding(Padding::horizontal(24))
@@ -456,7 +501,7 @@ impl PowerForm {
     ///
     /// Resets:
     /// - `input_value` to empty string
-    /// - `time_unit` to `TimeUnit::Seconds`
+    /// - `time_unit` to `default_unit` (`TimeUnit::Seconds` unless changed)
     ///
     /// The placeholder text is preserved.
     ///
@@ -478,7 +523,32 @@ impl PowerForm {
     /// ```
     pub fn clear(&mut self) {
         self.input_value.clear();
-        self.time_unit = TimeUnit::Seconds;
+        self.time_unit = self.default_unit;
+    }
+
+    /// Changes the unit the form starts with and selects it.
+    ///
+    /// # Arguments
+    ///
+    /// - `unit` - The new default unit, usually from the app's settings
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::components::power_form::PowerForm;
+    /// use chronomancer::utils::TimeUnit;
+    ///
+    /// let mut form = PowerForm::new("Enter time");
+    /// form.set_default_unit(TimeUnit::Minutes);
+    /// assert_eq!(form.time_unit, TimeUnit::Minutes);
+    ///
+    /// form.time_unit = TimeUnit::Hours;
+    /// form.clear();
+    /// assert_eq!(form.time_unit, TimeUnit::Minutes);
+    /// ```
+    pub fn set_default_unit(&mut self, unit: TimeUnit) {
+        self.default_unit = unit;
+        self.time_unit = unit;
     }
 }
 
@@ -500,6 +570,8 @@ mod tests {
         assert_eq!(form.input_value, "");
         assert_eq!(form.time_unit, TimeUnit::Seconds);
         assert_eq!(form.placeholder_text, "Enter time");
+        assert!(form.show_time_unit);
+        assert!(form.show_value_input);
     }
 
     #[test]
@@ -567,6 +639,8 @@ mod tests {
         assert_eq!(PowerOperation::from_index(2), PowerOperation::Logout);
         assert_eq!(PowerOperation::from_index(3), PowerOperation::Reboot);
         assert_eq!(PowerOperation::from_index(4), PowerOperation::Shutdown);
+        assert_eq!(PowerOperation::from_index(5), PowerOperation::Hibernate);
+        assert_eq!(PowerOperation::from_index(6), PowerOperation::Notify);
         // Test fallback for invalid index
         assert_eq!(PowerOperation::from_index(999), PowerOperation::Suspend);
     }
@@ -580,9 +654,11 @@ mod tests {
 
         // Others should have localized text (just verify they're not empty)
         assert!(!PowerOperation::Suspend.placeholder_text().is_empty());
+        assert!(!PowerOperation::Hibernate.placeholder_text().is_empty());
         assert!(!PowerOperation::Shutdown.placeholder_text().is_empty());
         assert!(!PowerOperation::Reboot.placeholder_text().is_empty());
         assert!(!PowerOperation::Logout.placeholder_text().is_empty());
+        assert!(!PowerOperation::Notify.placeholder_text().is_empty());
 
         // Verify they contain the operation name
         assert!(
@@ -614,6 +690,8 @@ mod tests {
         assert_eq!(PowerOperation::Logout.index(), 2);
         assert_eq!(PowerOperation::Reboot.index(), 3);
         assert_eq!(PowerOperation::Shutdown.index(), 4);
+        assert_eq!(PowerOperation::Hibernate.index(), 5);
+        assert_eq!(PowerOperation::Notify.index(), 6);
     }
 
     #[test]
@@ -635,5 +713,9 @@ mod tests {
             PowerOperation::Shutdown.icon_name(),
             "system-shutdown-symbolic"
         );
+        assert_eq!(
+            PowerOperation::Hibernate.icon_name(),
+            "io.vulpapps.Chronomancer-hibernate"
+        );
     }
 }
@@ -0,0 +1,92 @@
+//! Quick timer preset buttons.
+//!
+//! Renders the user's quick timer presets as a wrapping row of buttons, so the
+//! most common timers ("15 min tea", "shutdown in 1h") start with one click.
+//! Presets come from [`Config`](crate::config::Config) and are edited on the
+//! settings page.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::components::quick_timers;
+//! use chronomancer::config::QuickTimerPreset;
+//! use cosmic::Element;
+//!
+//! #[derive(Clone, Debug)]
+//! enum Message {
+//!     QuickTimer(usize),
+//! }
+//!
+//! fn view(presets: &[QuickTimerPreset]) -> Element<'_, Message> {
+//!     quick_timers::quick_timers(presets, Message::QuickTimer)
+//! }
+//! ```
+
+use cosmic::{
+    Element,
+    iced::{Alignment, Length},
+    iced_widget::column,
+    theme,
+    widget::{button, flex_row, text},
+};
+
+use crate::{
+    config::QuickTimerPreset,
+    fl,
+    utils::ui::{Gaps, Padding},
+};
+
+/// Renders a button per quick timer preset.
+///
+/// # Arguments
+///
+/// - `presets` - Presets in display order
+/// - `on_press` - Builds the message sent when the preset at the given index is pressed
+///
+/// # Returns
+///
+/// An [`Element`] with a header and the preset buttons.
+pub fn quick_timers<'a, Message: Clone + 'static>(
+    presets: &'a [QuickTimerPreset],
+    on_press: impl Fn(usize) -> Message,
+) -> Element<'a, Message> {
+    let buttons: Vec<Element<'a, Message>> = presets
+        .iter()
+        .enumerate()
+        .map(|(index, preset)| {
+            button::text(preset.label.as_str())
+                .class(theme::Button::Standard)
+                .on_press(on_press(index))
+                .into()
+        })
+        .collect();
+
+    column![
+        text::heading(fl!("quick-timers")),
+        flex_row(buttons)
+            .row_spacing(Gaps::xs())
+            .column_spacing(Gaps::xs()),
+    ]
+    .align_x(Alignment::Start)
+    .spacing(Gaps::xs())
+    .padding(Padding::horizontal(24))
+    .width(Length::Fill)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[derive(Debug, Clone)]
+    enum TestMessage {
+        QuickTimer(usize),
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let presets = Config::default().quick_timers;
+        let _element: Element<'_, TestMessage> = quick_timers(&presets, TestMessage::QuickTimer);
+    }
+}
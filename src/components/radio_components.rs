@@ -202,6 +202,65 @@ impl<T: RadioComponent> RadioComponents<T> {
         }
     }
 
+    /// Gets the option next to the selected one, wrapping around at either end.
+    ///
+    /// Used for moving through the group with the arrow keys.
+    ///
+    /// # Arguments
+    ///
+    /// - `forward` - Move to the next option instead of the previous one
+    ///
+    /// # Returns
+    ///
+    /// The index of the adjacent option, the first (or last) one if nothing is
+    /// selected, or `None` if the group is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chronomancer::components::radio_components::RadioComponents;
+    /// use chronomancer::components::icon_button::ToggleIconRadio;
+    ///
+    /// let mut radio_group = RadioComponents::new(vec![
+    ///     ToggleIconRadio::new(0, "icon-one"),
+    ///     ToggleIconRadio::new(1, "icon-two"),
+    ///     ToggleIconRadio::new(2, "icon-three"),
+    /// ]);
+    /// assert_eq!(radio_group.adjacent(true), Some(0));
+    ///
+    /// radio_group.selected = Some(2);
+    /// assert_eq!(radio_group.adjacent(true), Some(0));
+    /// assert_eq!(radio_group.adjacent(false), Some(1));
+    /// ```
+    #[must_use]
+    pub fn adjacent(&self, forward: bool) -> Option<usize> {
+        self.adjacent_to(self.selected, forward)
+    }
+
+    /// Gets the option next to the given one, wrapping around at either end.
+    ///
+    /// Like [`Self::adjacent`], but starting from any option, so callers can step
+    /// past options they don't want to land on.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The option to start from, `None` to start before the first (or after the last) one
+    /// - `forward` - Move to the next option instead of the previous one
+    #[must_use]
+    pub fn adjacent_to(&self, index: Option<usize>, forward: bool) -> Option<usize> {
+        let count = self.options.len();
+        if count == 0 {
+            return None;
+        }
+
+        Some(match (index, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+        })
+    }
+
     /// Displays options in a row layout with standard spacing (16px).
     ///
     /// # Arguments
@@ -315,6 +374,28 @@ mod tests {
         assert_eq!(radio_components.selected, None);
     }
 
+    #[test]
+    fn test_adjacent_wraps_around() {
+        let options = vec![
+            MockRadioOption::new(0),
+            MockRadioOption::new(1),
+            MockRadioOption::new(2),
+        ];
+        let mut radio_components = RadioComponents::new(options);
+
+        // Without a selection the arrows start at either end
+        assert_eq!(radio_components.adjacent(true), Some(0));
+        assert_eq!(radio_components.adjacent(false), Some(2));
+
+        radio_components.selected = Some(0);
+        assert_eq!(radio_components.adjacent(true), Some(1));
+        assert_eq!(radio_components.adjacent(false), Some(2));
+        assert_eq!(radio_components.adjacent_to(Some(2), true), Some(0));
+
+        let empty: RadioComponents<MockRadioOption> = RadioComponents::new(vec![]);
+        assert_eq!(empty.adjacent(true), None);
+    }
+
     #[test]
     fn test_view_compiles() {
         let options = vec![MockRadioOption::new(0), MockRadioOption::new(1)];
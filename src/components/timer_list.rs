@@ -0,0 +1,360 @@
+//! Active timer list component.
+//!
+//! Renders the timers that are still waiting to fire, one row per timer,
+//! showing what the timer will do and what it is waiting for (a countdown,
+//! a battery level, a quiet system, a watched file or the end of a track).
+//! Chained timers list the links that follow them, and every row can be
+//! cancelled, which also cancels the rest of its chain. Timers systemd can run
+//! can also be exported (see [`crate::utils::systemd`]). The row focused with
+//! the keyboard is marked with an arrow.
+//!
+//! The user's systemd timers are listed separately with
+//! [`systemd_timer_list`], read-only since systemd runs them.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::components::timer_list;
+//! use chronomancer::models::Timer;
+//! use cosmic::Element;
+//!
+//! #[derive(Clone, Debug)]
+//! enum Message {
+//!     Cancel(i64),
+//!     Export(i64),
+//! }
+//!
+//! fn view<'a>(timers: &'a [Timer], chained: &'a [Timer]) -> Element<'a, Message> {
+//!     let now = chrono::Utc::now().timestamp();
+//!     timer_list::timer_list(timers, chained, now, None, Message::Cancel, Message::Export)
+//! }
+//! ```
+
+use std::str::FromStr;
+
+use cosmic::{
+    Element,
+    iced::{Alignment, Length},
+    iced_widget::{column, row},
+    widget::{Space, button, icon, text},
+};
+
+use crate::{
+    components::power_form::PowerOperation,
+    fl,
+    models::{
+        Timer,
+        condition::{FileEvent, TimerCondition},
+        timer::TimerType,
+    },
+    utils::{Padding, format_duration, systemd::Schedule, ui::Gaps},
+};
+
+/// Size of the per-row operation icon in pixels.
+const ROW_ICON_SIZE: u16 = 16;
+
+/// Renders the list of active timers.
+///
+/// # Arguments
+///
+/// - `timers` - Timers to list, in display order
+/// - `chained` - Chain links still waiting on a parent, shown under their chain's running timer
+/// - `now` - Current Unix timestamp used for remaining time
+/// - `focused` - Id of the timer focused with the keyboard, if any
+/// - `on_cancel` - Builds the message sent when a timer's cancel button is pressed
+/// - `on_export` - Builds the message sent when a timer's export button is
+///   pressed, shown for timers with a schedule systemd can run and no chain
+///
+/// # Returns
+///
+/// An [`Element`] with a header and one row per timer.
+pub fn timer_list<'a, Message: Clone + 'static>(
+    timers: &'a [Timer],
+    chained: &'a [Timer],
+    now: i64,
+    focused: Option<i64>,
+    on_cancel: impl Fn(i64) -> Message,
+    on_export: impl Fn(i64) -> Message,
+) -> Element<'a, Message> {
+    let rows = timers.iter().map(|timer| {
+        let links = chain_after(timer, chained);
+        let name: Element<'a, Message> = if links.is_empty() {
+            text(timer_name(timer)).into()
+        } else {
+            let names: Vec<String> = links.iter().map(|link| timer_name(link)).collect();
+            column![
+                text(timer_name(timer)),
+                text::caption(fl!("timer-chain-next", names = names.join(", "))),
+            ]
+            .into()
+        };
+
+        let mut row = row![
+            focus_marker(focused == Some(timer.id)),
+            icon::from_name(timer_icon(timer))
+                .size(ROW_ICON_SIZE)
+                .icon(),
+            name,
+            Space::with_width(Length::Fill),
+            text(timer_summary(timer, now)),
+        ];
+        // systemd runs single timers with a deadline, chains stay here
+        if links.is_empty() && Schedule::of(timer).is_some() {
+            row = row.push(button::text(fl!("timer-export")).on_press(on_export(timer.id)));
+        }
+        row.push(
+            button::icon(icon::from_name("window-close-symbolic")).on_press(on_cancel(timer.id)),
+        )
+        .align_y(Alignment::Center)
+        .spacing(Gaps::s())
+        .into()
+    });
+
+    column![text::heading(fl!("active-timers"))]
+        .extend(rows)
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill)
+        .into()
+}
+
+/// Renders the user's systemd timers, read-only.
+///
+/// # Arguments
+///
+/// - `timers` - Unit names without the `.timer` suffix, with the timer each
+///   would be in Chronomancer (see [`crate::utils::systemd::ImportedTimer::to_timer`])
+/// - `now` - Current Unix timestamp used for remaining time
+///
+/// # Returns
+///
+/// An [`Element`] with a header and one row per timer, naming its unit.
+pub fn systemd_timer_list<'a, Message: 'static>(
+    timers: &'a [(String, Timer)],
+    now: i64,
+) -> Element<'a, Message> {
+    let rows = timers.iter().map(|(unit, timer)| {
+        row![
+            focus_marker(false),
+            icon::from_name(timer_icon(timer))
+                .size(ROW_ICON_SIZE)
+                .icon(),
+            column![
+                text(timer_name(timer)),
+                text::caption(fl!("systemd-timer-unit", unit = unit.as_str())),
+            ],
+            Space::with_width(Length::Fill),
+            text(timer_summary(timer, now)),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(Gaps::s())
+        .into()
+    });
+
+    column![text::heading(fl!("systemd-timers"))]
+        .extend(rows)
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill)
+        .into()
+}
+
+/// Renders the arrow in front of the row focused with the keyboard.
+///
+/// Rows without focus get an empty space of the same size, so rows stay aligned.
+///
+/// # Arguments
+///
+/// - `focused` - Whether the row is focused
+pub fn focus_marker<'a, Message: 'static>(focused: bool) -> Element<'a, Message> {
+    if focused {
+        icon::from_name("go-next-symbolic")
+            .size(ROW_ICON_SIZE)
+            .icon()
+            .into()
+    } else {
+        Space::new(ROW_ICON_SIZE, ROW_ICON_SIZE).into()
+    }
+}
+
+/// Gets the display name of a timer's action.
+///
+/// Power operations use their localized operation name, user-defined timers
+/// show their description.
+#[must_use]
+pub fn timer_name(timer: &Timer) -> String {
+    match TimerType::from_str(&timer.description) {
+        Ok(timer_type) => operation_name(&timer_type),
+        Err(_) => timer.description.clone(),
+    }
+}
+
+/// Gets the display name of a timer type.
+///
+/// Power operations use their localized operation name, user-defined types
+/// show their description.
+#[must_use]
+pub fn operation_name(timer_type: &TimerType) -> String {
+    match timer_type {
+        TimerType::Suspend => fl!("operation-suspend"),
+        TimerType::Hibernate => fl!("operation-hibernate"),
+        TimerType::Logout => fl!("operation-logout"),
+        TimerType::Shutdown => fl!("operation-shutdown"),
+        TimerType::Reboot => fl!("operation-reboot"),
+        TimerType::UserDefined(description) => description.clone(),
+    }
+}
+
+/// Gets the links that follow a timer in its chain, in the order they will run.
+///
+/// # Arguments
+///
+/// - `timer` - The running timer
+/// - `chained` - All chain links still waiting on a parent
+#[must_use]
+pub fn chain_after<'a>(timer: &Timer, chained: &'a [Timer]) -> Vec<&'a Timer> {
+    let mut links = vec![];
+    let mut parent_id = timer.id;
+    while let Some(link) = chained.iter().find(|link| link.parent_id == parent_id) {
+        links.push(link);
+        parent_id = link.id;
+    }
+    links
+}
+
+/// Gets the icon name for a timer's action, matching the power controls radio icons.
+#[must_use]
+pub fn timer_icon(timer: &Timer) -> &'static str {
+    let operation = match TimerType::from_str(&timer.description) {
+        Ok(TimerType::Suspend) => PowerOperation::Suspend,
+        Ok(TimerType::Hibernate) => PowerOperation::Hibernate,
+        Ok(TimerType::Logout) => PowerOperation::Logout,
+        Ok(TimerType::Shutdown) => PowerOperation::Shutdown,
+        Ok(TimerType::Reboot) => PowerOperation::Reboot,
+        Ok(TimerType::UserDefined(_)) | Err(_) => PowerOperation::Notify,
+    };
+    operation.icon_name()
+}
+
+/// Describes what a timer is waiting for.
+///
+/// Countdowns show the remaining time (and how often recurring ones repeat),
+/// trigger timers show their condition. Paused timers show the time they had
+/// left when paused.
+///
+/// # Arguments
+///
+/// - `timer` - The timer to describe
+/// - `now` - Current Unix timestamp
+#[must_use]
+pub fn timer_summary(timer: &Timer, now: i64) -> String {
+    let remaining = format_duration(i32::try_from(timer.remaining(now)).unwrap_or(i32::MAX));
+    let seconds = |value: u32| format_duration(i32::try_from(value).unwrap_or(i32::MAX));
+
+    if timer.is_paused() {
+        return fl!("timer-paused", time = remaining);
+    }
+    match timer.condition() {
+        TimerCondition::None => match timer.period() {
+            Some(period) => fl!(
+                "timer-repeating",
+                time = remaining,
+                period = format_duration(i32::try_from(period).unwrap_or(i32::MAX))
+            ),
+            None => fl!("timer-remaining", time = remaining),
+        },
+        TimerCondition::OnAcPower => fl!("timer-remaining-on-ac", time = remaining),
+        TimerCondition::BatteryBelow(percent) => fl!("timer-battery-below", percent = percent),
+        TimerCondition::SystemQuiet(thresholds) => {
+            fl!("timer-system-quiet", time = seconds(thresholds.window_secs))
+        }
+        TimerCondition::File(trigger) => {
+            // The file name is usually enough to recognize it in a narrow popup
+            let name = trigger.path.file_name().map_or_else(
+                || trigger.path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            match trigger.event {
+                FileEvent::Appears => fl!("timer-file-appears", path = name),
+                FileEvent::Disappears => fl!("timer-file-disappears", path = name),
+                FileEvent::StopsChanging(window) => {
+                    fl!("timer-file-stable", path = name, time = seconds(window))
+                }
+            }
+        }
+        TimerCondition::MediaEnds(_) => fl!("timer-media-ends"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::condition::FileTrigger;
+
+    #[derive(Debug, Clone)]
+    enum TestMessage {
+        Cancel(i64),
+        Export(i64),
+    }
+
+    #[test]
+    fn test_timer_name() {
+        let shutdown = Timer::new(60, false, &TimerType::Shutdown, 0);
+        assert_eq!(timer_name(&shutdown), fl!("operation-shutdown"));
+
+        let reminder = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 0);
+        assert_eq!(timer_name(&reminder), "Tea");
+    }
+
+    #[test]
+    fn test_timer_summary_shows_condition() {
+        let now = 1_700_000_000;
+
+        let mut countdown = Timer::new(90, false, &TimerType::Suspend, now);
+        countdown.ends_at = now + 90;
+        assert!(timer_summary(&countdown, now).contains(&format_duration(90)));
+
+        let watcher = Timer::new(30, false, &TimerType::Suspend, now).with_condition(
+            &TimerCondition::File(FileTrigger {
+                path: "/renders/output.mkv".into(),
+                event: FileEvent::StopsChanging(30),
+            }),
+        );
+        let summary = timer_summary(&watcher, now);
+        assert!(summary.contains("output.mkv"));
+        assert!(!summary.contains("/renders"));
+        assert!(summary.contains(&format_duration(30)));
+    }
+
+    #[test]
+    fn test_chain_after_follows_links_in_order() {
+        let mut head = Timer::new(60, false, &TimerType::UserDefined("Save".to_string()), 0);
+        head.id = 1;
+        let mut shutdown = Timer::chained(600, &TimerType::Shutdown, 0);
+        shutdown.id = 3;
+        shutdown.parent_id = 2;
+        let mut logout = Timer::chained(300, &TimerType::Logout, 0);
+        logout.id = 2;
+        logout.parent_id = 1;
+        let chained = vec![shutdown, logout];
+
+        let ids: Vec<i64> = chain_after(&head, &chained).iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert!(chain_after(&chained[0], &chained).is_empty());
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let timers = vec![Timer::new(60, false, &TimerType::Reboot, 0)];
+        let _element: Element<'_, TestMessage> = timer_list(
+            &timers,
+            &[],
+            0,
+            Some(0),
+            TestMessage::Cancel,
+            TestMessage::Export,
+        );
+        let systemd = vec![("backup".to_string(), timers[0].clone())];
+        let _element: Element<'_, TestMessage> = systemd_timer_list(&systemd, 0);
+    }
+}
@@ -1,9 +1,374 @@
 // SPDX-License-Identifier: MIT
 
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
-#[version = 1]
+use crate::{fl, models::timer::TimerType, pages::PopupPage, utils::TimeUnit};
+
+const APP_ID: &str = "io.vulpapps.Chronomancer";
+
+/// Settings that persist between runs, edited on the settings page.
+///
+/// # Fields
+///
+/// - `default_unit` - Unit the duration inputs start with
+/// - `grace_period_seconds` - Delay between a power timer finishing and its action running,
+///   during which the action can be cancelled (0 runs it right away)
+/// - `notification_timeout_seconds` - How long notifications stay up (0 uses the server's default)
+/// - `persistent_notifications` - Keep notifications up until dismissed, overriding the timeout
+/// - `panel_countdown` - Whether and where the panel shows the time left on the next timer
+/// - `progress_icon` - Turn the panel icon into a ring that drains as the next timer runs
+/// - `missed_timer_policy` - What to do with timers that ended while the applet wasn't running
+/// - `confirm_before_execute` - Ask before running a power action instead of running it
+/// - `stay_awake_min_battery` - Release stay-awake when discharging below this battery percentage (0 disables)
+/// - `quick_timers` - One-click timers shown above the power controls
+/// - `last_page` - Popup page shown last, so the popup opens where it was left
+/// - `log_level` - How much goes into the log; `CHRONOMANCER_LOG` overrides it, see [`crate::utils::logging`]
+#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
+#[version = 2]
 pub struct Config {
-    demo: String,
+    pub default_unit: TimeUnit,
+    pub grace_period_seconds: u32,
+    pub notification_timeout_seconds: u32,
+    pub persistent_notifications: bool,
+    pub panel_countdown: PanelCountdown,
+    pub progress_icon: bool,
+    pub missed_timer_policy: MissedTimerPolicy,
+    pub confirm_before_execute: bool,
+    pub stay_awake_min_battery: u8,
+    pub quick_timers: Vec<QuickTimerPreset>,
+    pub last_page: PopupPage,
+    pub log_level: LogLevel,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_unit: TimeUnit::Seconds,
+            grace_period_seconds: 0,
+            notification_timeout_seconds: 5,
+            persistent_notifications: false,
+            panel_countdown: PanelCountdown::default(),
+            progress_icon: true,
+            missed_timer_policy: MissedTimerPolicy::default(),
+            confirm_before_execute: false,
+            stay_awake_min_battery: 10,
+            quick_timers: vec![
+                QuickTimerPreset {
+                    label: fl!("preset-default-tea"),
+                    duration_seconds: 15 * 60,
+                    action: PresetAction::Notify,
+                    description: fl!("preset-default-tea-ready"),
+                },
+                QuickTimerPreset {
+                    label: fl!("preset-default-shutdown"),
+                    duration_seconds: 60 * 60,
+                    action: PresetAction::Shutdown,
+                    description: String::new(),
+                },
+            ],
+            last_page: PopupPage::default(),
+            log_level: LogLevel::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the stored settings, falling back to the defaults.
+    ///
+    /// Settings that can't be read are left at their defaults without a word.
+    /// This is meant for picking the log level before logging is set up; the
+    /// applet and the daemon read the settings again and report problems then.
+    #[must_use]
+    pub fn load() -> Self {
+        cosmic_config::Config::new(APP_ID, Self::VERSION)
+            .map(|context| Self::get_entry(&context).unwrap_or_else(|(_, config)| config))
+            .unwrap_or_default()
+    }
+
+    /// Gets the notification timeout in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// `None` when notifications should stay up until dismissed, `Some(0)` to
+    /// leave the timeout to the notification server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::config::Config;
+    ///
+    /// let mut config = Config::default();
+    /// assert_eq!(config.notification_timeout_ms(), Some(5000));
+    ///
+    /// config.persistent_notifications = true;
+    /// assert_eq!(config.notification_timeout_ms(), None);
+    /// ```
+    #[must_use]
+    pub fn notification_timeout_ms(&self) -> Option<u32> {
+        (!self.persistent_notifications)
+            .then(|| self.notification_timeout_seconds.saturating_mul(1000))
+    }
+}
+
+/// A change to one of the general settings, sent by the settings page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    DefaultUnit(TimeUnit),
+    GracePeriod(u32),
+    NotificationTimeout(u32),
+    PersistentNotifications(bool),
+    PanelCountdown(PanelCountdown),
+    ProgressIcon(bool),
+    MissedTimerPolicy(MissedTimerPolicy),
+    ConfirmBeforeExecute(bool),
+    StayAwakeMinBattery(u8),
+    LogLevel(LogLevel),
+}
+
+impl Setting {
+    /// Applies the change to a config.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::config::{Config, Setting};
+    ///
+    /// let mut config = Config::default();
+    /// Setting::GracePeriod(30).apply(&mut config);
+    /// assert_eq!(config.grace_period_seconds, 30);
+    /// ```
+    pub fn apply(self, config: &mut Config) {
+        match self {
+            Setting::DefaultUnit(unit) => config.default_unit = unit,
+            Setting::GracePeriod(seconds) => config.grace_period_seconds = seconds,
+            Setting::NotificationTimeout(seconds) => config.notification_timeout_seconds = seconds,
+            Setting::PersistentNotifications(enabled) => config.persistent_notifications = enabled,
+            Setting::PanelCountdown(mode) => config.panel_countdown = mode,
+            Setting::ProgressIcon(enabled) => config.progress_icon = enabled,
+            Setting::MissedTimerPolicy(policy) => config.missed_timer_policy = policy,
+            Setting::ConfirmBeforeExecute(enabled) => config.confirm_before_execute = enabled,
+            Setting::StayAwakeMinBattery(percent) => config.stay_awake_min_battery = percent,
+            Setting::LogLevel(level) => config.log_level = level,
+        }
+    }
+}
+
+/// Where the panel button shows the time left on the timer that ends next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PanelCountdown {
+    /// Only the applet icon
+    #[default]
+    Off,
+    /// The countdown after the applet icon
+    BesideIcon,
+    /// The countdown in place of the applet icon
+    InsteadOfIcon,
+}
+
+impl PanelCountdown {
+    /// All modes in dropdown order.
+    pub const ALL: [Self; 3] = [Self::Off, Self::BesideIcon, Self::InsteadOfIcon];
+
+    /// Converts a dropdown index to a mode, falling back to `Off`.
+    #[must_use]
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Gets the dropdown index for this mode.
+    #[must_use]
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|m| *m == self)
+            .unwrap_or_default()
+    }
+}
+
+/// What happens to timers whose deadline passed while the applet wasn't running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissedTimerPolicy {
+    /// Drop them without telling anyone
+    Discard,
+    /// Drop them and send a notification listing what was missed
+    #[default]
+    Notify,
+    /// Run them as if they had just ended
+    Run,
+}
+
+impl MissedTimerPolicy {
+    /// All policies in dropdown order.
+    pub const ALL: [Self; 3] = [Self::Notify, Self::Discard, Self::Run];
+
+    /// Converts a dropdown index to a policy, falling back to `Notify`.
+    #[must_use]
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Gets the dropdown index for this policy.
+    #[must_use]
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|p| *p == self)
+            .unwrap_or_default()
+    }
+}
+
+/// How much goes into the log, from failures only up to every step of every timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LogLevel {
+    /// Only failures
+    Error,
+    /// Failures and things that look wrong
+    Warn,
+    /// What happened to each timer, power action and D-Bus request
+    #[default]
+    Info,
+    /// Details for tracking down a misfiring timer
+    Debug,
+    /// Everything, including each tick
+    Trace,
+}
+
+impl LogLevel {
+    /// All levels in dropdown order.
+    pub const ALL: [Self; 5] = [
+        Self::Error,
+        Self::Warn,
+        Self::Info,
+        Self::Debug,
+        Self::Trace,
+    ];
+
+    /// Converts a dropdown index to a level, falling back to `Info`.
+    #[must_use]
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Gets the dropdown index for this level.
+    #[must_use]
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|l| *l == self)
+            .unwrap_or_default()
+    }
+
+    /// Gets the level's name as `tracing` filters spell it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::config::LogLevel;
+    ///
+    /// assert_eq!(LogLevel::default().as_str(), "info");
+    /// ```
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// A quick timer that starts with a single click.
+///
+/// # Fields
+///
+/// - `label` - Text on the preset's button
+/// - `duration_seconds` - How long the timer runs
+/// - `action` - What happens when the timer ends
+/// - `description` - Notification text for notify presets, unused otherwise
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickTimerPreset {
+    pub label: String,
+    pub duration_seconds: u32,
+    pub action: PresetAction,
+    pub description: String,
+}
+
+impl QuickTimerPreset {
+    /// Gets the timer type the preset creates.
+    ///
+    /// Notify presets without a description fall back to their label.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::config::{PresetAction, QuickTimerPreset};
+    /// use chronomancer::TimerType;
+    ///
+    /// let tea = QuickTimerPreset {
+    ///     label: "Tea".to_string(),
+    ///     duration_seconds: 900,
+    ///     action: PresetAction::Notify,
+    ///     description: String::new(),
+    /// };
+    /// assert_eq!(tea.timer_type(), TimerType::UserDefined("Tea".to_string()));
+    /// ```
+    #[must_use]
+    pub fn timer_type(&self) -> TimerType {
+        match self.action {
+            PresetAction::Notify => TimerType::UserDefined(
+                match self.description.trim() {
+                    "" => self.label.as_str(),
+                    description => description,
+                }
+                .to_string(),
+            ),
+            PresetAction::Suspend => TimerType::Suspend,
+            PresetAction::Hibernate => TimerType::Hibernate,
+            PresetAction::Logout => TimerType::Logout,
+            PresetAction::Shutdown => TimerType::Shutdown,
+            PresetAction::Reboot => TimerType::Reboot,
+        }
+    }
+}
+
+/// What a quick timer preset does when it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PresetAction {
+    #[default]
+    Notify,
+    Suspend,
+    Hibernate,
+    Logout,
+    Shutdown,
+    Reboot,
+}
+
+impl PresetAction {
+    /// All actions in dropdown order.
+    pub const ALL: [Self; 6] = [
+        Self::Notify,
+        Self::Suspend,
+        Self::Logout,
+        Self::Reboot,
+        Self::Shutdown,
+        Self::Hibernate,
+    ];
+
+    /// Converts a dropdown index to an action, falling back to `Notify`.
+    #[must_use]
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Gets the dropdown index for this action.
+    #[must_use]
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|a| *a == self)
+            .unwrap_or_default()
+    }
 }
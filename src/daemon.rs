@@ -0,0 +1,688 @@
+// SPDX-License-Identifier: MIT
+
+//! Headless timer daemon, run as `chronomancerd`.
+//!
+//! The daemon runs the timers without the panel. It takes over the timer
+//! service's bus name (see [`crate::service`]), after which the applet only
+//! shows the timers and forwards stay-awake and pending power actions here.
+//! Started as a systemd user service with `Restart=always`, timers keep running
+//! while the panel restarts, and after a crash of the daemon itself they are
+//! picked up from the database again.
+//!
+//! Timers run through the same [`crate::runner`] as in the applet, so it covers
+//! everything a timer needs to finish: conditions and guards, chains,
+//! the missed timer policy, history, notifications and power actions with their
+//! grace period or confirmation. Stay-awake lives here as well, so it survives
+//! the panel, and is let go below the configured battery level. Sequences, the
+//! stopwatch and "stay awake while media plays" belong to the popup and stay in
+//! the applet.
+//!
+//! Between deadlines the daemon sleeps (see [`crate::utils::scheduler`]) and
+//! checks the timers again whenever something wakes it up. While a file trigger
+//! waits, its paths are watched the same way the applet watches them (see
+//! [`file_watch::watch_paths`]) and read back with `stat` when something
+//! happens to them.
+//!
+//! Nothing else is polled on a fixed interval either. Writes by the applet or
+//! the CLI are noticed through inotify on the database's files (see
+//! [`crate::utils::database::ChangeDetector::written`]), the power state
+//! follows `UPower`'s change signals (see [`upower::watch_power_state`]) and
+//! the settings are read again when the applet stores them.
+//!
+//! Sleeps run on the wall clock, so a deadline that passed while the machine
+//! was suspended is met right after resume (see [`clock::Clock::wait_until`]).
+//! Such a sleep also ends when the clock is set, a new time zone is announced
+//! by `systemd-timedated` or DST starts or ends, after which the clock is
+//! checked (see [`ClockWatch`]). When NTP or the user sets it, countdowns keep
+//! their remaining time; when the time zone changes, timers set to a wall clock
+//! time are resolved again in the new one.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! // Runs until the session bus goes away
+//! chronomancer::daemon::run().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::VecDeque, fs::File, path::PathBuf, time::Duration};
+
+use anyhow::Result;
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use futures_util::{StreamExt, stream};
+use notify::RecommendedWatcher;
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver},
+    time::{Interval, MissedTickBehavior},
+};
+use zbus::Connection;
+
+use crate::{
+    components::timer_list,
+    config::Config,
+    error::AppError,
+    fl,
+    models::{HistoryEntry, Timer, timer::TimerType},
+    runner::{self, Effect, Notice, TimerRunner},
+    service::{self, Role, ServiceEvent, Signal},
+    utils::{
+        clock::{self, ClockChange, ClockWatch, SharedClock},
+        database::{Repository, SQLiteDatabase},
+        file_watch, mpris, resources,
+        scheduler::{self, Deadlines},
+        system_load::{self, ActivityCounters},
+        time_zone,
+        upower::{self, PowerState},
+    },
+};
+
+const APP_ID: &str = "io.vulpapps.Chronomancer";
+/// How often `/proc` is sampled while a "system quiet" trigger is waiting
+const ACTIVITY_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+/// How often MPRIS players are polled while a "media ends" trigger is waiting
+const MEDIA_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Runs the timers until the session bus connection goes away.
+///
+/// Opens the database, takes over the timer service, applies the missed timer
+/// policy to timers that ended while nothing ran them, and then sleeps until
+/// the next deadline, a change from outside or a service request, checking the
+/// timers after each.
+///
+/// # Errors
+///
+/// Returns an error if the database can't be opened, or the service can't be
+/// served, e.g. because another `chronomancerd` is already running.
+pub async fn run() -> Result<()> {
+    let database = SQLiteDatabase::new().await?;
+    let clock = clock::system();
+    let (connection, mut events) =
+        service::start(database.pool().clone(), clock.clone(), Role::Daemon).await?;
+    let mut changes = database.watch_changes().await?;
+
+    let mut daemon = Daemon::new(database, Some(connection), load_config(), clock);
+    daemon.handle_missed_timers().await;
+    daemon.reload().await;
+
+    let mut activity_poll = interval(ACTIVITY_SAMPLE_INTERVAL);
+    let mut media_poll = interval(MEDIA_POLL_INTERVAL);
+    // Only report the first failure in a row so a missing service doesn't spam the journal
+    let mut reported_failure = false;
+
+    let mut power_states = upower::watch_power_state().await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to follow power state: {e}");
+        stream::pending().boxed()
+    });
+    let mut zone_changes = time_zone::changes().await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to follow time zone changes: {e}");
+        stream::pending().boxed()
+    });
+    let (config_sender, mut config_changes) = mpsc::unbounded_channel();
+    // Kept alive for the whole loop, the watch ends when it's dropped
+    let _config_watcher = cosmic_config::Config::new(APP_ID, Config::VERSION)
+        .ok()
+        .and_then(|context| {
+            context
+                .watch(move |_, _| {
+                    let _ = config_sender.send(());
+                })
+                .inspect_err(|e| tracing::warn!("Failed to watch settings: {e}"))
+                .ok()
+        });
+
+    let clock = daemon.clock.clone();
+    let mut watch = ClockWatch::new(&*clock);
+    let mut file_watch = FileWatch::new(Vec::new());
+
+    loop {
+        // A sleep ends early when the clock is set, so whatever woke the daemon may have been that
+        if let Some(change) = watch.check(&*clock) {
+            daemon.follow_clock(&change).await;
+        }
+        // Whatever woke the daemon may have made a timer ready
+        daemon.tick().await;
+        // The watcher restarts when the watched paths change, like the applet's subscription
+        let watched_paths = daemon.runner.watched_paths();
+        if watched_paths != file_watch.paths {
+            file_watch = FileWatch::new(watched_paths);
+        }
+        let now = clock.now();
+        // Waking up for the next DST switch lets the clock check follow it
+        let next = daemon
+            .deadlines(now)
+            .next()
+            .into_iter()
+            .chain(time_zone::next_offset_change(&clock.time_zone(), now))
+            .min();
+        tokio::select! {
+            () = scheduler::sleep_until(next, &*clock) => {}
+            path = file_watch.changed() => daemon.runner.observe_file(&path, clock.now()),
+            () = changes.written() => match changes.has_changed().await {
+                Ok(outside_change) => {
+                    reported_failure = false;
+                    if outside_change {
+                        daemon.reload().await;
+                    }
+                }
+                Err(e) => {
+                    if !reported_failure {
+                        tracing::warn!("Failed to check the database for changes: {e}");
+                        reported_failure = true;
+                    }
+                }
+            },
+            Some(state) = power_states.next() => daemon.update_power_state(state).await,
+            Some(()) = config_changes.recv() => daemon.config = load_config(),
+            Some(()) = zone_changes.next() => {}
+            _ = activity_poll.tick(), if daemon.runner.longest_quiet_window().is_some() => {
+                daemon.sample_activity();
+            }
+            _ = media_poll.tick(), if daemon.runner.waits_on_media() => daemon.poll_media().await,
+            event = events.recv() => match event {
+                Some(event) => daemon.handle_event(event).await,
+                None => break,
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Creates an interval that skips ticks it missed instead of catching up.
+///
+/// Polls only run while a trigger needs them, and a burst of samples after a
+/// pause would give rates over a few milliseconds.
+fn interval(period: Duration) -> Interval {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval
+}
+
+/// Paths waited on by file triggers, with the watcher reporting activity on them.
+struct FileWatch {
+    paths: Vec<PathBuf>,
+    /// The watcher and what it reports, `None` without paths or if it couldn't be started
+    watcher: Option<(RecommendedWatcher, UnboundedReceiver<PathBuf>)>,
+}
+
+impl FileWatch {
+    /// Starts watching the paths.
+    ///
+    /// A watcher that can't be started is reported once, and not tried again
+    /// until the paths change; the deadlines of the triggers still apply.
+    fn new(paths: Vec<PathBuf>) -> Self {
+        let watcher = if paths.is_empty() {
+            None
+        } else {
+            file_watch::watch_paths(&paths)
+                .inspect_err(|e| tracing::warn!("Failed to watch files: {e}"))
+                .ok()
+        };
+        Self { paths, watcher }
+    }
+
+    /// Waits for the watcher to report activity on one of the paths.
+    ///
+    /// Never finishes without a watcher.
+    async fn changed(&mut self) -> PathBuf {
+        if let Some((_, changes)) = &mut self.watcher
+            && let Some(path) = changes.recv().await
+        {
+            return path;
+        }
+        std::future::pending().await
+    }
+}
+
+/// Reads the settings the applet stores, falling back to the defaults.
+fn load_config() -> Config {
+    cosmic_config::Config::new(APP_ID, Config::VERSION)
+        .map(|context| match Config::get_entry(&context) {
+            Ok(config) => config,
+            Err((errors, config)) => {
+                for why in errors {
+                    tracing::warn!("Error loading config: {why}");
+                }
+                config
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// State of the daemon between ticks.
+///
+/// The timers themselves are run by a [`TimerRunner`], like in the applet;
+/// the daemon carries out its effects. Everything happens on one task, so
+/// database calls are simply awaited in place.
+struct Daemon {
+    database: SQLiteDatabase,
+    /// Connection serving the timer service, `None` in tests
+    service: Option<Connection>,
+    config: Config,
+    /// Where the current time comes from, a manual clock in tests
+    clock: SharedClock,
+    runner: TimerRunner,
+    suspend_inhibitor: Option<File>,
+    activity_counters: Option<ActivityCounters>,
+    reported_power_failure: bool,
+    reported_activity_failure: bool,
+    reported_media_failure: bool,
+}
+
+impl Daemon {
+    fn new(
+        database: SQLiteDatabase,
+        service: Option<Connection>,
+        config: Config,
+        clock: SharedClock,
+    ) -> Self {
+        Self {
+            database,
+            service,
+            config,
+            clock,
+            runner: TimerRunner::default(),
+            suspend_inhibitor: None,
+            activity_counters: None,
+            reported_power_failure: false,
+            reported_activity_failure: false,
+            reported_media_failure: false,
+        }
+    }
+
+    /// Fetches the running timers from the database.
+    ///
+    /// Overdue timers are kept, the next tick finishes them. Observations of
+    /// paths that are still watched carry over.
+    async fn reload(&mut self) {
+        match Timer::get_all_running(self.database.pool()).await {
+            Ok(timers) => self.runner.set_timers(timers, self.clock.now()),
+            Err(e) => tracing::error!("Failed to load timers: {e}"),
+        }
+    }
+
+    /// Moves the timers and the pending power action along with a change of the clock.
+    ///
+    /// See [`TimerRunner::follow_clock`]. The stored timers are moved as well
+    /// and fetched again.
+    ///
+    /// # Arguments
+    ///
+    /// - `change`: What changed about the clock
+    async fn follow_clock(&mut self, change: &ClockChange) {
+        let effects = self.runner.follow_clock(change);
+        self.apply(effects).await;
+
+        match Timer::follow_clock_all(self.database.pool(), change).await {
+            Ok(changed) if !changed.is_empty() => self.reload().await,
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to move timers along with the clock: {e}"),
+        }
+    }
+
+    /// Applies the missed timer policy to timers that ended while nothing ran them.
+    ///
+    /// See [`TimerRunner::missed`].
+    async fn handle_missed_timers(&mut self) {
+        let now = self.clock.now();
+        match Timer::get_all_missed(self.database.pool(), now).await {
+            Ok(timers) => {
+                let effects = self.runner.missed(timers, &self.config, now);
+                self.apply(effects).await;
+            }
+            Err(e) => tracing::error!("Failed to load missed timers: {e}"),
+        }
+    }
+
+    /// Collects the deadlines to wake up for after the check at `now`.
+    ///
+    /// Earlier ones were just checked; a timer still waiting past its deadline
+    /// waits on an update, which wakes the daemon by itself.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: Current Unix timestamp in seconds
+    fn deadlines(&self, now: i64) -> Deadlines {
+        self.runner.wake_times().filter(|&at| at > now).collect()
+    }
+
+    /// Finishes the timers that are ready and runs a pending action whose grace period is over.
+    async fn tick(&mut self) {
+        let now = self.clock.now();
+
+        // Stop sampling (and forget stale samples) once no trigger needs them
+        if self.runner.longest_quiet_window().is_none() {
+            self.activity_counters = None;
+            self.runner.activity_history.clear();
+        }
+        if !self.runner.waits_on_media() {
+            self.runner.media_players = None;
+        }
+
+        let effects = self.runner.tick(&self.config, now);
+        self.apply(effects).await;
+    }
+
+    /// Carries out the effects of the runner, in order.
+    ///
+    /// A completed timer that arms the next link of its chain hands it back to
+    /// the runner, whose effects follow.
+    ///
+    /// # Arguments
+    ///
+    /// - `effects`: What the runner needs done
+    async fn apply(&mut self, effects: Vec<Effect>) {
+        let mut effects = VecDeque::from(effects);
+        while let Some(effect) = effects.pop_front() {
+            match effect {
+                Effect::Notify(notice) => notice.show(&self.config),
+                Effect::Record(entry) => {
+                    if let Err(e) = HistoryEntry::insert(self.database.pool(), &entry).await {
+                        tracing::error!("Failed to record timer history: {e}");
+                    }
+                }
+                Effect::Emit(signal) => self.emit(signal).await,
+                Effect::Complete { id, now } => {
+                    match Timer::complete(self.database.pool(), id, now).await {
+                        Ok(Some(next)) => effects.extend(self.runner.armed(next, now)),
+                        Ok(None) => {}
+                        Err(e) => tracing::error!("Failed to complete timer: {e}"),
+                    }
+                }
+                Effect::Discard { timers, now } => {
+                    if let Err(e) = runner::discard_missed(self.database.pool(), &timers, now).await
+                    {
+                        tracing::error!("Failed to delete timers: {e}");
+                    }
+                }
+                Effect::Execute(timer_type) => self.execute_power_action(&timer_type).await,
+            }
+        }
+    }
+
+    /// Turns stay-awake on or off, doing nothing if it already is.
+    ///
+    /// # Arguments
+    ///
+    /// - `enabled`: Whether the system should stay awake
+    async fn set_stay_awake(&mut self, enabled: bool) {
+        if enabled == self.suspend_inhibitor.is_some() {
+            return;
+        }
+        if enabled {
+            match resources::acquire_suspend_inhibit(
+                "Chronomancer",
+                "User requested stay-awake mode",
+                "block",
+            )
+            .await
+            {
+                Ok(file) => self.suspend_inhibitor = Some(file),
+                Err(e) => {
+                    tracing::warn!("Failed to acquire inhibit: {e}");
+                    return;
+                }
+            }
+        } else if let Some(inhibitor) = self.suspend_inhibitor.take() {
+            resources::release_suspend_inhibit(inhibitor);
+        }
+        self.emit(Signal::StayAwakeChanged(enabled)).await;
+    }
+
+    /// Handles a request that came in through the timer service.
+    ///
+    /// # Arguments
+    ///
+    /// - `event`: What the service asked for
+    async fn handle_event(&mut self, event: ServiceEvent) {
+        match event {
+            ServiceEvent::TimersChanged => self.reload().await,
+            ServiceEvent::ToggleStayAwake => {
+                self.set_stay_awake(self.suspend_inhibitor.is_none()).await;
+            }
+            ServiceEvent::SetStayAwake(enabled) => self.set_stay_awake(enabled).await,
+            ServiceEvent::RunPendingAction => {
+                let effects = self.runner.run_pending_action();
+                self.apply(effects).await;
+            }
+            ServiceEvent::CancelPendingAction => {
+                let effects = self.runner.cancel_pending_action();
+                self.apply(effects).await;
+            }
+            ServiceEvent::AdoptPendingAction(pending) => {
+                let effects = self.runner.adopt_pending_action(pending);
+                self.apply(effects).await;
+            }
+        }
+    }
+
+    /// Takes a new power state, letting stay-awake go below the configured battery level.
+    async fn update_power_state(&mut self, state: Result<PowerState>) {
+        let state = match state {
+            Ok(state) => {
+                self.reported_power_failure = false;
+                state
+            }
+            Err(e) => {
+                if !self.reported_power_failure {
+                    tracing::warn!("Failed to read power state: {e}");
+                    self.reported_power_failure = true;
+                }
+                return;
+            }
+        };
+
+        if let Some(notice) = self.runner.set_power_state(state, &self.config)
+            && let Some(inhibitor) = self.suspend_inhibitor.take()
+        {
+            resources::release_suspend_inhibit(inhibitor);
+            notice.show(&self.config);
+            self.emit(Signal::StayAwakeChanged(false)).await;
+        }
+    }
+
+    /// Reads the activity counters and records the rate since the previous reading.
+    fn sample_activity(&mut self) {
+        let counters = match system_load::read_counters(self.clock.now()) {
+            Ok(counters) => {
+                self.reported_activity_failure = false;
+                counters
+            }
+            Err(e) => {
+                if !self.reported_activity_failure {
+                    tracing::warn!("Failed to sample system activity: {e}");
+                    self.reported_activity_failure = true;
+                }
+                return;
+            }
+        };
+
+        // Rates need two readings, so the first one only primes the counters
+        if let Some(earlier) = self.activity_counters.replace(counters) {
+            let max_gap = 2 * i64::try_from(ACTIVITY_SAMPLE_INTERVAL.as_secs()).unwrap_or(i64::MAX);
+            let window = self.runner.longest_quiet_window().unwrap_or_default();
+            system_load::record_sample(
+                &mut self.runner.activity_history,
+                counters.rate_since(&earlier),
+                window,
+                max_gap,
+            );
+        }
+    }
+
+    /// Reads the playback state of all media players.
+    async fn poll_media(&mut self) {
+        match mpris::get_players().await {
+            Ok(players) => {
+                self.reported_media_failure = false;
+                self.runner.media_players = Some(players);
+            }
+            Err(e) => {
+                if !self.reported_media_failure {
+                    tracing::warn!("Failed to read media players: {e}");
+                    self.reported_media_failure = true;
+                }
+            }
+        }
+    }
+
+    /// Sends a signal from the timer service, logging failures.
+    ///
+    /// # Arguments
+    ///
+    /// - `signal`: The signal to send
+    async fn emit(&self, signal: Signal) {
+        let Some(connection) = &self.service else {
+            return;
+        };
+        if let Err(e) = service::emit(connection, signal).await {
+            tracing::warn!("Failed to send D-Bus signal: {e}");
+        }
+    }
+
+    /// Runs a power operation right away.
+    ///
+    /// Failures are sent as a notification, so a shutdown polkit didn't allow
+    /// doesn't go unnoticed. User-defined timer types have no power operation
+    /// and do nothing.
+    ///
+    /// # Arguments
+    ///
+    /// - `timer_type`: The power operation to run
+    #[tracing::instrument(skip(self))]
+    async fn execute_power_action(&self, timer_type: &TimerType) {
+        let result = match timer_type {
+            TimerType::Suspend => resources::execute_system_suspend().await,
+            TimerType::Hibernate => resources::execute_system_hibernate().await,
+            TimerType::Logout => resources::execute_system_logout().await,
+            TimerType::Shutdown => resources::execute_system_shutdown().await,
+            TimerType::Reboot => resources::execute_system_reboot().await,
+            TimerType::UserDefined(_) => return,
+        };
+        if let Err(e) = result {
+            let operation = timer_list::operation_name(timer_type);
+            let error = AppError::from(e);
+            tracing::error!("Failed to run {operation}: {error}");
+            let body = if error.is_permission_denied() {
+                fl!("error-permission-denied")
+            } else {
+                error.to_string()
+            };
+            Notice::new(
+                fl!("error-power-action", operation = operation),
+                body,
+                "dialog-error-symbolic",
+            )
+            .show(&self.config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::MissedTimerPolicy,
+        models::history::Outcome,
+        service::PendingAction,
+        utils::clock::{Clock, ManualClock},
+    };
+
+    const NOW: i64 = 1_767_600_000;
+
+    async fn get_test_daemon(config: Config) -> Result<(Daemon, ManualClock)> {
+        let database = SQLiteDatabase::new_in_memory().await?;
+        let clock = ManualClock::new(NOW);
+        let daemon = Daemon::new(database, None, config, std::sync::Arc::new(clock.clone()));
+        Ok((daemon, clock))
+    }
+
+    #[tokio::test]
+    async fn test_finished_timer_arms_next_link() -> Result<()> {
+        let (mut daemon, clock) = get_test_daemon(Config::default()).await?;
+        let head = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        let link = Timer::chained(300, &TimerType::UserDefined("Drink it".to_string()), NOW);
+        let stored = Timer::insert_chain(daemon.database.pool(), &head, &[link]).await?;
+
+        daemon.reload().await;
+        assert_eq!(daemon.runner.active_timers.len(), 1);
+        assert_eq!(daemon.deadlines(NOW).next(), Some(NOW + 60));
+
+        clock.advance(59);
+        daemon.tick().await;
+        assert_eq!(daemon.runner.active_timers[0].id, stored[0].id);
+
+        clock.advance(1);
+        daemon.tick().await;
+        assert_eq!(daemon.runner.active_timers.len(), 1);
+        assert_eq!(daemon.runner.active_timers[0].id, stored[1].id);
+        assert_eq!(daemon.runner.active_timers[0].ends_at, NOW + 60 + 300);
+
+        let history = HistoryEntry::get_all_active(daemon.database.pool(), clock.now()).await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].outcome(), Outcome::Finished);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pending_action_requests_reach_the_runner() -> Result<()> {
+        let (mut daemon, _clock) = get_test_daemon(Config::default()).await?;
+
+        // An action the applet held when the daemon took over keeps its grace period
+        let reboot = PendingAction {
+            timer_type: TimerType::Reboot,
+            runs_at: Some(NOW + 10),
+        };
+        daemon
+            .handle_event(ServiceEvent::AdoptPendingAction(reboot.clone()))
+            .await;
+        assert_eq!(daemon.runner.pending_action, Some(reboot));
+        assert_eq!(daemon.deadlines(NOW).next(), Some(NOW + 10));
+
+        daemon.handle_event(ServiceEvent::CancelPendingAction).await;
+        assert!(daemon.runner.pending_action.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_discarded_missed_timers_leave_the_database() -> Result<()> {
+        let config = Config {
+            missed_timer_policy: MissedTimerPolicy::Discard,
+            ..Config::default()
+        };
+        let (mut daemon, _clock) = get_test_daemon(config).await?;
+        let mut missed = Timer::new(60, false, &TimerType::Reboot, NOW);
+        missed.ends_at = NOW - 600;
+        Timer::insert(daemon.database.pool(), &missed).await?;
+
+        daemon.handle_missed_timers().await;
+        assert!(
+            Timer::get_all_running(daemon.database.pool())
+                .await?
+                .is_empty()
+        );
+        let history = HistoryEntry::get_all_active(daemon.database.pool(), NOW).await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].outcome(), Outcome::Missed);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clock_changes_are_stored() -> Result<()> {
+        let (mut daemon, clock) = get_test_daemon(Config::default()).await?;
+        let tea = Timer::new(600, false, &TimerType::UserDefined("Tea".to_string()), NOW);
+        Timer::insert(daemon.database.pool(), &tea).await?;
+        daemon.reload().await;
+        let mut watch = ClockWatch::new(&clock);
+
+        // NTP sets the clock half an hour ahead, tea still has ten minutes to go
+        clock.set(NOW + 1800);
+        let change = watch.check(&clock).expect("jump");
+        daemon.follow_clock(&change).await;
+        let stored = Timer::get_all_running(daemon.database.pool()).await?;
+        assert_eq!(stored[0].ends_at, NOW + 1800 + 600);
+        assert_eq!(daemon.runner.active_timers[0].ends_at, stored[0].ends_at);
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: MIT
+
+//! Errors the applet reports to the user.
+//!
+//! Work runs in async tasks whose results come back as messages, so failures
+//! have to be `Clone` and can't carry the original error along. [`AppError`]
+//! keeps what the user needs to know: which kind of failure it was and a
+//! message to show. Whether it was a permission problem matters most, since
+//! that is the one case the user can do something about (e.g. a shutdown
+//! polkit didn't allow).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::error::AppError;
+//! use chronomancer::utils::database::RepositoryError;
+//!
+//! let error = AppError::from(RepositoryError::NotFound { kind: "timer", id: 7 });
+//! assert_eq!(error, AppError::Invalid("No timer with id 7".to_string()));
+//!
+//! let denied = AppError::from(zbus::Error::FDO(Box::new(zbus::fdo::Error::AccessDenied(
+//!     "Permission denied".to_string(),
+//! ))));
+//! assert!(denied.is_permission_denied());
+//! ```
+
+use crate::utils::database::RepositoryError;
+
+/// D-Bus error names that mean the caller isn't allowed to do what it asked,
+/// as returned by logind when polkit turns a request down.
+const PERMISSION_ERRORS: [&str; 4] = [
+    "org.freedesktop.DBus.Error.AccessDenied",
+    "org.freedesktop.DBus.Error.AuthFailed",
+    "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired",
+    "org.freedesktop.PolicyKit1.Error.NotAuthorized",
+];
+
+/// A failure to show in the popup or in a notification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AppError {
+    /// The database couldn't be opened, read or written
+    #[error("Database error: {0}")]
+    Database(String),
+    /// A D-Bus call failed or its service isn't running
+    #[error("D-Bus error: {0}")]
+    DBus(String),
+    /// A file or directory couldn't be read or written
+    #[error("File error: {0}")]
+    Io(String),
+    /// The system refused the request, e.g. polkit denied a shutdown or the
+    /// data directory isn't writable
+    #[error("Not allowed: {0}")]
+    PermissionDenied(String),
+    /// The request itself can't be done, such as a timer that doesn't exist
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl AppError {
+    /// Gets the message without the kind of failure in front.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Database(message)
+            | AppError::DBus(message)
+            | AppError::Io(message)
+            | AppError::PermissionDenied(message)
+            | AppError::Invalid(message) => message,
+        }
+    }
+
+    /// Whether the system refused the request, see [`AppError::PermissionDenied`].
+    #[must_use]
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self, AppError::PermissionDenied(_))
+    }
+}
+
+impl From<RepositoryError> for AppError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::Database(_) => AppError::Database(error.to_string()),
+            RepositoryError::NotFound { .. } | RepositoryError::Invalid(_) => {
+                AppError::Invalid(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::Io(io) if io.kind() == std::io::ErrorKind::PermissionDenied => {
+                AppError::PermissionDenied(error.to_string())
+            }
+            _ => AppError::Database(error.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::PermissionDenied {
+            AppError::PermissionDenied(error.to_string())
+        } else {
+            AppError::Io(error.to_string())
+        }
+    }
+}
+
+impl From<zbus::Error> for AppError {
+    fn from(error: zbus::Error) -> Self {
+        let (denied, message) = match &error {
+            zbus::Error::MethodError(name, detail, _) => (
+                PERMISSION_ERRORS.contains(&name.as_str()),
+                detail.clone().unwrap_or_else(|| name.to_string()),
+            ),
+            zbus::Error::FDO(fdo) => (
+                matches!(
+                    **fdo,
+                    zbus::fdo::Error::AccessDenied(_)
+                        | zbus::fdo::Error::AuthFailed(_)
+                        | zbus::fdo::Error::InteractiveAuthorizationRequired(_)
+                ),
+                zbus::DBusError::description(&**fdo)
+                    .map_or_else(|| fdo.to_string(), str::to_string),
+            ),
+            _ => (false, error.to_string()),
+        };
+        if denied {
+            AppError::PermissionDenied(message)
+        } else {
+            AppError::DBus(message)
+        }
+    }
+}
+
+/// Sorts an error from code that reports through `anyhow` by what caused it.
+///
+/// The cause is looked up along the chain, so context added on top (like
+/// "D-Bus call to `PowerOff` failed") doesn't hide a polkit denial or an
+/// unwritable data directory. The message
+/// starts with the outermost context. Errors without a known cause count as
+/// invalid requests.
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let with_context = |depth: usize, cause: String| {
+            if depth == 0 {
+                cause
+            } else {
+                format!("{error}: {cause}")
+            }
+        };
+        for (depth, cause) in error.chain().enumerate() {
+            if let Some(repository) = cause.downcast_ref::<RepositoryError>() {
+                let message = with_context(depth, repository.to_string());
+                return match repository {
+                    RepositoryError::Database(_) => AppError::Database(message),
+                    _ => AppError::Invalid(message),
+                };
+            }
+            if let Some(database) = cause.downcast_ref::<sqlx::Error>() {
+                let message = with_context(depth, database.to_string());
+                return match database {
+                    sqlx::Error::Io(io) if io.kind() == std::io::ErrorKind::PermissionDenied => {
+                        AppError::PermissionDenied(message)
+                    }
+                    _ => AppError::Database(message),
+                };
+            }
+            if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+                let message = with_context(depth, io.to_string());
+                return if io.kind() == std::io::ErrorKind::PermissionDenied {
+                    AppError::PermissionDenied(message)
+                } else {
+                    AppError::Io(message)
+                };
+            }
+            if let Some(dbus) = cause.downcast_ref::<zbus::Error>() {
+                return match AppError::from(dbus.clone()) {
+                    AppError::PermissionDenied(message) => {
+                        AppError::PermissionDenied(with_context(depth, message))
+                    }
+                    other => AppError::DBus(with_context(depth, other.message().to_string())),
+                };
+            }
+        }
+        AppError::Invalid(format!("{error:#}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_dbus_denials_are_permission_errors() {
+        let denied = zbus::Error::FDO(Box::new(
+            zbus::fdo::Error::InteractiveAuthorizationRequired(
+                "Interactive authentication required.".to_string(),
+            ),
+        ));
+        assert!(AppError::from(denied).is_permission_denied());
+
+        let missing = zbus::Error::FDO(Box::new(zbus::fdo::Error::ServiceUnknown(
+            "login1".to_string(),
+        )));
+        assert!(matches!(AppError::from(missing), AppError::DBus(_)));
+    }
+
+    #[test]
+    fn test_anyhow_errors_keep_their_cause() {
+        let denied: anyhow::Result<()> = Err(zbus::Error::FDO(Box::new(
+            zbus::fdo::Error::AccessDenied("Permission denied".to_string()),
+        )))
+        .context("D-Bus call to PowerOff failed");
+        let error = AppError::from(denied.unwrap_err());
+        assert_eq!(
+            error,
+            AppError::PermissionDenied(
+                "D-Bus call to PowerOff failed: Permission denied".to_string()
+            )
+        );
+
+        let missing: anyhow::Result<()> =
+            Err(RepositoryError::Database(sqlx::Error::RowNotFound)).context("Failed to load");
+        assert!(matches!(
+            AppError::from(missing.unwrap_err()),
+            AppError::Database(_)
+        ));
+
+        let unwritable: anyhow::Result<()> =
+            Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+                .context("Failed to create the data directory");
+        assert!(AppError::from(unwritable.unwrap_err()).is_permission_denied());
+
+        let full: anyhow::Result<()> =
+            Err(std::io::Error::from(std::io::ErrorKind::StorageFull)).context("Failed to write");
+        assert!(matches!(AppError::from(full.unwrap_err()), AppError::Io(_)));
+
+        let other = anyhow::anyhow!("XDG_SESSION_ID environment variable not set");
+        assert!(matches!(AppError::from(other), AppError::Invalid(_)));
+    }
+}
@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: MIT
+
+//! Keyboard shortcuts for the popup.
+//!
+//! Key presses that no widget handled (typing into a text input never reaches
+//! here) are looked up in [`key_binds`] while the popup is open, and the
+//! matching [`MenuAction`] is turned into an app message. The actions act on
+//! whatever page is showing, e.g. Enter submits the power form on the power
+//! page and starts the sequence on the sequences page.
+//!
+//! | Keys | Action |
+//! |------|--------|
+//! | `1`–`7` | Pick a power operation, in the order of the power buttons |
+//! | `←` / `→` | Previous / next power operation |
+//! | `Enter` | Submit the form on the current page |
+//! | `↑` / `↓` | Move the focus through the listed timers or sequences |
+//! | `Delete` / `Backspace` | Cancel the focused timer, or stop the focused sequence |
+//! | `Space` / `P` | Pause or resume the focused timer or sequence |
+//! | `Ctrl+Tab` / `Ctrl+Shift+Tab` | Next / previous page |
+//! | `Tab` / `Shift+Tab` | Next / previous input or button |
+//! | `Escape` | Close the popup |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::key_binds::{MenuAction, key_binds};
+//! use cosmic::iced::keyboard::{Key, Modifiers, key::Named};
+//!
+//! let binds = key_binds();
+//! let action = binds
+//!     .iter()
+//!     .find(|(bind, _)| bind.matches(Modifiers::empty(), &Key::Named(Named::Escape)))
+//!     .map(|(_, action)| *action);
+//! assert_eq!(action, Some(MenuAction::ClosePopup));
+//! ```
+
+use std::collections::HashMap;
+
+use cosmic::{
+    iced::keyboard::{Key, key::Named},
+    widget::menu::{
+        self,
+        key_bind::{KeyBind, Modifier},
+    },
+};
+
+use crate::app_messages::AppMessage;
+
+/// Number of power operations that can be picked with the number keys, one per power button.
+pub const OPERATION_KEYS: usize = 7;
+
+/// Things a keyboard shortcut can do in the popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    /// Pick the power operation at the given index, showing the power page
+    SelectOperation(usize),
+    /// Pick the power operation after the selected one
+    NextOperation,
+    /// Pick the power operation before the selected one
+    PreviousOperation,
+    /// Submit the form on the current page
+    Submit,
+    /// Close the popup
+    ClosePopup,
+    /// Focus the next timer or sequence listed on the current page
+    FocusNextItem,
+    /// Focus the previous timer or sequence listed on the current page
+    FocusPreviousItem,
+    /// Cancel the focused timer, or stop the focused sequence
+    CancelFocused,
+    /// Pause or resume the focused timer or sequence
+    PauseFocused,
+    /// Show the next page
+    NextPage,
+    /// Show the previous page
+    PreviousPage,
+    /// Move the widget focus to the next input or button
+    FocusNextWidget,
+    /// Move the widget focus to the previous input or button
+    FocusPreviousWidget,
+}
+
+impl menu::action::MenuAction for MenuAction {
+    type Message = AppMessage;
+
+    fn message(&self) -> Self::Message {
+        AppMessage::Shortcut(*self)
+    }
+}
+
+/// Builds the popup's key bindings.
+///
+/// # Returns
+///
+/// A map from key binding to action. Several bindings may share an action.
+#[must_use]
+pub fn key_binds() -> HashMap<KeyBind, MenuAction> {
+    let mut binds = HashMap::new();
+    let mut bind = |modifiers: Vec<Modifier>, key: Key, action: MenuAction| {
+        binds.insert(KeyBind { modifiers, key }, action);
+    };
+
+    for index in 0..OPERATION_KEYS {
+        bind(
+            vec![],
+            Key::Character((index + 1).to_string().into()),
+            MenuAction::SelectOperation(index),
+        );
+    }
+    bind(
+        vec![],
+        Key::Named(Named::ArrowRight),
+        MenuAction::NextOperation,
+    );
+    bind(
+        vec![],
+        Key::Named(Named::ArrowLeft),
+        MenuAction::PreviousOperation,
+    );
+    bind(vec![], Key::Named(Named::Enter), MenuAction::Submit);
+    bind(vec![], Key::Named(Named::Escape), MenuAction::ClosePopup);
+    bind(
+        vec![],
+        Key::Named(Named::ArrowDown),
+        MenuAction::FocusNextItem,
+    );
+    bind(
+        vec![],
+        Key::Named(Named::ArrowUp),
+        MenuAction::FocusPreviousItem,
+    );
+    bind(vec![], Key::Named(Named::Delete), MenuAction::CancelFocused);
+    bind(
+        vec![],
+        Key::Named(Named::Backspace),
+        MenuAction::CancelFocused,
+    );
+    bind(vec![], Key::Named(Named::Space), MenuAction::PauseFocused);
+    bind(vec![], Key::Character("p".into()), MenuAction::PauseFocused);
+    bind(
+        vec![Modifier::Ctrl],
+        Key::Named(Named::Tab),
+        MenuAction::NextPage,
+    );
+    bind(
+        vec![Modifier::Ctrl, Modifier::Shift],
+        Key::Named(Named::Tab),
+        MenuAction::PreviousPage,
+    );
+    bind(vec![], Key::Named(Named::Tab), MenuAction::FocusNextWidget);
+    bind(
+        vec![Modifier::Shift],
+        Key::Named(Named::Tab),
+        MenuAction::FocusPreviousWidget,
+    );
+
+    binds
+}
+
+/// Moves a focus through a list of ids, wrapping around at either end.
+///
+/// # Arguments
+///
+/// - `ids` - The ids that can be focused, in display order
+/// - `current` - The focused id, `None` (or an id no longer listed) starts at an end
+/// - `forward` - Move down the list instead of up
+///
+/// # Returns
+///
+/// The newly focused id, or `None` if the list is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::key_binds::step_focus;
+///
+/// let ids = [4, 7, 9];
+/// assert_eq!(step_focus(&ids, None, true), Some(4));
+/// assert_eq!(step_focus(&ids, Some(4), true), Some(7));
+/// assert_eq!(step_focus(&ids, Some(9), true), Some(4));
+/// assert_eq!(step_focus(&ids, Some(4), false), Some(9));
+/// assert_eq!(step_focus(&[], Some(4), true), None);
+/// ```
+#[must_use]
+pub fn step_focus(ids: &[i64], current: Option<i64>, forward: bool) -> Option<i64> {
+    let position = current.and_then(|id| ids.iter().position(|listed| *listed == id));
+    let index = match (position, forward) {
+        (None, true) => 0,
+        (None, false) => ids.len().checked_sub(1)?,
+        (Some(position), true) => (position + 1) % ids.len(),
+        (Some(position), false) => (position + ids.len() - 1) % ids.len(),
+    };
+    ids.get(index).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmic::iced::keyboard::Modifiers;
+
+    fn action_for(modifiers: Modifiers, key: &Key) -> Option<MenuAction> {
+        key_binds()
+            .iter()
+            .find(|(bind, _)| bind.matches(modifiers, key))
+            .map(|(_, action)| *action)
+    }
+
+    #[test]
+    fn test_number_keys_pick_operations_in_popup_order() {
+        for index in 0..OPERATION_KEYS {
+            let key = Key::Character((index + 1).to_string().into());
+            assert_eq!(
+                action_for(Modifiers::empty(), &key),
+                Some(MenuAction::SelectOperation(index))
+            );
+        }
+        assert_eq!(
+            action_for(Modifiers::empty(), &Key::Character("8".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tab_bindings_depend_on_modifiers() {
+        let tab = Key::Named(Named::Tab);
+        assert_eq!(
+            action_for(Modifiers::empty(), &tab),
+            Some(MenuAction::FocusNextWidget)
+        );
+        assert_eq!(
+            action_for(Modifiers::SHIFT, &tab),
+            Some(MenuAction::FocusPreviousWidget)
+        );
+        assert_eq!(
+            action_for(Modifiers::CTRL, &tab),
+            Some(MenuAction::NextPage)
+        );
+        assert_eq!(
+            action_for(Modifiers::CTRL | Modifiers::SHIFT, &tab),
+            Some(MenuAction::PreviousPage)
+        );
+    }
+
+    #[test]
+    fn test_step_focus_skips_stale_ids() {
+        // A focused timer that was cancelled meanwhile starts over at the top
+        assert_eq!(step_focus(&[1, 2], Some(5), true), Some(1));
+        assert_eq!(step_focus(&[1, 2], Some(5), false), Some(2));
+        assert_eq!(step_focus(&[1], Some(1), true), Some(1));
+    }
+}
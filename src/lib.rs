@@ -6,9 +6,15 @@
 
 // Core modules
 pub mod app_messages;
+pub mod cli;
 pub mod config;
+pub mod daemon;
+pub mod error;
 pub mod i18n;
+pub mod key_binds;
 pub mod models;
+pub mod runner;
+pub mod service;
 pub mod utils;
 
 // UI layer (for component/message tests)
@@ -7,22 +7,55 @@ use cosmic::{
 use i18n::init;
 use i18n_embed::DesktopLanguageRequester;
 
+use crate::{config::Config, utils::logging};
+
 mod app;
 mod app_messages;
+mod cli;
 mod components;
 mod config;
+mod error;
 mod i18n;
+mod key_binds;
 mod models;
 mod pages;
+mod runner;
+mod service;
 mod utils;
 
 fn main() -> Result {
+    // Log to stderr and the log file, keeping the file writer's guard until exit
+    let log_guard = logging::init("chronomancer", Config::load().log_level);
+
     // Get the system's preferred languages.
     let requested_languages = DesktopLanguageRequester::requested_languages();
 
     // Enable localizations to be applied.
     init(&requested_languages);
 
+    // With a command (or `--quick-entry` from a global shortcut), work on the
+    // timers from the command line instead of running the applet.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse(&args) {
+        Ok(None) => {}
+        Ok(Some(invocation)) => {
+            let result = tokio::runtime::Runtime::new()
+                .map_err(anyhow::Error::from)
+                .and_then(|runtime| runtime.block_on(cli::run(invocation)));
+            if let Err(e) = result {
+                eprintln!("{e:#}");
+                // Exiting skips destructors, so flush the log file first
+                drop(log_guard);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Err(e) => {
+            eprintln!("{e:#}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+
     // Settings for configuring the application window and iced runtime.
     // Pretty sure this is set in the main panel rather than here?
     let _settings =
@@ -0,0 +1,853 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::utils::{
+    file_watch::WatchedFile,
+    mpris::{PlaybackStatus, PlayerState},
+    system_load::ActivitySample,
+    upower::PowerState,
+};
+
+/// Extra condition attached to a timer on top of its `ends_at` deadline.
+///
+/// Conditions come in two flavours:
+/// - **Guards** only matter once the deadline has passed and decide whether the
+///   action still runs (e.g. `OnAcPower`).
+/// - **Triggers** ignore the deadline and fire as soon as the system reaches a
+///   given state (e.g. `BatteryBelow`, `SystemQuiet`, `File`, `MediaEnds`). Trigger timers are stored with
+///   [`Timer::NO_DEADLINE`](super::Timer::NO_DEADLINE).
+///
+/// Conditions are persisted as short strings in the `condition` column, following
+/// the same approach as `TimerType` and the `description` column.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::condition::TimerCondition;
+/// use std::str::FromStr;
+///
+/// let condition = TimerCondition::BatteryBelow(10);
+/// assert_eq!(condition.to_string(), "battery-below:10");
+/// assert_eq!(TimerCondition::from_str("battery-below:10").unwrap(), condition);
+/// assert_eq!(TimerCondition::from_str("").unwrap(), TimerCondition::None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TimerCondition {
+    /// Plain countdown, fires when `ends_at` passes
+    #[default]
+    None,
+    /// Fires as soon as the system is on battery with less than the given percentage
+    BatteryBelow(u8),
+    /// Fires at `ends_at` only when on AC power, skipped when on battery
+    OnAcPower,
+    /// Fires once CPU, disk and network activity stayed below the thresholds for the whole window
+    SystemQuiet(QuietThresholds),
+    /// Fires once a watched file or directory appears, disappears or stops changing
+    File(FileTrigger),
+    /// Fires once a media player has finished (or moved on from) the given track
+    MediaEnds(MediaTrack),
+}
+
+/// A media player and the track it was playing when a [`TimerCondition::MediaEnds`] timer was set.
+///
+/// The power controls page submits an empty track, which the app binds to
+/// whatever is playing at the moment the timer is created.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::condition::{MediaTrack, TimerCondition};
+/// use std::str::FromStr;
+///
+/// let condition = TimerCondition::MediaEnds(MediaTrack {
+///     player: "org.mpris.MediaPlayer2.podcasts".to_string(),
+///     track: "/episode/42".to_string(),
+/// });
+/// assert_eq!(
+///     condition.to_string(),
+///     "media-ends:org.mpris.MediaPlayer2.podcasts:/episode/42"
+/// );
+/// assert_eq!(TimerCondition::from_str(&condition.to_string()).unwrap(), condition);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MediaTrack {
+    pub player: String,
+    pub track: String,
+}
+
+impl MediaTrack {
+    /// Binds to the track a player is currently playing.
+    #[must_use]
+    pub fn current(player: &PlayerState) -> Self {
+        Self {
+            player: player.bus_name.clone(),
+            track: player.track.clone(),
+        }
+    }
+
+    /// How close to its end a track counts as played through, in microseconds.
+    ///
+    /// Players that pause at the end of an episode report a position a little
+    /// short of the length, if only by the last polling interval.
+    pub const END_TOLERANCE_US: i64 = 2_000_000;
+
+    /// Whether the track is over according to the latest player snapshots.
+    ///
+    /// Pausing mid-track doesn't end the track; the player moving on to another
+    /// track, stopping, closing, or reaching the track's end does.
+    #[must_use]
+    pub fn has_ended(&self, players: &[PlayerState]) -> bool {
+        players
+            .iter()
+            .find(|player| player.bus_name == self.player)
+            .is_none_or(|player| {
+                let at_end = player.length_us.is_some_and(|length| {
+                    length > 0 && player.position_us >= length - Self::END_TOLERANCE_US
+                });
+                player.status == PlaybackStatus::Stopped || player.track != self.track || at_end
+            })
+    }
+}
+
+/// What has to happen to a watched path for a [`TimerCondition::File`] trigger to fire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEvent {
+    /// The path exists
+    Appears,
+    /// The path does not exist
+    Disappears,
+    /// The path exists and hasn't changed for the given number of seconds
+    StopsChanging(u32),
+}
+
+/// A watched path and the event that fires the trigger.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::condition::{FileEvent, FileTrigger, TimerCondition};
+/// use std::str::FromStr;
+///
+/// let condition = TimerCondition::File(FileTrigger {
+///     path: "/renders/output.mkv".into(),
+///     event: FileEvent::StopsChanging(30),
+/// });
+/// assert_eq!(condition.to_string(), "file-stable:30:/renders/output.mkv");
+/// assert_eq!(TimerCondition::from_str(&condition.to_string()).unwrap(), condition);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTrigger {
+    pub path: PathBuf,
+    pub event: FileEvent,
+}
+
+impl FileTrigger {
+    /// Checks the trigger against the latest observation of its path.
+    ///
+    /// # Arguments
+    ///
+    /// - `file` - Latest observation, `None` if the path hasn't been observed yet
+    /// - `now` - Current Unix timestamp in seconds
+    #[must_use]
+    pub fn is_met(&self, file: Option<&WatchedFile>, now: i64) -> bool {
+        let Some(file) = file else {
+            return false;
+        };
+
+        match self.event {
+            FileEvent::Appears => file.exists,
+            FileEvent::Disappears => !file.exists,
+            FileEvent::StopsChanging(seconds) => {
+                file.exists && now - file.last_changed >= i64::from(seconds)
+            }
+        }
+    }
+}
+
+/// Activity limits and the sustained window for a [`TimerCondition::SystemQuiet`] trigger.
+///
+/// Limits are stored as integers so conditions stay `Eq` and serialize without
+/// float formatting surprises.
+///
+/// # Fields
+///
+/// - `max_load_percent` - One minute load average per CPU core, as a percentage
+/// - `max_disk_kib_per_sec` - Combined disk read and write throughput
+/// - `max_net_kib_per_sec` - Combined network receive and transmit throughput
+/// - `window_secs` - How long every sample must stay below the limits
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::condition::QuietThresholds;
+///
+/// // Quiet for an hour with the default activity limits
+/// let thresholds = QuietThresholds { window_secs: 3600, ..QuietThresholds::default() };
+/// assert_eq!(thresholds.max_load_percent, 25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietThresholds {
+    pub max_load_percent: u16,
+    pub max_disk_kib_per_sec: u32,
+    pub max_net_kib_per_sec: u32,
+    pub window_secs: u32,
+}
+
+impl Default for QuietThresholds {
+    /// Limits that an idle desktop stays well under but a download, render or
+    /// backup clearly exceeds, sustained for ten minutes.
+    fn default() -> Self {
+        Self {
+            max_load_percent: 25,
+            max_disk_kib_per_sec: 1024,
+            max_net_kib_per_sec: 128,
+            window_secs: 600,
+        }
+    }
+}
+
+impl QuietThresholds {
+    /// Shortest window a trigger can be created with.
+    ///
+    /// Activity is sampled every ten seconds and a window is only judged once
+    /// samples cover it from start to end, so shorter windows would never fire.
+    pub const MIN_WINDOW_SECS: u32 = 30;
+
+    /// Whether a single activity sample is below every limit.
+    #[must_use]
+    pub fn is_met_by(&self, sample: &ActivitySample) -> bool {
+        sample.load_percent < f64::from(self.max_load_percent)
+            && sample.disk_bytes_per_sec < f64::from(self.max_disk_kib_per_sec) * 1024.0
+            && sample.net_bytes_per_sec < f64::from(self.max_net_kib_per_sec) * 1024.0
+    }
+
+    /// Whether the system has been quiet for the whole window ending at `now`.
+    ///
+    /// The history must reach back to the start of the window, so a freshly
+    /// started applet never fires before it has watched a full window.
+    ///
+    /// # Arguments
+    ///
+    /// - `history` - Activity samples ordered oldest first
+    /// - `now` - Current Unix timestamp in seconds
+    #[must_use]
+    pub fn is_quiet_for_window(&self, history: &[ActivitySample], now: i64) -> bool {
+        let window_start = now - i64::from(self.window_secs);
+        let covers_window = history
+            .first()
+            .is_some_and(|sample| sample.at <= window_start);
+        let mut in_window = history
+            .iter()
+            .filter(|sample| sample.at >= window_start)
+            .peekable();
+
+        covers_window && in_window.peek().is_some() && in_window.all(|s| self.is_met_by(s))
+    }
+}
+
+/// Outcome of evaluating a timer against the current system state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    /// Keep the timer around and check again at its next deadline or update
+    Waiting,
+    /// Run the timer's action now
+    Ready,
+    /// The deadline passed but a guard failed, drop the timer without running it
+    Skipped,
+}
+
+/// System state that conditions are evaluated against.
+///
+/// Built fresh by the app on every tick from whatever it has last observed.
+/// Missing information (e.g. `UPower` not running) is represented as `None`
+/// or an empty history.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TriggerContext<'a> {
+    /// Current Unix timestamp in seconds
+    pub now: i64,
+    /// Last known power state from `UPower`
+    pub power: Option<PowerState>,
+    /// Recent system activity samples, oldest first
+    pub activity: &'a [ActivitySample],
+    /// Latest observations of paths watched by file triggers
+    pub files: &'a [WatchedFile],
+    /// Latest media player snapshots, `None` until the players have been read
+    pub media: Option<&'a [PlayerState]>,
+}
+
+/// Gets the longest sustained window among "system quiet" conditions.
+///
+/// # Arguments
+///
+/// - `conditions` - Conditions of the running timers
+///
+/// # Returns
+///
+/// The window in seconds, or `None` when there is no quiet condition.
+#[must_use]
+pub fn longest_quiet_window(conditions: impl IntoIterator<Item = TimerCondition>) -> Option<i64> {
+    conditions
+        .into_iter()
+        .filter_map(|condition| match condition {
+            TimerCondition::SystemQuiet(thresholds) => Some(i64::from(thresholds.window_secs)),
+            _ => None,
+        })
+        .max()
+}
+
+/// Gets the distinct paths watched by file triggers, sorted.
+///
+/// # Arguments
+///
+/// - `conditions` - Conditions of the running timers
+#[must_use]
+pub fn watched_paths(conditions: impl IntoIterator<Item = TimerCondition>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = conditions
+        .into_iter()
+        .filter_map(|condition| condition.watched_path().map(PathBuf::from))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+impl TimerCondition {
+    /// Whether this condition fires on its own rather than waiting for a deadline.
+    #[must_use]
+    pub fn is_trigger(&self) -> bool {
+        matches!(
+            self,
+            TimerCondition::BatteryBelow(_)
+                | TimerCondition::SystemQuiet(_)
+                | TimerCondition::File(_)
+                | TimerCondition::MediaEnds(_)
+        )
+    }
+
+    /// Gets the path watched by a file trigger.
+    #[must_use]
+    pub fn watched_path(&self) -> Option<&Path> {
+        match self {
+            TimerCondition::File(trigger) => Some(&trigger.path),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the condition for a timer with the given deadline.
+    ///
+    /// # Arguments
+    ///
+    /// - `ends_at` - The timer's deadline as a Unix timestamp
+    /// - `context` - The current system state
+    ///
+    /// # Returns
+    ///
+    /// Whether the timer should wait, run, or be skipped. A system without `UPower`
+    /// is treated as being on AC power, so `OnAcPower` timers still run on desktops.
+    #[must_use]
+    pub fn evaluate(&self, ends_at: i64, context: &TriggerContext) -> Readiness {
+        let deadline_passed = context.now >= ends_at;
+
+        match self {
+            TimerCondition::None if deadline_passed => Readiness::Ready,
+            TimerCondition::BatteryBelow(percent) => {
+                if context
+                    .power
+                    .is_some_and(|power| power.is_battery_below(*percent))
+                {
+                    Readiness::Ready
+                } else {
+                    Readiness::Waiting
+                }
+            }
+            TimerCondition::SystemQuiet(thresholds) => {
+                if thresholds.is_quiet_for_window(context.activity, context.now) {
+                    Readiness::Ready
+                } else {
+                    Readiness::Waiting
+                }
+            }
+            TimerCondition::File(trigger) => {
+                let file = context.files.iter().find(|file| file.path == trigger.path);
+                if trigger.is_met(file, context.now) {
+                    Readiness::Ready
+                } else {
+                    Readiness::Waiting
+                }
+            }
+            TimerCondition::MediaEnds(target) => {
+                if context
+                    .media
+                    .is_some_and(|players| target.has_ended(players))
+                {
+                    Readiness::Ready
+                } else {
+                    Readiness::Waiting
+                }
+            }
+            TimerCondition::OnAcPower if deadline_passed => {
+                if context.power.is_some_and(|power| power.on_battery) {
+                    Readiness::Skipped
+                } else {
+                    Readiness::Ready
+                }
+            }
+            TimerCondition::None | TimerCondition::OnAcPower => Readiness::Waiting,
+        }
+    }
+
+    /// Gets the time at which [`TimerCondition::evaluate`] may change its answer by itself.
+    ///
+    /// Deadlines and "stops changing" windows run out with time alone. Every
+    /// other trigger waits on power, activity, file or media updates, which are
+    /// evaluated as they come in.
+    ///
+    /// # Arguments
+    ///
+    /// - `ends_at` - The timer's deadline
+    /// - `files` - Latest observations of watched paths
+    ///
+    /// # Returns
+    ///
+    /// The Unix timestamp to check the timer again at, or `None` to wait for an update.
+    #[must_use]
+    pub fn wakes_at(&self, ends_at: i64, files: &[WatchedFile]) -> Option<i64> {
+        match self {
+            TimerCondition::None | TimerCondition::OnAcPower => Some(ends_at),
+            TimerCondition::File(FileTrigger {
+                path,
+                event: FileEvent::StopsChanging(seconds),
+            }) => files
+                .iter()
+                .find(|file| &file.path == path && file.exists)
+                .map(|file| file.last_changed + i64::from(*seconds)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimerCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerCondition::None => Ok(()),
+            TimerCondition::BatteryBelow(percent) => write!(f, "battery-below:{percent}"),
+            TimerCondition::OnAcPower => write!(f, "on-ac"),
+            TimerCondition::SystemQuiet(thresholds) => write!(
+                f,
+                "quiet:{}:{}:{}:{}",
+                thresholds.max_load_percent,
+                thresholds.max_disk_kib_per_sec,
+                thresholds.max_net_kib_per_sec,
+                thresholds.window_secs
+            ),
+            TimerCondition::File(trigger) => {
+                let path = trigger.path.display();
+                match trigger.event {
+                    FileEvent::Appears => write!(f, "file-appears:{path}"),
+                    FileEvent::Disappears => write!(f, "file-disappears:{path}"),
+                    FileEvent::StopsChanging(seconds) => write!(f, "file-stable:{seconds}:{path}"),
+                }
+            }
+            TimerCondition::MediaEnds(target) => {
+                write!(f, "media-ends:{}:{}", target.player, target.track)
+            }
+        }
+    }
+}
+
+impl FromStr for TimerCondition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            None if s.is_empty() => Ok(TimerCondition::None),
+            None if s == "on-ac" => Ok(TimerCondition::OnAcPower),
+            Some(("battery-below", percent)) => {
+                Ok(TimerCondition::BatteryBelow(percent.parse().map_err(
+                    |e| anyhow!("Invalid battery percentage '{percent}': {e}"),
+                )?))
+            }
+            Some(("quiet", thresholds)) => {
+                let invalid = || anyhow!("Invalid quiet thresholds '{thresholds}'");
+                let values: Vec<u32> = thresholds
+                    .split(':')
+                    .map(str::parse)
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|_| invalid())?;
+                let [load, disk, net, window] = values[..] else {
+                    return Err(invalid());
+                };
+
+                Ok(TimerCondition::SystemQuiet(QuietThresholds {
+                    max_load_percent: u16::try_from(load).map_err(|_| invalid())?,
+                    max_disk_kib_per_sec: disk,
+                    max_net_kib_per_sec: net,
+                    window_secs: window,
+                }))
+            }
+            Some(("file-appears", path)) => Ok(TimerCondition::File(FileTrigger {
+                path: path.into(),
+                event: FileEvent::Appears,
+            })),
+            Some(("file-disappears", path)) => Ok(TimerCondition::File(FileTrigger {
+                path: path.into(),
+                event: FileEvent::Disappears,
+            })),
+            Some(("file-stable", rest)) => {
+                // Paths may contain colons themselves, so only split off the seconds
+                let (seconds, path) = rest
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("Missing path in file condition '{s}'"))?;
+                Ok(TimerCondition::File(FileTrigger {
+                    path: path.into(),
+                    event: FileEvent::StopsChanging(
+                        seconds
+                            .parse()
+                            .map_err(|e| anyhow!("Invalid seconds '{seconds}': {e}"))?,
+                    ),
+                }))
+            }
+            Some(("media-ends", rest)) => {
+                // Bus names never contain colons, track ids might
+                let (player, track) = rest
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("Missing track in media condition '{s}'"))?;
+                Ok(TimerCondition::MediaEnds(MediaTrack {
+                    player: player.to_string(),
+                    track: track.to_string(),
+                }))
+            }
+            _ => Err(anyhow!("Unknown timer condition '{s}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(now: i64, power: Option<PowerState>) -> TriggerContext<'static> {
+        TriggerContext {
+            now,
+            power,
+            activity: &[],
+            files: &[],
+            media: None,
+        }
+    }
+
+    fn sample(at: i64, load_percent: f64, net_bytes_per_sec: f64) -> ActivitySample {
+        ActivitySample {
+            at,
+            load_percent,
+            disk_bytes_per_sec: 0.0,
+            net_bytes_per_sec,
+        }
+    }
+
+    const ON_BATTERY_LOW: PowerState = PowerState {
+        on_battery: true,
+        battery_percentage: Some(5.0),
+    };
+
+    const ON_AC: PowerState = PowerState {
+        on_battery: false,
+        battery_percentage: Some(50.0),
+    };
+
+    #[test]
+    fn test_condition_round_trip() {
+        for condition in [
+            TimerCondition::None,
+            TimerCondition::BatteryBelow(10),
+            TimerCondition::OnAcPower,
+            TimerCondition::SystemQuiet(QuietThresholds::default()),
+            TimerCondition::File(FileTrigger {
+                path: "/tmp/done.flag".into(),
+                event: FileEvent::Appears,
+            }),
+            TimerCondition::File(FileTrigger {
+                path: "/tmp/lock".into(),
+                event: FileEvent::Disappears,
+            }),
+            TimerCondition::File(FileTrigger {
+                path: "/mnt/c:/weird path.mkv".into(),
+                event: FileEvent::StopsChanging(45),
+            }),
+            TimerCondition::MediaEnds(MediaTrack {
+                player: "org.mpris.MediaPlayer2.vlc".to_string(),
+                track: "/org/videolan/vlc/playlist/7".to_string(),
+            }),
+        ] {
+            let parsed = TimerCondition::from_str(&condition.to_string()).unwrap();
+            assert_eq!(parsed, condition);
+        }
+        assert!(TimerCondition::from_str("battery-below:lots").is_err());
+        assert!(TimerCondition::from_str("potato").is_err());
+        assert!(TimerCondition::from_str("quiet:25:1024:128").is_err());
+        assert!(TimerCondition::from_str("quiet:99999:1:1:1").is_err());
+        assert!(TimerCondition::from_str("file-stable:/no/seconds").is_err());
+        assert!(TimerCondition::from_str("media-ends:no-track").is_err());
+    }
+
+    #[test]
+    fn test_plain_timer_waits_for_deadline() {
+        let condition = TimerCondition::None;
+        assert_eq!(
+            condition.evaluate(100, &context(99, None)),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            condition.evaluate(100, &context(100, None)),
+            Readiness::Ready
+        );
+    }
+
+    #[test]
+    fn test_battery_trigger_ignores_deadline() {
+        let condition = TimerCondition::BatteryBelow(10);
+        assert_eq!(
+            condition.evaluate(i64::MAX, &context(0, Some(ON_BATTERY_LOW))),
+            Readiness::Ready
+        );
+        assert_eq!(
+            condition.evaluate(i64::MAX, &context(0, Some(ON_AC))),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            condition.evaluate(i64::MAX, &context(0, None)),
+            Readiness::Waiting
+        );
+    }
+
+    #[test]
+    fn test_ac_guard_skips_on_battery() {
+        let condition = TimerCondition::OnAcPower;
+        assert_eq!(
+            condition.evaluate(100, &context(50, Some(ON_AC))),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            condition.evaluate(100, &context(100, Some(ON_AC))),
+            Readiness::Ready
+        );
+        assert_eq!(
+            condition.evaluate(100, &context(100, Some(ON_BATTERY_LOW))),
+            Readiness::Skipped
+        );
+        // No UPower means no battery, so the action runs
+        assert_eq!(
+            condition.evaluate(100, &context(100, None)),
+            Readiness::Ready
+        );
+    }
+
+    #[test]
+    fn test_quiet_trigger_needs_a_full_quiet_window() {
+        let condition = TimerCondition::SystemQuiet(QuietThresholds {
+            window_secs: 60,
+            ..QuietThresholds::default()
+        });
+        // A download finishing at t=30, quiet afterwards
+        let history: Vec<ActivitySample> = (0..=12)
+            .map(|i| {
+                let at = i * 10;
+                let net = if at <= 30 { 5_000_000.0 } else { 1_000.0 };
+                sample(at, 5.0, net)
+            })
+            .collect();
+        let at = |now: i64| TriggerContext {
+            now,
+            power: None,
+            activity: &history[..=usize::try_from(now / 10).unwrap()],
+            files: &[],
+            media: None,
+        };
+
+        assert_eq!(condition.evaluate(i64::MAX, &at(40)), Readiness::Waiting);
+        assert_eq!(condition.evaluate(i64::MAX, &at(80)), Readiness::Waiting);
+        assert_eq!(condition.evaluate(i64::MAX, &at(100)), Readiness::Ready);
+
+        // Not enough history yet to cover the window
+        let quiet_start = [sample(100, 1.0, 0.0), sample(110, 1.0, 0.0)];
+        let context = TriggerContext {
+            now: 110,
+            power: None,
+            activity: &quiet_start,
+            files: &[],
+            media: None,
+        };
+        assert_eq!(condition.evaluate(i64::MAX, &context), Readiness::Waiting);
+    }
+
+    #[test]
+    fn test_shortest_quiet_window_fires() {
+        let condition = TimerCondition::SystemQuiet(QuietThresholds {
+            window_secs: QuietThresholds::MIN_WINDOW_SECS,
+            ..QuietThresholds::default()
+        });
+        let history = [
+            sample(100, 1.0, 0.0),
+            sample(110, 1.0, 0.0),
+            sample(120, 1.0, 0.0),
+            sample(130, 1.0, 0.0),
+        ];
+        let context = TriggerContext {
+            now: 135,
+            power: None,
+            activity: &history,
+            files: &[],
+            media: None,
+        };
+        assert_eq!(condition.evaluate(i64::MAX, &context), Readiness::Ready);
+    }
+
+    #[test]
+    fn test_file_triggers() {
+        let watched = |exists, last_changed| WatchedFile {
+            path: "/renders/output.mkv".into(),
+            exists,
+            size: 0,
+            modified: 0,
+            last_changed,
+        };
+        let condition = |event| {
+            TimerCondition::File(FileTrigger {
+                path: "/renders/output.mkv".into(),
+                event,
+            })
+        };
+        let evaluate = |event, files: &[WatchedFile]| {
+            condition(event).evaluate(
+                i64::MAX,
+                &TriggerContext {
+                    now: 100,
+                    power: None,
+                    activity: &[],
+                    files,
+                    media: None,
+                },
+            )
+        };
+
+        // Unobserved paths never fire
+        assert_eq!(evaluate(FileEvent::Disappears, &[]), Readiness::Waiting);
+
+        assert_eq!(
+            evaluate(FileEvent::Appears, &[watched(true, 90)]),
+            Readiness::Ready
+        );
+        assert_eq!(
+            evaluate(FileEvent::Disappears, &[watched(true, 90)]),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            evaluate(FileEvent::Disappears, &[watched(false, 90)]),
+            Readiness::Ready
+        );
+        // Still growing ten seconds ago
+        assert_eq!(
+            evaluate(FileEvent::StopsChanging(30), &[watched(true, 90)]),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            evaluate(FileEvent::StopsChanging(30), &[watched(true, 70)]),
+            Readiness::Ready
+        );
+    }
+
+    #[test]
+    fn test_wakes_at_only_when_time_alone_decides() {
+        let output = WatchedFile {
+            path: "/renders/output.mkv".into(),
+            exists: true,
+            size: 0,
+            modified: 0,
+            last_changed: 90,
+        };
+        let stops_changing = TimerCondition::File(FileTrigger {
+            path: "/renders/output.mkv".into(),
+            event: FileEvent::StopsChanging(30),
+        });
+
+        assert_eq!(TimerCondition::None.wakes_at(500, &[]), Some(500));
+        assert_eq!(TimerCondition::OnAcPower.wakes_at(500, &[]), Some(500));
+        assert_eq!(
+            stops_changing.wakes_at(i64::MAX, std::slice::from_ref(&output)),
+            Some(120)
+        );
+        // Nothing to count from until the file shows up
+        assert_eq!(stops_changing.wakes_at(i64::MAX, &[]), None);
+        // Power, activity and media updates are evaluated as they arrive
+        assert_eq!(
+            TimerCondition::BatteryBelow(10).wakes_at(i64::MAX, &[output]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_media_trigger_waits_for_track_to_end() {
+        let condition = TimerCondition::MediaEnds(MediaTrack {
+            player: "org.mpris.MediaPlayer2.podcasts".to_string(),
+            track: "/episode/42".to_string(),
+        });
+        let player = |status, track: &str| PlayerState {
+            bus_name: "org.mpris.MediaPlayer2.podcasts".to_string(),
+            status,
+            track: track.to_string(),
+            position_us: 0,
+            length_us: None,
+        };
+        let paused_at = |position_us| PlayerState {
+            position_us,
+            length_us: Some(1_800_000_000),
+            ..player(PlaybackStatus::Paused, "/episode/42")
+        };
+        let evaluate = |players: Option<&[PlayerState]>| {
+            condition.evaluate(
+                i64::MAX,
+                &TriggerContext {
+                    media: players,
+                    ..context(0, None)
+                },
+            )
+        };
+
+        // Players not read yet
+        assert_eq!(evaluate(None), Readiness::Waiting);
+        assert_eq!(
+            evaluate(Some(&[player(PlaybackStatus::Playing, "/episode/42")])),
+            Readiness::Waiting
+        );
+        // Pausing mid-episode keeps the timer waiting
+        assert_eq!(
+            evaluate(Some(&[player(PlaybackStatus::Paused, "/episode/42")])),
+            Readiness::Waiting
+        );
+        assert_eq!(
+            evaluate(Some(&[paused_at(900_000_000)])),
+            Readiness::Waiting
+        );
+        // The player paused at the end of the episode
+        assert_eq!(
+            evaluate(Some(&[paused_at(1_799_500_000)])),
+            Readiness::Ready
+        );
+        // Autoplay moved on to the next episode
+        assert_eq!(
+            evaluate(Some(&[player(PlaybackStatus::Playing, "/episode/43")])),
+            Readiness::Ready
+        );
+        assert_eq!(
+            evaluate(Some(&[player(PlaybackStatus::Stopped, "/episode/42")])),
+            Readiness::Ready
+        );
+        // Player was closed
+        assert_eq!(evaluate(Some(&[])), Readiness::Ready);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{
+    models::Timer,
+    utils::database::repository::{
+        Page, Repository, RepositoryError, RepositoryResult, push_ids, transaction,
+    },
+};
+use anyhow::{Result, anyhow};
+use sqlx::{FromRow, QueryBuilder, Sqlite, sqlite::SqlitePool};
+use std::str::FromStr;
+
+/// How many entries the history keeps, older entries are pruned on insert.
+pub const HISTORY_LIMIT: i64 = 100;
+
+/// A timer that is no longer running, kept for the history page.
+///
+/// `description` is the timer's description at the time, so power timers keep
+/// their `TimerType` string and user-defined timers their notification text.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::history::{HistoryEntry, Outcome};
+/// use chronomancer::models::timer::{Timer, TimerType};
+///
+/// let timer = Timer::new(60, false, &TimerType::Suspend, 1_700_000_000);
+/// let entry = HistoryEntry::new(&timer, Outcome::Cancelled, 1_700_000_000);
+///
+/// assert_eq!(entry.description, "System Suspend");
+/// assert_eq!(entry.outcome(), Outcome::Cancelled);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub description: String,
+    pub outcome: String,
+    pub finished_at: i64, // Unix timestamp
+}
+
+/// How a timer left the active list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The timer ran its action or sent its notification
+    Finished,
+    /// The user cancelled the timer (or the chain it belonged to)
+    Cancelled,
+    /// An "on AC power" timer ended while on battery
+    Skipped,
+    /// The timer ended while the applet wasn't running and was dropped
+    Missed,
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Finished => "finished",
+            Outcome::Cancelled => "cancelled",
+            Outcome::Skipped => "skipped",
+            Outcome::Missed => "missed",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "finished" => Ok(Outcome::Finished),
+            "cancelled" => Ok(Outcome::Cancelled),
+            "skipped" => Ok(Outcome::Skipped),
+            "missed" => Ok(Outcome::Missed),
+            other => Err(anyhow!("Unknown history outcome: {other}")),
+        }
+    }
+}
+
+/// Narrows down [`Repository::list`] for history entries.
+///
+/// Fields left at `None` let every entry through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    /// Only entries with this outcome
+    pub outcome: Option<Outcome>,
+    /// Only entries finished at or after this Unix timestamp
+    pub finished_from: Option<i64>,
+    /// Only entries finished before this Unix timestamp
+    pub finished_until: Option<i64>,
+}
+
+impl HistoryEntry {
+    /// Creates an unsaved entry for a timer.
+    ///
+    /// # Arguments
+    ///
+    /// - `timer` - The timer that stopped running
+    /// - `outcome` - How it stopped
+    /// - `finished_at` - Unix timestamp it stopped at
+    #[must_use]
+    pub fn new(timer: &Timer, outcome: Outcome, finished_at: i64) -> Self {
+        Self {
+            id: 0,
+            description: timer.description.clone(),
+            outcome: outcome.as_str().to_string(),
+            finished_at,
+        }
+    }
+
+    /// Gets the entry's outcome, treating unknown values as finished.
+    #[must_use]
+    pub fn outcome(&self) -> Outcome {
+        Outcome::from_str(&self.outcome).unwrap_or(Outcome::Finished)
+    }
+
+    /// Deletes every entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete query fails.
+    pub async fn clear(pool: &SqlitePool) -> Result<()> {
+        sqlx::query("DELETE FROM timer_history")
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+impl Repository<HistoryEntry> for HistoryEntry {
+    type Filter = HistoryFilter;
+
+    /// Stores the entry and prunes the history down to [`HISTORY_LIMIT`] entries.
+    async fn insert(pool: &SqlitePool, item: &HistoryEntry) -> RepositoryResult<HistoryEntry> {
+        let id = transaction(pool, async |connection| {
+            let result = sqlx::query(
+                "INSERT INTO timer_history (description, outcome, finished_at) VALUES (?, ?, ?)",
+            )
+            .bind(&item.description)
+            .bind(&item.outcome)
+            .bind(item.finished_at)
+            .execute(&mut *connection)
+            .await?;
+            sqlx::query(
+                "DELETE FROM timer_history WHERE id NOT IN (
+                    SELECT id FROM timer_history ORDER BY finished_at DESC, id DESC LIMIT ?
+                )",
+            )
+            .bind(HISTORY_LIMIT)
+            .execute(&mut *connection)
+            .await?;
+            Ok(result.last_insert_rowid())
+        })
+        .await?;
+
+        HistoryEntry::get_by_id(pool, &id)
+            .await?
+            .ok_or(RepositoryError::NotFound {
+                kind: "history entry",
+                id,
+            })
+    }
+
+    async fn update(pool: &SqlitePool, item: &HistoryEntry) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            "UPDATE timer_history SET description = ?, outcome = ?, finished_at = ? WHERE id = ?",
+        )
+        .bind(&item.description)
+        .bind(&item.outcome)
+        .bind(item.finished_at)
+        .bind(item.id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound {
+                kind: "history entry",
+                id: item.id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Every stored entry, newest first.
+    async fn get_all_active(pool: &SqlitePool, _now: i64) -> RepositoryResult<Vec<HistoryEntry>> {
+        let entries = sqlx::query_as::<_, HistoryEntry>(
+            "SELECT * FROM timer_history ORDER BY finished_at DESC, id DESC",
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(entries)
+    }
+
+    async fn get_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<Option<HistoryEntry>> {
+        let entry = sqlx::query_as::<_, HistoryEntry>("SELECT * FROM timer_history WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(entry)
+    }
+
+    /// Entries newest first, like [`Repository::get_all_active`].
+    async fn list(
+        pool: &SqlitePool,
+        filter: &HistoryFilter,
+        page: Page,
+    ) -> RepositoryResult<Vec<HistoryEntry>> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM timer_history WHERE 1 = 1");
+        if let Some(outcome) = filter.outcome {
+            query.push(" AND outcome = ").push_bind(outcome.as_str());
+        }
+        if let Some(from) = filter.finished_from {
+            query.push(" AND finished_at >= ").push_bind(from);
+        }
+        if let Some(until) = filter.finished_until {
+            query.push(" AND finished_at < ").push_bind(until);
+        }
+        query
+            .push(" ORDER BY finished_at DESC, id DESC LIMIT ")
+            .push_bind(page.limit)
+            .push(" OFFSET ")
+            .push_bind(page.offset);
+
+        let entries = query
+            .build_query_as::<HistoryEntry>()
+            .fetch_all(pool)
+            .await?;
+        Ok(entries)
+    }
+
+    async fn delete_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM timer_history WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_many(pool: &SqlitePool, ids: &[i64]) -> RepositoryResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut query = QueryBuilder::<Sqlite>::new("DELETE FROM timer_history WHERE id IN ");
+        push_ids(&mut query, ids);
+        Ok(query.build().execute(pool).await?.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timer::TimerType;
+
+    #[tokio::test]
+    async fn test_history_keeps_newest_entries() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let timer = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 0);
+        for finished_at in 0..=HISTORY_LIMIT {
+            HistoryEntry::insert(
+                &pool,
+                &HistoryEntry::new(&timer, Outcome::Finished, finished_at),
+            )
+            .await?;
+        }
+        let cancelled = HistoryEntry::insert(
+            &pool,
+            &HistoryEntry::new(&timer, Outcome::Cancelled, HISTORY_LIMIT + 1),
+        )
+        .await?;
+
+        let entries = HistoryEntry::get_all_active(&pool, 0).await?;
+        assert_eq!(i64::try_from(entries.len())?, HISTORY_LIMIT);
+        assert_eq!(entries[0], cancelled);
+        assert_eq!(entries[0].outcome(), Outcome::Cancelled);
+        // The oldest entries were pruned
+        assert_eq!(entries.last().map(|e| e.finished_at), Some(2));
+
+        HistoryEntry::clear(&pool).await?;
+        assert!(HistoryEntry::get_all_active(&pool, 0).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_history_list_and_update() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let timer = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 0);
+        let mut entries = Vec::new();
+        for (outcome, finished_at) in [
+            (Outcome::Finished, 100),
+            (Outcome::Cancelled, 200),
+            (Outcome::Finished, 300),
+        ] {
+            entries.push(
+                HistoryEntry::insert(&pool, &HistoryEntry::new(&timer, outcome, finished_at))
+                    .await?,
+            );
+        }
+
+        let finished = HistoryFilter {
+            outcome: Some(Outcome::Finished),
+            ..HistoryFilter::default()
+        };
+        let listed = HistoryEntry::list(&pool, &finished, Page::first(1)).await?;
+        assert_eq!(listed, [entries[2].clone()]);
+        let listed = HistoryEntry::list(&pool, &finished, Page::first(1).next()).await?;
+        assert_eq!(listed, [entries[0].clone()]);
+
+        let window = HistoryFilter {
+            finished_from: Some(200),
+            finished_until: Some(300),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(
+            HistoryEntry::list(&pool, &window, Page::ALL).await?,
+            [entries[1].clone()]
+        );
+
+        let mut renamed = entries[1].clone();
+        renamed.description = "Green tea".to_string();
+        HistoryEntry::update(&pool, &renamed).await?;
+        assert_eq!(
+            HistoryEntry::get_by_id(&pool, &renamed.id).await?,
+            Some(renamed)
+        );
+
+        assert_eq!(
+            HistoryEntry::delete_many(&pool, &[entries[0].id, entries[2].id]).await?,
+            2
+        );
+        assert_eq!(HistoryEntry::get_all_active(&pool, 0).await?.len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{Result, anyhow};
+use chrono::NaiveDateTime;
+
+use crate::utils::time_zone;
+
+/// Format of the wall clock time in a serialized [`TimerIntent::WallClock`].
+const WALL_CLOCK_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// What the user asked for when setting a timer: a countdown, or a time of day.
+///
+/// Both end at `ends_at`, but they move differently when the clock does:
+/// - **Relative** timers ("in 20 minutes") count elapsed time. When the wall
+///   clock is corrected, e.g. by NTP, their deadline moves with it so the
+///   countdown keeps its length.
+/// - **Wall clock** timers ("at 08:00") keep their local time. When the time
+///   zone changes, their deadline is resolved again in the new zone, so a
+///   reminder still goes off at 08:00 after flying somewhere else.
+///
+/// Intents are persisted as short strings in the `intent` column, like
+/// [`TimerCondition`](super::condition::TimerCondition) in `condition`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::intent::TimerIntent;
+/// use chrono::NaiveDate;
+/// use std::str::FromStr;
+///
+/// let at = NaiveDate::from_ymd_opt(2026, 10, 19)
+///     .and_then(|date| date.and_hms_opt(8, 0, 0))
+///     .unwrap();
+/// let intent = TimerIntent::WallClock {
+///     at,
+///     time_zone: "Europe/Berlin".to_string(),
+/// };
+/// assert_eq!(intent.to_string(), "wall-clock:2026-10-19T08:00:00@Europe/Berlin");
+/// assert_eq!(TimerIntent::from_str(&intent.to_string()).unwrap(), intent);
+/// assert_eq!(TimerIntent::from_str("").unwrap(), TimerIntent::Relative);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TimerIntent {
+    /// Counts down from when it was set
+    #[default]
+    Relative,
+    /// Ends when the clocks of a time zone show a given local time
+    WallClock {
+        /// The local time to end at
+        at: NaiveDateTime,
+        /// IANA name of the zone the deadline was last resolved in
+        time_zone: String,
+    },
+}
+
+impl TimerIntent {
+    /// Gets the deadline of a wall clock intent in a time zone.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_zone` - IANA name of the zone to resolve the local time in
+    ///
+    /// # Returns
+    ///
+    /// The Unix timestamp, or `None` for relative intents and unknown zones.
+    #[must_use]
+    pub fn resolve(&self, time_zone: &str) -> Option<i64> {
+        match self {
+            TimerIntent::Relative => None,
+            TimerIntent::WallClock { at, .. } => time_zone::resolve(*at, time_zone),
+        }
+    }
+}
+
+impl fmt::Display for TimerIntent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerIntent::Relative => Ok(()),
+            TimerIntent::WallClock { at, time_zone } => {
+                write!(f, "wall-clock:{}@{time_zone}", at.format(WALL_CLOCK_FORMAT))
+            }
+        }
+    }
+}
+
+impl FromStr for TimerIntent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            None if s.is_empty() => Ok(TimerIntent::Relative),
+            Some(("wall-clock", rest)) => {
+                let (at, time_zone) = rest
+                    .split_once('@')
+                    .ok_or_else(|| anyhow!("Wall clock intent '{rest}' has no time zone"))?;
+                let at = NaiveDateTime::parse_from_str(at, WALL_CLOCK_FORMAT)
+                    .map_err(|e| anyhow!("Invalid wall clock time '{at}': {e}"))?;
+                Ok(TimerIntent::WallClock {
+                    at,
+                    time_zone: time_zone.to_string(),
+                })
+            }
+            _ => Err(anyhow!("Unknown timer intent '{s}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intent_round_trip() {
+        let intent =
+            TimerIntent::from_str("wall-clock:2026-07-15T08:00:00@America/New_York").unwrap();
+        assert_eq!(
+            intent.to_string(),
+            "wall-clock:2026-07-15T08:00:00@America/New_York"
+        );
+        assert_eq!(intent.resolve("America/New_York"), Some(1_784_116_800));
+        // The same local time, a few hours earlier further east
+        assert_eq!(intent.resolve("Europe/Berlin"), Some(1_784_095_200));
+
+        assert_eq!(TimerIntent::Relative.to_string(), "");
+        assert_eq!(TimerIntent::Relative.resolve("UTC"), None);
+        assert!(TimerIntent::from_str("wall-clock:2026-07-15T08:00:00").is_err());
+        assert!(TimerIntent::from_str("wall-clock:tomorrow@UTC").is_err());
+        assert!(TimerIntent::from_str("whenever").is_err());
+    }
+}
@@ -1,3 +1,11 @@
+pub mod condition;
+pub mod history;
+pub mod intent;
+pub mod sequence;
+pub mod stopwatch;
 pub mod timer;
 
+pub use history::HistoryEntry;
+pub use sequence::Sequence;
+pub use stopwatch::Stopwatch;
 pub use timer::Timer;
@@ -0,0 +1,488 @@
+use crate::utils::{
+    clock::ClockChange,
+    database::repository::{
+        Page, Repository, RepositoryError, RepositoryResult, push_ids, transaction,
+    },
+};
+use anyhow::Result;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqliteConnection, sqlite::SqlitePool};
+
+/// A timer made of named phases that advance automatically, e.g. a pomodoro.
+///
+/// Phases run in order once per round. Phases marked `is_final` are left out of
+/// the rounds and run once after the last round, which is where a pomodoro's long
+/// break goes. The flattened run order is called the sequence's *steps*; only the
+/// current step and its deadline are stored, so a sequence survives restarts just
+/// like a [`Timer`](super::Timer).
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::sequence::{Phase, Sequence};
+///
+/// let pomodoro = Sequence::new(
+///     "Pomodoro",
+///     4,
+///     vec![
+///         Phase::new("Work", 25 * 60, false),
+///         Phase::new("Break", 5 * 60, false),
+///         Phase::new("Long break", 15 * 60, true),
+///     ],
+///     1_700_000_000,
+/// );
+///
+/// // Work and break four times, then the long break
+/// assert_eq!(pomodoro.steps().len(), 9);
+/// assert_eq!(pomodoro.current_phase().map(|p| p.name.as_str()), Some("Work"));
+/// ```
+#[derive(Debug, Clone, FromRow)]
+pub struct Sequence {
+    pub id: i64,
+    pub name: String,
+    pub rounds: i64,
+    pub current_step: i64,
+    pub step_ends_at: i64, // Unix timestamp
+    pub paused_at: i64,    // Unix timestamp, 0 while running
+    pub created_at: i64,   // Unix timestamp
+    #[sqlx(skip)]
+    pub phases: Vec<Phase>,
+}
+
+/// A single named phase of a [`Sequence`].
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct Phase {
+    pub id: i64,
+    pub sequence_id: i64,
+    pub position: i64,
+    pub name: String,
+    pub duration_seconds: i64,
+    pub is_final: bool,
+}
+
+impl Phase {
+    #[must_use]
+    pub fn new(name: &str, duration_seconds: i64, is_final: bool) -> Self {
+        Self {
+            id: 0,
+            sequence_id: 0,
+            position: 0,
+            name: name.to_string(),
+            duration_seconds,
+            is_final,
+        }
+    }
+}
+
+impl Sequence {
+    /// Creates a sequence that starts right away with its first step.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - Display name of the sequence
+    /// - `rounds` - How often the non-final phases repeat (at least once)
+    /// - `phases` - Phases in order; their positions are assigned here
+    /// - `now` - Current Unix timestamp in seconds
+    #[must_use]
+    pub fn new(name: &str, rounds: u32, phases: Vec<Phase>, now: i64) -> Self {
+        let phases = phases
+            .into_iter()
+            .zip(0..)
+            .map(|(phase, position)| Phase { position, ..phase })
+            .collect();
+
+        let mut sequence = Self {
+            id: 0,
+            name: name.to_string(),
+            rounds: i64::from(rounds.max(1)),
+            current_step: 0,
+            step_ends_at: now,
+            paused_at: 0,
+            created_at: now,
+            phases,
+        };
+        sequence.step_ends_at = now + sequence.current_phase().map_or(0, |p| p.duration_seconds);
+        sequence
+    }
+
+    /// Gets the phases in the order they run, with rounds expanded.
+    #[must_use]
+    pub fn steps(&self) -> Vec<&Phase> {
+        let round: Vec<&Phase> = self.phases.iter().filter(|p| !p.is_final).collect();
+        let rounds = usize::try_from(self.rounds).unwrap_or(1);
+
+        round
+            .iter()
+            .copied()
+            .cycle()
+            .take(round.len() * rounds)
+            .chain(self.phases.iter().filter(|p| p.is_final))
+            .collect()
+    }
+
+    /// Gets the phase that is currently running, `None` once the sequence is done.
+    #[must_use]
+    pub fn current_phase(&self) -> Option<&Phase> {
+        let step = usize::try_from(self.current_step).ok()?;
+        self.steps().get(step).copied()
+    }
+
+    /// Gets the round the current step belongs to, `None` for final phases.
+    ///
+    /// # Returns
+    ///
+    /// The current round starting at 1 and the total number of rounds.
+    #[must_use]
+    pub fn round(&self) -> Option<(i64, i64)> {
+        let per_round = self.phases.iter().filter(|p| !p.is_final).count();
+        let per_round = i64::try_from(per_round).ok().filter(|n| *n > 0)?;
+        let round = self.current_step / per_round + 1;
+        (round <= self.rounds).then_some((round, self.rounds))
+    }
+
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused_at > 0
+    }
+
+    /// Whether the current step has run out and the sequence should advance.
+    #[must_use]
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.is_paused() && now >= self.step_ends_at
+    }
+
+    /// Seconds left in the current step, frozen while paused.
+    #[must_use]
+    pub fn remaining(&self, now: i64) -> i64 {
+        let reference = if self.is_paused() {
+            self.paused_at
+        } else {
+            now
+        };
+        (self.step_ends_at - reference).max(0)
+    }
+
+    /// Moves on to the next step, used both when a step runs out and when it is skipped.
+    ///
+    /// The next step is timed from `now`, so a late tick or a skip never
+    /// shortens it. Skipping while paused keeps the sequence paused at the
+    /// start of the next step.
+    ///
+    /// # Returns
+    ///
+    /// The phase that is now running, or `None` when the sequence is finished.
+    pub fn advance(&mut self, now: i64) -> Option<&Phase> {
+        self.current_step += 1;
+        let duration = self.current_phase()?.duration_seconds;
+
+        self.step_ends_at = now + duration;
+        if self.is_paused() {
+            self.paused_at = now;
+        }
+        self.current_phase()
+    }
+
+    /// Freezes the current step's countdown.
+    pub fn pause(&mut self, now: i64) {
+        if !self.is_paused() {
+            self.paused_at = now;
+        }
+    }
+
+    /// Continues the current step with the time it had left when paused.
+    pub fn resume(&mut self, now: i64) {
+        if self.is_paused() {
+            self.step_ends_at = now + self.remaining(now);
+            self.paused_at = 0;
+        }
+    }
+
+    /// Keeps the current step's time left when the wall clock jumps.
+    ///
+    /// Sequences always count down, see [`Timer::follow_clock`](super::Timer::follow_clock).
+    ///
+    /// # Returns
+    ///
+    /// Whether the sequence changed and needs [`Sequence::update_progress`].
+    pub fn follow_clock(&mut self, change: &ClockChange) -> bool {
+        if change.jumped_by == 0 {
+            return false;
+        }
+        self.step_ends_at += change.jumped_by;
+        if self.is_paused() {
+            self.paused_at += change.jumped_by;
+        }
+        self.created_at += change.jumped_by;
+        true
+    }
+
+    /// Saves the current step, its deadline and the pause state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update query fails.
+    pub async fn update_progress(pool: &SqlitePool, item: &Sequence) -> Result<()> {
+        sqlx::query(
+            "UPDATE sequences SET current_step = ?, step_ends_at = ?, paused_at = ? WHERE id = ?",
+        )
+        .bind(item.current_step)
+        .bind(item.step_ends_at)
+        .bind(item.paused_at)
+        .bind(item.id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the phases of a sequence fetched without them.
+    async fn with_phases(mut self, pool: &SqlitePool) -> RepositoryResult<Self> {
+        self.phases = sqlx::query_as::<_, Phase>(
+            "SELECT * FROM sequence_phases WHERE sequence_id = ? ORDER BY position ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await?;
+        Ok(self)
+    }
+
+    /// Stores the phases of a sequence, replacing whatever it had before.
+    async fn store_phases(
+        connection: &mut SqliteConnection,
+        sequence_id: i64,
+        phases: &[Phase],
+    ) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM sequence_phases WHERE sequence_id = ?")
+            .bind(sequence_id)
+            .execute(&mut *connection)
+            .await?;
+        for phase in phases {
+            sqlx::query(
+                "INSERT INTO sequence_phases (sequence_id, position, name, duration_seconds, is_final) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(sequence_id)
+            .bind(phase.position)
+            .bind(&phase.name)
+            .bind(phase.duration_seconds)
+            .bind(phase.is_final)
+            .execute(&mut *connection)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository<Sequence> for Sequence {
+    /// Sequences have nothing to filter by yet.
+    type Filter = ();
+
+    async fn insert(pool: &SqlitePool, item: &Sequence) -> RepositoryResult<Sequence> {
+        let sequence_id = transaction(pool, async |connection| {
+            let result = sqlx::query(
+                "INSERT INTO sequences (name, rounds, current_step, step_ends_at, paused_at, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&item.name)
+            .bind(item.rounds)
+            .bind(item.current_step)
+            .bind(item.step_ends_at)
+            .bind(item.paused_at)
+            .bind(item.created_at)
+            .execute(&mut *connection)
+            .await?;
+            let sequence_id = result.last_insert_rowid();
+            Sequence::store_phases(connection, sequence_id, &item.phases).await?;
+            Ok(sequence_id)
+        })
+        .await?;
+
+        Sequence::get_by_id(pool, &sequence_id)
+            .await?
+            .ok_or(RepositoryError::NotFound {
+                kind: "sequence",
+                id: sequence_id,
+            })
+    }
+
+    /// Stores the sequence and replaces its phases.
+    async fn update(pool: &SqlitePool, item: &Sequence) -> RepositoryResult<()> {
+        transaction(pool, async |connection| {
+            let result = sqlx::query(
+                "UPDATE sequences SET name = ?, rounds = ?, current_step = ?, step_ends_at = ?, paused_at = ?, created_at = ? WHERE id = ?",
+            )
+            .bind(&item.name)
+            .bind(item.rounds)
+            .bind(item.current_step)
+            .bind(item.step_ends_at)
+            .bind(item.paused_at)
+            .bind(item.created_at)
+            .bind(item.id)
+            .execute(&mut *connection)
+            .await?;
+            if result.rows_affected() == 0 {
+                return Err(RepositoryError::NotFound {
+                    kind: "sequence",
+                    id: item.id,
+                });
+            }
+            Sequence::store_phases(connection, item.id, &item.phases).await
+        })
+        .await
+    }
+
+    /// Sequences are deleted once they finish, so every stored sequence is active.
+    async fn get_all_active(pool: &SqlitePool, _now: i64) -> RepositoryResult<Vec<Sequence>> {
+        Sequence::list(pool, &(), Page::ALL).await
+    }
+
+    async fn get_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<Option<Sequence>> {
+        let sequence = sqlx::query_as::<_, Sequence>("SELECT * FROM sequences WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        match sequence {
+            Some(sequence) => Ok(Some(sequence.with_phases(pool).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sequences oldest first.
+    async fn list(pool: &SqlitePool, _filter: &(), page: Page) -> RepositoryResult<Vec<Sequence>> {
+        let sequences = sqlx::query_as::<_, Sequence>(
+            "SELECT * FROM sequences ORDER BY created_at ASC, id ASC LIMIT ? OFFSET ?",
+        )
+        .bind(page.limit)
+        .bind(page.offset)
+        .fetch_all(pool)
+        .await?;
+
+        let mut listed = Vec::with_capacity(sequences.len());
+        for sequence in sequences {
+            listed.push(sequence.with_phases(pool).await?);
+        }
+        Ok(listed)
+    }
+
+    async fn delete_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<()> {
+        Sequence::delete_many(pool, &[*id]).await.map(|_| ())
+    }
+
+    async fn delete_many(pool: &SqlitePool, ids: &[i64]) -> RepositoryResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        // Phases go with their sequence through ON DELETE CASCADE
+        let mut query = QueryBuilder::<Sqlite>::new("DELETE FROM sequences WHERE id IN ");
+        push_ids(&mut query, ids);
+        Ok(query.build().execute(pool).await?.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pomodoro() -> Sequence {
+        Sequence::new(
+            "Pomodoro",
+            2,
+            vec![
+                Phase::new("Work", 1500, false),
+                Phase::new("Long break", 900, true),
+                Phase::new("Break", 300, false),
+            ],
+            0,
+        )
+    }
+
+    #[test]
+    fn test_steps_repeat_rounds_then_final_phases() {
+        let sequence = pomodoro();
+        let names: Vec<&str> = sequence.steps().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Work", "Break", "Work", "Break", "Long break"]);
+        assert_eq!(sequence.round(), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_advance_pause_and_resume() {
+        let mut sequence = pomodoro();
+        sequence.step_ends_at = 1500;
+
+        assert!(!sequence.is_due(1499));
+        assert!(sequence.is_due(1500));
+        assert_eq!(
+            sequence.advance(1500).map(|p| p.name.clone()),
+            Some("Break".to_string())
+        );
+        assert_eq!(sequence.step_ends_at, 1800);
+
+        // Paused with two minutes left, the countdown stands still
+        sequence.pause(1680);
+        assert!(!sequence.is_due(5000));
+        assert_eq!(sequence.remaining(5000), 120);
+        sequence.resume(5000);
+        assert_eq!(sequence.step_ends_at, 5120);
+
+        // Skip through the second round into the long break
+        sequence.advance(5000);
+        assert_eq!(sequence.round(), Some((2, 2)));
+        sequence.advance(5000);
+        assert_eq!(
+            sequence.advance(5000).map(|p| p.name.clone()),
+            Some("Long break".to_string())
+        );
+        assert_eq!(sequence.round(), None);
+        assert!(sequence.advance(5900).is_none());
+    }
+
+    #[test]
+    fn test_follow_clock_keeps_time_left() {
+        let mut sequence = pomodoro();
+        sequence.step_ends_at = 1500;
+
+        let zone_only = ClockChange {
+            jumped_by: 0,
+            time_zone: Some("Asia/Tokyo".to_string()),
+        };
+        assert!(!sequence.follow_clock(&zone_only));
+
+        let jump = ClockChange {
+            jumped_by: 600,
+            time_zone: None,
+        };
+        assert!(sequence.follow_clock(&jump));
+        assert_eq!(sequence.remaining(600), 1500);
+
+        sequence.pause(700);
+        assert!(sequence.follow_clock(&jump));
+        assert_eq!(sequence.paused_at, 1300);
+        assert_eq!(sequence.remaining(5000), 1400);
+    }
+
+    #[tokio::test]
+    async fn test_sequence_crud() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let inserted = Sequence::insert(&pool, &pomodoro()).await?;
+        assert!(inserted.id > 0);
+        assert_eq!(inserted.phases.len(), 3);
+        assert_eq!(inserted.phases[2].name, "Break");
+
+        let mut running = inserted.clone();
+        running.advance(2_000);
+        running.pause(2_000);
+        Sequence::update_progress(&pool, &running).await?;
+
+        let active = Sequence::get_all_active(&pool, 2_000).await?;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].current_step, 1);
+        assert!(active[0].is_paused());
+
+        Sequence::delete_by_id(&pool, &inserted.id).await?;
+        assert!(Sequence::get_by_id(&pool, &inserted.id).await?.is_none());
+        let orphans: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sequence_phases")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(orphans, 0);
+        Ok(())
+    }
+}
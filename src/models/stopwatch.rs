@@ -0,0 +1,339 @@
+use crate::utils::database::repository::{
+    Page, Repository, RepositoryError, RepositoryResult, push_ids, transaction,
+};
+use anyhow::Result;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqliteConnection, sqlite::SqlitePool};
+
+/// A stopwatch with laps that keeps counting across applet restarts.
+///
+/// Nothing is stored per tick: while running, the elapsed time is the time
+/// banked in `elapsed_ms` plus the time since `started_at`. Stopping banks the
+/// running time and clears `started_at`. All times are Unix milliseconds so
+/// laps can be told apart at a glance.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::stopwatch::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::default();
+/// stopwatch.start(1_000);
+/// stopwatch.lap(31_000);
+/// stopwatch.stop(46_000);
+///
+/// assert_eq!(stopwatch.elapsed(90_000), 45_000);
+/// assert_eq!(stopwatch.split(0), Some(30_000));
+/// ```
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct Stopwatch {
+    pub id: i64,
+    pub started_at: i64, // Unix timestamp in milliseconds, 0 while stopped
+    pub elapsed_ms: i64, // Time banked before started_at
+    pub created_at: i64, // Unix timestamp
+    #[sqlx(skip)]
+    pub laps: Vec<Lap>,
+}
+
+/// A lap of a [`Stopwatch`], holding the total elapsed time when it was taken.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct Lap {
+    pub id: i64,
+    pub stopwatch_id: i64,
+    pub position: i64,
+    pub elapsed_ms: i64,
+}
+
+impl Stopwatch {
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.started_at > 0
+    }
+
+    /// Whether the stopwatch was never started or has been reset.
+    #[must_use]
+    pub fn is_reset(&self) -> bool {
+        !self.is_running() && self.elapsed_ms == 0
+    }
+
+    /// Total elapsed milliseconds at `now_ms`.
+    #[must_use]
+    pub fn elapsed(&self, now_ms: i64) -> i64 {
+        if self.is_running() {
+            self.elapsed_ms + (now_ms - self.started_at).max(0)
+        } else {
+            self.elapsed_ms
+        }
+    }
+
+    /// Starts or continues counting, does nothing if already running.
+    pub fn start(&mut self, now_ms: i64) {
+        if self.is_running() {
+            return;
+        }
+        if self.created_at == 0 {
+            self.created_at = now_ms / 1000;
+        }
+        self.started_at = now_ms;
+    }
+
+    /// Stops counting, keeping the elapsed time.
+    pub fn stop(&mut self, now_ms: i64) {
+        self.elapsed_ms = self.elapsed(now_ms);
+        self.started_at = 0;
+    }
+
+    /// Records a lap at the current elapsed time.
+    ///
+    /// # Returns
+    ///
+    /// The new lap, or `None` if the stopwatch is not running.
+    pub fn lap(&mut self, now_ms: i64) -> Option<&Lap> {
+        if !self.is_running() {
+            return None;
+        }
+        self.laps.push(Lap {
+            id: 0,
+            stopwatch_id: self.id,
+            position: i64::try_from(self.laps.len()).unwrap_or(i64::MAX),
+            elapsed_ms: self.elapsed(now_ms),
+        });
+        self.laps.last()
+    }
+
+    /// Gets the duration of a single lap, measured from the lap before it.
+    #[must_use]
+    pub fn split(&self, index: usize) -> Option<i64> {
+        let lap = self.laps.get(index)?;
+        let previous = index
+            .checked_sub(1)
+            .and_then(|i| self.laps.get(i))
+            .map_or(0, |lap| lap.elapsed_ms);
+        Some(lap.elapsed_ms - previous)
+    }
+
+    /// Saves whether the stopwatch runs and the time banked so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update query fails.
+    pub async fn update_state(pool: &SqlitePool, item: &Stopwatch) -> Result<()> {
+        sqlx::query("UPDATE stopwatches SET started_at = ?, elapsed_ms = ? WHERE id = ?")
+            .bind(item.started_at)
+            .bind(item.elapsed_ms)
+            .bind(item.id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Stores a lap of the stopwatch with the given id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the insert query fails.
+    pub async fn insert_lap(pool: &SqlitePool, stopwatch_id: i64, lap: &Lap) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO stopwatch_laps (stopwatch_id, position, elapsed_ms) VALUES (?, ?, ?)",
+        )
+        .bind(stopwatch_id)
+        .bind(lap.position)
+        .bind(lap.elapsed_ms)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the laps of a stopwatch fetched without them.
+    async fn with_laps(mut self, pool: &SqlitePool) -> RepositoryResult<Self> {
+        self.laps = sqlx::query_as::<_, Lap>(
+            "SELECT * FROM stopwatch_laps WHERE stopwatch_id = ? ORDER BY position ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await?;
+        Ok(self)
+    }
+
+    /// Stores the laps of a stopwatch, replacing whatever it had before.
+    async fn store_laps(
+        connection: &mut SqliteConnection,
+        stopwatch_id: i64,
+        laps: &[Lap],
+    ) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM stopwatch_laps WHERE stopwatch_id = ?")
+            .bind(stopwatch_id)
+            .execute(&mut *connection)
+            .await?;
+        for lap in laps {
+            sqlx::query(
+                "INSERT INTO stopwatch_laps (stopwatch_id, position, elapsed_ms) VALUES (?, ?, ?)",
+            )
+            .bind(stopwatch_id)
+            .bind(lap.position)
+            .bind(lap.elapsed_ms)
+            .execute(&mut *connection)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository<Stopwatch> for Stopwatch {
+    /// Stopwatches have nothing to filter by yet.
+    type Filter = ();
+
+    async fn insert(pool: &SqlitePool, item: &Stopwatch) -> RepositoryResult<Stopwatch> {
+        let stopwatch_id = transaction(pool, async |connection| {
+            let result = sqlx::query(
+                "INSERT INTO stopwatches (started_at, elapsed_ms, created_at) VALUES (?, ?, ?)",
+            )
+            .bind(item.started_at)
+            .bind(item.elapsed_ms)
+            .bind(item.created_at)
+            .execute(&mut *connection)
+            .await?;
+            let stopwatch_id = result.last_insert_rowid();
+            Stopwatch::store_laps(connection, stopwatch_id, &item.laps).await?;
+            Ok(stopwatch_id)
+        })
+        .await?;
+
+        Stopwatch::get_by_id(pool, &stopwatch_id)
+            .await?
+            .ok_or(RepositoryError::NotFound {
+                kind: "stopwatch",
+                id: stopwatch_id,
+            })
+    }
+
+    /// Stores the stopwatch and replaces its laps.
+    async fn update(pool: &SqlitePool, item: &Stopwatch) -> RepositoryResult<()> {
+        transaction(pool, async |connection| {
+            let result = sqlx::query(
+                "UPDATE stopwatches SET started_at = ?, elapsed_ms = ?, created_at = ? WHERE id = ?",
+            )
+            .bind(item.started_at)
+            .bind(item.elapsed_ms)
+            .bind(item.created_at)
+            .bind(item.id)
+            .execute(&mut *connection)
+            .await?;
+            if result.rows_affected() == 0 {
+                return Err(RepositoryError::NotFound {
+                    kind: "stopwatch",
+                    id: item.id,
+                });
+            }
+            Stopwatch::store_laps(connection, item.id, &item.laps).await
+        })
+        .await
+    }
+
+    /// Stopwatches are deleted on reset, so every stored stopwatch is active.
+    async fn get_all_active(pool: &SqlitePool, _now: i64) -> RepositoryResult<Vec<Stopwatch>> {
+        Stopwatch::list(pool, &(), Page::ALL).await
+    }
+
+    async fn get_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<Option<Stopwatch>> {
+        let stopwatch = sqlx::query_as::<_, Stopwatch>("SELECT * FROM stopwatches WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        match stopwatch {
+            Some(stopwatch) => Ok(Some(stopwatch.with_laps(pool).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Stopwatches oldest first.
+    async fn list(pool: &SqlitePool, _filter: &(), page: Page) -> RepositoryResult<Vec<Stopwatch>> {
+        let stopwatches = sqlx::query_as::<_, Stopwatch>(
+            "SELECT * FROM stopwatches ORDER BY created_at ASC, id ASC LIMIT ? OFFSET ?",
+        )
+        .bind(page.limit)
+        .bind(page.offset)
+        .fetch_all(pool)
+        .await?;
+
+        let mut listed = Vec::with_capacity(stopwatches.len());
+        for stopwatch in stopwatches {
+            listed.push(stopwatch.with_laps(pool).await?);
+        }
+        Ok(listed)
+    }
+
+    async fn delete_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<()> {
+        Stopwatch::delete_many(pool, &[*id]).await.map(|_| ())
+    }
+
+    async fn delete_many(pool: &SqlitePool, ids: &[i64]) -> RepositoryResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        // Laps go with their stopwatch through ON DELETE CASCADE
+        let mut query = QueryBuilder::<Sqlite>::new("DELETE FROM stopwatches WHERE id IN ");
+        push_ids(&mut query, ids);
+        Ok(query.build().execute(pool).await?.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_stop_and_laps() {
+        let mut stopwatch = Stopwatch::default();
+        assert!(stopwatch.is_reset());
+        assert!(stopwatch.lap(0).is_none());
+
+        stopwatch.start(10_000);
+        assert_eq!(stopwatch.elapsed(12_500), 2_500);
+        assert_eq!(stopwatch.lap(12_500).map(|l| l.elapsed_ms), Some(2_500));
+
+        // Stopped time doesn't count, starting again picks up where it left off
+        stopwatch.stop(15_000);
+        assert_eq!(stopwatch.elapsed(60_000), 5_000);
+        stopwatch.start(60_000);
+        stopwatch.start(61_000);
+        assert_eq!(stopwatch.lap(62_000).map(|l| l.elapsed_ms), Some(7_000));
+
+        assert_eq!(stopwatch.split(0), Some(2_500));
+        assert_eq!(stopwatch.split(1), Some(4_500));
+        assert_eq!(stopwatch.split(2), None);
+        assert_eq!(stopwatch.laps[1].position, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stopwatch_crud() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(1_000);
+        let inserted = Stopwatch::insert(&pool, &stopwatch).await?;
+        assert!(inserted.id > 0);
+        assert!(inserted.is_running());
+
+        let mut running = inserted.clone();
+        let lap = running.lap(31_000).cloned().unwrap();
+        Stopwatch::insert_lap(&pool, running.id, &lap).await?;
+        running.stop(46_000);
+        Stopwatch::update_state(&pool, &running).await?;
+
+        let active = Stopwatch::get_all_active(&pool, 100).await?;
+        assert_eq!(active.len(), 1);
+        assert!(!active[0].is_running());
+        assert_eq!(active[0].elapsed(100_000), 45_000);
+        assert_eq!(active[0].laps.len(), 1);
+
+        Stopwatch::delete_by_id(&pool, &inserted.id).await?;
+        assert!(Stopwatch::get_by_id(&pool, &inserted.id).await?.is_none());
+        let orphans: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM stopwatch_laps")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(orphans, 0);
+        Ok(())
+    }
+}
@@ -1,22 +1,42 @@
 use std::str::FromStr;
 
-use crate::utils::database::repository::Repository;
-use anyhow::{Result, anyhow};
-use sqlx::{FromRow, sqlite::SqlitePool};
+use crate::{
+    models::{
+        condition::{Readiness, TimerCondition, TriggerContext},
+        history::{HistoryEntry, Outcome},
+        intent::TimerIntent,
+    },
+    utils::{
+        clock::ClockChange,
+        database::repository::{
+            Page, Repository, RepositoryError, RepositoryResult, push_ids, transaction,
+        },
+        file_watch::WatchedFile,
+    },
+};
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqliteConnection, sqlite::SqlitePool};
 
 #[derive(Debug, Clone, FromRow)]
 pub struct Timer {
     pub id: i64,
     pub description: String,
     pub is_recurring: bool,
-    pub paused_at: i64,  // Unix timestamp
-    pub ends_at: i64,    // Unix timestamp
-    pub created_at: i64, // Unix timestamp
+    pub paused_at: i64,     // Unix timestamp
+    pub ends_at: i64,       // Unix timestamp
+    pub created_at: i64,    // Unix timestamp
+    pub condition: String,  // Serialized TimerCondition, empty for plain countdowns
+    pub parent_id: i64,     // Timer this link waits on, 0 for timers that are running
+    pub delay_seconds: i64, // Delay after the parent finishes, only used while waiting
+    pub intent: String,     // Serialized TimerIntent, empty for countdowns
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TimerType {
     UserDefined(String),
     Suspend,
+    Hibernate,
     Logout,
     Shutdown,
     Reboot,
@@ -28,6 +48,7 @@ impl TimerType {
         match self {
             TimerType::UserDefined(name) => name,
             TimerType::Suspend => "System Suspend",
+            TimerType::Hibernate => "System Hibernate",
             TimerType::Logout => "System Logout",
             TimerType::Shutdown => "System Shutdown",
             TimerType::Reboot => "System Reboot",
@@ -35,12 +56,67 @@ impl TimerType {
     }
 }
 
+impl TimerType {
+    /// Gets a stable, untranslated name for the type, used by the CLI and D-Bus APIs.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TimerType::UserDefined(_) => "reminder",
+            TimerType::Suspend => "suspend",
+            TimerType::Hibernate => "hibernate",
+            TimerType::Logout => "logout",
+            TimerType::Shutdown => "shutdown",
+            TimerType::Reboot => "reboot",
+        }
+    }
+
+    /// Builds a type from its [`TimerType::kind`] name.
+    ///
+    /// # Arguments
+    ///
+    /// - `kind` - One of the names returned by [`TimerType::kind`]
+    /// - `text` - Notification text, only used for reminders
+    ///
+    /// # Returns
+    ///
+    /// The type, or `None` for unknown kinds and reminders without text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::models::timer::TimerType;
+    ///
+    /// assert_eq!(TimerType::from_kind("reboot", ""), Some(TimerType::Reboot));
+    /// assert_eq!(
+    ///     TimerType::from_kind("reminder", "Tea"),
+    ///     Some(TimerType::UserDefined("Tea".to_string()))
+    /// );
+    /// assert_eq!(TimerType::from_kind("reminder", ""), None);
+    /// assert_eq!(TimerType::from_kind("explode", ""), None);
+    /// ```
+    #[must_use]
+    pub fn from_kind(kind: &str, text: &str) -> Option<Self> {
+        match kind {
+            "reminder" if !text.trim().is_empty() => {
+                Some(TimerType::UserDefined(text.trim().to_string()))
+            }
+            "suspend" => Some(TimerType::Suspend),
+            "hibernate" => Some(TimerType::Hibernate),
+            "logout" => Some(TimerType::Logout),
+            "shutdown" => Some(TimerType::Shutdown),
+            "reboot" => Some(TimerType::Reboot),
+            _ => None,
+        }
+    }
+}
+
 impl FromStr for TimerType {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
         match s {
             "System Suspend" => Ok(TimerType::Suspend),
+            "System Hibernate" => Ok(TimerType::Hibernate),
             "System Logout" => Ok(TimerType::Logout),
             "System Shutdown" => Ok(TimerType::Shutdown),
             "System Reboot" => Ok(TimerType::Reboot),
@@ -49,52 +125,732 @@ impl FromStr for TimerType {
     }
 }
 
+/// Power operations, every [`TimerType`] but reminders.
+const POWER_TYPES: [TimerType; 5] = [
+    TimerType::Suspend,
+    TimerType::Hibernate,
+    TimerType::Logout,
+    TimerType::Shutdown,
+    TimerType::Reboot,
+];
+
+/// Where a stored timer is in its life, see [`TimerFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerState {
+    /// Counting down, or waiting on its trigger condition
+    Running,
+    /// Paused, with its remaining time frozen
+    Paused,
+    /// A chain link waiting on the timer before it
+    Chained,
+}
+
+/// Narrows down [`Repository::list`] for timers.
+///
+/// Fields left at `None` let every timer through.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::models::timer::{TimerFilter, TimerState};
+///
+/// // Paused reminders set during the first day of 2026 (UTC)
+/// let filter = TimerFilter {
+///     kind: Some("reminder".to_string()),
+///     state: Some(TimerState::Paused),
+///     created_from: Some(1_767_225_600),
+///     created_until: Some(1_767_225_600 + 86_400),
+/// };
+/// assert_ne!(filter, TimerFilter::default());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimerFilter {
+    /// Only timers of this type, named like [`TimerType::kind`]
+    pub kind: Option<String>,
+    /// Only timers in this state
+    pub state: Option<TimerState>,
+    /// Only timers created at or after this Unix timestamp
+    pub created_from: Option<i64>,
+    /// Only timers created before this Unix timestamp
+    pub created_until: Option<i64>,
+}
+
 impl Timer {
+    /// `ends_at` value for trigger timers that have no deadline of their own.
+    pub const NO_DEADLINE: i64 = i64::MAX;
+
+    /// Creates a timer that ends `duration_seconds` after `now`.
+    ///
+    /// # Arguments
+    ///
+    /// - `duration_seconds` - Length of the countdown
+    /// - `is_recurring` - Whether the timer starts over, see [`Timer::repeating`]
+    /// - `timer_type` - What happens when it ends
+    /// - `now` - Current Unix timestamp in seconds
     #[must_use]
-    pub fn new(duration_seconds: i32, is_recurring: bool, timer_type: &TimerType) -> Self {
+    pub fn new(
+        duration_seconds: i32,
+        is_recurring: bool,
+        timer_type: &TimerType,
+        now: i64,
+    ) -> Self {
         Self {
             id: 0,
             description: timer_type.as_str().into(),
             is_recurring,
             paused_at: 0,
-            ends_at: chrono::Utc::now().timestamp() + i64::from(duration_seconds),
-            created_at: chrono::Utc::now().timestamp(),
+            ends_at: now + i64::from(duration_seconds),
+            created_at: now,
+            condition: String::new(),
+            parent_id: 0,
+            delay_seconds: 0,
+            intent: String::new(),
         }
     }
 
+    /// Creates a timer that ends when the clocks of a time zone show a wall clock time.
+    ///
+    /// Unlike a countdown, it keeps its local time when the time zone changes,
+    /// see [`TimerIntent::WallClock`].
+    ///
+    /// # Arguments
+    ///
+    /// - `at` - The local time to end at
+    /// - `time_zone` - IANA name of the zone, usually [`Clock::time_zone`](crate::utils::clock::Clock::time_zone)
+    /// - `timer_type` - What happens when it ends
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The timer, or `None` for zones missing from tzdata.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use chronomancer::models::timer::{Timer, TimerType};
+    ///
+    /// let at = NaiveDate::from_ymd_opt(2026, 1, 15)
+    ///     .and_then(|date| date.and_hms_opt(8, 0, 0))
+    ///     .unwrap();
+    /// let timer = Timer::at(at, "UTC", &TimerType::Reboot, 1_768_000_000).unwrap();
+    /// assert_eq!(timer.ends_at, at.and_utc().timestamp());
+    /// assert_eq!(timer.intent, "wall-clock:2026-01-15T08:00:00@UTC");
+    /// ```
+    #[must_use]
+    pub fn at(
+        at: NaiveDateTime,
+        time_zone: &str,
+        timer_type: &TimerType,
+        now: i64,
+    ) -> Option<Self> {
+        let intent = TimerIntent::WallClock {
+            at,
+            time_zone: time_zone.to_string(),
+        };
+        Some(Self {
+            ends_at: intent.resolve(time_zone)?,
+            intent: intent.to_string(),
+            ..Self::new(0, false, timer_type, now)
+        })
+    }
+
+    /// Creates a chain link that runs `delay_seconds` after the timer before it finishes.
+    ///
+    /// Links are stored with [`Timer::insert_chain`] and stay out of the active list
+    /// until their parent finishes and [`Timer::complete`] arms them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::models::timer::{Timer, TimerType};
+    ///
+    /// let logout = Timer::chained(300, &TimerType::Logout, 1_000);
+    /// assert_eq!(logout.delay_seconds, 300);
+    /// assert!(!logout.is_chained());
+    /// ```
     #[must_use]
-    pub fn is_active(&self) -> bool {
-        let now = chrono::Utc::now().timestamp();
+    pub fn chained(delay_seconds: i32, timer_type: &TimerType, now: i64) -> Self {
+        Self {
+            delay_seconds: i64::from(delay_seconds),
+            ..Self::new(delay_seconds, false, timer_type, now)
+        }
+    }
+
+    /// Creates a timer that starts over every `period_seconds` after it finishes.
+    ///
+    /// The period is kept in `delay_seconds`, so [`Timer::started_at`] gives the
+    /// start of the current round and [`Timer::complete`] arms the next round
+    /// instead of deleting the timer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::models::timer::{Timer, TimerType};
+    ///
+    /// let standup = Timer::repeating(86_400, &TimerType::UserDefined("Standup".into()), 1_000);
+    /// assert_eq!(standup.period(), Some(86_400));
+    /// assert_eq!(Timer::new(60, false, &TimerType::Reboot, 1_000).period(), None);
+    /// ```
+    #[must_use]
+    pub fn repeating(period_seconds: i32, timer_type: &TimerType, now: i64) -> Self {
+        Self {
+            delay_seconds: i64::from(period_seconds),
+            ..Self::new(period_seconds, true, timer_type, now)
+        }
+    }
+
+    /// Gets how often a recurring timer runs, in seconds, `None` for one-shot timers.
+    #[must_use]
+    pub fn period(&self) -> Option<i64> {
+        (self.is_recurring && self.delay_seconds > 0 && !self.is_chained())
+            .then_some(self.delay_seconds)
+    }
+
+    /// Whether the timer is a chain link still waiting on its parent.
+    #[must_use]
+    pub fn is_chained(&self) -> bool {
+        self.parent_id != 0
+    }
+
+    /// Gets when the timer's countdown started.
+    ///
+    /// That's `created_at` for most timers. Chain links are stored with the rest
+    /// of their chain but only start counting once armed, `delay_seconds` before
+    /// their deadline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::models::timer::{Timer, TimerType};
+    ///
+    /// let mut link = Timer::chained(300, &TimerType::Shutdown, 1_000);
+    /// link.ends_at = link.created_at + 900;
+    /// assert_eq!(link.started_at(), link.created_at + 600);
+    /// ```
+    #[must_use]
+    pub fn started_at(&self) -> i64 {
+        if self.delay_seconds > 0 {
+            (self.ends_at - self.delay_seconds).max(self.created_at)
+        } else {
+            self.created_at
+        }
+    }
+
+    /// Attaches a condition to the timer.
+    ///
+    /// Trigger conditions don't wait for a deadline, so the timer's `ends_at`
+    /// is moved to [`Timer::NO_DEADLINE`] to keep it in the active list until it fires.
+    #[must_use]
+    pub fn with_condition(mut self, condition: &TimerCondition) -> Self {
+        if condition.is_trigger() {
+            self.ends_at = Self::NO_DEADLINE;
+        }
+        self.condition = condition.to_string();
+        self
+    }
+
+    /// Parses the stored condition, treating unknown values as no condition.
+    #[must_use]
+    pub fn condition(&self) -> TimerCondition {
+        TimerCondition::from_str(&self.condition).unwrap_or_default()
+    }
+
+    /// Parses the stored intent, treating unknown values as a countdown.
+    #[must_use]
+    pub fn intent(&self) -> TimerIntent {
+        TimerIntent::from_str(&self.intent).unwrap_or_default()
+    }
+
+    /// Whether the deadline is still ahead at `now`.
+    #[must_use]
+    pub fn is_active(&self, now: i64) -> bool {
         now < self.ends_at
     }
+
+    /// Whether the timer is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused_at > 0
+    }
+
+    /// Seconds left until the deadline, frozen while paused.
+    #[must_use]
+    pub fn remaining(&self, now: i64) -> i64 {
+        let reference = if self.is_paused() {
+            self.paused_at
+        } else {
+            now
+        };
+        (self.ends_at - reference).max(0)
+    }
+
+    /// Pauses the countdown, keeping the time it has left.
+    pub fn pause(&mut self, now: i64) {
+        if !self.is_paused() {
+            self.paused_at = now;
+        }
+    }
+
+    /// Continues the countdown with the time it had left when paused.
+    ///
+    /// A timer set to a wall clock time has a new deadline now, so it turns
+    /// into a plain countdown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::models::timer::{Timer, TimerType};
+    ///
+    /// let mut timer = Timer::new(600, false, &TimerType::Reboot, 1_000);
+    /// let start = timer.created_at;
+    /// timer.pause(start + 100);
+    /// assert_eq!(timer.remaining(start + 5000), 500);
+    ///
+    /// timer.resume(start + 5000);
+    /// assert!(!timer.is_paused());
+    /// assert_eq!(timer.ends_at, start + 5500);
+    /// ```
+    pub fn resume(&mut self, now: i64) {
+        if self.is_paused() {
+            // Trigger timers have no deadline to push back
+            if self.ends_at != Self::NO_DEADLINE {
+                self.ends_at += now - self.paused_at;
+            }
+            self.paused_at = 0;
+            self.intent.clear();
+        }
+    }
+
+    /// Moves the timer along with a change of the clock, see [`ClockWatch`](crate::utils::clock::ClockWatch).
+    ///
+    /// Countdowns and paused timers keep their remaining time across wall clock
+    /// jumps. Running timers set to a wall clock time keep their deadline when
+    /// the clock jumps, and resolve it again when the time zone changes.
+    ///
+    /// # Arguments
+    ///
+    /// - `change` - What changed about the clock
+    ///
+    /// # Returns
+    ///
+    /// Whether the timer changed and needs to be stored again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::{
+    ///     models::timer::{Timer, TimerType},
+    ///     utils::clock::ClockChange,
+    /// };
+    ///
+    /// let mut timer = Timer::new(600, false, &TimerType::Reboot, 1_000);
+    /// let change = ClockChange { jumped_by: 3600, time_zone: None };
+    /// assert!(timer.follow_clock(&change));
+    /// assert_eq!(timer.remaining(1_000 + 3600), 600);
+    /// ```
+    pub fn follow_clock(&mut self, change: &ClockChange) -> bool {
+        match self.intent() {
+            TimerIntent::WallClock { at, .. } if !self.is_paused() => {
+                let Some(zone) = change.time_zone.as_deref() else {
+                    return false;
+                };
+                let intent = TimerIntent::WallClock {
+                    at,
+                    time_zone: zone.to_string(),
+                };
+                // Zones missing from tzdata keep the deadline from the old one
+                let Some(ends_at) = intent.resolve(zone) else {
+                    return false;
+                };
+                let intent = intent.to_string();
+                let changed = ends_at != self.ends_at || intent != self.intent;
+                self.ends_at = ends_at;
+                self.intent = intent;
+                changed
+            }
+            _ if change.jumped_by == 0 => false,
+            _ => {
+                if self.ends_at != Self::NO_DEADLINE {
+                    self.ends_at += change.jumped_by;
+                }
+                if self.is_paused() {
+                    self.paused_at += change.jumped_by;
+                }
+                self.created_at += change.jumped_by;
+                true
+            }
+        }
+    }
+
+    /// Decides whether the timer should run, keep waiting, or be skipped.
+    ///
+    /// Paused timers keep waiting, whatever their condition says.
+    #[must_use]
+    pub fn readiness(&self, context: &TriggerContext) -> Readiness {
+        if self.is_paused() {
+            return Readiness::Waiting;
+        }
+        self.condition().evaluate(self.ends_at, context)
+    }
+
+    /// Gets the time at which the timer has to be checked again, see [`TimerCondition::wakes_at`].
+    ///
+    /// Paused timers and chain links that aren't armed yet never wake up by themselves.
+    ///
+    /// # Arguments
+    ///
+    /// - `files` - Latest observations of watched paths
+    #[must_use]
+    pub fn wakes_at(&self, files: &[WatchedFile]) -> Option<i64> {
+        if self.is_paused() {
+            return None;
+        }
+        self.condition()
+            .wakes_at(self.ends_at, files)
+            .filter(|&at| at != Self::NO_DEADLINE)
+    }
 }
 
-impl Repository<Timer> for Timer {
-    async fn insert(pool: &SqlitePool, item: &Timer) -> Result<Timer> {
+impl Timer {
+    /// Stores a chain of timers: the head runs as usual, every link waits on the one before it.
+    ///
+    /// # Arguments
+    ///
+    /// - `head` - The first timer, counting down (or waiting on its condition) right away
+    /// - `links` - Following timers in order, created with [`Timer::chained`]
+    ///
+    /// # Returns
+    ///
+    /// The stored head followed by the stored links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any insert fails, in which case nothing is stored.
+    pub async fn insert_chain(
+        pool: &SqlitePool,
+        head: &Timer,
+        links: &[Timer],
+    ) -> RepositoryResult<Vec<Timer>> {
+        let ids = transaction(pool, async |connection| {
+            let mut ids = Vec::with_capacity(links.len() + 1);
+            let mut parent_id = 0;
+            for timer in std::iter::once(head).chain(links) {
+                let mut stored = timer.clone();
+                stored.parent_id = parent_id;
+                // Waiting links have no deadline until they are armed
+                if parent_id != 0 {
+                    stored.ends_at = Self::NO_DEADLINE;
+                }
+                parent_id = Self::insert_row(connection, &stored).await?;
+                ids.push(parent_id);
+            }
+            Ok(ids)
+        })
+        .await?;
+
+        let mut chain = Vec::with_capacity(ids.len());
+        for id in ids {
+            chain.push(
+                Timer::get_by_id(pool, &id)
+                    .await?
+                    .ok_or(RepositoryError::NotFound { kind: "timer", id })?,
+            );
+        }
+        Ok(chain)
+    }
+
+    /// Gets all chain links that are still waiting on their parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_all_chained(pool: &SqlitePool) -> RepositoryResult<Vec<Timer>> {
+        let timers =
+            sqlx::query_as::<_, Timer>("SELECT * FROM timers WHERE parent_id != 0 ORDER BY id ASC")
+                .fetch_all(pool)
+                .await?;
+        Ok(timers)
+    }
+
+    /// Moves every running (or paused) timer along with a change of the clock.
+    ///
+    /// See [`Timer::follow_clock`]. Chain links waiting on their parent get a
+    /// fresh deadline once armed, so they are left alone.
+    ///
+    /// # Returns
+    ///
+    /// The timers that changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, in which case nothing is stored.
+    pub async fn follow_clock_all(
+        pool: &SqlitePool,
+        change: &ClockChange,
+    ) -> RepositoryResult<Vec<Timer>> {
+        let mut changed = Vec::new();
+        for mut timer in Self::get_all_running(pool).await? {
+            if timer.follow_clock(change) {
+                changed.push(timer);
+            }
+        }
+
+        transaction(pool, async |connection| {
+            for timer in &changed {
+                Self::update_row(connection, timer).await?;
+            }
+            Ok(())
+        })
+        .await?;
+        Ok(changed)
+    }
+
+    /// Cancels a running (or paused) timer, like the cancel button does.
+    ///
+    /// The rest of its chain is dropped too, and the timer is recorded in the history.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The timer to cancel
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The cancelled timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, or the timer doesn't exist or is a chain
+    /// link still waiting on the timer before it.
+    pub async fn cancel(pool: &SqlitePool, id: i64, now: i64) -> RepositoryResult<Timer> {
+        let timer = Self::get_running(pool, id).await?;
+        Self::delete_chain(pool, timer.id).await?;
+        HistoryEntry::insert(pool, &HistoryEntry::new(&timer, Outcome::Cancelled, now)).await?;
+        Ok(timer)
+    }
+
+    /// Pauses or resumes a running timer and stores the change.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The timer to change
+    /// - `paused` - Pause the timer instead of resuming it
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The changed timer. Pausing a paused timer (or resuming a running one) changes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, or the timer doesn't exist or is a chain
+    /// link still waiting on the timer before it.
+    pub async fn set_paused(
+        pool: &SqlitePool,
+        id: i64,
+        paused: bool,
+        now: i64,
+    ) -> RepositoryResult<Timer> {
+        let mut timer = Self::get_running(pool, id).await?;
+        if paused {
+            timer.pause(now);
+        } else {
+            timer.resume(now);
+        }
+        Self::update(pool, &timer).await?;
+        Ok(timer)
+    }
+
+    /// Gets a timer that is running (or paused), as opposed to a waiting chain link.
+    async fn get_running(pool: &SqlitePool, id: i64) -> RepositoryResult<Timer> {
+        match Self::get_by_id(pool, &id).await? {
+            Some(timer) if timer.is_chained() => Err(RepositoryError::Invalid(format!(
+                "Timer {id} waits on the timer before it in its chain"
+            ))),
+            Some(timer) => Ok(timer),
+            None => Err(RepositoryError::NotFound { kind: "timer", id }),
+        }
+    }
+
+    /// Gets running timers whose deadline has already passed.
+    ///
+    /// These ended while nothing was ticking (the applet wasn't running) and
+    /// are left out of [`Repository::get_all_active`]. Paused timers never end.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_all_missed(pool: &SqlitePool, now: i64) -> RepositoryResult<Vec<Timer>> {
+        let timers = sqlx::query_as::<_, Timer>(
+            "SELECT * FROM timers WHERE ends_at <= ? AND paused_at = 0 AND parent_id = 0 ORDER BY ends_at ASC",
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+        Ok(timers)
+    }
+
+    /// Gets every running (or paused) timer, whatever its deadline.
+    ///
+    /// Used to resync with the database while the applet runs: a timer whose
+    /// deadline passed since the last tick is still due and is left for the
+    /// tick to finish, rather than treated as missed. Chain links waiting on
+    /// their parent are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_all_running(pool: &SqlitePool) -> RepositoryResult<Vec<Timer>> {
+        let timers = sqlx::query_as::<_, Timer>(
+            "SELECT * FROM timers WHERE parent_id = 0 ORDER BY ends_at ASC",
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(timers)
+    }
+
+    /// Removes a finished timer and arms the next link of its chain.
+    ///
+    /// The successor's countdown starts at `now`; trigger conditions keep
+    /// waiting without a deadline, just like a freshly created trigger timer.
+    /// Recurring timers (see [`Timer::repeating`]) aren't removed but move on
+    /// to their next round after `now`.
+    ///
+    /// # Returns
+    ///
+    /// The armed successor or the recurring timer's next round, or `None` if
+    /// the timer was the end of its chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, in which case nothing changes.
+    pub async fn complete(pool: &SqlitePool, id: i64, now: i64) -> RepositoryResult<Option<Timer>> {
+        let armed = transaction(pool, async |connection| {
+            let timer = sqlx::query_as::<_, Timer>("SELECT * FROM timers WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *connection)
+                .await?;
+            if let Some(mut timer) = timer.filter(|timer| timer.period().is_some()) {
+                // Rounds that passed while nothing ran the timer are skipped
+                let period = timer.delay_seconds;
+                timer.ends_at += ((now - timer.ends_at).max(0) / period + 1) * period;
+                Self::update_row(connection, &timer).await?;
+                return Ok(Some(timer));
+            }
+
+            let successor = sqlx::query_as::<_, Timer>("SELECT * FROM timers WHERE parent_id = ?")
+                .bind(id)
+                .fetch_optional(&mut *connection)
+                .await?;
+            let armed = match successor {
+                Some(mut timer) => {
+                    timer.parent_id = 0;
+                    timer.ends_at = if timer.condition().is_trigger() {
+                        Self::NO_DEADLINE
+                    } else {
+                        now + timer.delay_seconds
+                    };
+                    Self::update_row(connection, &timer).await?;
+                    Some(timer)
+                }
+                None => None,
+            };
+
+            sqlx::query("DELETE FROM timers WHERE id = ?")
+                .bind(id)
+                .execute(&mut *connection)
+                .await?;
+            Ok(armed)
+        })
+        .await?;
+        Ok(armed)
+    }
+
+    /// Deletes a timer together with every link waiting on it, directly or further down the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete query fails.
+    pub async fn delete_chain(pool: &SqlitePool, id: i64) -> RepositoryResult<()> {
+        Self::delete_many(pool, &[id]).await?;
+        Ok(())
+    }
+
+    /// Inserts a timer with every field as it is, see [`Repository::insert`].
+    ///
+    /// # Returns
+    ///
+    /// The new timer's id.
+    async fn insert_row(connection: &mut SqliteConnection, item: &Timer) -> RepositoryResult<i64> {
         let result = sqlx::query(
-            "INSERT INTO timers (description, paused_at, ends_at, is_recurring, created_at) VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO timers (description, paused_at, ends_at, is_recurring, created_at, condition, parent_id, delay_seconds, intent) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&item.description)
         .bind(item.paused_at)
         .bind(item.ends_at)
         .bind(item.is_recurring)
         .bind(item.created_at)
-        .execute(pool)
+        .bind(&item.condition)
+        .bind(item.parent_id)
+        .bind(item.delay_seconds)
+        .bind(&item.intent)
+        .execute(&mut *connection)
         .await?;
+        Ok(result.last_insert_rowid())
+    }
 
-        // get inserted item
-        let item = Timer::get_by_id(pool, &result.last_insert_rowid()).await?;
-        if item.is_none() {
-            return Err(anyhow!("Failed to save timer"));
+    /// Stores every field of a timer, see [`Repository::update`].
+    async fn update_row(connection: &mut SqliteConnection, item: &Timer) -> RepositoryResult<()> {
+        let result = sqlx::query(
+            "UPDATE timers SET description = ?, is_recurring = ?, paused_at = ?, ends_at = ?, created_at = ?, condition = ?, parent_id = ?, delay_seconds = ?, intent = ? WHERE id = ?",
+        )
+        .bind(&item.description)
+        .bind(item.is_recurring)
+        .bind(item.paused_at)
+        .bind(item.ends_at)
+        .bind(item.created_at)
+        .bind(&item.condition)
+        .bind(item.parent_id)
+        .bind(item.delay_seconds)
+        .bind(&item.intent)
+        .bind(item.id)
+        .execute(&mut *connection)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound {
+                kind: "timer",
+                id: item.id,
+            });
         }
+        Ok(())
+    }
+}
+
+impl Repository<Timer> for Timer {
+    type Filter = TimerFilter;
+
+    async fn insert(pool: &SqlitePool, item: &Timer) -> RepositoryResult<Timer> {
+        let id = Self::insert_row(&mut *pool.acquire().await?, item).await?;
+        Timer::get_by_id(pool, &id)
+            .await?
+            .ok_or(RepositoryError::NotFound { kind: "timer", id })
+    }
 
-        Ok(item.unwrap())
+    async fn update(pool: &SqlitePool, item: &Timer) -> RepositoryResult<()> {
+        Self::update_row(&mut *pool.acquire().await?, item).await
     }
 
-    async fn get_all_active(pool: &SqlitePool) -> Result<Vec<Timer>> {
-        let now = chrono::Utc::now().timestamp();
+    async fn get_all_active(pool: &SqlitePool, now: i64) -> RepositoryResult<Vec<Timer>> {
+        // Chain links waiting on their parent are not running yet, paused timers
+        // stay active even after their old deadline
         let timers = sqlx::query_as::<_, Timer>(
-            "SELECT * FROM timers WHERE ends_at > ? ORDER BY ends_at ASC",
+            "SELECT * FROM timers WHERE (ends_at > ? OR paused_at != 0) AND parent_id = 0 ORDER BY ends_at ASC",
         )
         .bind(now)
         .fetch_all(pool)
@@ -102,7 +858,7 @@ impl Repository<Timer> for Timer {
         Ok(timers)
     }
 
-    async fn get_by_id(pool: &sqlx::SqlitePool, id: &i64) -> Result<Option<Timer>> {
+    async fn get_by_id(pool: &sqlx::SqlitePool, id: &i64) -> RepositoryResult<Option<Timer>> {
         let timer = sqlx::query_as::<_, Timer>("SELECT * FROM timers WHERE id = ?")
             .bind(id)
             .fetch_optional(pool)
@@ -110,20 +866,95 @@ impl Repository<Timer> for Timer {
         Ok(timer)
     }
 
-    async fn delete_by_id(pool: &sqlx::SqlitePool, id: &i64) -> Result<()> {
-        sqlx::query("DELETE FROM timers WHERE id = ?")
-            .bind(id)
-            .execute(pool)
-            .await?;
-        Ok(())
+    /// Timers in the order they were created.
+    async fn list(
+        pool: &SqlitePool,
+        filter: &TimerFilter,
+        page: Page,
+    ) -> RepositoryResult<Vec<Timer>> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM timers WHERE 1 = 1");
+
+        match filter.kind.as_deref() {
+            None => {}
+            // Reminders are stored under their text, anything that isn't a power operation
+            Some("reminder") => {
+                query.push(" AND description NOT IN (");
+                let mut separated = query.separated(", ");
+                for timer_type in POWER_TYPES {
+                    separated.push_bind(timer_type.as_str().to_string());
+                }
+                query.push(")");
+            }
+            Some(kind) => {
+                let timer_type = TimerType::from_kind(kind, "").ok_or_else(|| {
+                    RepositoryError::Invalid(format!("Unknown timer type '{kind}'"))
+                })?;
+                query
+                    .push(" AND description = ")
+                    .push_bind(timer_type.as_str().to_string());
+            }
+        }
+
+        match filter.state {
+            None => {}
+            Some(TimerState::Running) => {
+                query.push(" AND parent_id = 0 AND paused_at = 0");
+            }
+            Some(TimerState::Paused) => {
+                query.push(" AND parent_id = 0 AND paused_at != 0");
+            }
+            Some(TimerState::Chained) => {
+                query.push(" AND parent_id != 0");
+            }
+        }
+
+        if let Some(from) = filter.created_from {
+            query.push(" AND created_at >= ").push_bind(from);
+        }
+        if let Some(until) = filter.created_until {
+            query.push(" AND created_at < ").push_bind(until);
+        }
+
+        query
+            .push(" ORDER BY created_at ASC, id ASC LIMIT ")
+            .push_bind(page.limit)
+            .push(" OFFSET ")
+            .push_bind(page.offset);
+        let timers = query.build_query_as::<Timer>().fetch_all(pool).await?;
+        Ok(timers)
+    }
+
+    /// Deletes the timer along with the links waiting on it, see [`Repository::delete_many`].
+    async fn delete_by_id(pool: &sqlx::SqlitePool, id: &i64) -> RepositoryResult<()> {
+        Timer::delete_many(pool, &[*id]).await.map(|_| ())
+    }
+
+    /// Links waiting on a deleted timer would never run, so they are deleted
+    /// too, down to the end of their chains.
+    async fn delete_many(pool: &SqlitePool, ids: &[i64]) -> RepositoryResult<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "WITH RECURSIVE chain(id) AS (SELECT id FROM timers WHERE id IN ",
+        );
+        push_ids(&mut query, ids);
+        query.push(
+            " UNION ALL SELECT timers.id FROM timers JOIN chain ON timers.parent_id = chain.id)
+            DELETE FROM timers WHERE id IN chain",
+        );
+        Ok(query.build().execute(pool).await?.rows_affected())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anyhow::Result;
+    use anyhow::{Result, anyhow};
     use sqlx::SqlitePool;
+
+    const NOW: i64 = 1_767_600_000;
+
     async fn setup_db() -> Result<SqlitePool> {
         let pool = SqlitePool::connect("sqlite::memory:").await?;
         sqlx::query(
@@ -133,7 +964,11 @@ mod tests {
                 is_recurring BOOLEAN NOT NULL,
                 paused_at INTEGER NOT NULL,
                 ends_at INTEGER NOT NULL,
-                created_at INTEGER NOT NULL
+                created_at INTEGER NOT NULL,
+                condition TEXT NOT NULL DEFAULT '',
+                parent_id INTEGER NOT NULL DEFAULT 0,
+                delay_seconds INTEGER NOT NULL DEFAULT 0,
+                intent TEXT NOT NULL DEFAULT ''
             )",
         )
         .execute(&pool)
@@ -145,7 +980,7 @@ mod tests {
     async fn test_timer_crud() -> Result<()> {
         let pool = setup_db().await?;
         let timer_type = TimerType::UserDefined("Test Timer".into());
-        let new_timer = Timer::new(3600, false, &timer_type);
+        let new_timer = Timer::new(3600, false, &timer_type, NOW);
         let inserted_timer = Timer::insert(&pool, &new_timer).await?;
         assert_eq!(inserted_timer.description, "Test Timer");
 
@@ -153,7 +988,7 @@ mod tests {
         assert!(fetched_timer.is_some());
         assert_eq!(fetched_timer.unwrap().id, inserted_timer.id);
 
-        let active_timers = Timer::get_all_active(&pool).await?;
+        let active_timers = Timer::get_all_active(&pool, NOW).await?;
         assert_eq!(active_timers.len(), 1);
 
         Timer::delete_by_id(&pool, &inserted_timer.id).await?;
@@ -161,4 +996,333 @@ mod tests {
         assert!(deleted_timer.is_none());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_trigger_timer_persists_condition() -> Result<()> {
+        let pool = setup_db().await?;
+        let timer = Timer::new(0, false, &TimerType::Hibernate, NOW)
+            .with_condition(&TimerCondition::BatteryBelow(10));
+        assert_eq!(timer.ends_at, Timer::NO_DEADLINE);
+
+        let inserted = Timer::insert(&pool, &timer).await?;
+        assert_eq!(inserted.condition(), TimerCondition::BatteryBelow(10));
+
+        // Trigger timers stay active until their condition fires
+        let active_timers = Timer::get_all_active(&pool, NOW).await?;
+        assert_eq!(active_timers.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chain_arms_links_in_order() -> Result<()> {
+        let pool = setup_db().await?;
+        let reminder = Timer::new(
+            60,
+            false,
+            &TimerType::UserDefined("Save your work".into()),
+            NOW,
+        );
+        let links = [
+            Timer::chained(300, &TimerType::Logout, NOW),
+            Timer::chained(600, &TimerType::Shutdown, NOW),
+        ];
+
+        let chain = Timer::insert_chain(&pool, &reminder, &links).await?;
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[1].parent_id, chain[0].id);
+        assert_eq!(chain[2].parent_id, chain[1].id);
+
+        // Only the head is running
+        let active = Timer::get_all_active(&pool, NOW).await?;
+        assert_eq!(active.len(), 1);
+        assert_eq!(Timer::get_all_chained(&pool).await?.len(), 2);
+
+        let logout = Timer::complete(&pool, chain[0].id, NOW).await?.unwrap();
+        assert_eq!(logout.id, chain[1].id);
+        assert_eq!(logout.ends_at, NOW + 300);
+        assert!(Timer::get_by_id(&pool, &chain[0].id).await?.is_none());
+
+        let active = Timer::get_all_active(&pool, NOW).await?;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, logout.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_chain_cancels_every_link() -> Result<()> {
+        let pool = setup_db().await?;
+        let head = Timer::new(60, false, &TimerType::Suspend, NOW);
+        let links = [
+            Timer::chained(60, &TimerType::Logout, NOW),
+            Timer::chained(60, &TimerType::Shutdown, NOW),
+        ];
+        let chain = Timer::insert_chain(&pool, &head, &links).await?;
+        let unrelated =
+            Timer::insert(&pool, &Timer::new(60, false, &TimerType::Reboot, NOW)).await?;
+
+        Timer::delete_chain(&pool, chain[0].id).await?;
+
+        assert!(Timer::get_all_chained(&pool).await?.is_empty());
+        let active = Timer::get_all_active(&pool, NOW).await?;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, unrelated.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paused_timer_outlives_its_deadline() -> Result<()> {
+        let pool = setup_db().await?;
+        let mut timer =
+            Timer::insert(&pool, &Timer::new(60, false, &TimerType::Shutdown, NOW)).await?;
+        timer.pause(NOW);
+        timer.ends_at = NOW - 30;
+        Timer::update(&pool, &timer).await?;
+
+        assert!(Timer::get_all_missed(&pool, NOW).await?.is_empty());
+        let active = Timer::get_all_active(&pool, NOW).await?;
+        assert_eq!(active.len(), 1);
+        assert!(active[0].is_paused());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_paused_skips_waiting_links() -> Result<()> {
+        let pool = setup_db().await?;
+        let head = Timer::new(60, false, &TimerType::UserDefined("Save".into()), NOW);
+        let chain =
+            Timer::insert_chain(&pool, &head, &[Timer::chained(60, &TimerType::Logout, NOW)])
+                .await?;
+
+        assert!(matches!(
+            Timer::set_paused(&pool, chain[1].id, true, NOW).await,
+            Err(RepositoryError::Invalid(_))
+        ));
+        assert!(matches!(
+            Timer::set_paused(&pool, 999, true, NOW).await,
+            Err(RepositoryError::NotFound { id: 999, .. })
+        ));
+
+        let paused = Timer::set_paused(&pool, chain[0].id, true, NOW).await?;
+        assert_eq!(paused.paused_at, NOW);
+        let resumed = Timer::set_paused(&pool, chain[0].id, false, NOW + 10).await?;
+        assert_eq!(resumed.ends_at, chain[0].ends_at + 10);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recurring_timer_moves_to_next_round() -> Result<()> {
+        let pool = setup_db().await?;
+        let mut standup = Timer::repeating(3600, &TimerType::UserDefined("Standup".into()), NOW);
+        standup.ends_at = NOW;
+        let standup = Timer::insert(&pool, &standup).await?;
+        assert_eq!(standup.period(), Some(3600));
+
+        let next = Timer::complete(&pool, standup.id, NOW).await?;
+        assert_eq!(next.map(|timer| timer.ends_at), Some(NOW + 3600));
+
+        // Rounds missed while nothing ran the timer are skipped
+        let next = Timer::complete(&pool, standup.id, NOW + 3 * 3600 + 10).await?;
+        assert_eq!(next.map(|timer| timer.ends_at), Some(NOW + 4 * 3600));
+        assert_eq!(Timer::get_all_running(&pool).await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missed_timers_are_not_active() -> Result<()> {
+        let pool = setup_db().await?;
+        let mut missed = Timer::new(60, false, &TimerType::Shutdown, NOW);
+        missed.ends_at = NOW - 30;
+        let missed = Timer::insert(&pool, &missed).await?;
+        Timer::insert(&pool, &Timer::new(60, false, &TimerType::Suspend, NOW)).await?;
+
+        let fetched = Timer::get_all_missed(&pool, NOW).await?;
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[0].id, missed.id);
+        assert_eq!(Timer::get_all_active(&pool, NOW).await?.len(), 1);
+
+        // Resyncing keeps overdue timers too, but not links waiting on their parent
+        let mut head = Timer::new(60, false, &TimerType::Suspend, NOW);
+        head.ends_at = NOW - 10;
+        let chain =
+            Timer::insert_chain(&pool, &head, &[Timer::chained(60, &TimerType::Logout, NOW)])
+                .await?;
+        let running = Timer::get_all_running(&pool).await?;
+        let ids: Vec<i64> = running.iter().map(|t| t.id).collect();
+        assert_eq!(running.len(), 3);
+        assert_eq!(ids[..2], [missed.id, chain[0].id]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_timers_follow_clock_changes() -> Result<()> {
+        let pool = setup_db().await?;
+        let countdown =
+            Timer::insert(&pool, &Timer::new(600, false, &TimerType::Suspend, NOW)).await?;
+        let mut paused = Timer::new(600, false, &TimerType::Logout, NOW);
+        paused.pause(NOW + 100);
+        let paused = Timer::insert(&pool, &paused).await?;
+        let at = NaiveDateTime::parse_from_str("2026-07-15 08:00", "%Y-%m-%d %H:%M")?;
+        let alarm = Timer::at(at, "America/New_York", &TimerType::Reboot, NOW)
+            .ok_or_else(|| anyhow!("New York is in tzdata"))?;
+        assert_eq!(alarm.ends_at, 1_784_116_800);
+        let alarm = Timer::insert(&pool, &alarm).await?;
+
+        // NTP sets the clock an hour ahead: countdowns keep their time left
+        let jump = ClockChange {
+            jumped_by: 3600,
+            time_zone: None,
+        };
+        let changed = Timer::follow_clock_all(&pool, &jump).await?;
+        assert_eq!(changed.len(), 2);
+        let countdown = Timer::get_by_id(&pool, &countdown.id).await?.unwrap();
+        assert_eq!(countdown.remaining(NOW + 3600), 600);
+        let paused = Timer::get_by_id(&pool, &paused.id).await?.unwrap();
+        assert_eq!(paused.remaining(NOW + 3600), 500);
+        let alarm = Timer::get_by_id(&pool, &alarm.id).await?.unwrap();
+        assert_eq!(alarm.ends_at, 1_784_116_800);
+
+        // Flying to Berlin: 08:00 comes six hours earlier, countdowns stay put
+        let flight = ClockChange {
+            jumped_by: 0,
+            time_zone: Some("Europe/Berlin".to_string()),
+        };
+        let changed = Timer::follow_clock_all(&pool, &flight).await?;
+        assert_eq!(changed.len(), 1);
+        let alarm = Timer::get_by_id(&pool, &alarm.id).await?.unwrap();
+        assert_eq!(alarm.ends_at, 1_784_095_200);
+        assert_eq!(
+            alarm.intent(),
+            TimerIntent::WallClock {
+                at,
+                time_zone: "Europe/Berlin".to_string(),
+            }
+        );
+        assert_eq!(Timer::follow_clock_all(&pool, &flight).await?.len(), 0);
+
+        // Resuming moves the deadline, so the alarm becomes a countdown
+        let mut alarm = alarm;
+        alarm.pause(NOW);
+        alarm.resume(NOW + 60);
+        assert_eq!(alarm.intent(), TimerIntent::Relative);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_and_pages() -> Result<()> {
+        let pool = setup_db().await?;
+        let tea = Timer::insert(
+            &pool,
+            &Timer::new(300, false, &TimerType::UserDefined("Tea".into()), NOW),
+        )
+        .await?;
+        let mut shutdown = Timer::new(3600, false, &TimerType::Shutdown, NOW + 10);
+        shutdown.pause(NOW + 20);
+        let shutdown = Timer::insert(&pool, &shutdown).await?;
+        let laundry = Timer::insert(
+            &pool,
+            &Timer::new(
+                900,
+                false,
+                &TimerType::UserDefined("Laundry".into()),
+                NOW + 86_400,
+            ),
+        )
+        .await?;
+
+        let ids = async |filter: &TimerFilter, page: Page| -> Result<Vec<i64>> {
+            let timers = Timer::list(&pool, filter, page).await?;
+            Ok(timers.iter().map(|timer| timer.id).collect())
+        };
+        assert_eq!(
+            ids(&TimerFilter::default(), Page::ALL).await?,
+            [tea.id, shutdown.id, laundry.id]
+        );
+
+        let reminders = TimerFilter {
+            kind: Some("reminder".to_string()),
+            ..TimerFilter::default()
+        };
+        assert_eq!(ids(&reminders, Page::first(1)).await?, [tea.id]);
+        assert_eq!(ids(&reminders, Page::first(1).next()).await?, [laundry.id]);
+        assert!(
+            ids(&reminders, Page::first(1).next().next())
+                .await?
+                .is_empty()
+        );
+
+        let paused = TimerFilter {
+            state: Some(TimerState::Paused),
+            ..TimerFilter::default()
+        };
+        assert_eq!(ids(&paused, Page::ALL).await?, [shutdown.id]);
+
+        let first_day = TimerFilter {
+            state: Some(TimerState::Running),
+            created_from: Some(NOW),
+            created_until: Some(NOW + 86_400),
+            ..TimerFilter::default()
+        };
+        assert_eq!(ids(&first_day, Page::ALL).await?, [tea.id]);
+
+        let unknown = TimerFilter {
+            kind: Some("nap".to_string()),
+            ..TimerFilter::default()
+        };
+        assert!(matches!(
+            Timer::list(&pool, &unknown, Page::ALL).await,
+            Err(RepositoryError::Invalid(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_many() -> Result<()> {
+        let pool = setup_db().await?;
+        let mut tea = Timer::insert(
+            &pool,
+            &Timer::new(300, false, &TimerType::UserDefined("Tea".into()), NOW),
+        )
+        .await?;
+        let reboot = Timer::insert(&pool, &Timer::new(600, false, &TimerType::Reboot, NOW)).await?;
+
+        tea.description = "Green tea".to_string();
+        tea.is_recurring = true;
+        Timer::update(&pool, &tea).await?;
+        let stored = Timer::get_by_id(&pool, &tea.id)
+            .await?
+            .expect("stored timer");
+        assert_eq!(stored.description, "Green tea");
+        assert!(stored.is_recurring);
+
+        let mut missing = tea.clone();
+        missing.id = 99;
+        assert!(matches!(
+            Timer::update(&pool, &missing).await,
+            Err(RepositoryError::NotFound {
+                kind: "timer",
+                id: 99
+            })
+        ));
+
+        assert_eq!(Timer::delete_many(&pool, &[]).await?, 0);
+        assert_eq!(
+            Timer::delete_many(&pool, &[tea.id, reboot.id, 99]).await?,
+            2
+        );
+
+        // Links go with the timer they wait on
+        let chain = Timer::insert_chain(
+            &pool,
+            &Timer::new(60, false, &TimerType::Suspend, NOW),
+            &[Timer::chained(60, &TimerType::Shutdown, NOW)],
+        )
+        .await?;
+        assert_eq!(Timer::delete_many(&pool, &[chain[0].id]).await?, 2);
+        assert!(
+            Timer::list(&pool, &TimerFilter::default(), Page::ALL)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,328 @@
+//! Chained timers page.
+//!
+//! An editor for a chain of timers where every step runs a number of minutes
+//! after the one before it, e.g. a "save your work" reminder, then a logout
+//! five minutes later, then a shutdown. The first step counts down from the
+//! moment the chain is started. Running chains show up in the active timer
+//! list, where cancelling the first step cancels the whole chain.
+
+use crate::{
+    components::power_form::PowerOperation,
+    fl,
+    models::timer::{Timer, TimerType},
+    utils::{
+        filters,
+        ui::{Gaps, Padding},
+    },
+};
+use cosmic::{
+    Action, Element, Task,
+    iced::{Alignment, Length},
+    iced_widget::{column, row},
+    widget::{Space, TextInput, button, dropdown, icon, text},
+};
+
+/// Width of the minutes input in a step row.
+const MINUTES_INPUT_WIDTH: f32 = 64.0;
+
+/// Actions a chain step can run, in dropdown order.
+pub const ACTIONS: [PowerOperation; 6] = [
+    PowerOperation::Notify,
+    PowerOperation::Suspend,
+    PowerOperation::Logout,
+    PowerOperation::Reboot,
+    PowerOperation::Shutdown,
+    PowerOperation::Hibernate,
+];
+
+/// Messages for the chains page
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Step action dropdown selection changed
+    ActionSelected(usize, usize),
+    /// Step minutes input changed
+    MinutesChanged(usize, String),
+    /// Step notification text changed (notification steps only)
+    NoteChanged(usize, String),
+    /// Add a step to the end of the chain
+    AddStep,
+    /// Remove a step from the chain
+    RemoveStep(usize),
+    /// Start button pressed
+    StartPressed,
+    /// Reset the editor after a chain was started
+    ClearForm,
+    /// Request to start the chain, first timer first
+    Start(Vec<Timer>),
+}
+
+/// A chain step as typed into the editor, validated when the chain is started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepDraft {
+    pub action: usize,
+    pub minutes: String,
+    pub note: String,
+}
+
+impl StepDraft {
+    fn new(action: PowerOperation, minutes: u32, note: String) -> Self {
+        Self {
+            action: ACTIONS
+                .iter()
+                .position(|a| *a == action)
+                .unwrap_or_default(),
+            minutes: minutes.to_string(),
+            note,
+        }
+    }
+
+    /// The operation selected in the step's dropdown.
+    #[must_use]
+    pub fn operation(&self) -> PowerOperation {
+        ACTIONS
+            .get(self.action)
+            .copied()
+            .unwrap_or(PowerOperation::Notify)
+    }
+}
+
+/// Struct representing the chains page
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub steps: Vec<StepDraft>,
+    pub action_labels: Vec<String>,
+}
+
+impl Default for Page {
+    /// Create a default instance of `Page`, prefilled with a reminder, logout and shutdown
+    fn default() -> Self {
+        Self {
+            steps: vec![
+                StepDraft::new(PowerOperation::Notify, 10, fl!("chain-save-work")),
+                StepDraft::new(PowerOperation::Logout, 5, String::new()),
+                StepDraft::new(PowerOperation::Shutdown, 5, String::new()),
+            ],
+            action_labels: ACTIONS.iter().map(|action| action_name(*action)).collect(),
+        }
+    }
+}
+
+impl Page {
+    /// Render the chains page
+    ///
+    /// # Returns
+    /// An `Element` representing the page view
+    pub fn view(&self) -> Element<'_, Message> {
+        let step_rows = self.steps.iter().enumerate().map(|(index, step)| {
+            let mut step_row = row![
+                dropdown(&self.action_labels, Some(step.action), move |action| {
+                    Message::ActionSelected(index, action)
+                }),
+                TextInput::new(fl!("minutes"), &step.minutes)
+                    .on_input(move |minutes| Message::MinutesChanged(index, minutes))
+                    .width(MINUTES_INPUT_WIDTH),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(Gaps::xs());
+
+            step_row = if step.operation() == PowerOperation::Notify {
+                step_row.push(
+                    TextInput::new(fl!("chain-note"), &step.note)
+                        .on_input(move |note| Message::NoteChanged(index, note))
+                        .width(Length::Fill),
+                )
+            } else {
+                step_row.push(Space::with_width(Length::Fill))
+            };
+
+            step_row
+                .push(
+                    button::icon(icon::from_name("list-remove-symbolic"))
+                        .on_press(Message::RemoveStep(index)),
+                )
+                .into()
+        });
+
+        column![
+            text::heading(fl!("chain-new")),
+            text::caption(fl!("chain-hint")),
+        ]
+        .extend(step_rows)
+        .push(
+            row![
+                button::text(fl!("chain-add-step")).on_press(Message::AddStep),
+                Space::with_width(Length::Fill),
+                button::suggested(fl!("chain-start")).on_press(Message::StartPressed),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(Gaps::xs()),
+        )
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill)
+        .into()
+    }
+
+    /// Update the chains page state based on messages
+    ///
+    /// Editor messages update the draft. `StartPressed` and `Start` are handled by
+    /// the app, which builds the timers with [`Page::to_timers`], and ignored here.
+    ///
+    /// # Arguments
+    /// - `message` - The message to process
+    ///
+    /// # Returns
+    /// A `Task` representing any actions to be taken
+    pub fn update(&mut self, message: Message) -> Task<Action<Message>> {
+        match message {
+            Message::ActionSelected(index, action) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.action = action;
+                }
+            }
+            Message::MinutesChanged(index, minutes) => {
+                if let Some(step) = self.steps.get_mut(index)
+                    && let Some(minutes) = filters::filter_positive_integer(&minutes)
+                {
+                    step.minutes = minutes;
+                }
+            }
+            Message::NoteChanged(index, note) => {
+                if let Some(step) = self.steps.get_mut(index) {
+                    step.note = note;
+                }
+            }
+            Message::AddStep => {
+                self.steps
+                    .push(StepDraft::new(PowerOperation::Notify, 5, String::new()));
+            }
+            Message::RemoveStep(index) => {
+                if index < self.steps.len() {
+                    self.steps.remove(index);
+                }
+            }
+            Message::ClearForm => *self = Self::default(),
+            Message::StartPressed | Message::Start(_) => {}
+        }
+        Task::none()
+    }
+
+    /// Builds the chain's timers from the editor.
+    ///
+    /// The first timer starts counting down right away, the others are links
+    /// created with [`Timer::chained`]. Notification steps without text get a
+    /// generic one.
+    ///
+    /// # Arguments
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    /// The timers in order, or `None` if the chain is empty or a step has no duration.
+    #[must_use]
+    pub fn to_timers(&self, now: i64) -> Option<Vec<Timer>> {
+        let timers = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let seconds = step
+                    .minutes
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|minutes| *minutes > 0)
+                    .and_then(|minutes| minutes.checked_mul(60))?;
+                let timer_type = step_timer_type(step, index);
+
+                Some(if index == 0 {
+                    Timer::new(seconds, false, &timer_type, now)
+                } else {
+                    Timer::chained(seconds, &timer_type, now)
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        (!timers.is_empty()).then_some(timers)
+    }
+}
+
+/// Gets the localized name of a chain step action.
+fn action_name(operation: PowerOperation) -> String {
+    match operation {
+        PowerOperation::Suspend => fl!("operation-suspend"),
+        PowerOperation::Hibernate => fl!("operation-hibernate"),
+        PowerOperation::Shutdown => fl!("operation-shutdown"),
+        PowerOperation::Reboot => fl!("operation-reboot"),
+        PowerOperation::Logout => fl!("operation-logout"),
+        PowerOperation::StayAwake | PowerOperation::Notify => fl!("operation-notify"),
+    }
+}
+
+/// Maps a step onto the timer type it runs.
+fn step_timer_type(step: &StepDraft, index: usize) -> TimerType {
+    match step.operation() {
+        PowerOperation::Suspend => TimerType::Suspend,
+        PowerOperation::Hibernate => TimerType::Hibernate,
+        PowerOperation::Shutdown => TimerType::Shutdown,
+        PowerOperation::Reboot => TimerType::Reboot,
+        PowerOperation::Logout => TimerType::Logout,
+        PowerOperation::StayAwake | PowerOperation::Notify => match step.note.trim() {
+            "" => TimerType::UserDefined(fl!("chain-step-default", number = index + 1)),
+            note => TimerType::UserDefined(note.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_page_builds_chain() {
+        let page = Page::default();
+        let timers = page.to_timers(0).unwrap();
+
+        assert_eq!(timers.len(), 3);
+        assert_eq!(timers[0].description, fl!("chain-save-work"));
+        assert_eq!(timers[0].delay_seconds, 0);
+        assert_eq!(timers[1].description, TimerType::Logout.as_str());
+        assert_eq!(timers[1].delay_seconds, 300);
+        assert_eq!(timers[2].description, TimerType::Shutdown.as_str());
+    }
+
+    #[test]
+    fn test_editor_validates_steps() {
+        let mut page = Page::default();
+        let _ = page.update(Message::AddStep);
+        let _ = page.update(Message::MinutesChanged(3, String::new()));
+
+        // The new step has no duration yet, so nothing can start
+        assert!(page.to_timers(0).is_none());
+
+        let _ = page.update(Message::MinutesChanged(3, "0".to_string()));
+        assert!(page.steps[3].minutes.is_empty());
+        let _ = page.update(Message::MinutesChanged(3, "2".to_string()));
+        let _ = page.update(Message::ActionSelected(3, 5));
+
+        let timers = page.to_timers(0).unwrap();
+        assert_eq!(timers[3].description, TimerType::Hibernate.as_str());
+        assert_eq!(timers[3].delay_seconds, 120);
+
+        let _ = page.update(Message::ActionSelected(3, 0));
+        let timers = page.to_timers(0).unwrap();
+        assert_eq!(timers[3].description, fl!("chain-step-default", number = 4));
+
+        for _ in 0..4 {
+            let _ = page.update(Message::RemoveStep(0));
+        }
+        assert!(page.to_timers(0).is_none());
+
+        let _ = page.update(Message::ClearForm);
+        assert_eq!(page.steps.len(), 3);
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let page = Page::default();
+        let _element: Element<'_, Message> = page.view();
+    }
+}
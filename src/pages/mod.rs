@@ -11,6 +11,16 @@
 //! # Pages
 //!
 //! - [`PowerControls`] - Page for scheduling system power operations like shutdown and suspend.
+//! - [`Sequences`] - Page for pomodoro-style sequence timers made of phases.
+//! - [`Chains`] - Page for chains of timers that each run after the one before.
+//! - [`Stopwatch`] - Page for the stopwatch and its laps.
+//! - [`Reminders`] - Page for notification timers with their own text.
+//! - [`History`] - Page listing timers that finished, were cancelled or were missed.
+//! - [`QuickEntry`] - Prompt for typing a timer, opened from a global shortcut.
+//! - [`Settings`] - Page for editing the quick timer presets.
+//!
+//! The popup groups them into top-level tabs (see [`PopupPage`]); the timer
+//! pages share a tab with a second row of tabs (see [`TimerPage`]).
 //!
 //! # Design Principles
 //!
@@ -23,6 +33,102 @@
 //! 5. **Overengineered** - ...probably...
 //!
 
+use serde::{Deserialize, Serialize};
+
+pub mod chains;
+pub mod history;
 pub mod power_controls;
+pub mod quick_entry;
+pub mod reminders;
+pub mod sequences;
+pub mod settings;
+pub mod stopwatch;
 
+pub use chains::Page as Chains;
+pub use history::Page as History;
 pub use power_controls::Page as PowerControls;
+pub use quick_entry::Page as QuickEntry;
+pub use reminders::Page as Reminders;
+pub use sequences::Page as Sequences;
+pub use settings::Page as Settings;
+pub use stopwatch::Page as Stopwatch;
+
+/// Pages that can be shown in the popup, switched with the tabs at its top.
+///
+/// The last page shown is stored in the config, so the popup opens where it was left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PopupPage {
+    #[default]
+    PowerControls,
+    Timers(TimerPage),
+    Reminders,
+    History,
+    Settings,
+}
+
+impl PopupPage {
+    /// Every page in tab order, timer pages in their second row's order.
+    pub const ALL: [Self; 8] = [
+        Self::PowerControls,
+        Self::Timers(TimerPage::Active),
+        Self::Timers(TimerPage::Sequences),
+        Self::Timers(TimerPage::Chains),
+        Self::Timers(TimerPage::Stopwatch),
+        Self::Reminders,
+        Self::History,
+        Self::Settings,
+    ];
+
+    /// Gets the page after (or before) this one in [`Self::ALL`], wrapping around.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::pages::{PopupPage, TimerPage};
+    ///
+    /// let power = PopupPage::PowerControls;
+    /// assert_eq!(power.adjacent(true), PopupPage::Timers(TimerPage::Active));
+    /// assert_eq!(power.adjacent(false), PopupPage::Settings);
+    /// ```
+    #[must_use]
+    pub fn adjacent(self, forward: bool) -> PopupPage {
+        let count = Self::ALL.len();
+        let index = Self::ALL
+            .iter()
+            .position(|page| *page == self)
+            .unwrap_or_default();
+        let next = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        Self::ALL[next]
+    }
+
+    /// Whether both pages sit under the same top-level tab.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::pages::{PopupPage, TimerPage};
+    ///
+    /// let stopwatch = PopupPage::Timers(TimerPage::Stopwatch);
+    /// assert!(stopwatch.same_tab(PopupPage::Timers(TimerPage::Active)));
+    /// assert!(!stopwatch.same_tab(PopupPage::History));
+    /// ```
+    #[must_use]
+    pub fn same_tab(self, other: PopupPage) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+}
+
+/// Pages under the timers tab, switched with the second row of tabs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimerPage {
+    /// Every running timer, with a cancel button per timer
+    #[default]
+    Active,
+    Sequences,
+    Chains,
+    Stopwatch,
+}
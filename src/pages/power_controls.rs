@@ -3,12 +3,18 @@ use crate::{
         PowerForm, ToggleIconRadio, power_form::PowerOperation, radio_components::RadioComponents,
     },
     fl,
+    models::condition::{FileEvent, FileTrigger, MediaTrack, QuietThresholds, TimerCondition},
     utils::{
-        TimeUnit,
+        TimeUnit, filters,
         ui::{Gaps, Padding},
     },
 };
-use cosmic::{Action, Element, Task, iced::Alignment, iced_widget::column, widget::Space};
+use cosmic::{
+    Action, Element, Task,
+    iced::{Alignment, Length},
+    iced_widget::column,
+    widget::{Space, TextInput, container, dropdown, toggler},
+};
 
 /// Messages for the power controls page
 #[derive(Debug, Clone)]
@@ -19,24 +25,125 @@ pub enum Message {
     FormTextChanged(String),
     /// Time unit changed in the power form
     FormTimeUnitChanged(TimeUnit),
+    /// Condition dropdown selection changed
+    ConditionSelected(usize),
+    /// Path input changed for file conditions
+    WatchPathChanged(String),
     /// Form submit button pressed
     FormSubmitPressed,
     /// Clear the form after successful submission
     ClearForm,
     /// Request to toggle stay awake mode
     ToggleStayAwake,
+    /// The "stay awake while media plays" toggler was flipped
+    MediaStayAwakeToggled(bool),
+    /// Request to turn "stay awake while media plays" on or off
+    SetMediaStayAwake(bool),
     /// Request to set suspend timer
-    SetSuspendTime(i32),
+    SetSuspendTime(i32, TimerCondition),
+    /// Request to set hibernate timer
+    SetHibernateTime(i32, TimerCondition),
     /// Request to set shutdown timer
-    SetShutdownTime(i32),
+    SetShutdownTime(i32, TimerCondition),
     /// Request to set logout timer
-    SetLogoutTime(i32),
+    SetLogoutTime(i32, TimerCondition),
     /// Request to set reboot timer
-    SetRebootTime(i32),
+    SetRebootTime(i32, TimerCondition),
+    /// Request to set a notification-only timer
+    SetNotifyTime(i32, TimerCondition),
     /// Request to close the popup
     ClosePopup,
 }
 
+/// When a scheduled power operation should run, as offered in the condition dropdown.
+///
+/// Each choice maps onto a [`TimerCondition`] once the form is submitted. The
+/// battery choice reuses the form's numeric input as a percentage instead of a duration,
+/// and file choices add a path input (appearing and disappearing need no number at all).
+/// The media choice needs no input either, it follows whatever is playing on submit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConditionChoice {
+    /// Run when the countdown ends
+    #[default]
+    AtTime,
+    /// Run when the countdown ends, but only while on AC power
+    AtTimeOnAc,
+    /// Run as soon as the battery drops below the entered percentage
+    BatteryBelow,
+    /// Run once the system has been idle for the entered duration
+    SystemQuiet,
+    /// Run as soon as the entered path exists
+    FileAppears,
+    /// Run as soon as the entered path is gone
+    FileDisappears,
+    /// Run once the entered path hasn't changed for the entered duration
+    FileStopsChanging,
+    /// Run once the currently playing track or episode has ended
+    MediaEnds,
+}
+
+impl ConditionChoice {
+    /// All choices in dropdown order.
+    pub const ALL: [Self; 8] = [
+        Self::AtTime,
+        Self::AtTimeOnAc,
+        Self::BatteryBelow,
+        Self::SystemQuiet,
+        Self::FileAppears,
+        Self::FileDisappears,
+        Self::FileStopsChanging,
+        Self::MediaEnds,
+    ];
+
+    /// Converts a dropdown index to a choice, falling back to `AtTime`.
+    #[must_use]
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    /// Gets the dropdown index for this choice.
+    #[must_use]
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|c| *c == self)
+            .unwrap_or_default()
+    }
+
+    /// Gets the localized dropdown label for this choice.
+    #[must_use]
+    pub fn label(self) -> String {
+        match self {
+            Self::AtTime => fl!("condition-at-time"),
+            Self::AtTimeOnAc => fl!("condition-on-ac"),
+            Self::BatteryBelow => fl!("condition-battery-below"),
+            Self::SystemQuiet => fl!("condition-system-quiet"),
+            Self::FileAppears => fl!("condition-file-appears"),
+            Self::FileDisappears => fl!("condition-file-disappears"),
+            Self::FileStopsChanging => fl!("condition-file-stable"),
+            Self::MediaEnds => fl!("condition-media-ends"),
+        }
+    }
+
+    /// Whether this choice watches a path entered by the user.
+    #[must_use]
+    pub fn watches_path(self) -> bool {
+        matches!(
+            self,
+            Self::FileAppears | Self::FileDisappears | Self::FileStopsChanging
+        )
+    }
+
+    /// Whether this choice needs the form's numeric input.
+    #[must_use]
+    pub fn needs_value(self) -> bool {
+        !matches!(
+            self,
+            Self::FileAppears | Self::FileDisappears | Self::MediaEnds
+        )
+    }
+}
+
 /// Struct representing the power controls page
 ///
 /// Includes radio buttons for power operations and a form for time input
@@ -46,6 +153,10 @@ pub enum Message {
 pub struct Page {
     pub power_buttons: RadioComponents<ToggleIconRadio>,
     pub power_form: PowerForm,
+    pub condition: ConditionChoice,
+    pub condition_labels: Vec<String>,
+    pub watch_path: String,
+    pub media_stay_awake: bool,
 }
 
 impl Default for Page {
@@ -73,8 +184,23 @@ impl Default for Page {
                     PowerOperation::Shutdown.index(),
                     PowerOperation::Shutdown.icon_name(),
                 ),
+                ToggleIconRadio::new(
+                    PowerOperation::Hibernate.index(),
+                    PowerOperation::Hibernate.icon_name(),
+                ),
+                ToggleIconRadio::new(
+                    PowerOperation::Notify.index(),
+                    PowerOperation::Notify.icon_name(),
+                ),
             ]),
             power_form: PowerForm::new(fl!("set-time-label", operation = fl!("operation-suspend"))),
+            condition: ConditionChoice::default(),
+            condition_labels: ConditionChoice::ALL
+                .iter()
+                .map(|choice| choice.label())
+                .collect(),
+            watch_path: String::new(),
+            media_stay_awake: false,
         }
     }
 }
@@ -82,28 +208,60 @@ impl Default for Page {
 impl Page {
     /// Render the power controls page
     ///
-    /// Displays radio buttons and conditionally shows the power form
-    /// based on the selected operation.
+    /// Displays radio buttons and the "stay awake while media plays" toggler, and
+    /// conditionally shows the condition dropdown and power form based on the
+    /// selected operation.
     ///
     /// # Returns
     /// An `Element` representing the page view
     pub fn view(&self) -> Element<'_, Message> {
         let power_buttons = self.power_buttons.view(Message::RadioOptionSelected);
+        let media_toggle = container(
+            toggler(self.media_stay_awake)
+                .label(fl!("stay-awake-media"))
+                .on_toggle(Message::MediaStayAwakeToggled),
+        )
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill);
 
         // Show power form only if one of the radio buttons is active (not stay-awake)
         let form = if let Some(index) = self.power_buttons.selected
             && index > 0
         {
-            self.power_form.view(
+            let mut form = column![
+                container(dropdown(
+                    &self.condition_labels,
+                    Some(self.condition.index()),
+                    Message::ConditionSelected,
+                ))
+                .padding(Padding::horizontal(24))
+                .width(Length::Fill),
+            ];
+
+            if self.condition.watches_path() {
+                form = form.push(
+                    container(
+                        TextInput::new(fl!("watch-path-label"), &self.watch_path)
+                            .on_input(Message::WatchPathChanged)
+                            .on_submit(|_| Message::FormSubmitPressed)
+                            .width(Length::Fill),
+                    )
+                    .padding(Padding::horizontal(24)),
+                );
+            }
+
+            form.push(self.power_form.view(
                 Message::FormTextChanged,
                 Message::FormTimeUnitChanged,
                 Message::FormSubmitPressed,
-            )
+            ))
+            .spacing(Gaps::s())
+            .into()
         } else {
             Space::new(0, 0).into()
         };
 
-        column![power_buttons, form]
+        column![power_buttons, media_toggle, form]
             .align_x(Alignment::Center)
             .padding(Padding::no_bottom())
             .spacing(Gaps::s())
@@ -132,16 +290,39 @@ impl Page {
                 self.power_form.time_unit = unit;
                 Task::none()
             }
+            Message::ConditionSelected(index) => {
+                self.condition = ConditionChoice::from_index(index);
+                self.power_form.show_time_unit = self.condition != ConditionChoice::BatteryBelow;
+                self.power_form.show_value_input = self.condition.needs_value();
+                self.refresh_placeholder();
+                Task::none()
+            }
+            Message::WatchPathChanged(path) => {
+                self.watch_path = path;
+                Task::none()
+            }
+            Message::MediaStayAwakeToggled(enabled) => {
+                self.media_stay_awake = enabled;
+                Task::done(Action::App(Message::SetMediaStayAwake(enabled)))
+            }
             Message::FormSubmitPressed => self.handle_form_submit(),
             Message::ClearForm => {
                 self.power_form.clear();
+                self.condition = ConditionChoice::default();
+                self.watch_path.clear();
+                self.power_form.show_time_unit = true;
+                self.power_form.show_value_input = true;
+                self.refresh_placeholder();
                 Task::none()
             }
             Message::ToggleStayAwake
-            | Message::SetSuspendTime(_)
-            | Message::SetShutdownTime(_)
-            | Message::SetLogoutTime(_)
-            | Message::SetRebootTime(_)
+            | Message::SetMediaStayAwake(_)
+            | Message::SetSuspendTime(..)
+            | Message::SetHibernateTime(..)
+            | Message::SetShutdownTime(..)
+            | Message::SetLogoutTime(..)
+            | Message::SetRebootTime(..)
+            | Message::SetNotifyTime(..)
             | Message::ClosePopup => Task::none(),
         }
     }
@@ -178,7 +359,7 @@ impl Page {
 
         // For other buttons, select normally
         self.power_buttons.selected = Some(new_index);
-        self.power_form.placeholder_text = operation.placeholder_text();
+        self.refresh_placeholder();
 
         // If switching from stay awake to another option, toggle it off
         if previous == Some(PowerOperation::StayAwake.index()) {
@@ -188,31 +369,121 @@ impl Page {
         }
     }
 
+    /// Get the power operation the arrow keys move to
+    ///
+    /// Stay-awake is stepped over: selecting it toggles stay-awake and closes the
+    /// popup, which is no place to land while arrowing through the options.
+    ///
+    /// # Arguments
+    /// - `forward` - Move to the next operation instead of the previous one
+    ///
+    /// # Returns
+    /// The index of the operation to select, `None` if there is none
+    #[must_use]
+    pub fn adjacent_operation(&self, forward: bool) -> Option<usize> {
+        let next = self.power_buttons.adjacent(forward)?;
+        if PowerOperation::from_index(next) == PowerOperation::StayAwake {
+            self.power_buttons.adjacent_to(Some(next), forward)
+        } else {
+            Some(next)
+        }
+    }
+
+    /// Update the form placeholder to match the selected operation and condition
+    ///
+    /// The battery condition asks for a percentage rather than a duration.
+    fn refresh_placeholder(&mut self) {
+        self.power_form.placeholder_text = if self.condition == ConditionChoice::BatteryBelow {
+            fl!("battery-percent-label")
+        } else {
+            PowerOperation::from_index(self.power_buttons.selected.unwrap_or_default())
+                .placeholder_text()
+        };
+    }
+
     /// Handle form submission
     ///
     /// Validates the input and constructs the appropriate action
-    /// based on the selected power operation.
+    /// based on the selected power operation and condition.
     ///
     /// # Returns
     /// A `Task` representing any actions to be taken
     fn handle_form_submit(&mut self) -> Task<Action<Message>> {
-        if !self.power_form.validate_input() {
-            self.power_form.clear();
-            return Task::none();
-        }
+        let input = if self.condition.needs_value() {
+            if !self.power_form.validate_input() {
+                self.power_form.clear();
+                return Task::none();
+            }
+            self.power_form.input_value.parse::<i32>().unwrap()
+        } else {
+            0
+        };
+        let seconds = input.saturating_mul(self.power_form.time_unit.to_seconds_multiplier());
 
-        let value = self.power_form.input_value.parse::<i32>().unwrap()
-            * self.power_form.time_unit.to_seconds_multiplier();
+        let watch_path = if self.condition.watches_path() {
+            // Keep the typed path around so it can be fixed instead of retyped
+            let Some(path) = filters::parse_watch_path(&self.watch_path) else {
+                return Task::none();
+            };
+            Some(path)
+        } else {
+            None
+        };
+        let file_trigger = |event| {
+            TimerCondition::File(FileTrigger {
+                path: watch_path.clone().unwrap_or_default(),
+                event,
+            })
+        };
+
+        let (value, condition) = match self.condition {
+            ConditionChoice::AtTime => (seconds, TimerCondition::None),
+            ConditionChoice::AtTimeOnAc => (seconds, TimerCondition::OnAcPower),
+            ConditionChoice::BatteryBelow => match u8::try_from(input) {
+                Ok(percent) if percent <= 100 => (0, TimerCondition::BatteryBelow(percent)),
+                _ => {
+                    self.power_form.clear();
+                    return Task::none();
+                }
+            },
+            ConditionChoice::SystemQuiet => (
+                seconds,
+                TimerCondition::SystemQuiet(QuietThresholds {
+                    window_secs: seconds.unsigned_abs().max(QuietThresholds::MIN_WINDOW_SECS),
+                    ..QuietThresholds::default()
+                }),
+            ),
+            ConditionChoice::FileAppears => (0, file_trigger(FileEvent::Appears)),
+            ConditionChoice::FileDisappears => (0, file_trigger(FileEvent::Disappears)),
+            ConditionChoice::FileStopsChanging => (
+                seconds,
+                file_trigger(FileEvent::StopsChanging(seconds.unsigned_abs())),
+            ),
+            // The app binds this to whatever is playing when the timer is created
+            ConditionChoice::MediaEnds => (0, TimerCondition::MediaEnds(MediaTrack::default())),
+        };
 
         if let Some(index) = self.power_buttons.selected {
             let operation = PowerOperation::from_index(index);
             match operation {
-                PowerOperation::Suspend => Task::done(Action::App(Message::SetSuspendTime(value))),
+                PowerOperation::Suspend => {
+                    Task::done(Action::App(Message::SetSuspendTime(value, condition)))
+                }
+                PowerOperation::Hibernate => {
+                    Task::done(Action::App(Message::SetHibernateTime(value, condition)))
+                }
                 PowerOperation::Shutdown => {
-                    Task::done(Action::App(Message::SetShutdownTime(value)))
+                    Task::done(Action::App(Message::SetShutdownTime(value, condition)))
+                }
+                PowerOperation::Reboot => {
+                    Task::done(Action::App(Message::SetRebootTime(value, condition)))
+                }
+                PowerOperation::Logout => {
+                    Task::done(Action::App(Message::SetLogoutTime(value, condition)))
+                }
+                PowerOperation::Notify => {
+                    Task::done(Action::App(Message::SetNotifyTime(value, condition)))
                 }
-                PowerOperation::Reboot => Task::done(Action::App(Message::SetRebootTime(value))),
-                PowerOperation::Logout => Task::done(Action::App(Message::SetLogoutTime(value))),
                 PowerOperation::StayAwake => Task::none(),
             }
         } else {
@@ -232,13 +503,25 @@ mod tests {
     #[test]
     fn test_create_power_controls() {
         let page = get_test_page();
-        assert_eq!(page.power_buttons.options.len(), 5);
+        assert_eq!(page.power_buttons.options.len(), 7);
+        assert_eq!(page.condition, ConditionChoice::AtTime);
         assert_eq!(
             page.power_form.placeholder_text,
             fl!("set-time-label", operation = fl!("operation-suspend"))
         );
     }
 
+    #[test]
+    fn test_adjacent_operation_skips_stay_awake() {
+        let mut page = get_test_page();
+        assert_eq!(page.adjacent_operation(true), Some(1));
+        assert_eq!(page.adjacent_operation(false), Some(6));
+
+        let _ = page.update(Message::RadioOptionSelected(1));
+        assert_eq!(page.adjacent_operation(true), Some(2));
+        assert_eq!(page.adjacent_operation(false), Some(6));
+    }
+
     #[test]
     fn test_radio_selection_updates_placeholder() {
         let mut page = get_test_page();
@@ -285,4 +568,106 @@ mod tests {
         let _ = page.update(Message::FormTimeUnitChanged(TimeUnit::Minutes));
         assert_eq!(page.power_form.time_unit, TimeUnit::Minutes);
     }
+
+    #[test]
+    fn test_battery_condition_hides_time_unit() {
+        let mut page = get_test_page();
+        let _ = page.update(Message::RadioOptionSelected(
+            PowerOperation::Hibernate.index(),
+        ));
+
+        let _ = page.update(Message::ConditionSelected(
+            ConditionChoice::BatteryBelow.index(),
+        ));
+        assert!(!page.power_form.show_time_unit);
+        assert_eq!(
+            page.power_form.placeholder_text,
+            fl!("battery-percent-label")
+        );
+
+        // Clearing the form goes back to a plain countdown
+        let _ = page.update(Message::ClearForm);
+        assert_eq!(page.condition, ConditionChoice::AtTime);
+        assert!(page.power_form.show_time_unit);
+        assert_eq!(
+            page.power_form.placeholder_text,
+            fl!("set-time-label", operation = fl!("operation-hibernate"))
+        );
+    }
+
+    #[test]
+    fn test_condition_choice_index_round_trip() {
+        for choice in ConditionChoice::ALL {
+            assert_eq!(ConditionChoice::from_index(choice.index()), choice);
+        }
+        assert_eq!(ConditionChoice::from_index(99), ConditionChoice::AtTime);
+    }
+
+    #[test]
+    fn test_quiet_condition_keeps_duration_input() {
+        let mut page = get_test_page();
+        let _ = page.update(Message::RadioOptionSelected(
+            PowerOperation::Shutdown.index(),
+        ));
+
+        // The quiet window is entered like a countdown
+        let _ = page.update(Message::ConditionSelected(
+            ConditionChoice::SystemQuiet.index(),
+        ));
+        assert_eq!(page.condition, ConditionChoice::SystemQuiet);
+        assert!(page.power_form.show_time_unit);
+        assert_eq!(
+            page.power_form.placeholder_text,
+            fl!("set-time-label", operation = fl!("operation-shutdown"))
+        );
+    }
+
+    #[test]
+    fn test_file_condition_submits_watch_path() {
+        let mut page = get_test_page();
+        let _ = page.update(Message::RadioOptionSelected(PowerOperation::Notify.index()));
+        let _ = page.update(Message::ConditionSelected(
+            ConditionChoice::FileAppears.index(),
+        ));
+        assert!(!page.power_form.show_value_input);
+
+        // Relative paths are rejected and kept for editing
+        let _ = page.update(Message::WatchPathChanged("output.mkv".to_string()));
+        let _ = page.update(Message::FormSubmitPressed);
+        assert_eq!(page.watch_path, "output.mkv");
+
+        let _ = page.update(Message::WatchPathChanged("/renders/output.mkv".to_string()));
+        let _ = page.update(Message::FormSubmitPressed);
+
+        // Clearing the form resets the file inputs
+        let _ = page.update(Message::ClearForm);
+        assert!(page.watch_path.is_empty());
+        assert!(page.power_form.show_value_input);
+    }
+
+    #[test]
+    fn test_media_toggle_requests_mode_change() {
+        let mut page = get_test_page();
+        assert!(!page.media_stay_awake);
+
+        let _ = page.update(Message::MediaStayAwakeToggled(true));
+        assert!(page.media_stay_awake);
+
+        let _ = page.update(Message::MediaStayAwakeToggled(false));
+        assert!(!page.media_stay_awake);
+    }
+
+    #[test]
+    fn test_media_condition_needs_no_input() {
+        let mut page = get_test_page();
+        let _ = page.update(Message::RadioOptionSelected(
+            PowerOperation::Suspend.index(),
+        ));
+        let _ = page.update(Message::ConditionSelected(
+            ConditionChoice::MediaEnds.index(),
+        ));
+        assert_eq!(page.condition, ConditionChoice::MediaEnds);
+        assert!(!page.power_form.show_value_input);
+        assert!(!page.condition.watches_path());
+    }
 }
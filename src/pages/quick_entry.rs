@@ -0,0 +1,277 @@
+//! Quick entry prompt.
+//!
+//! A single text input for starting a timer by typing it, e.g. `25m standup`
+//! or `1h shutdown`. It is opened from a global shortcut through D-Bus
+//! activation (see [`crate::utils::activation`]) and shown in the popup in
+//! place of the tabbed pages, so it works without reaching for the panel.
+//!
+//! The duration comes first (or last) and uses [`parse_duration`]. A time of
+//! day such as `8:00` (or `at 8:00`) can take its place, for a timer that ends
+//! when the local clock shows that time, see [`Timer::at`](crate::models::Timer::at).
+//! The rest is the reminder text, unless it names a power operation, which
+//! schedules that operation instead.
+
+use crate::{
+    components::timer_list::operation_name,
+    fl,
+    models::timer::TimerType,
+    utils::{
+        format_duration,
+        time::{parse_duration, parse_time_of_day},
+        ui::{Gaps, Padding},
+    },
+};
+use chrono::NaiveTime;
+use cosmic::{
+    Action, Element, Task,
+    iced::Length,
+    iced_widget::column,
+    widget::{Id, TextInput, text},
+};
+
+/// Messages for the quick entry prompt
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Prompt text changed
+    InputChanged(String),
+    /// Enter pressed in the prompt
+    SubmitPressed,
+    /// Request to create a timer that ends at the given time
+    Create(When, TimerType),
+}
+
+/// When a quick entry timer ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    /// After the given number of seconds
+    In(i32),
+    /// The next time the local clock shows this time of day
+    At(NaiveTime),
+}
+
+/// Struct representing the quick entry prompt
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    pub input: String,
+}
+
+/// Gets the id of the prompt's text input, so it can be focused when the prompt opens.
+#[must_use]
+pub fn input_id() -> Id {
+    Id::new("quick-entry")
+}
+
+impl Page {
+    /// Render the quick entry prompt
+    ///
+    /// Below the input, a preview says what the typed text will start, or an
+    /// example while it doesn't parse yet.
+    ///
+    /// # Returns
+    /// An `Element` representing the page view
+    pub fn view(&self) -> Element<'_, Message> {
+        let preview = match parse_quick_entry(&self.input) {
+            Some((When::In(seconds), timer_type)) => fl!(
+                "quick-entry-preview",
+                name = operation_name(&timer_type),
+                time = format_duration(seconds)
+            ),
+            Some((When::At(time), timer_type)) => fl!(
+                "quick-entry-preview-at",
+                name = operation_name(&timer_type),
+                time = time.format("%H:%M").to_string()
+            ),
+            None => fl!("quick-entry-hint"),
+        };
+
+        column![
+            text::heading(fl!("quick-entry-title")),
+            TextInput::new(fl!("quick-entry-placeholder"), &self.input)
+                .id(input_id())
+                .on_input(Message::InputChanged)
+                .on_submit(|_| Message::SubmitPressed)
+                .width(Length::Fill),
+            text::caption(preview),
+        ]
+        .spacing(Gaps::xs())
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill)
+        .into()
+    }
+
+    /// Update the quick entry prompt state based on messages
+    ///
+    /// `Create` is handled by the app and ignored here.
+    ///
+    /// # Arguments
+    /// - `message` - The message to process
+    ///
+    /// # Returns
+    /// A `Task` representing any actions to be taken
+    pub fn update(&mut self, message: Message) -> Task<Action<Message>> {
+        match message {
+            Message::InputChanged(input) => self.input = input,
+            Message::SubmitPressed => {
+                if let Some((when, timer_type)) = parse_quick_entry(&self.input) {
+                    return Task::done(Action::App(Message::Create(when, timer_type)));
+                }
+            }
+            Message::Create(..) => {}
+        }
+        Task::none()
+    }
+}
+
+/// Parses a typed quick entry into a timer.
+///
+/// # Arguments
+///
+/// - `input` - A duration or a time of day and what to do, in either order,
+///   e.g. `25m standup`, `shutdown 1h` or `standup at 9:30`. Without text a
+///   generic reminder is used.
+///
+/// # Returns
+///
+/// When the timer ends and its type, or `None` without a valid duration or time.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chrono::NaiveTime;
+/// use chronomancer::models::timer::TimerType;
+/// use chronomancer::pages::quick_entry::{When, parse_quick_entry};
+///
+/// assert_eq!(
+///     parse_quick_entry("25m standup"),
+///     Some((When::In(1500), TimerType::UserDefined("standup".to_string())))
+/// );
+/// assert_eq!(parse_quick_entry("shutdown 1h"), Some((When::In(3600), TimerType::Shutdown)));
+/// assert_eq!(
+///     parse_quick_entry("at 23:00 shutdown"),
+///     Some((When::At(NaiveTime::from_hms_opt(23, 0, 0).unwrap()), TimerType::Shutdown))
+/// );
+/// assert_eq!(parse_quick_entry("standup"), None);
+/// ```
+#[must_use]
+pub fn parse_quick_entry(input: &str) -> Option<(When, TimerType)> {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    // "at 8:00 standup" and "standup at 8:00" read better with the "at"
+    let is_time_at =
+        |at: &str, time: &str| at.eq_ignore_ascii_case("at") && parse_time_of_day(time).is_some();
+    if let [at, time, ..] = words[..]
+        && is_time_at(at, time)
+    {
+        words.remove(0);
+    } else if let [.., at, time] = words[..]
+        && is_time_at(at, time)
+    {
+        words.remove(words.len() - 2);
+    }
+
+    let parse_when = |word: &str| {
+        parse_time_of_day(word)
+            .map(When::At)
+            .or_else(|| parse_duration(word).map(When::In))
+    };
+    let (when, text) = words
+        .split_first()
+        .and_then(|(first, rest)| Some((parse_when(first)?, rest)))
+        .or_else(|| {
+            let (last, rest) = words.split_last()?;
+            Some((parse_when(last)?, rest))
+        })?;
+
+    let text = text.join(" ");
+    let timer_type = match text.to_lowercase().as_str() {
+        "" => TimerType::UserDefined(fl!("quick-entry-default")),
+        "suspend" | "sleep" => TimerType::Suspend,
+        "hibernate" => TimerType::Hibernate,
+        "logout" | "log out" => TimerType::Logout,
+        "shutdown" | "shut down" | "power off" => TimerType::Shutdown,
+        "reboot" | "restart" => TimerType::Reboot,
+        _ => TimerType::UserDefined(text.to_string()),
+    };
+    Some((when, timer_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quick_entry() {
+        assert_eq!(
+            parse_quick_entry("  1h30m   water the plants "),
+            Some((
+                When::In(5400),
+                TimerType::UserDefined("water the plants".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_quick_entry("Take out the tea 4m"),
+            Some((
+                When::In(240),
+                TimerType::UserDefined("Take out the tea".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_quick_entry("10m Shut Down"),
+            Some((When::In(600), TimerType::Shutdown))
+        );
+        assert_eq!(
+            parse_quick_entry("90s"),
+            Some((
+                When::In(90),
+                TimerType::UserDefined(fl!("quick-entry-default"))
+            ))
+        );
+        assert_eq!(parse_quick_entry("tea soon"), None);
+        assert_eq!(parse_quick_entry(""), None);
+    }
+
+    #[test]
+    fn test_parse_quick_entry_time_of_day() {
+        let nine_thirty = When::At(NaiveTime::from_hms_opt(9, 30, 0).unwrap());
+        assert_eq!(
+            parse_quick_entry("9:30 standup"),
+            Some((nine_thirty, TimerType::UserDefined("standup".to_string())))
+        );
+        assert_eq!(
+            parse_quick_entry("At 9:30 standup"),
+            Some((nine_thirty, TimerType::UserDefined("standup".to_string())))
+        );
+        assert_eq!(
+            parse_quick_entry("meet at the station at 9:30"),
+            Some((
+                nine_thirty,
+                TimerType::UserDefined("meet at the station".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_quick_entry("at 9:30"),
+            Some((
+                nine_thirty,
+                TimerType::UserDefined(fl!("quick-entry-default"))
+            ))
+        );
+        assert_eq!(parse_quick_entry("at 24:00"), None);
+        assert_eq!(parse_quick_entry("look at"), None);
+    }
+
+    #[test]
+    fn test_submit_only_valid_entries() {
+        let mut page = Page::default();
+        let _ = page.update(Message::InputChanged("standup".to_string()));
+        let _ = page.update(Message::SubmitPressed);
+        // Nothing to check on the task itself, but the input is kept for fixing
+        assert_eq!(page.input, "standup");
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let page = Page {
+            input: "25m standup".to_string(),
+        };
+        let _element: Element<'_, Message> = page.view();
+    }
+}
@@ -0,0 +1,211 @@
+//! Reminders page.
+//!
+//! A short form for notification timers with their own text ("Take the tea
+//! out in 4 minutes"), above the reminders that are still waiting to fire.
+//! Reminders are ordinary user-defined timers, so they also show up in the
+//! active timer list and the panel countdown.
+
+use std::str::FromStr;
+
+use crate::{
+    components::timer_list::{focus_marker, timer_summary},
+    fl,
+    models::timer::{Timer, TimerType},
+    utils::{
+        filters,
+        ui::{Gaps, Padding},
+    },
+};
+use cosmic::{
+    Action, Element, Task,
+    iced::{Alignment, Length},
+    iced_widget::{column, row},
+    widget::{Space, TextInput, button, icon, text},
+};
+
+/// Width of the minutes input.
+const MINUTES_INPUT_WIDTH: f32 = 64.0;
+
+/// Messages for the reminders page
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Reminder text input changed
+    TextChanged(String),
+    /// Minutes input changed
+    MinutesChanged(String),
+    /// Submit button pressed (or Enter in the text input)
+    SubmitPressed,
+    /// Reset the form after a reminder was created
+    ClearForm,
+    /// Request to create a reminder after the given number of seconds with the given text
+    Create(i32, String),
+    /// Cancel the reminder timer with the given id
+    Cancel(i64),
+}
+
+/// Struct representing the reminders page
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    pub text: String,
+    pub minutes: String,
+}
+
+impl Page {
+    /// Render the reminders page
+    ///
+    /// # Arguments
+    /// - `timers` - The active timers, of which only reminders are listed
+    /// - `now` - Current Unix timestamp used for remaining time
+    /// - `focused` - Id of the reminder focused with the keyboard, if any
+    ///
+    /// # Returns
+    /// An `Element` representing the page view
+    pub fn view<'a>(
+        &'a self,
+        timers: &'a [Timer],
+        now: i64,
+        focused: Option<i64>,
+    ) -> Element<'a, Message> {
+        let form = row![
+            TextInput::new(fl!("reminder-text"), &self.text)
+                .on_input(Message::TextChanged)
+                .on_submit(|_| Message::SubmitPressed)
+                .width(Length::Fill),
+            TextInput::new(fl!("minutes"), &self.minutes)
+                .on_input(Message::MinutesChanged)
+                .on_submit(|_| Message::SubmitPressed)
+                .width(MINUTES_INPUT_WIDTH),
+            button::suggested(fl!("reminder-create")).on_press_maybe(
+                self.to_request()
+                    .is_some()
+                    .then_some(Message::SubmitPressed)
+            ),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(Gaps::xs());
+
+        let reminders: Vec<Element<'a, Message>> = timers
+            .iter()
+            .filter_map(|timer| match TimerType::from_str(&timer.description) {
+                Ok(TimerType::UserDefined(description)) => Some((timer, description)),
+                _ => None,
+            })
+            .map(|(timer, description)| {
+                row![
+                    focus_marker(focused == Some(timer.id)),
+                    text(description),
+                    Space::with_width(Length::Fill),
+                    text(timer_summary(timer, now)),
+                    button::icon(icon::from_name("window-close-symbolic"))
+                        .on_press(Message::Cancel(timer.id)),
+                ]
+                .align_y(Alignment::Center)
+                .spacing(Gaps::s())
+                .into()
+            })
+            .collect();
+
+        let mut content = column![text::heading(fl!("reminder-new")), form];
+        content = if reminders.is_empty() {
+            content.push(text::caption(fl!("reminder-none")))
+        } else {
+            content
+                .push(text::heading(fl!("reminder-pending")))
+                .extend(reminders)
+        };
+
+        content
+            .spacing(Gaps::xs())
+            .padding(Padding::horizontal(24))
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Update the reminders page state based on messages
+    ///
+    /// Form messages update the draft. `Create` and `Cancel` are handled by the app and ignored here.
+    ///
+    /// # Arguments
+    /// - `message` - The message to process
+    ///
+    /// # Returns
+    /// A `Task` representing any actions to be taken
+    pub fn update(&mut self, message: Message) -> Task<Action<Message>> {
+        match message {
+            Message::TextChanged(text) => self.text = text,
+            Message::MinutesChanged(minutes) => {
+                if let Some(minutes) = filters::filter_positive_integer(&minutes) {
+                    self.minutes = minutes;
+                }
+            }
+            Message::SubmitPressed => {
+                if let Some((seconds, text)) = self.to_request() {
+                    return Task::done(Action::App(Message::Create(seconds, text)));
+                }
+            }
+            Message::ClearForm => *self = Self::default(),
+            Message::Create(..) | Message::Cancel(_) => {}
+        }
+        Task::none()
+    }
+
+    /// Checks whether a timer is a reminder, i.e. a notification with its own text.
+    ///
+    /// # Arguments
+    /// - `timer` - The timer to check
+    #[must_use]
+    pub fn is_reminder(timer: &Timer) -> bool {
+        matches!(
+            TimerType::from_str(&timer.description),
+            Ok(TimerType::UserDefined(_))
+        )
+    }
+
+    /// Validates the form.
+    ///
+    /// # Returns
+    /// The delay in seconds and the trimmed text, or `None` if either is missing.
+    #[must_use]
+    pub fn to_request(&self) -> Option<(i32, String)> {
+        let text = self.text.trim();
+        let seconds = self
+            .minutes
+            .parse::<i32>()
+            .ok()
+            .filter(|minutes| *minutes > 0)
+            .and_then(|minutes| minutes.checked_mul(60))?;
+
+        (!text.is_empty()).then(|| (seconds, text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_validates_and_submits() {
+        let mut page = Page::default();
+        let _ = page.update(Message::TextChanged("  Tea  ".to_string()));
+        assert!(page.to_request().is_none());
+
+        let _ = page.update(Message::MinutesChanged("0".to_string()));
+        assert!(page.minutes.is_empty());
+        let _ = page.update(Message::MinutesChanged("4".to_string()));
+        assert_eq!(page.to_request(), Some((240, "Tea".to_string())));
+
+        let _ = page.update(Message::ClearForm);
+        assert!(page.text.is_empty());
+        assert!(page.to_request().is_none());
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let timers = vec![
+            Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 0),
+            Timer::new(60, false, &TimerType::Shutdown, 0),
+        ];
+        let page = Page::default();
+        let _element: Element<'_, Message> = page.view(&timers, 0, Some(0));
+    }
+}
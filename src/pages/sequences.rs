@@ -0,0 +1,358 @@
+//! Sequence timers page.
+//!
+//! Lists running sequences with pause, skip and stop controls, and offers an
+//! editor for starting a new one. The editor starts out as a classic pomodoro
+//! (work and break four times, then a long break) so the common case is a
+//! single click on "Start".
+
+use crate::{
+    components::timer_list::focus_marker,
+    fl,
+    models::sequence::{Phase, Sequence},
+    utils::{
+        filters,
+        time::format_countdown,
+        ui::{Gaps, Padding},
+    },
+};
+use cosmic::{
+    Action, Element, Task,
+    iced::{Alignment, Length},
+    iced_widget::{column, row},
+    widget::{Space, TextInput, button, checkbox, icon, text},
+};
+
+/// Width of the minutes input in a phase row.
+const MINUTES_INPUT_WIDTH: f32 = 64.0;
+
+/// Messages for the sequences page
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Sequence name input changed
+    NameChanged(String),
+    /// Rounds input changed
+    RoundsChanged(String),
+    /// Phase name input changed
+    PhaseNameChanged(usize, String),
+    /// Phase minutes input changed
+    PhaseMinutesChanged(usize, String),
+    /// Phase "after the last round" checkbox toggled
+    PhaseFinalToggled(usize, bool),
+    /// Add an empty phase to the editor
+    AddPhase,
+    /// Remove a phase from the editor
+    RemovePhase(usize),
+    /// Start button pressed
+    StartPressed,
+    /// Reset the editor after a sequence was started
+    ClearForm,
+    /// Request to start the given sequence
+    Start(Sequence),
+    /// Request to pause the running sequence with the given id
+    Pause(i64),
+    /// Request to resume the paused sequence with the given id
+    Resume(i64),
+    /// Request to skip to the next phase of the sequence with the given id
+    Skip(i64),
+    /// Request to stop the sequence with the given id
+    Stop(i64),
+}
+
+/// A phase as typed into the editor, validated when the sequence is started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseDraft {
+    pub name: String,
+    pub minutes: String,
+    pub is_final: bool,
+}
+
+impl PhaseDraft {
+    fn new(name: String, minutes: u32, is_final: bool) -> Self {
+        Self {
+            name,
+            minutes: minutes.to_string(),
+            is_final,
+        }
+    }
+}
+
+/// Struct representing the sequences page
+///
+/// Holds the editor state for a new sequence. Running sequences are owned by the
+/// app and passed into [`Page::view`].
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub name: String,
+    pub rounds: String,
+    pub phases: Vec<PhaseDraft>,
+}
+
+impl Default for Page {
+    /// Create a default instance of `Page`, prefilled with a pomodoro
+    fn default() -> Self {
+        Self {
+            name: fl!("sequence-pomodoro"),
+            rounds: "4".to_string(),
+            phases: vec![
+                PhaseDraft::new(fl!("sequence-work"), 25, false),
+                PhaseDraft::new(fl!("sequence-break"), 5, false),
+                PhaseDraft::new(fl!("sequence-long-break"), 15, true),
+            ],
+        }
+    }
+}
+
+impl Page {
+    /// Render the sequences page
+    ///
+    /// # Arguments
+    /// - `running` - Sequences currently running or paused
+    /// - `now` - Current Unix timestamp used for the countdowns
+    /// - `focused` - Id of the sequence focused with the keyboard, if any
+    ///
+    /// # Returns
+    /// An `Element` representing the page view
+    pub fn view<'a>(
+        &'a self,
+        running: &'a [Sequence],
+        now: i64,
+        focused: Option<i64>,
+    ) -> Element<'a, Message> {
+        let mut content = column![].spacing(Gaps::s()).width(Length::Fill);
+
+        if !running.is_empty() {
+            let rows = running
+                .iter()
+                .map(|sequence| running_row(sequence, now, focused == Some(sequence.id)));
+            content = content.push(
+                column![text::heading(fl!("sequence-running"))]
+                    .extend(rows)
+                    .spacing(Gaps::xs()),
+            );
+        }
+
+        let phase_rows = self.phases.iter().enumerate().map(|(index, phase)| {
+            row![
+                TextInput::new(fl!("sequence-phase-name"), &phase.name)
+                    .on_input(move |name| Message::PhaseNameChanged(index, name))
+                    .width(Length::Fill),
+                TextInput::new(fl!("minutes"), &phase.minutes)
+                    .on_input(move |minutes| Message::PhaseMinutesChanged(index, minutes))
+                    .width(MINUTES_INPUT_WIDTH),
+                checkbox(fl!("sequence-final"), phase.is_final)
+                    .on_toggle(move |is_final| Message::PhaseFinalToggled(index, is_final)),
+                button::icon(icon::from_name("list-remove-symbolic"))
+                    .on_press(Message::RemovePhase(index)),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(Gaps::xs())
+            .into()
+        });
+
+        let editor = column![
+            text::heading(fl!("sequence-new")),
+            TextInput::new(fl!("sequence-name"), &self.name)
+                .on_input(Message::NameChanged)
+                .width(Length::Fill),
+        ]
+        .extend(phase_rows)
+        .push(
+            row![
+                button::text(fl!("sequence-add-phase")).on_press(Message::AddPhase),
+                Space::with_width(Length::Fill),
+                text(fl!("sequence-rounds")),
+                TextInput::new("1", &self.rounds)
+                    .on_input(Message::RoundsChanged)
+                    .on_submit(|_| Message::StartPressed)
+                    .width(MINUTES_INPUT_WIDTH),
+                button::suggested(fl!("sequence-start")).on_press(Message::StartPressed),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(Gaps::xs()),
+        )
+        .spacing(Gaps::xs());
+
+        content.push(editor).padding(Padding::horizontal(24)).into()
+    }
+
+    /// Update the sequences page state based on messages
+    ///
+    /// Editor messages update the draft. App-level messages, including
+    /// `StartPressed` (the app builds the sequence with [`Page::to_sequence`]),
+    /// are ignored here returning `Task::none()`.
+    ///
+    /// # Arguments
+    /// - `message` - The message to process
+    ///
+    /// # Returns
+    /// A `Task` representing any actions to be taken
+    pub fn update(&mut self, message: Message) -> Task<Action<Message>> {
+        match message {
+            Message::NameChanged(name) => self.name = name,
+            Message::RoundsChanged(rounds) => {
+                if let Some(rounds) = filters::filter_positive_integer(&rounds) {
+                    self.rounds = rounds;
+                }
+            }
+            Message::PhaseNameChanged(index, name) => {
+                if let Some(phase) = self.phases.get_mut(index) {
+                    phase.name = name;
+                }
+            }
+            Message::PhaseMinutesChanged(index, minutes) => {
+                if let Some(phase) = self.phases.get_mut(index)
+                    && let Some(minutes) = filters::filter_positive_integer(&minutes)
+                {
+                    phase.minutes = minutes;
+                }
+            }
+            Message::PhaseFinalToggled(index, is_final) => {
+                if let Some(phase) = self.phases.get_mut(index) {
+                    phase.is_final = is_final;
+                }
+            }
+            Message::AddPhase => self.phases.push(PhaseDraft {
+                name: String::new(),
+                minutes: String::new(),
+                is_final: false,
+            }),
+            Message::RemovePhase(index) => {
+                if index < self.phases.len() {
+                    self.phases.remove(index);
+                }
+            }
+            Message::ClearForm => *self = Self::default(),
+            Message::StartPressed
+            | Message::Start(_)
+            | Message::Pause(_)
+            | Message::Resume(_)
+            | Message::Skip(_)
+            | Message::Stop(_) => {}
+        }
+        Task::none()
+    }
+
+    /// Builds a sequence from the editor.
+    ///
+    /// Unnamed phases are called "Phase N" and an unnamed sequence gets a generic name.
+    ///
+    /// # Arguments
+    /// - `now` - Current Unix timestamp in seconds, when the first phase starts
+    ///
+    /// # Returns
+    /// The sequence, or `None` if there are no phases or a phase has no duration.
+    #[must_use]
+    pub fn to_sequence(&self, now: i64) -> Option<Sequence> {
+        let phases = self
+            .phases
+            .iter()
+            .enumerate()
+            .map(|(index, draft)| {
+                let minutes: i64 = draft.minutes.parse().ok().filter(|m| *m > 0)?;
+                let name = match draft.name.trim() {
+                    "" => fl!("sequence-phase-default", number = index + 1),
+                    name => name.to_string(),
+                };
+                Some(Phase::new(&name, minutes * 60, draft.is_final))
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|phases| !phases.is_empty())?;
+
+        let name = match self.name.trim() {
+            "" => fl!("sequence-default-name"),
+            name => name.to_string(),
+        };
+        let rounds = self.rounds.parse().unwrap_or(1);
+
+        Some(Sequence::new(&name, rounds, phases, now))
+    }
+}
+
+/// Renders a running sequence with its current phase, countdown and controls.
+fn running_row(sequence: &Sequence, now: i64, focused: bool) -> Element<'_, Message> {
+    let phase = sequence
+        .current_phase()
+        .map(|phase| match sequence.round() {
+            Some((round, rounds)) => fl!(
+                "sequence-phase-round",
+                phase = phase.name.as_str(),
+                round = round,
+                rounds = rounds
+            ),
+            None => phase.name.clone(),
+        })
+        .unwrap_or_default();
+
+    let pause_button = if sequence.is_paused() {
+        button::icon(icon::from_name("media-playback-start-symbolic"))
+            .on_press(Message::Resume(sequence.id))
+    } else {
+        button::icon(icon::from_name("media-playback-pause-symbolic"))
+            .on_press(Message::Pause(sequence.id))
+    };
+
+    row![
+        focus_marker(focused),
+        column![text(sequence.name.as_str()), text::caption(phase)].width(Length::Fill),
+        text(format_countdown(sequence.remaining(now))),
+        pause_button,
+        button::icon(icon::from_name("media-skip-forward-symbolic"))
+            .on_press(Message::Skip(sequence.id)),
+        button::icon(icon::from_name("media-playback-stop-symbolic"))
+            .on_press(Message::Stop(sequence.id)),
+    ]
+    .align_y(Alignment::Center)
+    .spacing(Gaps::xs())
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_page_is_a_pomodoro() {
+        let page = Page::default();
+        let sequence = page.to_sequence(0).unwrap();
+
+        assert_eq!(sequence.rounds, 4);
+        assert_eq!(sequence.steps().len(), 9);
+        assert_eq!(sequence.phases[0].duration_seconds, 25 * 60);
+        assert!(sequence.phases[2].is_final);
+    }
+
+    #[test]
+    fn test_editor_builds_custom_sequence() {
+        let mut page = Page::default();
+        let _ = page.update(Message::RemovePhase(2));
+        let _ = page.update(Message::RemovePhase(1));
+        let _ = page.update(Message::AddPhase);
+        let _ = page.update(Message::PhaseMinutesChanged(1, "abc".to_string()));
+
+        // The new phase has no duration yet, so nothing can start
+        assert!(page.to_sequence(0).is_none());
+
+        let _ = page.update(Message::PhaseMinutesChanged(1, "10".to_string()));
+        let _ = page.update(Message::RoundsChanged("0".to_string()));
+        assert_eq!(page.rounds, "4");
+        let _ = page.update(Message::RoundsChanged("2".to_string()));
+
+        let sequence = page.to_sequence(0).unwrap();
+        assert_eq!(sequence.rounds, 2);
+        assert_eq!(
+            sequence.phases[1].name,
+            fl!("sequence-phase-default", number = 2)
+        );
+        assert_eq!(sequence.phases[1].duration_seconds, 600);
+
+        let _ = page.update(Message::ClearForm);
+        assert_eq!(page.phases.len(), 3);
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let page = Page::default();
+        let running = vec![page.to_sequence(0).unwrap()];
+        let _element: Element<'_, Message> = page.view(&running, 0, Some(0));
+    }
+}
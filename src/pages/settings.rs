@@ -0,0 +1,480 @@
+//! Settings page.
+//!
+//! Shows the general settings (default time unit, grace period, confirmation,
+//! stay-awake battery limit, notifications, panel countdown and icon, missed
+//! timers, log level), then lists the quick
+//! timer presets with edit and remove buttons, and a form to add a preset or
+//! change the one being edited. Settings live in the app's [`Config`]; the page only
+//! holds the preset form, and asks the app to change settings and to save or
+//! remove presets.
+
+use crate::{
+    config::{
+        Config, LogLevel, MissedTimerPolicy, PanelCountdown, PresetAction, QuickTimerPreset,
+        Setting,
+    },
+    fl,
+    utils::{
+        TimeUnit, filters, format_duration,
+        ui::{Gaps, Padding},
+    },
+};
+use cosmic::{
+    Action, Element, Task,
+    iced::{Alignment, Length},
+    iced_widget::{column, row},
+    widget::{Space, TextInput, button, dropdown, icon, text, toggler},
+};
+
+/// Width of the minutes input in the preset form.
+const MINUTES_INPUT_WIDTH: f32 = 64.0;
+/// Width of the seconds inputs in the general settings.
+const SECONDS_INPUT_WIDTH: f32 = 64.0;
+/// Width of the battery percentage input in the general settings.
+const PERCENT_INPUT_WIDTH: f32 = 64.0;
+
+/// Messages for the settings page
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Load a preset into the form for editing
+    Edit(usize, QuickTimerPreset),
+    /// Preset label input changed
+    LabelChanged(String),
+    /// Preset minutes input changed
+    MinutesChanged(String),
+    /// Preset action dropdown selection changed
+    ActionSelected(usize),
+    /// Preset notification text changed
+    DescriptionChanged(String),
+    /// Save button pressed
+    SavePressed,
+    /// Reset the form to add a new preset
+    ClearForm,
+    /// Request to save a preset, replacing the one at the index if given
+    Save(Option<usize>, QuickTimerPreset),
+    /// Request to remove the preset at the index
+    Remove(usize),
+    /// Request to change a general setting
+    Set(Setting),
+}
+
+/// Struct representing the settings page
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// Index of the preset being edited, `None` while adding a new one
+    pub editing: Option<usize>,
+    pub label: String,
+    pub minutes: String,
+    pub action: PresetAction,
+    pub description: String,
+    pub action_labels: Vec<String>,
+    pub unit_labels: Vec<String>,
+    pub policy_labels: Vec<String>,
+    pub countdown_labels: Vec<String>,
+    pub log_level_labels: Vec<String>,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            editing: None,
+            label: String::new(),
+            minutes: String::new(),
+            action: PresetAction::default(),
+            description: String::new(),
+            action_labels: PresetAction::ALL
+                .iter()
+                .map(|action| action_name(*action))
+                .collect(),
+            unit_labels: TimeUnit::ALL.iter().map(ToString::to_string).collect(),
+            policy_labels: MissedTimerPolicy::ALL
+                .iter()
+                .map(|policy| policy_name(*policy))
+                .collect(),
+            countdown_labels: PanelCountdown::ALL
+                .iter()
+                .map(|mode| countdown_name(*mode))
+                .collect(),
+            log_level_labels: LogLevel::ALL
+                .iter()
+                .map(|level| log_level_name(*level))
+                .collect(),
+        }
+    }
+}
+
+impl Page {
+    /// Render the settings page
+    ///
+    /// # Arguments
+    /// - `config` - The current settings, including the quick timer presets
+    ///
+    /// # Returns
+    /// An `Element` representing the page view
+    pub fn view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let preset_rows = config
+            .quick_timers
+            .iter()
+            .enumerate()
+            .map(|(index, preset)| {
+                let duration =
+                    format_duration(i32::try_from(preset.duration_seconds).unwrap_or(i32::MAX));
+                row![
+                    column![
+                        text(preset.label.as_str()),
+                        text::caption(format!("{}, {duration}", action_name(preset.action))),
+                    ]
+                    .width(Length::Fill),
+                    button::icon(icon::from_name("document-edit-symbolic"))
+                        .on_press(Message::Edit(index, preset.clone())),
+                    button::icon(icon::from_name("list-remove-symbolic"))
+                        .on_press(Message::Remove(index)),
+                ]
+                .align_y(Alignment::Center)
+                .spacing(Gaps::xs())
+                .into()
+            });
+
+        let heading = if self.editing.is_some() {
+            fl!("preset-edit")
+        } else {
+            fl!("preset-new")
+        };
+        let mut form = column![
+            text::heading(heading),
+            TextInput::new(fl!("preset-label"), &self.label)
+                .on_input(Message::LabelChanged)
+                .width(Length::Fill),
+            row![
+                dropdown(
+                    &self.action_labels,
+                    Some(self.action.index()),
+                    Message::ActionSelected
+                ),
+                TextInput::new(fl!("minutes"), &self.minutes)
+                    .on_input(Message::MinutesChanged)
+                    .width(MINUTES_INPUT_WIDTH),
+                text(fl!("minutes")),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(Gaps::xs()),
+        ]
+        .spacing(Gaps::xs());
+
+        if self.action == PresetAction::Notify {
+            form = form.push(
+                TextInput::new(fl!("preset-description"), &self.description)
+                    .on_input(Message::DescriptionChanged)
+                    .on_submit(|_| Message::SavePressed)
+                    .width(Length::Fill),
+            );
+        }
+
+        let mut buttons = row![Space::with_width(Length::Fill)].spacing(Gaps::xs());
+        if self.editing.is_some() {
+            buttons = buttons.push(button::text(fl!("preset-cancel")).on_press(Message::ClearForm));
+        }
+        form = form.push(
+            buttons.push(button::suggested(fl!("preset-save")).on_press(Message::SavePressed)),
+        );
+
+        column![
+            self.general_settings(config),
+            text::heading(fl!("quick-timers"))
+        ]
+        .extend(preset_rows)
+        .push(form)
+        .spacing(Gaps::s())
+        .padding(Padding::horizontal(24))
+        .width(Length::Fill)
+        .into()
+    }
+
+    /// Update the settings page state based on messages
+    ///
+    /// Form messages update the form. Save and remove requests are handled by
+    /// the app and ignored here.
+    ///
+    /// # Arguments
+    /// - `message` - The message to process
+    ///
+    /// # Returns
+    /// A `Task` representing any actions to be taken
+    pub fn update(&mut self, message: Message) -> Task<Action<Message>> {
+        match message {
+            Message::Edit(index, preset) => {
+                self.editing = Some(index);
+                self.label = preset.label;
+                self.minutes = (preset.duration_seconds / 60).max(1).to_string();
+                self.action = preset.action;
+                self.description = preset.description;
+            }
+            Message::LabelChanged(label) => self.label = label,
+            Message::MinutesChanged(minutes) => {
+                if let Some(minutes) = filters::filter_positive_integer(&minutes) {
+                    self.minutes = minutes;
+                }
+            }
+            Message::ActionSelected(index) => self.action = PresetAction::from_index(index),
+            Message::DescriptionChanged(description) => self.description = description,
+            Message::SavePressed => {
+                return match self.to_preset() {
+                    Some(preset) => Task::done(Action::App(Message::Save(self.editing, preset))),
+                    // Keep the form filled so it can be fixed instead of retyped
+                    None => Task::none(),
+                };
+            }
+            Message::ClearForm => *self = Self::default(),
+            Message::Save(..) | Message::Remove(_) | Message::Set(_) => {}
+        }
+        Task::none()
+    }
+
+    /// Renders the general settings section.
+    fn general_settings<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let grace = config.grace_period_seconds;
+        let timeout = config.notification_timeout_seconds;
+        let min_battery = config.stay_awake_min_battery;
+        let labeled = |label: String, control: Element<'a, Message>| -> Element<'a, Message> {
+            row![text(label), Space::with_width(Length::Fill), control]
+                .align_y(Alignment::Center)
+                .spacing(Gaps::xs())
+                .into()
+        };
+
+        column![
+            text::heading(fl!("settings-general")),
+            labeled(
+                fl!("settings-default-unit"),
+                dropdown(
+                    &self.unit_labels,
+                    TimeUnit::ALL.iter().position(|u| *u == config.default_unit),
+                    |index| Message::Set(Setting::DefaultUnit(
+                        TimeUnit::ALL.get(index).copied().unwrap_or_default()
+                    )),
+                )
+                .into(),
+            ),
+            labeled(
+                fl!("settings-grace-period"),
+                TextInput::new(fl!("seconds"), grace.to_string())
+                    .on_input(
+                        move |input| Message::Set(Setting::GracePeriod(parse_seconds(
+                            &input, grace
+                        )))
+                    )
+                    .width(SECONDS_INPUT_WIDTH)
+                    .into(),
+            ),
+            toggler(config.confirm_before_execute)
+                .label(fl!("settings-confirm"))
+                .on_toggle(|enabled| Message::Set(Setting::ConfirmBeforeExecute(enabled))),
+            labeled(
+                fl!("settings-stay-awake-battery"),
+                TextInput::new(fl!("percent"), min_battery.to_string())
+                    .on_input(move |input| Message::Set(Setting::StayAwakeMinBattery(
+                        parse_percent(&input, min_battery)
+                    )))
+                    .width(PERCENT_INPUT_WIDTH)
+                    .into(),
+            ),
+            labeled(
+                fl!("settings-notification-timeout"),
+                TextInput::new(fl!("seconds"), timeout.to_string())
+                    .on_input(move |input| Message::Set(Setting::NotificationTimeout(
+                        parse_seconds(&input, timeout)
+                    )))
+                    .width(SECONDS_INPUT_WIDTH)
+                    .into(),
+            ),
+            toggler(config.persistent_notifications)
+                .label(fl!("settings-persistent-notifications"))
+                .on_toggle(|enabled| Message::Set(Setting::PersistentNotifications(enabled))),
+            labeled(
+                fl!("settings-panel-countdown"),
+                dropdown(
+                    &self.countdown_labels,
+                    Some(config.panel_countdown.index()),
+                    |index| Message::Set(Setting::PanelCountdown(PanelCountdown::from_index(
+                        index
+                    ))),
+                )
+                .into(),
+            ),
+            toggler(config.progress_icon)
+                .label(fl!("settings-progress-icon"))
+                .on_toggle(|enabled| Message::Set(Setting::ProgressIcon(enabled))),
+            labeled(
+                fl!("settings-missed-timers"),
+                dropdown(
+                    &self.policy_labels,
+                    Some(config.missed_timer_policy.index()),
+                    |index| Message::Set(Setting::MissedTimerPolicy(
+                        MissedTimerPolicy::from_index(index)
+                    )),
+                )
+                .into(),
+            ),
+            labeled(
+                fl!("settings-log-level"),
+                dropdown(
+                    &self.log_level_labels,
+                    Some(config.log_level.index()),
+                    |index| Message::Set(Setting::LogLevel(LogLevel::from_index(index))),
+                )
+                .into(),
+            ),
+        ]
+        .spacing(Gaps::xs())
+        .into()
+    }
+
+    /// Builds a preset from the form.
+    ///
+    /// # Returns
+    /// The preset, or `None` if the label or duration is missing.
+    #[must_use]
+    pub fn to_preset(&self) -> Option<QuickTimerPreset> {
+        let label = self.label.trim();
+        let minutes: u32 = self.minutes.parse().ok().filter(|m| *m > 0)?;
+        if label.is_empty() {
+            return None;
+        }
+
+        Some(QuickTimerPreset {
+            label: label.to_string(),
+            duration_seconds: minutes.checked_mul(60)?,
+            action: self.action,
+            description: self.description.trim().to_string(),
+        })
+    }
+}
+
+/// Parses a seconds input, treating an empty input as zero.
+///
+/// Anything that isn't a whole number keeps the current value.
+fn parse_seconds(input: &str, current: u32) -> u32 {
+    match input.trim() {
+        "" => 0,
+        input => input.parse().unwrap_or(current),
+    }
+}
+
+/// Parses a battery percentage input, treating an empty input as zero.
+///
+/// Anything that isn't a whole number up to 100 keeps the current value.
+fn parse_percent(input: &str, current: u8) -> u8 {
+    match input.trim() {
+        "" => 0,
+        input => input
+            .parse()
+            .ok()
+            .filter(|percent| *percent <= 100)
+            .unwrap_or(current),
+    }
+}
+
+/// Gets the localized name of a log level.
+fn log_level_name(level: LogLevel) -> String {
+    match level {
+        LogLevel::Error => fl!("log-error"),
+        LogLevel::Warn => fl!("log-warn"),
+        LogLevel::Info => fl!("log-info"),
+        LogLevel::Debug => fl!("log-debug"),
+        LogLevel::Trace => fl!("log-trace"),
+    }
+}
+
+/// Gets the localized name of a panel countdown mode.
+fn countdown_name(mode: PanelCountdown) -> String {
+    match mode {
+        PanelCountdown::Off => fl!("countdown-off"),
+        PanelCountdown::BesideIcon => fl!("countdown-beside-icon"),
+        PanelCountdown::InsteadOfIcon => fl!("countdown-instead-of-icon"),
+    }
+}
+
+/// Gets the localized name of a missed timer policy.
+fn policy_name(policy: MissedTimerPolicy) -> String {
+    match policy {
+        MissedTimerPolicy::Discard => fl!("missed-discard"),
+        MissedTimerPolicy::Notify => fl!("missed-notify"),
+        MissedTimerPolicy::Run => fl!("missed-run"),
+    }
+}
+
+/// Gets the localized name of a preset action.
+fn action_name(action: PresetAction) -> String {
+    match action {
+        PresetAction::Notify => fl!("operation-notify"),
+        PresetAction::Suspend => fl!("operation-suspend"),
+        PresetAction::Hibernate => fl!("operation-hibernate"),
+        PresetAction::Logout => fl!("operation-logout"),
+        PresetAction::Shutdown => fl!("operation-shutdown"),
+        PresetAction::Reboot => fl!("operation-reboot"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_builds_preset() {
+        let mut page = Page::default();
+        let _ = page.update(Message::MinutesChanged("45".to_string()));
+
+        // A preset needs a label for its button
+        assert!(page.to_preset().is_none());
+
+        let _ = page.update(Message::LabelChanged(" Laundry ".to_string()));
+        let _ = page.update(Message::ActionSelected(PresetAction::Suspend.index()));
+        let preset = page.to_preset().unwrap();
+        assert_eq!(preset.label, "Laundry");
+        assert_eq!(preset.duration_seconds, 45 * 60);
+        assert_eq!(preset.action, PresetAction::Suspend);
+    }
+
+    #[test]
+    fn test_edit_loads_preset() {
+        let mut page = Page::default();
+        let preset = QuickTimerPreset {
+            label: "Tea".to_string(),
+            duration_seconds: 900,
+            action: PresetAction::Notify,
+            description: "Tea is ready".to_string(),
+        };
+
+        let _ = page.update(Message::Edit(2, preset.clone()));
+        assert_eq!(page.editing, Some(2));
+        assert_eq!(page.minutes, "15");
+        assert_eq!(page.to_preset(), Some(preset));
+        assert_eq!(page.action_labels.len(), PresetAction::ALL.len());
+
+        let _ = page.update(Message::ClearForm);
+        assert_eq!(page.editing, None);
+        assert!(page.label.is_empty());
+    }
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(parse_seconds("45", 10), 45);
+        assert_eq!(parse_seconds("", 10), 0);
+        assert_eq!(parse_seconds("4s", 10), 10);
+    }
+
+    #[test]
+    fn test_parse_percent() {
+        assert_eq!(parse_percent("25", 10), 25);
+        assert_eq!(parse_percent("", 10), 0);
+        assert_eq!(parse_percent("101", 10), 10);
+        assert_eq!(parse_percent("5%", 10), 10);
+    }
+
+    #[test]
+    fn test_view_compiles() {
+        let page = Page::default();
+        let config = Config::default();
+        let _element: Element<'_, Message> = page.view(&config);
+    }
+}
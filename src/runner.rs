@@ -0,0 +1,858 @@
+// SPDX-License-Identifier: MIT
+
+//! Running timers, shared by the applet and `chronomancerd`.
+//!
+//! Whoever owns the timer service runs the timers (see [`crate::service`]):
+//! the applet on its own, or [`crate::daemon`] once it took over. Both keep a
+//! [`TimerRunner`] with the running timers, what their triggers wait on and the
+//! pending power action. The runner decides what happens when a timer is ready
+//! or missed, and when a power action runs; it only reads watched paths
+//! itself. Everything else comes back as [`Effect`]s, which the applet turns
+//! into tasks and the daemon awaits in place.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::{
+//!     Config, Timer, TimerType,
+//!     runner::{Effect, TimerRunner},
+//! };
+//!
+//! let mut runner = TimerRunner::default();
+//! let mut tea = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 1_000);
+//! tea.id = 1;
+//! runner.add_timers([tea], 1_000);
+//!
+//! assert!(runner.tick(&Config::default(), 1_059).is_empty());
+//! let effects = runner.tick(&Config::default(), 1_060);
+//! assert!(matches!(effects.last(), Some(Effect::Complete { id: 1, now: 1_060 })));
+//! assert!(runner.active_timers.is_empty());
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use notify_rust::{Hint, Notification, Timeout};
+use sqlx::SqlitePool;
+
+use crate::{
+    components::timer_list,
+    config::{Config, MissedTimerPolicy},
+    models::{
+        HistoryEntry, Timer,
+        condition::{self, Readiness, TimerCondition, TriggerContext},
+        history::Outcome,
+        timer::TimerType,
+    },
+    service::{PendingAction, Signal},
+    utils::{
+        clock::ClockChange, database::RepositoryResult, file_watch::WatchedFile,
+        mpris::PlayerState, system_load::ActivitySample, upower::PowerState,
+    },
+};
+
+/// Something the runner needs done outside of it.
+#[derive(Debug, Clone)]
+pub enum Effect {
+    /// Send a desktop notification
+    Notify(Notice),
+    /// Store a history entry
+    Record(HistoryEntry),
+    /// Send a signal from the timer service
+    Emit(Signal),
+    /// Delete a finished timer with [`Timer::complete`], then hand the link it
+    /// armed to [`TimerRunner::armed`]
+    Complete { id: i64, now: i64 },
+    /// Drop timers that ended while nothing ran them, see [`discard_missed`]
+    Discard { timers: Vec<Timer>, now: i64 },
+    /// Run a power operation right away
+    Execute(TimerType),
+}
+
+/// A desktop notification about the timers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notice {
+    pub summary: String,
+    pub body: String,
+    /// Icon name from the freedesktop icon theme
+    pub icon: String,
+    /// Whether this is a finished reminder, which stays in the notification center
+    pub reminder: bool,
+}
+
+impl Notice {
+    /// Creates a notification about the system, like a timer being set.
+    ///
+    /// # Arguments
+    ///
+    /// - `summary`: Notification title
+    /// - `body`: Notification body text
+    /// - `icon`: Icon name from the freedesktop icon theme (e.g., "alarm", "battery")
+    pub fn new(summary: impl Into<String>, body: impl Into<String>, icon: &str) -> Self {
+        Self {
+            summary: summary.into(),
+            body: body.into(),
+            icon: icon.to_string(),
+            reminder: false,
+        }
+    }
+
+    /// Creates the notification of a finished reminder.
+    ///
+    /// # Arguments
+    ///
+    /// - `description`: The reminder's text
+    #[must_use]
+    pub fn reminder(description: &str) -> Self {
+        Self {
+            reminder: true,
+            ..Self::new("Timer Finished", description, "alarm")
+        }
+    }
+
+    /// Creates the notification of a timer its guard skipped.
+    #[must_use]
+    pub fn skipped(timer: &Timer) -> Self {
+        Self::new(
+            "Timer Skipped",
+            format!(
+                "{} was skipped because the system is on battery",
+                timer.description
+            ),
+            "battery-symbolic",
+        )
+    }
+
+    /// Creates the notification of a chain link that started after the one before it finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `next`: The link that started
+    /// - `now`: Current Unix timestamp in seconds
+    #[must_use]
+    pub fn next_started(next: &Timer, now: i64) -> Self {
+        Self::new(
+            "Next Timer Started",
+            format!(
+                "{} {}",
+                timer_list::timer_name(next),
+                timer_list::timer_summary(next, now)
+            ),
+            "alarm-symbolic",
+        )
+    }
+
+    /// Creates the notification listing timers that ended while nothing ran them.
+    #[must_use]
+    pub fn missed(timers: &[Timer]) -> Self {
+        let names: Vec<String> = timers.iter().map(timer_list::timer_name).collect();
+        Self::new(
+            "Missed Timers",
+            format!(
+                "Ended while Chronomancer wasn't running: {}",
+                names.join(", ")
+            ),
+            "alarm-symbolic",
+        )
+    }
+
+    /// Creates the notification of a power action that started waiting.
+    ///
+    /// # Arguments
+    ///
+    /// - `pending`: The action that waits out its grace period or for confirmation
+    /// - `now`: Current Unix timestamp in seconds
+    #[must_use]
+    pub fn action_waiting(pending: &PendingAction, now: i64) -> Self {
+        let operation = timer_list::operation_name(&pending.timer_type);
+        match pending.runs_at {
+            Some(runs_at) => Self::new(
+                format!("{operation} in {} seconds", runs_at - now),
+                "Open Chronomancer to cancel it",
+                "alarm-symbolic",
+            ),
+            None => Self::new(
+                format!("{operation} Waiting"),
+                "Open Chronomancer to run or cancel it",
+                "dialog-question-symbolic",
+            ),
+        }
+    }
+
+    /// Creates the notification of a pending power action that won't run.
+    #[must_use]
+    pub fn action_cancelled(timer_type: &TimerType) -> Self {
+        Self::new(
+            "Power Action Cancelled",
+            format!("{} won't run", timer_list::operation_name(timer_type)),
+            "alarm-symbolic",
+        )
+    }
+
+    /// Creates the notification of a pending power action another one took the place of.
+    ///
+    /// # Arguments
+    ///
+    /// - `replaced`: The action that won't run
+    /// - `by`: The action pending instead
+    #[must_use]
+    pub fn action_replaced(replaced: &TimerType, by: &TimerType) -> Self {
+        Self::new(
+            "Power Action Replaced",
+            format!(
+                "{} won't run, {} is waiting instead",
+                timer_list::operation_name(replaced),
+                timer_list::operation_name(by)
+            ),
+            "alarm-symbolic",
+        )
+    }
+
+    /// Creates the notification of stay-awake let go on a low battery.
+    ///
+    /// # Arguments
+    ///
+    /// - `threshold`: The configured minimum battery level in percent
+    #[must_use]
+    pub fn stay_awake_released(threshold: u8) -> Self {
+        Self::new(
+            "Stay Awake Released",
+            format!("Battery is below {threshold}%, allowing the system to sleep"),
+            "battery-caution-symbolic",
+        )
+    }
+
+    /// Sends the notification with the configured timeout, logging failures.
+    ///
+    /// Reminders stay in the notification center once they time out.
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: Settings with the notification timeout
+    pub fn show(&self, config: &Config) {
+        let mut notification = Notification::new();
+        notification
+            .summary(&self.summary)
+            .body(&self.body)
+            .icon(&self.icon)
+            .timeout(notification_timeout(config));
+        if self.reminder {
+            notification
+                .hint(Hint::Category("alarm".to_owned()))
+                .hint(Hint::Resident(true));
+        } else {
+            notification.hint(Hint::Category("device".to_owned()));
+        }
+        if let Err(e) = notification.show() {
+            tracing::warn!("Failed to send notification: {e}");
+        }
+    }
+}
+
+/// Gets the notification timeout from the settings.
+#[must_use]
+pub fn notification_timeout(config: &Config) -> Timeout {
+    match config.notification_timeout_ms() {
+        None => Timeout::Never,
+        Some(0) => Timeout::Default,
+        Some(ms) => Timeout::Milliseconds(ms),
+    }
+}
+
+/// Drops timers that ended while nothing ran them.
+///
+/// They are deleted along with the rest of their chains, except recurring
+/// timers, which skip the rounds they missed instead.
+///
+/// # Arguments
+///
+/// - `pool`: Database connection pool
+/// - `timers`: The missed timers
+/// - `now`: Current Unix timestamp in seconds
+///
+/// # Errors
+///
+/// Returns the first database error; timers before it are already dropped.
+pub async fn discard_missed(pool: &SqlitePool, timers: &[Timer], now: i64) -> RepositoryResult<()> {
+    for timer in timers {
+        if timer.period().is_some() {
+            Timer::complete(pool, timer.id, now).await?;
+        } else {
+            Timer::delete_chain(pool, timer.id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The running timers, what their triggers wait on and the pending power action.
+#[derive(Debug, Clone, Default)]
+pub struct TimerRunner {
+    pub active_timers: Vec<Timer>,
+    /// A power action whose timer finished, waiting out its grace period or for confirmation
+    pub pending_action: Option<PendingAction>,
+    /// Last known battery and AC state
+    pub power_state: Option<PowerState>,
+    /// Recent activity rates for "system quiet" triggers, oldest first
+    pub activity_history: Vec<ActivitySample>,
+    /// Paths watched by file triggers, with their latest observation
+    pub watched_files: Vec<WatchedFile>,
+    /// Media players, read while a "media ends" trigger waits
+    pub media_players: Option<Vec<PlayerState>>,
+}
+
+impl TimerRunner {
+    /// Replaces the running timers with ones fetched from the database.
+    ///
+    /// # Arguments
+    ///
+    /// - `timers`: The running timers
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn set_timers(&mut self, timers: Vec<Timer>, now: i64) {
+        self.active_timers = timers;
+        self.sync_watched_files(now);
+    }
+
+    /// Adds timers that started running, skipping ones that already run.
+    ///
+    /// A reload can fetch a timer before the message that created or armed it
+    /// arrives, so adding it again would make it fire twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `timers`: The timers that started running
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn add_timers(&mut self, timers: impl IntoIterator<Item = Timer>, now: i64) {
+        for timer in timers {
+            if !self.active_timers.iter().any(|t| t.id == timer.id) {
+                self.active_timers.push(timer);
+            }
+        }
+        self.sync_watched_files(now);
+    }
+
+    /// Removes timers that stopped running.
+    ///
+    /// # Arguments
+    ///
+    /// - `ids`: The timers that stopped
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn remove_timers(&mut self, ids: &[i64], now: i64) {
+        self.active_timers.retain(|t| !ids.contains(&t.id));
+        self.sync_watched_files(now);
+    }
+
+    /// Gets the longest sustained window among waiting "system quiet" triggers.
+    ///
+    /// # Returns
+    ///
+    /// The window in seconds, or `None` when no quiet trigger is active.
+    #[must_use]
+    pub fn longest_quiet_window(&self) -> Option<i64> {
+        condition::longest_quiet_window(self.active_timers.iter().map(Timer::condition))
+    }
+
+    /// Whether a waiting "media ends" trigger needs the media player state.
+    #[must_use]
+    pub fn waits_on_media(&self) -> bool {
+        self.active_timers
+            .iter()
+            .any(|timer| matches!(timer.condition(), TimerCondition::MediaEnds(_)))
+    }
+
+    /// Brings the watched file list in line with the active file triggers.
+    ///
+    /// Newly watched paths get their first observation, paths no longer
+    /// watched by any timer are dropped, and existing observations are kept
+    /// so their change history survives.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn sync_watched_files(&mut self, now: i64) {
+        let watched_paths =
+            condition::watched_paths(self.active_timers.iter().map(Timer::condition));
+
+        self.watched_files
+            .retain(|file| watched_paths.contains(&file.path));
+        for path in watched_paths {
+            if !self.watched_files.iter().any(|file| file.path == path) {
+                self.watched_files
+                    .push(WatchedFile::observe(&path, now, None));
+            }
+        }
+    }
+
+    /// Gets the paths watched by file triggers.
+    #[must_use]
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        self.watched_files
+            .iter()
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    /// Reads a watched path again after a watcher reported activity on it.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: The path that changed
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn observe_file(&mut self, path: &Path, now: i64) {
+        if let Some(file) = self.watched_files.iter_mut().find(|file| file.path == path) {
+            *file = WatchedFile::observe(path, now, Some(&*file));
+        }
+    }
+
+    /// Gets the times the running timers and the pending power action are due at.
+    ///
+    /// Timers that only wait on an update have none, see [`Timer::wakes_at`].
+    pub fn wake_times(&self) -> impl Iterator<Item = i64> + '_ {
+        let pending_action = self
+            .pending_action
+            .as_ref()
+            .and_then(|pending| pending.runs_at);
+        self.active_timers
+            .iter()
+            .filter_map(|timer| timer.wakes_at(&self.watched_files))
+            .chain(pending_action)
+    }
+
+    /// Moves the timers and the pending power action along with a change of the clock.
+    ///
+    /// See [`Timer::follow_clock`]; the database needs the same change through
+    /// [`Timer::follow_clock_all`]. The grace period of a pending action is a
+    /// countdown too, so it keeps its remaining time.
+    ///
+    /// # Arguments
+    ///
+    /// - `change`: What changed about the clock
+    pub fn follow_clock(&mut self, change: &ClockChange) -> Vec<Effect> {
+        for timer in &mut self.active_timers {
+            timer.follow_clock(change);
+        }
+        if change.jumped_by != 0
+            && let Some(runs_at) = self
+                .pending_action
+                .as_mut()
+                .and_then(|pending| pending.runs_at.as_mut())
+        {
+            *runs_at += change.jumped_by;
+            return vec![Effect::Emit(Signal::PendingActionChanged(
+                self.pending_action.clone(),
+            ))];
+        }
+        Vec::new()
+    }
+
+    /// Finishes the timers that are ready and runs a pending action whose grace period is over.
+    ///
+    /// Each timer's condition is evaluated against the current time, the last
+    /// known power state, recent system activity, watched files and media
+    /// players. Timers that are ready at the same time are all finished
+    /// together, since the next check may be a long way off.
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: Settings with the grace period and notification timeout
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn tick(&mut self, config: &Config, now: i64) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        if self
+            .pending_action
+            .as_ref()
+            .and_then(|pending| pending.runs_at)
+            .is_some_and(|runs_at| runs_at <= now)
+        {
+            effects.extend(self.run_pending_action());
+        }
+
+        let context = TriggerContext {
+            now,
+            power: self.power_state,
+            activity: &self.activity_history,
+            files: &self.watched_files,
+            media: self.media_players.as_deref(),
+        };
+        let finished: Vec<(Timer, Outcome)> = self
+            .active_timers
+            .iter()
+            .filter_map(|timer| match timer.readiness(&context) {
+                Readiness::Waiting => None,
+                Readiness::Skipped => Some((timer.clone(), Outcome::Skipped)),
+                Readiness::Ready => Some((timer.clone(), Outcome::Finished)),
+            })
+            .collect();
+
+        for (timer, outcome) in finished {
+            effects.extend(self.finish(&timer, outcome, config, now));
+        }
+        effects
+    }
+
+    /// Runs a finished timer's action and removes it.
+    ///
+    /// Skipped timers (a guard such as "AC power only" failed) send a
+    /// notification instead of running. Reminders send theirs, power
+    /// operations go through [`Self::schedule_power_action`].
+    ///
+    /// # Arguments
+    ///
+    /// - `timer`: The timer that is done waiting
+    /// - `outcome`: Whether it finished or its guard skipped it
+    /// - `config`: Settings with the grace period
+    /// - `now`: Current Unix timestamp in seconds
+    #[tracing::instrument(skip_all, fields(id = timer.id, description = %timer.description, ?outcome))]
+    fn finish(
+        &mut self,
+        timer: &Timer,
+        outcome: Outcome,
+        config: &Config,
+        now: i64,
+    ) -> Vec<Effect> {
+        tracing::info!("Timer done");
+        self.remove_timers(&[timer.id], now);
+
+        let mut effects = Vec::new();
+        if outcome == Outcome::Skipped {
+            effects.push(Effect::Notify(Notice::skipped(timer)));
+        } else {
+            match TimerType::from_str(&timer.description) {
+                Ok(TimerType::UserDefined(description)) => {
+                    effects.push(Effect::Notify(Notice::reminder(&description)));
+                }
+                Ok(timer_type) => {
+                    effects.extend(self.schedule_power_action(timer_type, config, now));
+                }
+                Err(e) => tracing::error!("Failed to parse timer type: {e}"),
+            }
+        }
+
+        effects.push(Effect::Record(HistoryEntry::new(timer, outcome, now)));
+        if outcome == Outcome::Finished {
+            effects.push(Effect::Emit(Signal::TimerFired(timer.clone())));
+        }
+        effects.push(Effect::Complete { id: timer.id, now });
+        effects
+    }
+
+    /// Starts the chain link a completed timer armed.
+    ///
+    /// # Arguments
+    ///
+    /// - `next`: The link [`Timer::complete`] armed
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn armed(&mut self, next: Timer, now: i64) -> Vec<Effect> {
+        let notice = Notice::next_started(&next, now);
+        self.add_timers([next], now);
+        vec![Effect::Notify(notice)]
+    }
+
+    /// Applies the missed timer policy to timers that ended while nothing ran them.
+    ///
+    /// Running them leaves them to the next tick. Otherwise they are dropped,
+    /// after a notification listing them if the policy asks for one.
+    ///
+    /// # Arguments
+    ///
+    /// - `timers`: The missed timers, as fetched from the database
+    /// - `config`: Settings with the missed timer policy
+    /// - `now`: Current Unix timestamp in seconds
+    pub fn missed(&mut self, timers: Vec<Timer>, config: &Config, now: i64) -> Vec<Effect> {
+        let ids: Vec<i64> = timers.iter().map(|timer| timer.id).collect();
+        self.remove_timers(&ids, now);
+        if timers.is_empty() {
+            return Vec::new();
+        }
+
+        let mut effects = Vec::new();
+        match config.missed_timer_policy {
+            MissedTimerPolicy::Run => {
+                self.add_timers(timers, now);
+                return effects;
+            }
+            MissedTimerPolicy::Notify => effects.push(Effect::Notify(Notice::missed(&timers))),
+            MissedTimerPolicy::Discard => {}
+        }
+        effects.extend(
+            timers
+                .iter()
+                .map(|timer| Effect::Record(HistoryEntry::new(timer, Outcome::Missed, now))),
+        );
+        effects.push(Effect::Discard { timers, now });
+        effects
+    }
+
+    /// Runs a power action whose timer finished, or holds it back first.
+    ///
+    /// With no grace period and no confirmation required the action runs right
+    /// away. Otherwise it becomes the pending action and a notification says
+    /// how to stop it; [`Self::tick`] runs it once the grace period is over,
+    /// unless it waits for confirmation. A newer action replaces one that is
+    /// still pending, see [`Self::set_pending_action`].
+    ///
+    /// # Arguments
+    ///
+    /// - `timer_type`: The power operation to run
+    /// - `config`: Settings with the grace period and confirmation
+    /// - `now`: Current Unix timestamp in seconds
+    #[tracing::instrument(skip(self, config, now))]
+    pub fn schedule_power_action(
+        &mut self,
+        timer_type: TimerType,
+        config: &Config,
+        now: i64,
+    ) -> Vec<Effect> {
+        let grace = i64::from(config.grace_period_seconds);
+        let confirm = config.confirm_before_execute;
+        if grace == 0 && !confirm {
+            return vec![Effect::Execute(timer_type)];
+        }
+
+        let pending = PendingAction {
+            timer_type,
+            runs_at: (!confirm).then_some(now + grace),
+        };
+        let mut effects = vec![Effect::Notify(Notice::action_waiting(&pending, now))];
+        effects.extend(self.set_pending_action(pending));
+        effects
+    }
+
+    /// Takes over a power action that was pending elsewhere.
+    ///
+    /// The applet hands its pending action to `chronomancerd` when the daemon
+    /// takes over, since its timer is already gone. Its notification was sent
+    /// there, and a grace period that ran out meanwhile runs it on the next tick.
+    ///
+    /// # Arguments
+    ///
+    /// - `pending`: The action as it was pending
+    pub fn adopt_pending_action(&mut self, pending: PendingAction) -> Vec<Effect> {
+        self.set_pending_action(pending)
+    }
+
+    /// Makes an action the pending one, replacing the one that was pending.
+    ///
+    /// Only one action waits at a time, so a replaced action of another kind
+    /// gets a notification saying it won't run.
+    ///
+    /// # Arguments
+    ///
+    /// - `pending`: The action to hold back
+    fn set_pending_action(&mut self, pending: PendingAction) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        if let Some(replaced) = self.pending_action.take()
+            && replaced.timer_type != pending.timer_type
+        {
+            effects.push(Effect::Notify(Notice::action_replaced(
+                &replaced.timer_type,
+                &pending.timer_type,
+            )));
+        }
+        self.pending_action = Some(pending);
+        effects.push(Effect::Emit(Signal::PendingActionChanged(
+            self.pending_action.clone(),
+        )));
+        effects
+    }
+
+    /// Runs the pending power action now, if there is one.
+    pub fn run_pending_action(&mut self) -> Vec<Effect> {
+        match self.pending_action.take() {
+            Some(pending) => vec![
+                Effect::Emit(Signal::PendingActionChanged(None)),
+                Effect::Execute(pending.timer_type),
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    /// Drops the pending power action, if there is one.
+    pub fn cancel_pending_action(&mut self) -> Vec<Effect> {
+        match self.pending_action.take() {
+            Some(pending) => vec![
+                Effect::Notify(Notice::action_cancelled(&pending.timer_type)),
+                Effect::Emit(Signal::PendingActionChanged(None)),
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    /// Stores a new power state for battery triggers and AC guards.
+    ///
+    /// # Arguments
+    ///
+    /// - `state`: The latest battery and AC state
+    /// - `config`: Settings with the minimum battery level for stay-awake
+    ///
+    /// # Returns
+    ///
+    /// The notification to send when letting stay-awake go, `Some` while the
+    /// battery is below the configured level.
+    pub fn set_power_state(&mut self, state: PowerState, config: &Config) -> Option<Notice> {
+        self.power_state = Some(state);
+        let threshold = config.stay_awake_min_battery;
+        (threshold > 0 && state.is_battery_below(threshold))
+            .then(|| Notice::stay_awake_released(threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_767_600_000;
+
+    fn stored(mut timer: Timer, id: i64) -> Timer {
+        timer.id = id;
+        timer
+    }
+
+    #[test]
+    fn test_power_action_waits_out_grace_period() {
+        let config = Config {
+            grace_period_seconds: 30,
+            confirm_before_execute: false,
+            ..Config::default()
+        };
+        let mut runner = TimerRunner::default();
+        let shutdown = stored(Timer::new(60, false, &TimerType::Shutdown, NOW), 1);
+        runner.add_timers([shutdown], NOW);
+
+        let effects = runner.tick(&config, NOW + 60);
+        assert!(runner.active_timers.is_empty());
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, Effect::Execute(_)))
+        );
+        assert!(matches!(
+            effects.last(),
+            Some(Effect::Complete { id: 1, now }) if *now == NOW + 60
+        ));
+        let pending = runner
+            .pending_action
+            .clone()
+            .expect("shutdown should be pending");
+        assert_eq!(pending.runs_at, Some(NOW + 90));
+        assert_eq!(runner.wake_times().collect::<Vec<_>>(), vec![NOW + 90]);
+
+        assert!(runner.tick(&config, NOW + 89).is_empty());
+        let effects = runner.tick(&config, NOW + 90);
+        assert!(matches!(
+            effects.as_slice(),
+            [
+                Effect::Emit(Signal::PendingActionChanged(None)),
+                Effect::Execute(TimerType::Shutdown),
+            ]
+        ));
+        assert!(runner.pending_action.is_none());
+    }
+
+    #[test]
+    fn test_replaced_power_action_is_announced() {
+        let config = Config {
+            confirm_before_execute: true,
+            ..Config::default()
+        };
+        let mut runner = TimerRunner::default();
+        runner.schedule_power_action(TimerType::Reboot, &config, NOW);
+
+        let effects = runner.schedule_power_action(TimerType::Shutdown, &config, NOW + 1);
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::Notify(notice) if notice.summary == "Power Action Replaced"
+        )));
+        assert_eq!(
+            runner.pending_action.map(|pending| pending.timer_type),
+            Some(TimerType::Shutdown)
+        );
+    }
+
+    #[test]
+    fn test_missed_timers_follow_policy() {
+        let mut missed = stored(Timer::new(60, false, &TimerType::Reboot, NOW - 600), 1);
+        missed.ends_at = NOW - 540;
+
+        let mut runner = TimerRunner::default();
+        let config = Config {
+            missed_timer_policy: MissedTimerPolicy::Run,
+            ..Config::default()
+        };
+        assert!(runner.missed(vec![missed.clone()], &config, NOW).is_empty());
+        assert_eq!(runner.active_timers.len(), 1);
+
+        let config = Config {
+            missed_timer_policy: MissedTimerPolicy::Discard,
+            ..Config::default()
+        };
+        let effects = runner.missed(vec![missed], &config, NOW);
+        assert!(runner.active_timers.is_empty());
+        match effects.as_slice() {
+            [Effect::Record(entry), Effect::Discard { timers, now: NOW }] => {
+                assert_eq!(entry.outcome(), Outcome::Missed);
+                assert_eq!(timers[0].id, 1);
+            }
+            effects => panic!("unexpected effects: {effects:?}"),
+        }
+    }
+
+    #[test]
+    fn test_timers_follow_clock_changes() {
+        let tea = stored(
+            Timer::new(600, false, &TimerType::UserDefined("Tea".to_string()), NOW),
+            1,
+        );
+        // 09:00 UTC, an hour from now
+        let at = chrono::DateTime::from_timestamp(NOW + 3600, 0)
+            .expect("valid timestamp")
+            .naive_utc();
+        let standup = Timer::at(
+            at,
+            "UTC",
+            &TimerType::UserDefined("Standup".to_string()),
+            NOW,
+        )
+        .expect("UTC is in tzdata");
+        let mut runner = TimerRunner::default();
+        runner.add_timers([tea, stored(standup, 2)], NOW);
+        runner.pending_action = Some(PendingAction {
+            timer_type: TimerType::Suspend,
+            runs_at: Some(NOW + 30),
+        });
+
+        // NTP sets the clock half an hour ahead, countdowns keep their time left
+        let effects = runner.follow_clock(&ClockChange {
+            jumped_by: 1800,
+            time_zone: None,
+        });
+        let moved = Some(PendingAction {
+            timer_type: TimerType::Suspend,
+            runs_at: Some(NOW + 1800 + 30),
+        });
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::Emit(Signal::PendingActionChanged(pending))] if *pending == moved
+        ));
+        assert_eq!(runner.pending_action, moved);
+        assert_eq!(runner.active_timers[0].ends_at, NOW + 1800 + 600);
+        // Wall clock times stay where they are
+        assert_eq!(runner.active_timers[1].ends_at, NOW + 3600);
+
+        // In Tokyo 09:00 has already passed, so the standup is due right away
+        let effects = runner.follow_clock(&ClockChange {
+            jumped_by: 0,
+            time_zone: Some("Asia/Tokyo".to_string()),
+        });
+        assert!(effects.is_empty());
+        let effects = runner.tick(&Config::default(), NOW + 1800);
+        assert!(
+            effects
+                .iter()
+                .any(|effect| matches!(effect, Effect::Complete { id: 2, .. }))
+        );
+        let ids: Vec<i64> = runner.active_timers.iter().map(|timer| timer.id).collect();
+        assert_eq!(ids, [1]);
+    }
+}
@@ -0,0 +1,800 @@
+// SPDX-License-Identifier: MIT
+
+//! Session D-Bus service exposing the timer API.
+//!
+//! Whoever runs the timers serves the `io.vulpapps.Chronomancer` interface at
+//! [`OBJECT_PATH`] under the bus name [`BUS_NAME`], so desktop tools, shell
+//! scripts and extensions can manage timers without touching the database file.
+//! The app id itself is taken by the applet's single-instance activation (see
+//! [`crate::utils::activation`]), which is why the service has its own name.
+//!
+//! Owning [`BUS_NAME`] is also what decides who runs the timers. The applet
+//! serves it as [`Role::Applet`], letting `chronomancerd` ([`crate::daemon`])
+//! take it over and queueing to get it back should the daemon stop. While it
+//! doesn't own the name the applet is a client: it follows the owner with
+//! [`updates`] and sends it [`Request`]s.
+//!
+//! Methods work on the database directly and then tell the owner through a
+//! [`ServiceEvent`] to reload, like the CLI does. Signals for things that happen
+//! inside the owner (a timer firing, stay-awake toggled from the popup) are
+//! sent with [`emit`].
+//!
+//! # D-Bus API
+//!
+//! ```text
+//! Create(u seconds, s kind, s text) -> x id
+//! List() -> a(xssxxb)          id, kind, description, ends_at, remaining seconds, paused
+//! Cancel(x id)
+//! Pause(x id)
+//! Resume(x id)
+//! ToggleStayAwake()
+//! SetStayAwake(b enabled)
+//! RunPendingAction()
+//! CancelPendingAction()
+//! AdoptPendingAction(s kind, x runs_at)
+//! State() -> (bsx)             stay-awake, pending action kind, when it runs
+//!
+//! signal TimerCreated(x id, s kind, s description)
+//! signal TimerFired(x id, s kind, s description)
+//! signal TimerCancelled(x id, s kind, s description)
+//! signal StayAwakeChanged(b enabled)
+//! signal PendingActionChanged(s kind, x runs_at)
+//! ```
+//!
+//! `kind` is one of `reminder`, `suspend`, `hibernate`, `logout`, `shutdown`
+//! or `reboot`; `text` is the reminder's notification text and ignored for the
+//! others. Trigger timers without a deadline list `-1` for `ends_at` and the
+//! remaining seconds. A power action waiting out its grace period has an empty
+//! `kind` when there is none, and `runs_at` is `-1` while it waits for confirmation.
+//! `AdoptPendingAction` hands the owner a power action in the same form, which
+//! the applet does with its own when `chronomancerd` takes over.
+//!
+//! ```text
+//! busctl --user call io.vulpapps.Chronomancer.Timers /io/vulpapps/Chronomancer/Timers \
+//!     io.vulpapps.Chronomancer Create uss 3600 reboot ""
+//! ```
+
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+use zbus::{
+    Connection, MatchRule, MessageStream, Proxy,
+    connection::Builder,
+    fdo::{self, RequestNameFlags},
+    interface,
+    message::Type,
+    names::BusName,
+    object_server::SignalEmitter,
+};
+
+use crate::{
+    models::{Timer, timer::TimerType},
+    utils::{
+        clock::SharedClock,
+        database::{Repository, RepositoryError},
+    },
+};
+
+/// Well-known bus name of the timer service.
+pub const BUS_NAME: &str = "io.vulpapps.Chronomancer.Timers";
+
+/// Object path the timer service is served at.
+pub const OBJECT_PATH: &str = "/io/vulpapps/Chronomancer/Timers";
+
+/// Interface name of the timer service.
+pub const INTERFACE: &str = "io.vulpapps.Chronomancer";
+
+/// A listed timer: id, kind, description, `ends_at`, remaining seconds and whether it is paused.
+pub type TimerInfo = (i64, String, String, i64, i64, bool);
+
+/// Who is publishing the service, which decides how [`BUS_NAME`] is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The panel applet, which gives the name up to the daemon and queues for it
+    Applet,
+    /// `chronomancerd`, which takes the name over and keeps it
+    Daemon,
+}
+
+/// Requests from the service that its owner has to act on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceEvent {
+    /// Timers were created, cancelled, paused or resumed, reload them
+    TimersChanged,
+    /// Toggle the stay-awake inhibit lock
+    ToggleStayAwake,
+    /// Turn the stay-awake inhibit lock on or off
+    SetStayAwake(bool),
+    /// Run the pending power action now
+    RunPendingAction,
+    /// Drop the pending power action
+    CancelPendingAction,
+    /// Take over a power action that was pending elsewhere
+    AdoptPendingAction(PendingAction),
+}
+
+/// Signals the owner sends about things that happened inside it.
+#[derive(Debug, Clone)]
+pub enum Signal {
+    /// A timer was created from the popup
+    TimerCreated(Timer),
+    /// A timer finished and ran its action or sent its notification
+    TimerFired(Timer),
+    /// A timer was cancelled from the popup
+    TimerCancelled(Timer),
+    /// Stay-awake was turned on or off
+    StayAwakeChanged(bool),
+    /// A power action started waiting, or stopped waiting because it ran or was cancelled
+    PendingActionChanged(Option<PendingAction>),
+}
+
+/// A power action whose timer has finished but that hasn't run yet.
+///
+/// Holds the action during the grace period, or until the user confirms it
+/// when confirmation is required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingAction {
+    /// The power operation to run
+    pub timer_type: TimerType,
+    /// When the action runs on its own, `None` while it waits for confirmation
+    pub runs_at: Option<i64>,
+}
+
+/// What a client learns from the owner of [`BUS_NAME`], see [`updates`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    /// Whether the client's own connection owns the name, i.e. runs the timers
+    Owner(bool),
+    /// The owner turned stay-awake on or off
+    StayAwakeChanged(bool),
+    /// The owner's pending power action changed
+    PendingActionChanged(Option<PendingAction>),
+}
+
+/// Requests a client sends to the owner of [`BUS_NAME`], see [`request`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// Toggle stay-awake
+    ToggleStayAwake,
+    /// Turn stay-awake on or off
+    SetStayAwake(bool),
+    /// Run the pending power action now
+    RunPendingAction,
+    /// Drop the pending power action
+    CancelPendingAction,
+    /// Hand over a power action that is pending in the client
+    AdoptPendingAction(PendingAction),
+}
+
+/// The object served at [`OBJECT_PATH`].
+///
+/// Besides the database it keeps its owner's stay-awake and pending action,
+/// as last sent with [`emit`], for the `State` method.
+pub struct TimerService {
+    pool: SqlitePool,
+    clock: SharedClock,
+    events: mpsc::Sender<ServiceEvent>,
+    stay_awake: bool,
+    pending_action: Option<PendingAction>,
+}
+
+/// Gets a timer's kind for the signals and listing, see [`TimerType::kind`].
+fn timer_kind(timer: &Timer) -> &'static str {
+    TimerType::from_str(&timer.description).map_or("reminder", |timer_type| timer_type.kind())
+}
+
+/// Converts a pending action to its `(kind, runs_at)` form on the bus.
+fn pending_to_wire(pending: Option<&PendingAction>) -> (String, i64) {
+    pending.map_or((String::new(), -1), |pending| {
+        (
+            pending.timer_type.kind().to_string(),
+            pending.runs_at.unwrap_or(-1),
+        )
+    })
+}
+
+/// Reads a pending action from its `(kind, runs_at)` form on the bus.
+fn pending_from_wire(kind: &str, runs_at: i64) -> Option<PendingAction> {
+    match TimerType::from_kind(kind, "") {
+        Some(TimerType::UserDefined(_)) | None => None,
+        Some(timer_type) => Some(PendingAction {
+            timer_type,
+            runs_at: (runs_at >= 0).then_some(runs_at),
+        }),
+    }
+}
+
+/// Converts a failed database operation into a D-Bus error.
+///
+/// An id that doesn't name a running timer is the caller's mistake, not a failure.
+fn failed(error: &RepositoryError) -> fdo::Error {
+    match error {
+        RepositoryError::NotFound { .. } | RepositoryError::Invalid(_) => {
+            fdo::Error::InvalidArgs(error.to_string())
+        }
+        RepositoryError::Database(_) => fdo::Error::Failed(error.to_string()),
+    }
+}
+
+impl TimerService {
+    /// Tells the owner the timers changed. A closed channel means the owner is shutting down.
+    async fn timers_changed(&self) {
+        let _ = self.events.send(ServiceEvent::TimersChanged).await;
+    }
+}
+
+#[interface(name = "io.vulpapps.Chronomancer")]
+impl TimerService {
+    /// Starts a timer after the given number of seconds.
+    async fn create(
+        &self,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        seconds: u32,
+        kind: &str,
+        text: &str,
+    ) -> fdo::Result<i64> {
+        let timer_type = TimerType::from_kind(kind, text)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Unknown timer kind `{kind}`")))?;
+        let seconds = i32::try_from(seconds)
+            .map_err(|_| fdo::Error::InvalidArgs("Too many seconds".to_string()))?;
+
+        let timer = Timer::new(seconds, false, &timer_type, self.clock.now());
+        let timer = Timer::insert(&self.pool, &timer)
+            .await
+            .map_err(|e| failed(&e))?;
+        self.timers_changed().await;
+        Self::timer_created(&emitter, timer.id, timer_kind(&timer), &timer.description).await?;
+        Ok(timer.id)
+    }
+
+    /// Lists running and paused timers, soonest first.
+    async fn list(&self) -> fdo::Result<Vec<TimerInfo>> {
+        let now = self.clock.now();
+        let timers = Timer::get_all_active(&self.pool, now)
+            .await
+            .map_err(|e| failed(&e))?;
+        Ok(timers
+            .iter()
+            .map(|timer| {
+                let has_deadline = timer.ends_at != Timer::NO_DEADLINE;
+                (
+                    timer.id,
+                    timer_kind(timer).to_string(),
+                    timer.description.clone(),
+                    if has_deadline { timer.ends_at } else { -1 },
+                    if has_deadline {
+                        timer.remaining(now)
+                    } else {
+                        -1
+                    },
+                    timer.is_paused(),
+                )
+            })
+            .collect())
+    }
+
+    /// Cancels a timer along with the rest of its chain.
+    async fn cancel(
+        &self,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        id: i64,
+    ) -> fdo::Result<()> {
+        let now = self.clock.now();
+        let timer = Timer::cancel(&self.pool, id, now)
+            .await
+            .map_err(|e| failed(&e))?;
+        self.timers_changed().await;
+        Self::timer_cancelled(&emitter, timer.id, timer_kind(&timer), &timer.description).await?;
+        Ok(())
+    }
+
+    /// Pauses a timer.
+    async fn pause(&self, id: i64) -> fdo::Result<()> {
+        let now = self.clock.now();
+        Timer::set_paused(&self.pool, id, true, now)
+            .await
+            .map_err(|e| failed(&e))?;
+        self.timers_changed().await;
+        Ok(())
+    }
+
+    /// Resumes a paused timer.
+    async fn resume(&self, id: i64) -> fdo::Result<()> {
+        let now = self.clock.now();
+        Timer::set_paused(&self.pool, id, false, now)
+            .await
+            .map_err(|e| failed(&e))?;
+        self.timers_changed().await;
+        Ok(())
+    }
+
+    /// Toggles stay-awake. `StayAwakeChanged` follows once the inhibit lock changed.
+    async fn toggle_stay_awake(&self) {
+        let _ = self.events.send(ServiceEvent::ToggleStayAwake).await;
+    }
+
+    /// Turns stay-awake on or off. `StayAwakeChanged` follows if that changed anything.
+    async fn set_stay_awake(&self, enabled: bool) {
+        let _ = self.events.send(ServiceEvent::SetStayAwake(enabled)).await;
+    }
+
+    /// Runs the pending power action right away.
+    async fn run_pending_action(&self) {
+        let _ = self.events.send(ServiceEvent::RunPendingAction).await;
+    }
+
+    /// Drops the pending power action.
+    async fn cancel_pending_action(&self) {
+        let _ = self.events.send(ServiceEvent::CancelPendingAction).await;
+    }
+
+    /// Takes over a power action that was pending elsewhere. `PendingActionChanged` follows.
+    async fn adopt_pending_action(&self, kind: &str, runs_at: i64) -> fdo::Result<()> {
+        let pending = pending_from_wire(kind, runs_at)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Unknown power action `{kind}`")))?;
+        let _ = self
+            .events
+            .send(ServiceEvent::AdoptPendingAction(pending))
+            .await;
+        Ok(())
+    }
+
+    /// Gets whether stay-awake is on, and the pending power action's kind and when it runs.
+    async fn state(&self) -> (bool, String, i64) {
+        let (kind, runs_at) = pending_to_wire(self.pending_action.as_ref());
+        (self.stay_awake, kind, runs_at)
+    }
+
+    #[zbus(signal)]
+    async fn timer_created(
+        emitter: &SignalEmitter<'_>,
+        id: i64,
+        kind: &str,
+        description: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn timer_fired(
+        emitter: &SignalEmitter<'_>,
+        id: i64,
+        kind: &str,
+        description: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn timer_cancelled(
+        emitter: &SignalEmitter<'_>,
+        id: i64,
+        kind: &str,
+        description: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn stay_awake_changed(emitter: &SignalEmitter<'_>, enabled: bool) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn pending_action_changed(
+        emitter: &SignalEmitter<'_>,
+        kind: &str,
+        runs_at: i64,
+    ) -> zbus::Result<()>;
+}
+
+/// Publishes the timer service on the session bus.
+///
+/// # Arguments
+///
+/// - `pool` - The database the service works on
+/// - `clock` - Where the service reads the current time from
+/// - `role` - Who is publishing it
+///
+/// # Returns
+///
+/// The service's connection, used with [`emit`], and the owner's end of the event channel.
+///
+/// # Errors
+///
+/// Returns an error if the session bus is unreachable, or for [`Role::Daemon`]
+/// if another daemon already owns [`BUS_NAME`].
+pub async fn start(
+    pool: SqlitePool,
+    clock: SharedClock,
+    role: Role,
+) -> Result<(Connection, mpsc::Receiver<ServiceEvent>)> {
+    serve(Builder::session()?, pool, clock, role).await
+}
+
+/// Publishes the timer service on the connection a builder describes.
+///
+/// This is the core of [`start`]; tests pass a builder for a private bus.
+/// The applet lets the daemon replace it as the owner of [`BUS_NAME`] and
+/// waits in the queue for the name otherwise, so it takes over again as soon
+/// as the daemon stops. The daemon replaces the applet but not another daemon.
+///
+/// # Arguments
+///
+/// - `builder` - The connection to serve on
+/// - `pool` - The database the service works on
+/// - `clock` - Where the service reads the current time from
+/// - `role` - Who is publishing it
+///
+/// # Errors
+///
+/// Returns an error if the connection can't be established, or for
+/// [`Role::Daemon`] if another daemon already owns [`BUS_NAME`].
+pub async fn serve(
+    builder: Builder<'_>,
+    pool: SqlitePool,
+    clock: SharedClock,
+    role: Role,
+) -> Result<(Connection, mpsc::Receiver<ServiceEvent>)> {
+    let (events, receiver) = mpsc::channel(8);
+    let service = TimerService {
+        pool,
+        clock,
+        events,
+        stay_awake: false,
+        pending_action: None,
+    };
+    let connection = builder.serve_at(OBJECT_PATH, service)?.build().await?;
+
+    // The builder always asks not to be queued, which would keep the applet
+    // from getting the name back, so the name is requested here instead
+    let flags = match role {
+        Role::Applet => RequestNameFlags::AllowReplacement.into(),
+        Role::Daemon => RequestNameFlags::ReplaceExisting | RequestNameFlags::DoNotQueue,
+    };
+    match connection.request_name_with_flags(BUS_NAME, flags).await {
+        Ok(_) => Ok((connection, receiver)),
+        Err(zbus::Error::NameTaken) => bail!("chronomancerd is already running"),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether the owner of [`BUS_NAME`] is the given connection.
+async fn owner_is(dbus: &fdo::DBusProxy<'_>, connection: &Connection) -> bool {
+    let Ok(name) = BusName::try_from(BUS_NAME) else {
+        return false;
+    };
+    match (dbus.get_name_owner(name).await, connection.unique_name()) {
+        (Ok(owner), Some(own_name)) => owner.as_str() == own_name.as_str(),
+        _ => false,
+    }
+}
+
+/// Sends a signal from the timer service.
+///
+/// Stay-awake and pending action changes are also kept for the `State` method.
+///
+/// # Arguments
+///
+/// - `connection` - The connection returned by [`start`]
+/// - `signal` - What happened
+///
+/// # Errors
+///
+/// Returns an error if the signal can't be sent.
+#[tracing::instrument(level = "debug", skip(connection))]
+pub async fn emit(connection: &Connection, signal: Signal) -> Result<()> {
+    let emitter = SignalEmitter::new(connection, OBJECT_PATH)?;
+    if let Signal::StayAwakeChanged(_) | Signal::PendingActionChanged(_) = &signal {
+        let service = connection
+            .object_server()
+            .interface::<_, TimerService>(OBJECT_PATH)
+            .await?;
+        let mut service = service.get_mut().await;
+        match &signal {
+            Signal::StayAwakeChanged(enabled) => service.stay_awake = *enabled,
+            Signal::PendingActionChanged(pending) => service.pending_action.clone_from(pending),
+            _ => {}
+        }
+    }
+    match signal {
+        Signal::TimerCreated(timer) => {
+            TimerService::timer_created(&emitter, timer.id, timer_kind(&timer), &timer.description)
+                .await?;
+        }
+        Signal::TimerFired(timer) => {
+            TimerService::timer_fired(&emitter, timer.id, timer_kind(&timer), &timer.description)
+                .await?;
+        }
+        Signal::TimerCancelled(timer) => {
+            TimerService::timer_cancelled(
+                &emitter,
+                timer.id,
+                timer_kind(&timer),
+                &timer.description,
+            )
+            .await?;
+        }
+        Signal::StayAwakeChanged(enabled) => {
+            TimerService::stay_awake_changed(&emitter, enabled).await?;
+        }
+        Signal::PendingActionChanged(pending) => {
+            let (kind, runs_at) = pending_to_wire(pending.as_ref());
+            TimerService::pending_action_changed(&emitter, &kind, runs_at).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Follows the owner of [`BUS_NAME`] from a connection serving as [`Role::Applet`].
+///
+/// The stream starts with whether the connection owns the name right now,
+/// followed by every change of owner and the owner's stay-awake and pending
+/// action signals. Signals the connection sends itself are left out.
+///
+/// # Arguments
+///
+/// - `connection` - The connection returned by [`start`]
+///
+/// # Errors
+///
+/// Returns an error if the bus can't be asked for the owner or the signals.
+pub async fn updates(connection: &Connection) -> Result<BoxStream<'static, Update>> {
+    let dbus = fdo::DBusProxy::new(connection).await?;
+    let own_name = connection
+        .unique_name()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    // Subscribe before reading the current owner so no change slips through in between
+    let owner_changes = dbus
+        .receive_name_owner_changed_with_args(&[(0, BUS_NAME)])
+        .await?;
+    let rule = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .interface(INTERFACE)?
+        .path(OBJECT_PATH)?
+        .build();
+    let signals = MessageStream::for_match_rule(rule, connection, None).await?;
+    let owner = Update::Owner(owner_is(&dbus, connection).await);
+
+    let own = own_name.clone();
+    let owner_changes = owner_changes.filter_map(move |change| {
+        let own = own.clone();
+        async move {
+            let args = change.args().ok()?;
+            let new_owner: &Option<_> = args.new_owner();
+            Some(Update::Owner(
+                new_owner
+                    .as_ref()
+                    .is_some_and(|owner| owner.as_str() == own),
+            ))
+        }
+    });
+    let signals = signals.filter_map(move |message| {
+        let own = own_name.clone();
+        async move {
+            let message = message.ok()?;
+            let header = message.header();
+            if header.sender().is_some_and(|sender| sender.as_str() == own) {
+                return None;
+            }
+            match header.member()?.as_str() {
+                "StayAwakeChanged" => message
+                    .body()
+                    .deserialize::<bool>()
+                    .ok()
+                    .map(Update::StayAwakeChanged),
+                "PendingActionChanged" => {
+                    let (kind, runs_at): (String, i64) = message.body().deserialize().ok()?;
+                    Some(Update::PendingActionChanged(pending_from_wire(
+                        &kind, runs_at,
+                    )))
+                }
+                _ => None,
+            }
+        }
+    });
+
+    Ok(stream::once(async move { owner })
+        .chain(stream::select(owner_changes, signals))
+        .boxed())
+}
+
+/// Gets the owner's stay-awake state and pending power action.
+///
+/// # Errors
+///
+/// Returns an error if nobody owns [`BUS_NAME`] or the call fails.
+#[tracing::instrument(level = "debug", skip(connection))]
+pub async fn fetch_state(connection: &Connection) -> Result<(bool, Option<PendingAction>)> {
+    let proxy = Proxy::new(connection, BUS_NAME, OBJECT_PATH, INTERFACE).await?;
+    let (stay_awake, kind, runs_at): (bool, String, i64) = proxy.call("State", &()).await?;
+    Ok((stay_awake, pending_from_wire(&kind, runs_at)))
+}
+
+/// Sends a request to the owner of [`BUS_NAME`].
+///
+/// # Errors
+///
+/// Returns an error if nobody owns [`BUS_NAME`] or the call fails.
+#[tracing::instrument(level = "debug", skip(connection))]
+pub async fn request(connection: &Connection, request: Request) -> Result<()> {
+    let proxy = Proxy::new(connection, BUS_NAME, OBJECT_PATH, INTERFACE).await?;
+    match request {
+        Request::ToggleStayAwake => proxy.call_method("ToggleStayAwake", &()).await?,
+        Request::SetStayAwake(enabled) => proxy.call_method("SetStayAwake", &(enabled,)).await?,
+        Request::RunPendingAction => proxy.call_method("RunPendingAction", &()).await?,
+        Request::CancelPendingAction => proxy.call_method("CancelPendingAction", &()).await?,
+        Request::AdoptPendingAction(pending) => {
+            let wire = pending_to_wire(Some(&pending));
+            proxy.call_method("AdoptPendingAction", &wire).await?
+        }
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{clock, database::SQLiteDatabase};
+    use futures_util::StreamExt;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use zbus::Proxy;
+
+    /// A private session bus, stopped when dropped.
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    /// Starts a private session bus, or `None` where `dbus-daemon` isn't installed.
+    fn private_bus() -> Option<PrivateBus> {
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .ok()?;
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take()?)
+            .read_line(&mut address)
+            .ok()?;
+        Some(PrivateBus {
+            daemon,
+            address: address.trim().to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_service_on_private_bus() -> anyhow::Result<()> {
+        let Some(bus) = private_bus() else {
+            return Ok(());
+        };
+        let database = SQLiteDatabase::new_in_memory().await?;
+        let (service, mut events) = serve(
+            Builder::address(bus.address.as_str())?,
+            database.pool().clone(),
+            clock::system(),
+            Role::Applet,
+        )
+        .await?;
+
+        let client = Builder::address(bus.address.as_str())?.build().await?;
+        let proxy = Proxy::new(&client, BUS_NAME, OBJECT_PATH, INTERFACE).await?;
+        let mut created = proxy.receive_signal("TimerCreated").await?;
+        let mut fired = proxy.receive_signal("TimerFired").await?;
+
+        let id: i64 = proxy.call("Create", &(3600u32, "reboot", "")).await?;
+        let (signal_id, kind, _description): (i64, String, String) =
+            created.next().await.unwrap().body().deserialize()?;
+        assert_eq!((signal_id, kind.as_str()), (id, "reboot"));
+        assert_eq!(events.recv().await, Some(ServiceEvent::TimersChanged));
+
+        let _: () = proxy.call("Pause", &(id,)).await?;
+        let timers: Vec<TimerInfo> = proxy.call("List", &()).await?;
+        assert_eq!(timers.len(), 1);
+        assert!(timers[0].5);
+
+        let _: () = proxy.call("Cancel", &(id,)).await?;
+        let timers: Vec<TimerInfo> = proxy.call("List", &()).await?;
+        assert!(timers.is_empty());
+
+        // A timer that's gone is the caller's mistake, not a failure
+        let result: zbus::Result<()> = proxy.call("Cancel", &(id,)).await;
+        assert!(matches!(
+            result.map_err(fdo::Error::from),
+            Err(fdo::Error::InvalidArgs(_))
+        ));
+
+        // Bad arguments are rejected without touching the database
+        let result: zbus::Result<i64> = proxy.call("Create", &(60u32, "reminder", "")).await;
+        assert!(result.is_err());
+
+        // Signals for things that happen inside the applet
+        let timer = Timer::new(60, false, &TimerType::UserDefined("Tea".to_string()), 0);
+        emit(&service, Signal::TimerFired(timer)).await?;
+        let (_, kind, description): (i64, String, String) =
+            fired.next().await.unwrap().body().deserialize()?;
+        assert_eq!((kind.as_str(), description.as_str()), ("reminder", "Tea"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_daemon_takes_over_from_applet() -> anyhow::Result<()> {
+        let Some(bus) = private_bus() else {
+            return Ok(());
+        };
+        let database = SQLiteDatabase::new_in_memory().await?;
+        let pool = database.pool().clone();
+        let (applet, _applet_events) = serve(
+            Builder::address(bus.address.as_str())?,
+            pool.clone(),
+            clock::system(),
+            Role::Applet,
+        )
+        .await?;
+        let mut owner_updates = updates(&applet).await?;
+        assert_eq!(owner_updates.next().await, Some(Update::Owner(true)));
+
+        let (daemon, mut daemon_events) = serve(
+            Builder::address(bus.address.as_str())?,
+            pool.clone(),
+            clock::system(),
+            Role::Daemon,
+        )
+        .await?;
+        assert_eq!(owner_updates.next().await, Some(Update::Owner(false)));
+
+        // A second daemon doesn't get in
+        let second = serve(
+            Builder::address(bus.address.as_str())?,
+            pool.clone(),
+            clock::system(),
+            Role::Daemon,
+        )
+        .await;
+        assert!(second.is_err());
+
+        // The applet follows the daemon's state and sends it requests
+        emit(&daemon, Signal::StayAwakeChanged(true)).await?;
+        assert_eq!(
+            owner_updates.next().await,
+            Some(Update::StayAwakeChanged(true))
+        );
+        let pending = PendingAction {
+            timer_type: TimerType::Shutdown,
+            runs_at: None,
+        };
+        emit(&daemon, Signal::PendingActionChanged(Some(pending.clone()))).await?;
+        assert_eq!(
+            owner_updates.next().await,
+            Some(Update::PendingActionChanged(Some(pending.clone())))
+        );
+        assert_eq!(fetch_state(&applet).await?, (true, Some(pending)));
+        request(&applet, Request::RunPendingAction).await?;
+        assert_eq!(
+            daemon_events.recv().await,
+            Some(ServiceEvent::RunPendingAction)
+        );
+        let reboot = PendingAction {
+            timer_type: TimerType::Reboot,
+            runs_at: Some(1_767_600_030),
+        };
+        request(&applet, Request::AdoptPendingAction(reboot.clone())).await?;
+        assert_eq!(
+            daemon_events.recv().await,
+            Some(ServiceEvent::AdoptPendingAction(reboot))
+        );
+
+        // The applet gets the name back once the daemon stops
+        daemon.close().await?;
+        assert_eq!(owner_updates.next().await, Some(Update::Owner(true)));
+        Ok(())
+    }
+}
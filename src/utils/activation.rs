@@ -0,0 +1,107 @@
+//! D-Bus activation of the running applet.
+//!
+//! The applet runs as a single instance and serves the freedesktop
+//! `org.freedesktop.Application` interface under its app id. Other processes,
+//! like a global COSMIC custom shortcut running `chronomancer --quick-entry`,
+//! use [`activate_action`] to ask the running applet to do something instead
+//! of starting a second copy.
+//!
+//! # Actions
+//!
+//! - [`QUICK_ENTRY_ACTION`] - Open the popup with the quick entry prompt focused
+//! - [`RELOAD_ACTION`] - Reload timers after another process changed the database
+//! - [`STAY_AWAKE_ACTION`] - Turn stay-awake on or off, with a boolean parameter
+//!
+//! The same action can be triggered without the binary:
+//!
+//! ```text
+//! gdbus call --session --dest io.vulpapps.Chronomancer \
+//!     --object-path /io/vulpapps/Chronomancer \
+//!     --method org.freedesktop.Application.ActivateAction "quick-entry" [] {}
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::utils::activation;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! activation::activate_action(activation::QUICK_ENTRY_ACTION).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use zbus::{Connection, Proxy, zvariant::Value};
+
+/// Well-known bus name of the running applet (its app id).
+pub const BUS_NAME: &str = "io.vulpapps.Chronomancer";
+
+/// Object path the applet serves `org.freedesktop.Application` at.
+pub const OBJECT_PATH: &str = "/io/vulpapps/Chronomancer";
+
+/// Action that opens the quick entry prompt.
+pub const QUICK_ENTRY_ACTION: &str = "quick-entry";
+
+/// Action that reloads the timers from the database, e.g. after the CLI changed them.
+pub const RELOAD_ACTION: &str = "reload";
+
+/// Action that turns stay-awake on (`true`) or off (`false`).
+pub const STAY_AWAKE_ACTION: &str = "stay-awake";
+
+/// Asks the running applet to perform an action.
+///
+/// # Arguments
+///
+/// - `action` - Name of the action, e.g. [`QUICK_ENTRY_ACTION`]
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Failed to connect to the session D-Bus
+/// - The applet isn't running in the panel, or rejected the action
+///
+/// # D-Bus API
+///
+/// ```text
+/// org.freedesktop.Application.ActivateAction(s action, av parameter, a{sv} platform_data)
+/// ```
+pub async fn activate_action(action: &str) -> Result<()> {
+    activate_action_with(action, Vec::new()).await
+}
+
+/// Asks the running applet to perform an action that takes parameters.
+///
+/// # Arguments
+///
+/// - `action` - Name of the action, e.g. [`STAY_AWAKE_ACTION`]
+/// - `parameters` - The action's parameters, e.g. `vec![Value::from(true)]`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Failed to connect to the session D-Bus
+/// - The applet isn't running in the panel, or rejected the action
+pub async fn activate_action_with(action: &str, parameters: Vec<Value<'_>>) -> Result<()> {
+    let connection = Connection::session()
+        .await
+        .context("Failed to connect to session bus")?;
+
+    let application = Proxy::new(
+        &connection,
+        BUS_NAME,
+        OBJECT_PATH,
+        "org.freedesktop.Application",
+    )
+    .await?;
+
+    let platform_data: HashMap<&str, Value<'_>> = HashMap::new();
+    application
+        .call_method("ActivateAction", &(action, parameters, platform_data))
+        .await
+        .context("Chronomancer isn't running in the panel")?;
+
+    Ok(())
+}
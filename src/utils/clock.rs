@@ -0,0 +1,462 @@
+//! Where the current time comes from.
+//!
+//! Models take `now` as an argument; the app, the daemon and the timer service
+//! read it from a [`Clock`] they are handed. Production code runs on
+//! [`SystemClock`], tests on a [`ManualClock`] that only moves when told to, so
+//! nothing depends on which second the test happens to start in.
+//!
+//! Besides the wall clock, a [`Clock`] reports a boot clock that only moves
+//! forward while the machine runs, and the local time zone. [`ClockWatch`]
+//! compares both clocks between checks to notice when the wall clock was set
+//! (by NTP or by hand) or the time zone changed, so countdowns can keep their
+//! remaining time and timers set to a wall clock time can be resolved again.
+//!
+//! Waiting for a deadline goes through the clock as well, see
+//! [`Clock::wait_until`]. Tokio's timers run on the monotonic clock, which
+//! stops while the machine is suspended, so a deadline that passed during
+//! suspend would only be noticed as late as the machine slept. The system
+//! clock waits on a `timerfd` on the wall clock instead, which fires right
+//! after resume and also ends the wait when the clock is set, so that is the
+//! moment to check a [`ClockWatch`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::utils::clock::{Clock, ClockWatch, ManualClock};
+//!
+//! let clock = ManualClock::new(1_000);
+//! let shared = clock.clone();
+//!
+//! clock.advance(90);
+//! assert_eq!(shared.now(), 1_090);
+//! assert_eq!(shared.now_millis(), 1_090_000);
+//!
+//! // The wall clock jumps an hour ahead while the boot clock does not
+//! let mut watch = ClockWatch::new(&clock);
+//! clock.set(1_090 + 3600);
+//! assert_eq!(watch.check(&clock).map(|change| change.jumped_by), Some(3600));
+//! ```
+
+use std::{
+    fmt, io,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicI64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+use rustix::{
+    io::Errno,
+    time::{
+        Itimerspec, TimerfdClockId, TimerfdFlags, TimerfdTimerFlags, Timespec, timerfd_create,
+        timerfd_settime,
+    },
+};
+use tokio::{io::unix::AsyncFd, sync::watch};
+
+use crate::utils::time_zone;
+
+/// How far the wall clock may drift from the boot clock between two checks
+/// before it counts as a jump. Covers scheduling delays and slow NTP slewing.
+const JUMP_TOLERANCE_MILLIS: i64 = 2000;
+
+/// Longest sleep without a `timerfd`, so suspend or a set clock delay a deadline by this much at most
+const FALLBACK_STEP: Duration = Duration::from_secs(10);
+
+/// Source of the current time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Current Unix timestamp in milliseconds.
+    fn now_millis(&self) -> i64;
+
+    /// Milliseconds since boot, unaffected by changes to the wall clock.
+    fn boot_millis(&self) -> i64;
+
+    /// IANA name of the local time zone, such as `Europe/Berlin`.
+    fn time_zone(&self) -> String;
+
+    /// Waits until the wall clock reaches a Unix timestamp in milliseconds.
+    ///
+    /// Time spent suspended counts. The wait may end early, e.g. when the wall
+    /// clock is set or the machine resumes, so callers check the time (and
+    /// their [`ClockWatch`]) again afterwards.
+    fn wait_until(&self, at_millis: i64) -> BoxFuture<'_, ()>;
+
+    /// Current Unix timestamp in seconds.
+    fn now(&self) -> i64 {
+        self.now_millis().div_euclid(1000)
+    }
+}
+
+/// A clock shared by everything that runs timers in one process.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Creates a shared handle to the system clock.
+#[must_use]
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// The wall clock, as reported by the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    /// Reads `/proc/uptime`, which keeps counting through suspend. Falls back
+    /// to the wall clock where it is not available, so jumps go unnoticed
+    /// there instead of being reported all the time.
+    fn boot_millis(&self) -> i64 {
+        std::fs::read_to_string("/proc/uptime")
+            .ok()
+            .and_then(|uptime| parse_uptime(&uptime))
+            .unwrap_or_else(|| self.now_millis())
+    }
+
+    fn time_zone(&self) -> String {
+        time_zone::local_zone()
+    }
+
+    /// Waits on a `timerfd` on the wall clock, falling back to short tokio
+    /// sleeps where one can't be made.
+    fn wait_until(&self, at_millis: i64) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            if let Err(e) = wait_on_timerfd(at_millis).await {
+                tracing::debug!("Failed to wait on the wall clock, sleeping instead: {e}");
+                let remaining = at_millis.saturating_sub(self.now_millis());
+                let remaining = Duration::from_millis(u64::try_from(remaining).unwrap_or(0));
+                tokio::time::sleep(remaining.min(FALLBACK_STEP)).await;
+            }
+        })
+    }
+}
+
+/// Waits on a `timerfd` until the wall clock reaches a Unix timestamp in milliseconds.
+///
+/// The timer is absolute and cancelled when the clock is set, which includes
+/// resuming from suspend, so the wait ends then as well.
+///
+/// # Errors
+///
+/// Returns an error if the `timerfd` can't be made, armed or read.
+async fn wait_on_timerfd(at_millis: i64) -> io::Result<()> {
+    let timer = timerfd_create(
+        TimerfdClockId::Realtime,
+        TimerfdFlags::CLOEXEC | TimerfdFlags::NONBLOCK,
+    )?;
+    let at = Itimerspec {
+        it_interval: Timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        },
+        // A zero time would disarm the timer, anything up to then has passed anyway
+        it_value: Timespec {
+            tv_sec: at_millis.div_euclid(1000).max(1),
+            tv_nsec: at_millis.rem_euclid(1000) * 1_000_000,
+        },
+    };
+    match timerfd_settime(
+        &timer,
+        TimerfdTimerFlags::ABSTIME | TimerfdTimerFlags::CANCEL_ON_SET,
+        &at,
+    ) {
+        Err(Errno::CANCELED) => return Ok(()),
+        result => result?,
+    };
+
+    let timer = AsyncFd::new(timer)?;
+    loop {
+        let mut ready = timer.readable().await?;
+        let mut expirations = [0u8; 8];
+        match ready.try_io(|timer| {
+            rustix::io::read(timer.get_ref(), &mut expirations).map_err(io::Error::from)
+        }) {
+            Ok(Ok(_)) => return Ok(()),
+            Ok(Err(e)) if e.raw_os_error() == Some(Errno::CANCELED.raw_os_error()) => {
+                return Ok(());
+            }
+            Ok(Err(e)) => return Err(e),
+            // Woken without anything to read, wait again
+            Err(_would_block) => {}
+        }
+    }
+}
+
+/// Parses the first field of `/proc/uptime` into milliseconds.
+fn parse_uptime(uptime: &str) -> Option<i64> {
+    let seconds = uptime.split_whitespace().next()?;
+    let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, "0"));
+    let centis: i64 = format!("{fraction:0<2}").get(..2)?.parse().ok()?;
+    Some(whole.parse::<i64>().ok()?.checked_mul(1000)? + centis * 10)
+}
+
+/// A clock that stands still until it is set or advanced.
+///
+/// Clones share the same time, so a test can keep one and hand another to the
+/// code under test. Its boot clock starts at zero and its time zone is `UTC`.
+/// Waits on it end when it is advanced past their time or set, without any
+/// tokio timer involved.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    millis: Arc<AtomicI64>,
+    boot_millis: Arc<AtomicI64>,
+    time_zone: Arc<Mutex<String>>,
+    /// Counts how often the clock was set, and wakes waits whenever it moves
+    sets: Arc<watch::Sender<u64>>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at the given Unix timestamp in seconds.
+    #[must_use]
+    pub fn new(now: i64) -> Self {
+        Self {
+            millis: Arc::new(AtomicI64::new(now.saturating_mul(1000))),
+            boot_millis: Arc::new(AtomicI64::new(0)),
+            time_zone: Arc::new(Mutex::new("UTC".to_string())),
+            sets: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    /// Sets the wall clock to the given Unix timestamp in seconds, like NTP or
+    /// the user would. The boot clock does not move.
+    pub fn set(&self, now: i64) {
+        self.millis
+            .store(now.saturating_mul(1000), Ordering::SeqCst);
+        self.sets.send_modify(|sets| *sets += 1);
+    }
+
+    /// Lets time pass by whole seconds (or go back, for a negative value).
+    pub fn advance(&self, seconds: i64) {
+        self.advance_millis(seconds.saturating_mul(1000));
+    }
+
+    /// Lets time pass by milliseconds (or go back, for a negative value).
+    ///
+    /// Moves the wall clock and the boot clock together.
+    pub fn advance_millis(&self, millis: i64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+        self.boot_millis.fetch_add(millis, Ordering::SeqCst);
+        self.sets.send_modify(|_| {});
+    }
+
+    /// Switches the local time zone, given its IANA name.
+    pub fn set_time_zone(&self, zone: &str) {
+        *self
+            .time_zone
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = zone.to_string();
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> i64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+
+    fn boot_millis(&self) -> i64 {
+        self.boot_millis.load(Ordering::SeqCst)
+    }
+
+    fn time_zone(&self) -> String {
+        self.time_zone
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Ends once the clock is advanced to `at_millis`, or as soon as it is set.
+    fn wait_until(&self, at_millis: i64) -> BoxFuture<'_, ()> {
+        let mut changes = self.sets.subscribe();
+        let sets = *changes.borrow_and_update();
+        Box::pin(async move {
+            while self.now_millis() < at_millis && *changes.borrow_and_update() == sets {
+                // The sender lives as long as the clock
+                let _ = changes.changed().await;
+            }
+        })
+    }
+}
+
+/// What changed about the clock since the last check.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClockChange {
+    /// Seconds the wall clock was set forward (negative: back) by
+    pub jumped_by: i64,
+    /// The new local time zone, when it or its UTC offset changed
+    pub time_zone: Option<String>,
+}
+
+/// Notices wall clock jumps and time zone changes between checks.
+///
+/// Daylight saving time switches show up as a change of the UTC offset within
+/// the same zone, so they are reported like a change of zone.
+#[derive(Debug, Clone)]
+pub struct ClockWatch {
+    wall_millis: i64,
+    boot_millis: i64,
+    time_zone: String,
+    utc_offset: Option<i32>,
+}
+
+impl ClockWatch {
+    /// Starts watching from the clock's current state.
+    #[must_use]
+    pub fn new(clock: &dyn Clock) -> Self {
+        let wall_millis = clock.now_millis();
+        let time_zone = clock.time_zone();
+        Self {
+            wall_millis,
+            boot_millis: clock.boot_millis(),
+            utc_offset: time_zone::utc_offset(&time_zone, wall_millis.div_euclid(1000)),
+            time_zone,
+        }
+    }
+
+    /// Compares the clock against the last check.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock` - The clock being watched
+    ///
+    /// # Returns
+    ///
+    /// What changed, or `None` when the wall clock kept pace with the boot
+    /// clock and the time zone stayed the same.
+    pub fn check(&mut self, clock: &dyn Clock) -> Option<ClockChange> {
+        let current = Self::new(clock);
+        let drift =
+            (current.wall_millis - self.wall_millis) - (current.boot_millis - self.boot_millis);
+        let jumped_by = if drift.abs() < JUMP_TOLERANCE_MILLIS {
+            0
+        } else {
+            (drift + 500).div_euclid(1000)
+        };
+        let time_zone = (current.time_zone != self.time_zone
+            || current.utc_offset != self.utc_offset)
+            .then(|| current.time_zone.clone());
+
+        *self = current;
+        (jumped_by != 0 || time_zone.is_some()).then_some(ClockChange {
+            jumped_by,
+            time_zone,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_only_moves_when_told() {
+        let clock = ManualClock::new(1_000);
+        let shared: SharedClock = Arc::new(clock.clone());
+        assert_eq!(shared.now(), 1_000);
+        assert_eq!(shared.time_zone(), "UTC");
+
+        clock.advance_millis(1_500);
+        assert_eq!(shared.now(), 1_001);
+        assert_eq!(shared.now_millis(), 1_001_500);
+        assert_eq!(shared.boot_millis(), 1_500);
+
+        clock.set(50);
+        clock.advance(-60);
+        // Rounds towards the past, like a wall clock before the epoch would
+        assert_eq!(shared.now(), -10);
+        assert_eq!(shared.boot_millis(), -58_500);
+
+        clock.set_time_zone("Asia/Tokyo");
+        assert_eq!(shared.time_zone(), "Asia/Tokyo");
+    }
+
+    #[tokio::test]
+    async fn test_manual_clock_wakes_waits() {
+        let clock = ManualClock::new(1_000);
+        let waiting = clock.clone();
+        let wait = tokio::spawn(async move { waiting.wait_until(1_060_000).await });
+        tokio::task::yield_now().await;
+
+        clock.advance(30);
+        tokio::task::yield_now().await;
+        assert!(!wait.is_finished());
+
+        // Setting the clock ends the wait even before the time, so the caller can look at it
+        clock.set(1_040);
+        tokio::time::timeout(Duration::from_secs(5), wait)
+            .await
+            .expect("the wait ended")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_system_clock_waits_on_the_wall_clock() {
+        let at = SystemClock.now_millis() + 50;
+        tokio::time::timeout(Duration::from_secs(5), wait_on_timerfd(at))
+            .await
+            .expect("the wait ended")
+            .unwrap();
+        assert!(SystemClock.now_millis() >= at);
+
+        // A time that already passed doesn't wait at all
+        tokio::time::timeout(Duration::from_secs(5), SystemClock.wait_until(0))
+            .await
+            .expect("the wait ended");
+    }
+
+    #[test]
+    fn test_system_clock_follows_the_wall_clock() {
+        let before = chrono::Utc::now().timestamp();
+        let now = SystemClock.now();
+        assert!(now >= before && now <= chrono::Utc::now().timestamp());
+    }
+
+    #[test]
+    fn test_watch_sees_jumps_and_zone_changes() {
+        // 2026-03-29 00:30 UTC, half an hour before Berlin springs forward
+        let clock = ManualClock::new(1_774_744_200);
+        clock.set_time_zone("Europe/Berlin");
+        let mut watch = ClockWatch::new(&clock);
+
+        clock.advance(10);
+        assert_eq!(watch.check(&clock), None);
+
+        // NTP corrections within the tolerance are not jumps
+        clock.set(clock.now() + 1);
+        assert_eq!(watch.check(&clock), None);
+
+        clock.set(clock.now() - 300);
+        let change = watch.check(&clock).expect("jump back");
+        assert_eq!(change.jumped_by, -300);
+        assert_eq!(change.time_zone, None);
+
+        // Crossing into summer time changes the offset but not the zone
+        clock.advance(3600);
+        let change = watch.check(&clock).expect("DST switch");
+        assert_eq!(change.jumped_by, 0);
+        assert_eq!(change.time_zone.as_deref(), Some("Europe/Berlin"));
+
+        clock.set_time_zone("America/New_York");
+        clock.set(clock.now() + 7200);
+        assert_eq!(
+            watch.check(&clock),
+            Some(ClockChange {
+                jumped_by: 7200,
+                time_zone: Some("America/New_York".to_string()),
+            })
+        );
+        assert_eq!(watch.check(&clock), None);
+    }
+
+    #[test]
+    fn test_parse_uptime() {
+        assert_eq!(parse_uptime("350735.47 234388.90\n"), Some(350_735_470));
+        assert_eq!(parse_uptime("12.5 1.0"), Some(12_500));
+        assert_eq!(parse_uptime("7"), Some(7000));
+        assert_eq!(parse_uptime(""), None);
+        assert_eq!(parse_uptime("uptime"), None);
+    }
+}
@@ -1,5 +1,5 @@
 pub mod repository;
 pub mod sqlite;
 
-pub use repository::Repository;
-pub use sqlite::SQLiteDatabase;
+pub use repository::{Page, Repository, RepositoryError, RepositoryResult};
+pub use sqlite::{ChangeDetector, SQLiteDatabase};
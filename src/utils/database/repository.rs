@@ -1,18 +1,191 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
+
+/// Errors from [`Repository`] operations.
+///
+/// Callers that only report the error can keep using `?` into `anyhow`, the
+/// rest can tell a missing row from a bad request or a failing database.
+#[derive(Debug, thiserror::Error)]
+pub enum RepositoryError {
+    /// No stored item has the id
+    #[error("No {kind} with id {id}")]
+    NotFound { kind: &'static str, id: i64 },
+    /// The request can't be run as asked, e.g. a filter on an unknown timer type
+    #[error("{0}")]
+    Invalid(String),
+    /// The query itself failed
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Result of a [`Repository`] operation.
+pub type RepositoryResult<T> = Result<T, RepositoryError>;
+
+/// A window into a listing, see [`Repository::list`].
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::database::repository::Page;
+///
+/// let page = Page::first(20);
+/// assert_eq!(page.next(), Page { offset: 20, limit: 20 });
+/// assert_eq!(Page::default(), Page::ALL);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// How many items to skip
+    pub offset: i64,
+    /// How many items to return at most, negative for no limit (`LIMIT -1` in SQL)
+    pub limit: i64,
+}
+
+impl Page {
+    /// Every item, in one page.
+    pub const ALL: Page = Page {
+        offset: 0,
+        limit: -1,
+    };
+
+    /// Creates the first page of `limit` items.
+    #[must_use]
+    pub fn first(limit: i64) -> Self {
+        Self { offset: 0, limit }
+    }
+
+    /// Gets the page after this one.
+    #[must_use]
+    pub fn next(self) -> Self {
+        Self {
+            offset: self.offset + self.limit.max(0),
+            ..self
+        }
+    }
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
 
 /// Defines traits for common database repository operations.
-// This doesn't account for joins or composite keys but as this is a simple sqlite driven app with likely one model, it's fine
+// This doesn't account for joins or composite keys; models with child rows
+// (phases, laps) load and store them as part of the item
 #[allow(async_fn_in_trait)]
 pub trait Repository<T> {
+    /// Narrows down [`Repository::list`], e.g. by type, state or date range.
+    /// The default filter lets everything through.
+    type Filter: Default;
+
     /// Inserts a new item into the repository.
-    async fn insert(pool: &SqlitePool, item: &T) -> anyhow::Result<T>;
+    async fn insert(pool: &SqlitePool, item: &T) -> RepositoryResult<T>;
 
-    /// Fetches all active items from the repository.
-    async fn get_all_active(pool: &SqlitePool) -> anyhow::Result<Vec<T>>;
+    /// Stores every field of an item that is already in the repository.
+    ///
+    /// Fails with [`RepositoryError::NotFound`] when nothing has the item's id.
+    async fn update(pool: &SqlitePool, item: &T) -> RepositoryResult<()>;
+
+    /// Fetches all items from the repository that are still active at `now` (a Unix timestamp in seconds).
+    async fn get_all_active(pool: &SqlitePool, now: i64) -> RepositoryResult<Vec<T>>;
 
     /// Retrieves an item by its ID.
-    async fn get_by_id(pool: &SqlitePool, id: &i64) -> anyhow::Result<Option<T>>;
+    async fn get_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<Option<T>>;
+
+    /// Lists the items a filter lets through, one page at a time.
+    async fn list(pool: &SqlitePool, filter: &Self::Filter, page: Page)
+    -> RepositoryResult<Vec<T>>;
 
     /// Deletes an item by its ID.
-    async fn delete_by_id(pool: &SqlitePool, id: &i64) -> anyhow::Result<()>;
+    async fn delete_by_id(pool: &SqlitePool, id: &i64) -> RepositoryResult<()>;
+
+    /// Deletes several items at once, all or none of them.
+    ///
+    /// Returns how many items were deleted; ids that don't exist are skipped.
+    async fn delete_many(pool: &SqlitePool, ids: &[i64]) -> RepositoryResult<u64>;
+}
+
+/// Runs several queries as one transaction.
+///
+/// The transaction is committed when `operation` succeeds and rolled back when
+/// it fails, so a multi-step change is stored completely or not at all.
+///
+/// # Arguments
+///
+/// - `pool` - The database to run in
+/// - `operation` - The queries, run on the transaction's connection
+///
+/// # Errors
+///
+/// Returns the error of `operation`, or of starting or committing the transaction.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chronomancer::utils::database::repository::{RepositoryResult, transaction};
+/// use sqlx::SqlitePool;
+///
+/// # async fn example(pool: &SqlitePool) -> RepositoryResult<()> {
+/// // Both timers are gone, or neither is
+/// transaction(pool, async |connection| {
+///     for id in [3, 4] {
+///         sqlx::query("DELETE FROM timers WHERE id = ?")
+///             .bind(id)
+///             .execute(&mut *connection)
+///             .await?;
+///     }
+///     Ok(())
+/// })
+/// .await
+/// # }
+/// ```
+pub async fn transaction<R>(
+    pool: &SqlitePool,
+    operation: impl AsyncFnOnce(&mut SqliteConnection) -> RepositoryResult<R>,
+) -> RepositoryResult<R> {
+    let mut transaction = pool.begin().await?;
+    let result = operation(&mut transaction).await?;
+    transaction.commit().await?;
+    Ok(result)
+}
+
+/// Appends `(?, ?, …)` binding every id, for `WHERE id IN` clauses.
+pub(crate) fn push_ids(builder: &mut sqlx::QueryBuilder<'_, sqlx::Sqlite>, ids: &[i64]) {
+    builder.push("(");
+    let mut separated = builder.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    builder.push(")");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_transaction_rolls_back_on_error() -> anyhow::Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::query("CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT NOT NULL)")
+            .execute(&pool)
+            .await?;
+
+        let result: RepositoryResult<()> = transaction(&pool, async |connection| {
+            sqlx::query("INSERT INTO notes (body) VALUES ('kept?')")
+                .execute(&mut *connection)
+                .await?;
+            Err(RepositoryError::Invalid("second step failed".to_string()))
+        })
+        .await;
+        assert!(matches!(result, Err(RepositoryError::Invalid(_))));
+
+        let count = transaction(&pool, async |connection| {
+            let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notes")
+                .fetch_one(&mut *connection)
+                .await?;
+            Ok(count)
+        })
+        .await?;
+        assert_eq!(count, 0);
+        Ok(())
+    }
 }
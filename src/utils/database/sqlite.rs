@@ -1,5 +1,11 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{Result, anyhow};
-use sqlx::{SqlitePool, sqlite::SqliteConnectOptions};
+use notify::RecommendedWatcher;
+use sqlx::{SqliteConnection, SqlitePool, sqlite::SqliteConnectOptions};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::utils::file_watch;
 
 const APP_ID: &str = "io.vulpapps.Chronomancer";
 
@@ -10,6 +16,8 @@ const DB_FILENAME: &str = constcat::concat!("chronomancer-v", DB_VERSION, ".db")
 #[derive(Clone, Debug)]
 pub struct SQLiteDatabase {
     pool: SqlitePool,
+    /// Database file, `None` for in-memory databases
+    path: Option<PathBuf>,
 }
 
 impl SQLiteDatabase {
@@ -29,10 +37,25 @@ impl SQLiteDatabase {
             .join(APP_ID);
         std::fs::create_dir_all(&data_dir)?;
 
-        let db_path = data_dir.join(DB_FILENAME);
-        let db_path_str = db_path
+        Self::open(&data_dir.join(DB_FILENAME)).await
+    }
+
+    /// Open (or create) the `SQLite` database at the given path
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - Database file, created if it doesn't exist yet
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The database path is invalid
+    /// - The database connection fails
+    /// - Database migrations fail
+    pub async fn open(path: &Path) -> Result<Self> {
+        let db_path_str = path
             .to_str()
-            .ok_or_else(|| anyhow::anyhow!("Invalid database path"))?;
+            .ok_or_else(|| anyhow!("Invalid database path"))?;
 
         let options = SqliteConnectOptions::new()
             .create_if_missing(true)
@@ -42,9 +65,12 @@ impl SQLiteDatabase {
         let pool = SqlitePool::connect_with(options).await?;
         sqlx::migrate!("./migrations").run(&pool).await?;
 
-        println!("Database migrations completed successfully");
+        tracing::debug!("Database migrations completed successfully");
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            path: Some(path.to_path_buf()),
+        })
     }
 
     /// Create a new in-memory `SQLite` database connection pool (for testing)
@@ -60,13 +86,158 @@ impl SQLiteDatabase {
         let pool = SqlitePool::connect("sqlite::memory:").await?;
         sqlx::migrate!("./migrations").run(&pool).await?;
 
-        println!("In-memory database migrations completed successfully");
+        tracing::debug!("In-memory database migrations completed successfully");
 
-        Ok(Self { pool })
+        Ok(Self { pool, path: None })
     }
 
     #[must_use]
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// Start watching for changes committed by other connections
+    ///
+    /// Takes a connection out of the pool for good, since `SQLite` reports
+    /// changes per connection. Writes through the pool's other connections
+    /// count as changes too, as do writes by other processes like the CLI.
+    /// The database's files are watched through inotify, so nothing needs to
+    /// be checked until one of them is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no connection could be opened or the version can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chronomancer::utils::database::SQLiteDatabase;
+    ///
+    /// # async fn example(database: SQLiteDatabase) -> anyhow::Result<()> {
+    /// let mut changes = database.watch_changes().await?;
+    /// loop {
+    ///     changes.written().await;
+    ///     if changes.has_changed().await? {
+    ///         // Reload whatever is kept in memory
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn watch_changes(&self) -> Result<ChangeDetector> {
+        let mut connection = self.pool.acquire().await?.detach();
+        let data_version = ChangeDetector::read_version(&mut connection).await?;
+        let files = match &self.path {
+            Some(path) => Some(watch_files(path)?),
+            None => None,
+        };
+        Ok(ChangeDetector {
+            connection,
+            data_version,
+            files,
+        })
+    }
+}
+
+/// Watches a database file along with the journal files `SQLite` writes next to it.
+///
+/// Commits write to the database file itself, or to the `-wal` file in WAL mode.
+fn watch_files(path: &Path) -> Result<(RecommendedWatcher, UnboundedReceiver<PathBuf>)> {
+    let with_suffix = |suffix: &str| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(suffix);
+        PathBuf::from(name)
+    };
+    file_watch::watch_paths(&[
+        path.to_path_buf(),
+        with_suffix("-wal"),
+        with_suffix("-journal"),
+    ])
+}
+
+/// Notices commits made to the database by other connections
+///
+/// Reads `PRAGMA data_version`, which changes whenever another connection
+/// (in this process or another one) commits, after the database's files were
+/// written. Created by [`SQLiteDatabase::watch_changes`].
+#[derive(Debug)]
+pub struct ChangeDetector {
+    connection: SqliteConnection,
+    data_version: i64,
+    /// Watcher on the database's files and what it reports, `None` in memory
+    files: Option<(RecommendedWatcher, UnboundedReceiver<PathBuf>)>,
+}
+
+impl ChangeDetector {
+    /// Waits until something writes to the database's files.
+    ///
+    /// Merely opening the database counts as well, so follow up with
+    /// [`ChangeDetector::has_changed`]. Safe to cancel, e.g. in
+    /// a `tokio::select!`. Never finishes for in-memory databases, which no
+    /// other process can write to.
+    pub async fn written(&mut self) {
+        if let Some((_, activity)) = &mut self.files
+            && activity.recv().await.is_some()
+        {
+            // Several events usually come in for one commit, one check covers them all
+            while activity.try_recv().is_ok() {}
+            return;
+        }
+        std::future::pending::<()>().await;
+    }
+
+    /// Whether the database changed since the last check (or since watching started)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version can't be read.
+    pub async fn has_changed(&mut self) -> Result<bool> {
+        let data_version = Self::read_version(&mut self.connection).await?;
+        let changed = data_version != self.data_version;
+        self.data_version = data_version;
+        Ok(changed)
+    }
+
+    async fn read_version(connection: &mut SqliteConnection) -> Result<i64> {
+        let data_version = sqlx::query_scalar::<_, i64>("PRAGMA data_version")
+            .fetch_one(connection)
+            .await?;
+        Ok(data_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watch_changes_sees_other_connections() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("chronomancer-watch-{}.db", std::process::id()));
+        let database = SQLiteDatabase::open(&path).await?;
+        let mut changes = database.watch_changes().await?;
+        assert!(!changes.has_changed().await?);
+
+        // Another process writing, like the CLI
+        let other = SQLiteDatabase::open(&path).await?;
+        sqlx::query("DELETE FROM timers")
+            .execute(other.pool())
+            .await?;
+        tokio::time::timeout(std::time::Duration::from_secs(5), changes.written())
+            .await
+            .expect("the write was noticed");
+        assert!(changes.has_changed().await?);
+        assert!(!changes.has_changed().await?);
+
+        // The applet's own pool counts as well
+        sqlx::query("DELETE FROM timers")
+            .execute(database.pool())
+            .await?;
+        assert!(changes.has_changed().await?);
+
+        drop(changes);
+        database.pool().close().await;
+        other.pool().close().await;
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
 }
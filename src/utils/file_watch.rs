@@ -0,0 +1,232 @@
+//! File and directory watching for file-based timer triggers.
+//!
+//! This module keeps track of watched paths so timers can fire when a file
+//! appears, disappears, or stops changing (e.g. a render's output file that
+//! is no longer growing). Change notifications come from inotify via the
+//! `notify` crate; the state itself is always read back with `stat`, so a
+//! missed or coalesced event can only delay a trigger, never fake one.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::utils::file_watch::{self, WatchedFile};
+//! use std::path::PathBuf;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let path = PathBuf::from("/home/user/renders/output.mkv");
+//! let (_watcher, mut changes) = file_watch::watch_paths(&[path.clone()])?;
+//!
+//! let mut state = WatchedFile::observe(&path, chrono::Utc::now().timestamp(), None);
+//! while let Some(changed) = changes.recv().await {
+//!     state = WatchedFile::observe(&changed, chrono::Utc::now().timestamp(), Some(&state));
+//!     println!("{} changed, exists: {}", changed.display(), state.exists);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+/// Last observed state of a watched file or directory.
+///
+/// # Fields
+///
+/// - `path` - The watched path
+/// - `exists` - Whether the path existed when last observed
+/// - `size` - Size in bytes (0 when missing)
+/// - `modified` - Modification time as a Unix timestamp (0 when missing)
+/// - `last_changed` - Unix timestamp of the last observed change to any of the above
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub exists: bool,
+    pub size: u64,
+    pub modified: i64,
+    pub last_changed: i64,
+}
+
+impl WatchedFile {
+    /// Reads the current state of a path, carrying over `last_changed` when nothing changed.
+    ///
+    /// On the first observation the file's own modification time is used as
+    /// `last_changed`, so a file that finished writing before the applet started
+    /// doesn't have to wait out a whole extra window.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - Path to observe
+    /// - `now` - Current Unix timestamp
+    /// - `previous` - The previous observation of the same path, if any
+    ///
+    /// # Returns
+    ///
+    /// The new observation. Unreadable paths count as missing.
+    #[must_use]
+    pub fn observe(path: &Path, now: i64, previous: Option<&WatchedFile>) -> Self {
+        let (exists, size, modified) = match std::fs::metadata(path) {
+            Ok(metadata) => {
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+                    .unwrap_or_default();
+                (true, metadata.len(), modified)
+            }
+            Err(_) => (false, 0, 0),
+        };
+
+        let last_changed = match previous {
+            Some(previous)
+                if previous.exists == exists
+                    && previous.size == size
+                    && previous.modified == modified =>
+            {
+                previous.last_changed
+            }
+            None if exists => modified.min(now),
+            Some(_) | None => now,
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            exists,
+            size,
+            modified,
+            last_changed,
+        }
+    }
+}
+
+/// Starts watching the given paths for changes.
+///
+/// Each path's parent directory is watched (non-recursively) so files that
+/// don't exist yet, or get deleted and recreated, are still noticed. Existing
+/// directories are additionally watched themselves so changes to their entries
+/// count as changes to the directory.
+///
+/// # Arguments
+///
+/// - `paths` - Absolute paths to watch
+///
+/// # Returns
+///
+/// The watcher, which stops watching when dropped, and a receiver that yields
+/// a watched path every time something happens to it.
+///
+/// # Errors
+///
+/// Returns an error if the inotify watcher cannot be created. Parents that
+/// cannot be watched (e.g. they don't exist) are skipped with a log message.
+pub fn watch_paths(paths: &[PathBuf]) -> Result<(RecommendedWatcher, UnboundedReceiver<PathBuf>)> {
+    let targets: HashSet<PathBuf> = paths.iter().cloned().collect();
+    let (sender, receiver) = unbounded_channel();
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        for path in event.paths {
+            // Events inside a watched directory count as changes to the directory
+            let target = if targets.contains(&path) {
+                Some(path)
+            } else {
+                path.parent()
+                    .filter(|parent| targets.contains(*parent))
+                    .map(Path::to_path_buf)
+            };
+            if let Some(target) = target {
+                let _ = sender.send(target);
+            }
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    let mut watched_directories = HashSet::new();
+    for path in paths {
+        let directories = path
+            .parent()
+            .into_iter()
+            .chain(path.is_dir().then_some(path.as_path()));
+        for directory in directories {
+            if watched_directories.insert(directory.to_path_buf())
+                && let Err(e) = watcher.watch(directory, RecursiveMode::NonRecursive)
+            {
+                tracing::warn!("Failed to watch {}: {e}", directory.display());
+            }
+        }
+    }
+
+    Ok((watcher, receiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chronomancer-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_observe_tracks_changes() {
+        let dir = temp_dir("observe");
+        let path = dir.join("output.mkv");
+
+        let missing = WatchedFile::observe(&path, 100, None);
+        assert!(!missing.exists);
+        assert_eq!(missing.last_changed, 100);
+
+        std::fs::write(&path, b"frame").unwrap();
+        let created = WatchedFile::observe(&path, 200, Some(&missing));
+        assert!(created.exists);
+        assert_eq!(created.size, 5);
+        assert_eq!(created.last_changed, 200);
+
+        // Nothing happened since, so the change time is kept
+        let unchanged = WatchedFile::observe(&path, 300, Some(&created));
+        assert_eq!(unchanged.last_changed, 200);
+
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"frame")
+            .unwrap();
+        let grown = WatchedFile::observe(&path, 400, Some(&unchanged));
+        assert_eq!(grown.size, 10);
+        assert_eq!(grown.last_changed, 400);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_paths_reports_watched_file() {
+        let dir = temp_dir("watch");
+        let path = dir.join("done.flag");
+        let (_watcher, mut changes) = watch_paths(std::slice::from_ref(&path)).unwrap();
+
+        // Unrelated files in the same directory are ignored
+        std::fs::write(dir.join("other.txt"), b"noise").unwrap();
+        std::fs::write(&path, b"").unwrap();
+
+        let received = tokio::time::timeout(std::time::Duration::from_secs(5), changes.recv())
+            .await
+            .expect("no file event received");
+        assert_eq!(received, Some(path));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -173,3 +173,47 @@ pub fn filter_alphanumeric(input: &str) -> Option<String> {
         Some(filtered)
     }
 }
+
+/// Parses a user-entered path for a file trigger.
+///
+/// Surrounding whitespace is trimmed and a leading `~` is expanded to the
+/// home directory. Relative paths are rejected since the applet's working
+/// directory means nothing to the user.
+///
+/// # Arguments
+///
+/// - `input` - The text to parse
+///
+/// # Returns
+///
+/// - `Some(PathBuf)` - Absolute path to watch
+/// - `None` - Empty or relative input
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::filters::parse_watch_path;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(
+///     parse_watch_path(" /renders/output.mkv "),
+///     Some(PathBuf::from("/renders/output.mkv"))
+/// );
+/// assert!(parse_watch_path("~/output.mkv").is_some_and(|path| path.is_absolute()));
+///
+/// // Relative and empty paths are rejected
+/// assert_eq!(parse_watch_path("output.mkv"), None);
+/// assert_eq!(parse_watch_path("   "), None);
+/// ```
+#[must_use]
+pub fn parse_watch_path(input: &str) -> Option<std::path::PathBuf> {
+    let input = input.trim();
+    let path = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            dirs::home_dir()?.join(rest.trim_start_matches('/'))
+        }
+        _ => std::path::PathBuf::from(input),
+    };
+
+    path.is_absolute().then_some(path)
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: MIT
+
+//! Log output for the applet, the daemon and the command line.
+//!
+//! Everything logs through `tracing`. [`init`] sends it to stderr, where
+//! systemd puts it into the journal for `chronomancerd`, and to a file that
+//! rotates daily under the app's data directory, so a timer that misfired
+//! while the panel was running can be looked into afterwards:
+//!
+//! ```text
+//! ~/.local/share/io.vulpapps.Chronomancer/logs/chronomancer.2026-10-18.log
+//! ~/.local/share/io.vulpapps.Chronomancer/logs/chronomancerd.2026-10-18.log
+//! ```
+//!
+//! The level comes from the `log_level` setting (see [`Config`](crate::config::Config)),
+//! read once at start, or from the `CHRONOMANCER_LOG` environment variable, which takes `tracing`
+//! filter directives and overrides the setting:
+//!
+//! ```text
+//! CHRONOMANCER_LOG=debug chronomancerd
+//! CHRONOMANCER_LOG=warn,chronomancer::daemon=trace chronomancerd
+//! ```
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::{config::LogLevel, utils::logging};
+//!
+//! // Keep the guard until the process exits, dropping it flushes the file
+//! let _guard = logging::init("chronomancerd", LogLevel::Info);
+//! tracing::info!("Started");
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use tracing_appender::{
+    non_blocking::{NonBlocking, WorkerGuard},
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::config::LogLevel;
+
+const APP_ID: &str = "io.vulpapps.Chronomancer";
+/// Environment variable with filter directives that override the configured level
+pub const LOG_ENV: &str = "CHRONOMANCER_LOG";
+/// How many days of log files are kept
+const MAX_LOG_FILES: usize = 7;
+
+/// Gets the directory log files are written to.
+#[must_use]
+pub fn log_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(APP_ID).join("logs"))
+}
+
+/// Builds the filter directives for a configured level.
+///
+/// Chronomancer's own crates log at the level, dependencies like `sqlx` and
+/// `zbus` only their warnings, so a debug log stays about the timers.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::{config::LogLevel, utils::logging};
+///
+/// assert_eq!(
+///     logging::directives(LogLevel::Debug),
+///     "warn,chronomancer=debug,chronomancerd=debug"
+/// );
+/// ```
+#[must_use]
+pub fn directives(level: LogLevel) -> String {
+    let level = level.as_str();
+    format!("warn,chronomancer={level},chronomancerd={level}")
+}
+
+/// Starts logging to stderr and to the process's log file.
+///
+/// Logging without the file is better than no logging, so a log directory
+/// that can't be created only costs the file; the reason goes to stderr.
+/// Calling it again does nothing, the first call's setup stays.
+///
+/// # Arguments
+///
+/// - `name` - Name of the process, used as the log file's name
+/// - `level` - Level from the settings, unless `CHRONOMANCER_LOG` is set
+///
+/// # Returns
+///
+/// The guard of the file writer, if there is a log file. Lines are written on
+/// a background thread and dropping the guard flushes them, so keep it until
+/// the process exits.
+#[must_use]
+pub fn init(name: &str, level: LogLevel) -> Option<WorkerGuard> {
+    let (filter, invalid_env) = match std::env::var(LOG_ENV) {
+        Ok(value) => match EnvFilter::try_new(&value) {
+            Ok(filter) => (filter, None),
+            Err(e) => (EnvFilter::new(directives(level)), Some(e)),
+        },
+        Err(_) => (EnvFilter::new(directives(level)), None),
+    };
+    let (file, guard) = match file_writer(name) {
+        Ok((writer, guard)) => (Some(writer), Some(guard)),
+        Err(e) => {
+            eprintln!("Logging to stderr only, no log file: {e:#}");
+            (None, None)
+        }
+    };
+
+    let initialized = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(file.map(|writer| fmt::layer().with_ansi(false).with_writer(writer)))
+        .try_init();
+    if initialized.is_err() {
+        return None;
+    }
+    if let Some(e) = invalid_env {
+        tracing::warn!("Ignoring {LOG_ENV}, it isn't a valid filter: {e}");
+    }
+    guard
+}
+
+/// Opens the daily log file of a process, written on a background thread.
+fn file_writer(name: &str) -> Result<(NonBlocking, WorkerGuard)> {
+    let dir = log_dir().ok_or_else(|| anyhow!("Failed to get data directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(name)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)?;
+    Ok(tracing_appender::non_blocking(appender))
+}
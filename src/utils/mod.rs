@@ -6,10 +6,21 @@
 //!
 //! # Organization
 //!
+//! - [`activation`] - Asking the running applet to perform an action (D-Bus integration)
+//! - [`clock`] - The current time, from the system or a manual clock in tests
 //! - [`database`] - Database abstractions and `SQLite` implementation
+//! - [`file_watch`] - File and directory change tracking via inotify
 //! - [`filters`] - Text input validation and filtering functions
+//! - [`logging`] - Log output to stderr and a rotating file under the data directory
+//! - [`mpris`] - Media player playback state via MPRIS (D-Bus integration)
+//! - [`progress_ring`] - SVG for the progress ring panel icon
 //! - [`resources`] - System icons and power management (D-Bus integration)
+//! - [`scheduler`] - Deadlines to sleep until instead of a periodic tick
+//! - [`system_load`] - CPU, disk and network activity sampled from `/proc`
+//! - [`systemd`] - Exporting timers to systemd user units and importing them back
 //! - [`time`] - Time unit conversion and duration formatting
+//! - [`time_zone`] - Wall clock times in IANA time zones, DST included
+//! - [`upower`] - Battery and AC power state via UPower (D-Bus integration)
 //! - [`ui`] - UI spacing, sizing, and padding constants
 //!
 //! # Module Philosophy
@@ -59,11 +70,22 @@
 //! ```
 //!
 
+pub mod activation;
+pub mod clock;
 pub mod database;
+pub mod file_watch;
 pub mod filters;
+pub mod logging;
+pub mod mpris;
+pub mod progress_ring;
 pub mod resources;
+pub mod scheduler;
+pub mod system_load;
+pub mod systemd;
 pub mod time;
+pub mod time_zone;
 pub mod ui;
+pub mod upower;
 
 pub use time::{TimeUnit, format_duration};
 #[allow(dead_code)]
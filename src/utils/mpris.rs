@@ -0,0 +1,308 @@
+//! MPRIS D-Bus client for media player playback state.
+//!
+//! This module reads every media player on the session bus that implements the
+//! [MPRIS](https://specifications.freedesktop.org/mpris-spec/latest/) interface,
+//! so Chronomancer can stay awake while something is playing and sleep once the
+//! current track or episode has finished.
+//!
+//! # Mocking
+//!
+//! [`get_players`] talks to the real session bus. [`get_players_from`] takes the
+//! connection explicitly, so tests can serve a stand-in player under any
+//! `org.mpris.MediaPlayer2.*` name and read it back.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::utils::mpris;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let players = mpris::get_players().await?;
+//!
+//! if let Some(player) = mpris::playing_player(&players) {
+//!     println!("{} is playing, {:?}s left", player.bus_name, player.remaining_secs());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use zbus::{Connection, Proxy};
+use zvariant::{OwnedValue, Value};
+
+/// Bus name prefix shared by all MPRIS players.
+pub const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+/// Object path every MPRIS player is served at.
+pub const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// Track id players report when nothing is loaded.
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+/// Playback status as reported by the `PlaybackStatus` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl PlaybackStatus {
+    /// Parses the MPRIS status string, treating unknown values as stopped.
+    #[must_use]
+    pub fn from_mpris(status: &str) -> Self {
+        match status {
+            "Playing" => Self::Playing,
+            "Paused" => Self::Paused,
+            _ => Self::Stopped,
+        }
+    }
+}
+
+/// Snapshot of a single media player.
+///
+/// # Fields
+///
+/// - `bus_name` - The player's well-known bus name (e.g. `org.mpris.MediaPlayer2.spotify`)
+/// - `status` - Current playback status
+/// - `track` - Identifier of the current track (track id, falling back to the title)
+/// - `position_us` - Playback position in microseconds
+/// - `length_us` - Track length in microseconds, `None` for streams or unknown lengths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerState {
+    pub bus_name: String,
+    pub status: PlaybackStatus,
+    pub track: String,
+    pub position_us: i64,
+    pub length_us: Option<i64>,
+}
+
+impl PlayerState {
+    /// Seconds left in the current track, when the track has a known length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::utils::mpris::{PlaybackStatus, PlayerState};
+    ///
+    /// let player = PlayerState {
+    ///     bus_name: "org.mpris.MediaPlayer2.podcasts".to_string(),
+    ///     status: PlaybackStatus::Playing,
+    ///     track: "/episode/42".to_string(),
+    ///     position_us: 30_000_000,
+    ///     length_us: Some(90_000_000),
+    /// };
+    /// assert_eq!(player.remaining_secs(), Some(60));
+    /// ```
+    #[must_use]
+    pub fn remaining_secs(&self) -> Option<i64> {
+        self.length_us
+            .map(|length| (length - self.position_us).max(0) / 1_000_000)
+    }
+}
+
+/// Gets the first player that is currently playing.
+#[must_use]
+pub fn playing_player(players: &[PlayerState]) -> Option<&PlayerState> {
+    players
+        .iter()
+        .find(|player| player.status == PlaybackStatus::Playing)
+}
+
+/// Reads all MPRIS players on the session bus.
+///
+/// # Errors
+///
+/// Returns an error if the session bus is unreachable or the bus names cannot be listed.
+pub async fn get_players() -> Result<Vec<PlayerState>> {
+    let connection = Connection::session()
+        .await
+        .context("Failed to connect to session bus")?;
+
+    get_players_from(&connection).await
+}
+
+/// Reads all MPRIS players reachable on the given connection.
+///
+/// This is the mockable core of [`get_players`]. Players that disappear or fail
+/// to answer while being read are skipped rather than failing the whole read.
+///
+/// # Errors
+///
+/// Returns an error if the bus names cannot be listed.
+///
+/// # D-Bus API
+///
+/// ```text
+/// org.freedesktop.DBus.ListNames() -> as
+/// org.mpris.MediaPlayer2.Player.PlaybackStatus -> s
+/// org.mpris.MediaPlayer2.Player.Position -> x          (microseconds)
+/// org.mpris.MediaPlayer2.Player.Metadata -> a{sv}      (mpris:trackid, mpris:length, xesam:title)
+/// ```
+pub async fn get_players_from(connection: &Connection) -> Result<Vec<PlayerState>> {
+    let dbus = Proxy::new(
+        connection,
+        "org.freedesktop.DBus",
+        "/org/freedesktop/DBus",
+        "org.freedesktop.DBus",
+    )
+    .await?;
+
+    let names: Vec<String> = dbus
+        .call("ListNames", &())
+        .await
+        .context("Failed to list bus names")?;
+
+    let mut players = vec![];
+    for name in names.into_iter().filter(|n| n.starts_with(MPRIS_PREFIX)) {
+        match get_player(connection, name.clone()).await {
+            Ok(player) => players.push(player),
+            Err(e) => tracing::warn!("Failed to read media player {name}: {e}"),
+        }
+    }
+
+    Ok(players)
+}
+
+/// Reads a single player's playback state.
+async fn get_player(connection: &Connection, bus_name: String) -> Result<PlayerState> {
+    let player = Proxy::new(
+        connection,
+        bus_name.as_str(),
+        MPRIS_PATH,
+        "org.mpris.MediaPlayer2.Player",
+    )
+    .await?;
+
+    let status: String = player.get_property("PlaybackStatus").await?;
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").await?;
+    // Position is optional in practice, plenty of players don't implement it
+    let position_us: i64 = player.get_property("Position").await.unwrap_or_default();
+
+    let track = match metadata.get("mpris:trackid").map(|value| &**value) {
+        Some(Value::ObjectPath(path)) if path.as_str() != NO_TRACK => path.to_string(),
+        Some(Value::Str(id)) if !id.is_empty() => id.to_string(),
+        _ => match metadata.get("xesam:title").map(|value| &**value) {
+            Some(Value::Str(title)) => title.to_string(),
+            _ => String::new(),
+        },
+    };
+
+    let length_us = match metadata.get("mpris:length").map(|value| &**value) {
+        Some(Value::I64(length)) => Some(*length),
+        Some(Value::U64(length)) => i64::try_from(*length).ok(),
+        _ => None,
+    };
+
+    Ok(PlayerState {
+        bus_name,
+        status: PlaybackStatus::from_mpris(&status),
+        track,
+        position_us,
+        length_us,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zvariant::ObjectPath;
+
+    /// Stand-in for an MPRIS player.
+    struct StandInPlayer {
+        status: String,
+        position: i64,
+        length: i64,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl StandInPlayer {
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status.clone()
+        }
+
+        #[zbus(property)]
+        fn position(&self) -> i64 {
+            self.position
+        }
+
+        #[zbus(property)]
+        fn metadata(&self) -> HashMap<String, OwnedValue> {
+            HashMap::from([
+                (
+                    "mpris:trackid".to_string(),
+                    Value::from(ObjectPath::from_static_str_unchecked("/episode/42"))
+                        .try_into()
+                        .unwrap(),
+                ),
+                (
+                    "mpris:length".to_string(),
+                    Value::from(self.length).try_into().unwrap(),
+                ),
+            ])
+        }
+    }
+
+    #[test]
+    fn test_playing_player() {
+        let player = |bus_name: &str, status| PlayerState {
+            bus_name: bus_name.to_string(),
+            status,
+            track: String::new(),
+            position_us: 0,
+            length_us: None,
+        };
+        let players = [
+            player("org.mpris.MediaPlayer2.music", PlaybackStatus::Paused),
+            player("org.mpris.MediaPlayer2.video", PlaybackStatus::Playing),
+        ];
+
+        assert_eq!(
+            playing_player(&players).map(|p| p.bus_name.as_str()),
+            Some("org.mpris.MediaPlayer2.video")
+        );
+        assert!(playing_player(&players[..1]).is_none());
+        assert_eq!(players[0].remaining_secs(), None);
+    }
+
+    // Serves a stand-in player on the session bus under a private name.
+    // Skipped when no session bus is available (e.g. headless CI).
+    #[tokio::test]
+    async fn test_get_players_from_stand_in() -> Result<()> {
+        let name = format!("{MPRIS_PREFIX}ChronomancerTest{}", std::process::id());
+
+        let Ok(builder) = zbus::connection::Builder::session() else {
+            return Ok(());
+        };
+        let Ok(_service) = builder
+            .name(name.as_str())?
+            .serve_at(
+                MPRIS_PATH,
+                StandInPlayer {
+                    status: "Playing".to_string(),
+                    position: 540_000_000,
+                    length: 600_000_000,
+                },
+            )?
+            .build()
+            .await
+        else {
+            return Ok(());
+        };
+
+        let client = Connection::session().await?;
+        let players = get_players_from(&client).await?;
+        let player = players
+            .iter()
+            .find(|player| player.bus_name == name)
+            .expect("stand-in player not listed");
+
+        assert_eq!(player.status, PlaybackStatus::Playing);
+        assert_eq!(player.track, "/episode/42");
+        assert_eq!(player.remaining_secs(), Some(60));
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! Progress ring panel icon.
+//!
+//! Generates the SVG for the applet's panel icon while something is running:
+//! a ring that drains as the nearest timer approaches its end, around a glyph
+//! for what is running (stay-awake, a power action or a reminder). The glyphs
+//! live in `resources/icons/glyphs` and are embedded at compile time.
+//!
+//! The ring is drawn in a fixed number of [`STEPS`], so the icon only changes
+//! (and gets rasterized again) when the ring visibly moves, not on every tick.
+//! [`next_step_at`] tells when that is, so the applet only has to wake up then.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::utils::progress_ring::{Glyph, progress_ring_svg, remaining_fraction};
+//!
+//! // A 10 minute timer with 2.5 minutes to go
+//! let remaining = remaining_fraction(0, 600, 450);
+//! assert_eq!(remaining, 0.25);
+//!
+//! let svg = progress_ring_svg(Some(remaining), Glyph::Power);
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use std::f32::consts::PI;
+
+/// Number of distinct ring lengths between empty and full.
+pub const STEPS: u16 = 60;
+
+/// Radius of the ring in the icon's 24×24 view box.
+const RING_RADIUS: f32 = 10.5;
+
+/// What the glyph inside the ring shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// Stay-awake (or media stay-awake) is keeping the system up
+    StayAwake,
+    /// A suspend, hibernate, logout, shutdown or reboot timer
+    Power,
+    /// A notification timer
+    Reminder,
+}
+
+impl Glyph {
+    /// Gets the glyph's SVG document.
+    #[must_use]
+    pub fn svg(self) -> &'static str {
+        match self {
+            Glyph::StayAwake => include_str!("../../resources/icons/glyphs/stay-awake.svg"),
+            Glyph::Power => include_str!("../../resources/icons/glyphs/power.svg"),
+            Glyph::Reminder => include_str!("../../resources/icons/glyphs/reminder.svg"),
+        }
+    }
+}
+
+/// Gets how much of a timer is left, rounded up to a ring step.
+///
+/// # Arguments
+///
+/// - `started_at` - Unix timestamp the countdown started at
+/// - `ends_at` - Unix timestamp the countdown ends at
+/// - `now` - Current Unix timestamp
+///
+/// # Returns
+///
+/// A fraction from 0.0 (done) to 1.0 (just started). Rounding up keeps a sliver
+/// of ring until the timer actually ends.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::progress_ring::remaining_fraction;
+///
+/// assert_eq!(remaining_fraction(100, 160, 100), 1.0);
+/// assert_eq!(remaining_fraction(100, 160, 130), 0.5);
+/// assert_eq!(remaining_fraction(100, 160, 200), 0.0);
+/// ```
+#[must_use]
+pub fn remaining_fraction(started_at: i64, ends_at: i64, now: i64) -> f32 {
+    let duration = ends_at - started_at;
+    if duration <= 0 {
+        return 0.0;
+    }
+
+    let remaining = (ends_at - now).clamp(0, duration).unsigned_abs();
+    // Integer math so the same step always gives the same bytes
+    let steps = (remaining * u64::from(STEPS)).div_ceil(duration.unsigned_abs());
+    f32::from(u16::try_from(steps).unwrap_or(STEPS)) / f32::from(STEPS)
+}
+
+/// Gets when the ring moves to its next step.
+///
+/// # Arguments
+///
+/// - `started_at` - Unix timestamp the countdown started at
+/// - `ends_at` - Unix timestamp the countdown ends at
+/// - `now` - Current Unix timestamp
+///
+/// # Returns
+///
+/// The first Unix timestamp after `now` at which [`remaining_fraction`] gives
+/// a different value, or `None` once the ring is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::progress_ring::next_step_at;
+///
+/// // An hour split into 60 steps moves once a minute
+/// assert_eq!(next_step_at(0, 3600, 30), Some(60));
+/// assert_eq!(next_step_at(0, 3600, 3600), None);
+/// ```
+#[must_use]
+pub fn next_step_at(started_at: i64, ends_at: i64, now: i64) -> Option<i64> {
+    let duration = ends_at - started_at;
+    if duration <= 0 || now >= ends_at {
+        return None;
+    }
+
+    let remaining = (ends_at - now).min(duration).unsigned_abs();
+    let step = (remaining * u64::from(STEPS)).div_ceil(duration.unsigned_abs());
+    // The step is shown while more than (step - 1) / STEPS of the countdown is left
+    let next_remaining = (step - 1) * duration.unsigned_abs() / u64::from(STEPS);
+    Some(ends_at - i64::try_from(next_remaining).ok()?)
+}
+
+/// Builds the panel icon SVG.
+///
+/// # Arguments
+///
+/// - `remaining` - Fraction of the ring to draw, from [`remaining_fraction`]. `None`
+///   draws only the faint track, for states without a deadline like stay-awake.
+/// - `glyph` - What to draw inside the ring
+///
+/// # Returns
+///
+/// A 24×24 SVG document using `currentColor`, so it can be shown as a symbolic icon.
+#[must_use]
+pub fn progress_ring_svg(remaining: Option<f32>, glyph: Glyph) -> String {
+    let circumference = 2.0 * PI * RING_RADIUS;
+    let arc = remaining.map_or(String::new(), |remaining| {
+        format!(
+            r#"<circle cx="12" cy="12" r="{RING_RADIUS}" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-dasharray="{:.2} {circumference:.2}" transform="rotate(-90 12 12)"/>"#,
+            remaining.clamp(0.0, 1.0) * circumference
+        )
+    });
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24"><circle cx="12" cy="12" r="{RING_RADIUS}" fill="none" stroke="currentColor" stroke-width="2" opacity="0.3"/>{arc}<svg x="6" y="6" width="12" height="12" viewBox="0 0 24 24">{}</svg></svg>"#,
+        glyph_contents(glyph.svg())
+    )
+}
+
+/// Strips the outer `<svg>` element from a glyph, keeping what it draws.
+fn glyph_contents(svg: &str) -> &str {
+    let start = svg.find('>').map_or(0, |index| index + 1);
+    let end = svg.rfind("</svg>").unwrap_or(svg.len());
+    svg.get(start..end).unwrap_or_default().trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_fraction_rounds_up_to_a_step() {
+        // One second into an hour still shows a full ring
+        assert!((remaining_fraction(0, 3600, 1) - 1.0).abs() < f32::EPSILON);
+        // One second before the end still shows a sliver
+        assert!(remaining_fraction(0, 3600, 3599) > 0.0);
+        // Timers without a duration are treated as done
+        assert!(remaining_fraction(50, 50, 0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_next_step_at_is_where_the_fraction_changes() {
+        for (started_at, ends_at) in [(0, 600), (100, 107), (0, 7 * 3600 + 13)] {
+            let mut now = started_at;
+            while let Some(next) = next_step_at(started_at, ends_at, now) {
+                assert!(next > now);
+                let current = remaining_fraction(started_at, ends_at, now);
+                assert!(
+                    (remaining_fraction(started_at, ends_at, next - 1) - current).abs()
+                        < f32::EPSILON
+                );
+                assert!(remaining_fraction(started_at, ends_at, next) < current);
+                now = next;
+            }
+            assert_eq!(now, ends_at);
+        }
+    }
+
+    #[test]
+    fn test_svg_draws_arc_only_with_progress() {
+        for glyph in [Glyph::StayAwake, Glyph::Power, Glyph::Reminder] {
+            let with_arc = progress_ring_svg(Some(0.5), glyph);
+            let without_arc = progress_ring_svg(None, glyph);
+            assert!(with_arc.contains("stroke-dasharray"));
+            assert!(!without_arc.contains("stroke-dasharray"));
+            // The glyph is nested without its own outer element
+            assert_eq!(without_arc.matches("<svg").count(), 2);
+            assert!(without_arc.ends_with("</svg></svg>"));
+        }
+    }
+
+    #[test]
+    fn test_same_step_gives_same_icon() {
+        let a = progress_ring_svg(Some(remaining_fraction(0, 600, 301)), Glyph::Power);
+        let b = progress_ring_svg(Some(remaining_fraction(0, 600, 305)), Glyph::Power);
+        assert_eq!(a, b);
+    }
+}
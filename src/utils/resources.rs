@@ -4,7 +4,7 @@
 //! - Loading system icons with consistent styling
 //! - Interacting with systemd-logind for power management
 //! - Managing suspend inhibitor locks
-//! - Executing system power operations (suspend, shutdown, reboot, logout)
+//! - Executing system power operations (suspend, hibernate, shutdown, reboot, logout)
 //!
 //! # D-Bus Integration
 //!
@@ -295,6 +295,63 @@ pub async fn execute_system_suspend() -> Result<()> {
     Ok(())
 }
 
+/// Hibernates the system (suspend to disk).
+///
+/// Calls the systemd-logind D-Bus API to hibernate the system. Unlike suspend,
+/// the machine powers off completely and restores the session on next boot, so
+/// it survives the battery running flat.
+///
+/// **Note**: Hibernation requires a configured swap device or file large enough
+/// to hold memory contents. The call fails if logind reports it as unsupported.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the hibernate command was successfully sent.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Failed to connect to the system D-Bus
+/// - The D-Bus call to `Hibernate` fails (unsupported, no swap, insufficient permissions)
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chronomancer::utils::resources;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// resources::execute_system_hibernate().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # D-Bus API
+///
+/// This function calls:
+/// ```text
+/// org.freedesktop.login1.Manager.Hibernate(interactive: true)
+/// ```
+pub async fn execute_system_hibernate() -> Result<()> {
+    let connection = Connection::system()
+        .await
+        .context("Failed to connect to system bus")?;
+
+    let proxy = Proxy::new(
+        &connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )
+    .await?;
+
+    let _: () = proxy
+        .call("Hibernate", &(true,))
+        .await
+        .context("D-Bus call to Hibernate failed")?;
+
+    Ok(())
+}
+
 /// Powers off the system.
 ///
 /// Calls the systemd-logind D-Bus API to shut down the system. This is equivalent
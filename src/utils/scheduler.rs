@@ -0,0 +1,160 @@
+//! Deadlines that timers wake up for, instead of checking them every second.
+//!
+//! The app and the daemon collect the next deadline of every running timer,
+//! sequence step and pending power action into [`Deadlines`], then sleep until
+//! the earliest one or until something changes. Triggers that wait on power,
+//! activity, file or media updates are evaluated when those updates arrive, so
+//! nothing needs a periodic wake up unless a countdown is on screen.
+//!
+//! Deadlines are Unix timestamps in seconds, like `ends_at`. Timers ending in
+//! the same second share one wake up and are finished together. Sleeping is
+//! left to the [`Clock`], which keeps counting through suspend and wakes up
+//! early when the wall clock is set, see [`Clock::wait_until`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::utils::scheduler::Deadlines;
+//!
+//! let mut deadlines: Deadlines = [1_300, 1_060, 1_060, 1_600].into_iter().collect();
+//! assert_eq!(deadlines.next(), Some(1_060));
+//!
+//! // Both timers ending at 1060 are due on the same wake up
+//! assert_eq!(deadlines.pop_due(1_060), 2);
+//! assert_eq!(deadlines.next(), Some(1_300));
+//! ```
+
+use std::{cmp::Reverse, collections::BinaryHeap, time::Duration};
+
+use crate::utils::clock::Clock;
+
+/// How long a sleep without a deadline lasts at most, in milliseconds
+const NO_DEADLINE_WAIT_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Min-heap of the Unix timestamps something has to happen at.
+#[derive(Debug, Clone, Default)]
+pub struct Deadlines {
+    heap: BinaryHeap<Reverse<i64>>,
+}
+
+impl Deadlines {
+    /// Gets the earliest deadline, `None` when there is nothing to wake up for.
+    #[must_use]
+    pub fn next(&self) -> Option<i64> {
+        self.heap.peek().map(|Reverse(at)| *at)
+    }
+
+    /// Removes every deadline that has passed.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// How many deadlines were due.
+    pub fn pop_due(&mut self, now: i64) -> usize {
+        let mut due = 0;
+        while self.next().is_some_and(|at| at <= now) {
+            self.heap.pop();
+            due += 1;
+        }
+        due
+    }
+
+    /// Whether there is nothing to wake up for.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl FromIterator<i64> for Deadlines {
+    fn from_iter<I: IntoIterator<Item = i64>>(deadlines: I) -> Self {
+        Self {
+            heap: deadlines.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+/// Gets how long to sleep until a deadline, zero once it has passed.
+///
+/// Measured in milliseconds, so the wake up lands right after the second flips
+/// over rather than up to a second late.
+///
+/// # Arguments
+///
+/// - `deadline` - Unix timestamp in seconds
+/// - `clock` - Where the current time comes from
+#[must_use]
+pub fn time_until(deadline: i64, clock: &dyn Clock) -> Duration {
+    let millis = deadline
+        .saturating_mul(1000)
+        .saturating_sub(clock.now_millis());
+    Duration::from_millis(u64::try_from(millis).unwrap_or(0))
+}
+
+/// Sleeps until a deadline, or until the wall clock is set.
+///
+/// Meant for a `tokio::select!` branch that races the deadline against
+/// updates which may bring a new one. Without a deadline it only ends when the
+/// clock is set or the machine resumes (or after a day), so the caller gets to
+/// check its [`ClockWatch`](crate::utils::clock::ClockWatch) either way.
+///
+/// # Arguments
+///
+/// - `deadline` - Unix timestamp in seconds, see [`Deadlines::next`]
+/// - `clock` - Where the current time comes from
+pub async fn sleep_until(deadline: Option<i64>, clock: &dyn Clock) {
+    let at_millis = match deadline {
+        Some(deadline) => deadline.saturating_mul(1000),
+        None => clock.now_millis().saturating_add(NO_DEADLINE_WAIT_MILLIS),
+    };
+    clock.wait_until(at_millis).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::ManualClock;
+
+    #[test]
+    fn test_deadlines_pop_in_order() {
+        let mut deadlines: Deadlines = [50, 10, 30, 10].into_iter().collect();
+        assert_eq!(deadlines.next(), Some(10));
+
+        assert_eq!(deadlines.pop_due(9), 0);
+        assert_eq!(deadlines.pop_due(30), 3);
+        assert_eq!(deadlines.next(), Some(50));
+        assert_eq!(deadlines.pop_due(100), 1);
+        assert!(deadlines.is_empty());
+        assert_eq!(deadlines.next(), None);
+    }
+
+    #[test]
+    fn test_time_until() {
+        let clock = ManualClock::new(1_000);
+        assert_eq!(time_until(990, &clock), Duration::ZERO);
+        assert_eq!(time_until(i64::MIN, &clock), Duration::ZERO);
+        assert_eq!(time_until(1_010, &clock), Duration::from_secs(10));
+
+        clock.advance_millis(250);
+        assert_eq!(time_until(1_010, &clock), Duration::from_millis(9_750));
+    }
+
+    #[tokio::test]
+    async fn test_sleep_follows_the_wall_clock() {
+        let clock = ManualClock::new(1_000);
+        let sleeping = clock.clone();
+        let sleep = tokio::spawn(async move { sleep_until(Some(1_060), &sleeping).await });
+        tokio::task::yield_now().await;
+        assert!(!sleep.is_finished());
+
+        // Like resuming from suspend: the deadline passed, but no tokio timer ran meanwhile
+        clock.advance(120);
+        tokio::time::timeout(Duration::from_secs(5), sleep)
+            .await
+            .expect("woke up for the passed deadline")
+            .unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+//! System activity sampling from `/proc` for "when the system is quiet" triggers.
+//!
+//! This module reads cumulative counters from the kernel and turns pairs of
+//! readings into per-second activity rates:
+//! - `/proc/loadavg` - one minute load average, normalized per CPU core
+//! - `/proc/diskstats` - sectors read and written by whole disks
+//! - `/proc/net/dev` - bytes received and transmitted by non-loopback interfaces
+//!
+//! Parsing is split from file access so the parsers can be tested against
+//! captured `/proc` contents.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::utils::system_load;
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let first = system_load::read_counters(0)?;
+//! std::thread::sleep(std::time::Duration::from_secs(10));
+//! let second = system_load::read_counters(10)?;
+//!
+//! let sample = second.rate_since(&first);
+//! println!("Network: {} bytes/s", sample.net_bytes_per_sec);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result, anyhow};
+
+/// Size of a sector as reported by `/proc/diskstats`, regardless of the device.
+const DISKSTATS_SECTOR_SIZE: u64 = 512;
+
+/// Device name prefixes that mirror or fake other block devices and would double count.
+const IGNORED_DISK_PREFIXES: [&str; 4] = ["loop", "ram", "zram", "dm-"];
+
+/// Raw cumulative counters read at a single point in time.
+///
+/// # Fields
+///
+/// - `at` - Unix timestamp of the reading
+/// - `load_percent` - One minute load average per CPU core, as a percentage
+/// - `disk_bytes` - Total bytes read and written by whole disks since boot
+/// - `net_bytes` - Total bytes received and sent by non-loopback interfaces since boot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivityCounters {
+    pub at: i64,
+    pub load_percent: f64,
+    pub disk_bytes: u64,
+    pub net_bytes: u64,
+}
+
+/// System activity over the interval between two counter readings.
+///
+/// # Fields
+///
+/// - `at` - Unix timestamp at the end of the interval
+/// - `load_percent` - One minute load average per CPU core, as a percentage
+/// - `disk_bytes_per_sec` - Disk throughput over the interval
+/// - `net_bytes_per_sec` - Network throughput over the interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivitySample {
+    pub at: i64,
+    pub load_percent: f64,
+    pub disk_bytes_per_sec: f64,
+    pub net_bytes_per_sec: f64,
+}
+
+impl ActivityCounters {
+    /// Computes activity rates between an earlier reading and this one.
+    ///
+    /// Counters that went backwards (e.g. an interface was removed) count as no activity.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rate_since(&self, earlier: &ActivityCounters) -> ActivitySample {
+        let elapsed = (self.at - earlier.at).max(1) as f64;
+
+        ActivitySample {
+            at: self.at,
+            load_percent: self.load_percent,
+            disk_bytes_per_sec: self.disk_bytes.saturating_sub(earlier.disk_bytes) as f64 / elapsed,
+            net_bytes_per_sec: self.net_bytes.saturating_sub(earlier.net_bytes) as f64 / elapsed,
+        }
+    }
+}
+
+/// Reads all activity counters from `/proc`.
+///
+/// # Arguments
+///
+/// - `at` - Unix timestamp to stamp the reading with
+///
+/// # Errors
+///
+/// Returns an error if any of the `/proc` files cannot be read or parsed.
+pub fn read_counters(at: i64) -> Result<ActivityCounters> {
+    let loadavg = std::fs::read_to_string("/proc/loadavg").context("Failed to read loadavg")?;
+    let diskstats =
+        std::fs::read_to_string("/proc/diskstats").context("Failed to read diskstats")?;
+    let net_dev = std::fs::read_to_string("/proc/net/dev").context("Failed to read net/dev")?;
+    let cores = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+
+    Ok(ActivityCounters {
+        at,
+        load_percent: parse_loadavg(&loadavg)? / f64::from(u32::try_from(cores).unwrap_or(1))
+            * 100.0,
+        disk_bytes: parse_diskstats(&diskstats),
+        net_bytes: parse_net_dev(&net_dev),
+    })
+}
+
+/// Parses the one minute load average from `/proc/loadavg` contents.
+///
+/// # Errors
+///
+/// Returns an error if the first field is missing or not a number.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::system_load::parse_loadavg;
+///
+/// assert_eq!(parse_loadavg("0.52 0.58 0.59 1/1234 5678").unwrap(), 0.52);
+/// assert!(parse_loadavg("").is_err());
+/// ```
+pub fn parse_loadavg(contents: &str) -> Result<f64> {
+    contents
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow!("Empty loadavg"))?
+        .parse()
+        .context("Invalid load average")
+}
+
+/// Sums bytes read and written by whole disks from `/proc/diskstats` contents.
+///
+/// Partitions are skipped when their parent disk is also listed (e.g. `sda1`
+/// under `sda`, `nvme0n1p2` under `nvme0n1`), as are loop, RAM and device-mapper
+/// devices, so each byte is only counted once. Malformed lines are ignored.
+#[must_use]
+pub fn parse_diskstats(contents: &str) -> u64 {
+    let devices: Vec<(&str, u64)> = contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // major minor name reads merged sectors_read ms writes merged sectors_written ...
+            let name = *fields.get(2)?;
+            let sectors_read: u64 = fields.get(5)?.parse().ok()?;
+            let sectors_written: u64 = fields.get(9)?.parse().ok()?;
+            Some((name, sectors_read + sectors_written))
+        })
+        .filter(|(name, _)| {
+            !IGNORED_DISK_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+
+    devices
+        .iter()
+        .filter(|(name, _)| {
+            !devices
+                .iter()
+                .any(|(parent, _)| is_partition_of(name, parent))
+        })
+        .map(|(_, sectors)| sectors * DISKSTATS_SECTOR_SIZE)
+        .sum()
+}
+
+/// Whether a block device is a partition of a disk, judging by its name.
+///
+/// Partitions add a number to the disk's name (`sda1`), or `p` and a number
+/// when the name already ends in a digit (`nvme0n1p2`, `mmcblk0p1`). Other
+/// names that merely start with the disk's name are disks too (`sdaa`,
+/// `nvme0n10`).
+fn is_partition_of(name: &str, disk: &str) -> bool {
+    let Some(suffix) = name.strip_prefix(disk) else {
+        return false;
+    };
+    let number = if disk.ends_with(|c: char| c.is_ascii_digit()) {
+        suffix.strip_prefix('p').unwrap_or_default()
+    } else {
+        suffix
+    };
+    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Sums bytes received and transmitted by all non-loopback interfaces from `/proc/net/dev` contents.
+///
+/// The two header lines and malformed lines are ignored.
+#[must_use]
+pub fn parse_net_dev(contents: &str) -> u64 {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (interface, counters) = line.split_once(':')?;
+            if interface.trim() == "lo" {
+                return None;
+            }
+            let fields: Vec<&str> = counters.split_whitespace().collect();
+            // 8 receive fields followed by 8 transmit fields, bytes first in each group
+            let received: u64 = fields.first()?.parse().ok()?;
+            let transmitted: u64 = fields.get(8)?.parse().ok()?;
+            Some(received + transmitted)
+        })
+        .sum()
+}
+
+/// Appends a sample to an activity history, oldest first.
+///
+/// The history is trimmed to the window (keeping one older sample so the
+/// window stays fully covered). A gap between samples, e.g. after sampling was
+/// stopped or the system slept, throws the history away since nobody watched
+/// what happened in between.
+///
+/// # Arguments
+///
+/// - `history` - Samples so far, oldest first
+/// - `sample` - The newest sample
+/// - `window` - Seconds of history to keep
+/// - `max_gap` - Longest expected time between two samples, in seconds
+pub fn record_sample(
+    history: &mut Vec<ActivitySample>,
+    sample: ActivitySample,
+    window: i64,
+    max_gap: i64,
+) {
+    if history
+        .last()
+        .is_some_and(|last| sample.at - last.at > max_gap)
+    {
+        history.clear();
+    }
+    history.push(sample);
+
+    let cutoff = sample.at - window;
+    if let Some(oldest_needed) = history.iter().rposition(|s| s.at <= cutoff) {
+        history.drain(..oldest_needed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISKSTATS: &str = "\
+ 259       0 nvme0n1 1000 0 2000 0 500 0 4000 0 0 0 0 0 0 0 0 0 0
+ 259       1 nvme0n1p1 10 0 20 0 5 0 40 0 0 0 0 0 0 0 0 0 0
+ 259       2 nvme0n1p2 990 0 1980 0 495 0 3960 0 0 0 0 0 0 0 0 0 0
+   7       0 loop0 50 0 100 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 253       0 dm-0 990 0 1980 0 495 0 3960 0 0 0 0 0 0 0 0 0 0
+   8       0 sda 100 0 200 0 50 0 400 0 0 0 0 0 0 0 0 0 0";
+
+    const NET_DEV: &str = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 9999999     100    0    0    0     0          0         0  9999999     100    0    0    0     0       0          0
+wlp2s0: 1000000     800    0    0    0     0          0         0   250000     600    0    0    0     0       0          0
+  eth0:     500       5    0    0    0     0          0         0      500       5    0    0    0     0       0          0";
+
+    #[test]
+    fn test_parse_diskstats_counts_whole_disks_once() {
+        // nvme0n1: 2000 + 4000 sectors, sda: 200 + 400 sectors
+        assert_eq!(parse_diskstats(DISKSTATS), (6000 + 600) * 512);
+        assert_eq!(parse_diskstats(""), 0);
+    }
+
+    #[test]
+    fn test_disks_sharing_a_prefix_are_not_partitions() {
+        assert!(is_partition_of("sda1", "sda"));
+        assert!(is_partition_of("nvme0n1p2", "nvme0n1"));
+        assert!(!is_partition_of("sdaa", "sda"));
+        assert!(!is_partition_of("nvme0n10", "nvme0n1"));
+        assert!(!is_partition_of("sda", "sda"));
+    }
+
+    #[test]
+    fn test_parse_net_dev_skips_loopback() {
+        assert_eq!(parse_net_dev(NET_DEV), 1_000_000 + 250_000 + 500 + 500);
+    }
+
+    #[test]
+    fn test_rate_since() {
+        let earlier = ActivityCounters {
+            at: 100,
+            load_percent: 10.0,
+            disk_bytes: 1000,
+            net_bytes: 5000,
+        };
+        let later = ActivityCounters {
+            at: 110,
+            load_percent: 12.0,
+            disk_bytes: 21_000,
+            net_bytes: 4000, // interface went away
+        };
+
+        let sample = later.rate_since(&earlier);
+        assert_eq!(sample.at, 110);
+        assert!((sample.load_percent - 12.0).abs() < f64::EPSILON);
+        assert!((sample.disk_bytes_per_sec - 2000.0).abs() < f64::EPSILON);
+        assert!(sample.net_bytes_per_sec.abs() < f64::EPSILON);
+    }
+}
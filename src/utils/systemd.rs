@@ -0,0 +1,992 @@
+//! systemd user timer units for Chronomancer timers.
+//!
+//! Exporting writes a timer as a `.timer`/`.service` pair into the user's unit
+//! directory (`~/.config/systemd/user`) and starts it, so its action runs from
+//! the user's service manager even when nobody is logged into the desktop
+//! (given lingering is on, see `loginctl enable-linger`). Importing reads the
+//! timers in that directory back as Chronomancer timers.
+//!
+//! Schedules translate between [`Schedule`] and the unit's `[Timer]` section.
+//! One-shot timers become an `OnCalendar=` date. Recurring timers become an
+//! `OnCalendar=` repetition that keeps their phase when the period divides a
+//! minute, hour or day (or is a whole week), and `OnActiveSec=` plus
+//! `OnUnitActiveSec=` otherwise.
+//!
+//! Calendar times name their time zone, so systemd keeps them where
+//! Chronomancer would: a timer set to a wall clock time keeps the zone it was
+//! set in (see [`TimerIntent::WallClock`]), a countdown the zone it was
+//! exported in. Reading a unit back, times without a zone are local time, like
+//! systemd's.
+//!
+//! Exported units carry the timer's kind in `X-Chronomancer-Kind=` (and a
+//! reminder's text in `X-Chronomancer-Text=`), which systemd ignores, so they
+//! come back as the same action. Other timers come back as reminders named
+//! after their description.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chronomancer::utils::systemd::{self, Schedule};
+//!
+//! let now = 1_700_000_000;
+//! let schedule = Schedule::Every { period: 900, next: now + 300 };
+//! let spec = systemd::on_calendar(&schedule, "Europe/Berlin").unwrap();
+//! assert_eq!(spec, "*-*-* *:03/15:20 Europe/Berlin");
+//! assert_eq!(systemd::parse_on_calendar(&spec, now), Some(schedule));
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{Datelike, NaiveDate, Timelike, Weekday};
+use sqlx::SqlitePool;
+use zbus::{Connection, Proxy, zvariant::OwnedObjectPath};
+
+use crate::{
+    models::{Timer, intent::TimerIntent, timer::TimerType},
+    utils::{database::Repository, time_zone},
+};
+
+/// Prefix of the unit names Chronomancer exports, followed by the timer id.
+pub const UNIT_PREFIX: &str = "chronomancer-";
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// When a timer runs, in the terms both Chronomancer and systemd understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// Runs once, at a Unix timestamp
+    Once(i64),
+    /// Runs every `period` seconds, next at the Unix timestamp `next`
+    Every { period: i64, next: i64 },
+}
+
+impl Schedule {
+    /// Gets the schedule of a timer.
+    ///
+    /// # Returns
+    ///
+    /// The schedule, or `None` for timers systemd can't run: triggers without a
+    /// deadline, paused timers and chain links waiting on another timer.
+    #[must_use]
+    pub fn of(timer: &Timer) -> Option<Self> {
+        if timer.ends_at == Timer::NO_DEADLINE || timer.is_paused() || timer.is_chained() {
+            return None;
+        }
+        Some(match timer.period() {
+            Some(period) => Schedule::Every {
+                period,
+                next: timer.ends_at,
+            },
+            None => Schedule::Once(timer.ends_at),
+        })
+    }
+
+    /// Builds a timer running on this schedule.
+    ///
+    /// # Arguments
+    ///
+    /// - `timer_type` - What the timer does
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The timer, or `None` for one-shot schedules that already passed.
+    #[must_use]
+    pub fn to_timer(&self, timer_type: &TimerType, now: i64) -> Option<Timer> {
+        match *self {
+            Schedule::Once(at) if at <= now => None,
+            Schedule::Once(at) => Some(Timer {
+                ends_at: at,
+                ..Timer::new(0, false, timer_type, now)
+            }),
+            Schedule::Every { period, next } => {
+                let period = i32::try_from(period).ok()?;
+                Some(Timer {
+                    ends_at: next,
+                    ..Timer::repeating(period, timer_type, now)
+                })
+            }
+        }
+    }
+
+    /// Gets the `[Timer]` section lines that run this schedule.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` - Current Unix timestamp, for the first run of monotonic schedules
+    /// - `time_zone` - IANA name of the zone calendar times are written in
+    #[must_use]
+    pub fn timer_lines(&self, now: i64, time_zone: &str) -> Vec<String> {
+        if let Some(spec) = on_calendar(self, time_zone) {
+            return vec![format!("OnCalendar={spec}")];
+        }
+        match *self {
+            Schedule::Every { period, next } => vec![
+                format!("OnActiveSec={}s", (next - now).max(1)),
+                format!("OnUnitActiveSec={period}s"),
+            ],
+            Schedule::Once(at) => vec![format!("OnActiveSec={}s", (at - now).max(1))],
+        }
+    }
+}
+
+/// Formats a schedule as an `OnCalendar=` expression in a time zone.
+///
+/// # Arguments
+///
+/// - `schedule` - When the timer runs
+/// - `time_zone` - IANA name of the zone, written after the time so systemd
+///   reads it in that zone too
+///
+/// # Returns
+///
+/// The expression, or `None` for recurring schedules whose period doesn't
+/// divide a minute, hour or day and isn't a week, and for zones missing from
+/// tzdata.
+#[must_use]
+pub fn on_calendar(schedule: &Schedule, time_zone: &str) -> Option<String> {
+    let (period, at) = match *schedule {
+        Schedule::Once(at) => {
+            let time = time_zone::local_time(at, time_zone)?;
+            return Some(format!("{} {time_zone}", time.format("%Y-%m-%d %H:%M:%S")));
+        }
+        Schedule::Every { period, next } => (period, next),
+    };
+    let time = time_zone::local_time(at, time_zone)?;
+    let (hour, minute, second) = (
+        i64::from(time.hour()),
+        i64::from(time.minute()),
+        i64::from(time.second()),
+    );
+
+    let spec = match period {
+        WEEK => Some(format!(
+            "{} *-*-* {hour:02}:{minute:02}:{second:02}",
+            time.format("%a")
+        )),
+        DAY => Some(format!("*-*-* {hour:02}:{minute:02}:{second:02}")),
+        period if period > 0 && period < MINUTE && MINUTE % period == 0 => {
+            Some(format!("*-*-* *:*:{:02}/{period}", second % period))
+        }
+        period if period < HOUR && period % MINUTE == 0 && HOUR % period == 0 => {
+            let step = period / MINUTE;
+            Some(format!("*-*-* *:{:02}/{step}:{second:02}", minute % step))
+        }
+        period if period < DAY && period % HOUR == 0 && DAY % period == 0 => {
+            let step = period / HOUR;
+            Some(format!(
+                "*-*-* {:02}/{step}:{minute:02}:{second:02}",
+                hour % step
+            ))
+        }
+        _ => None,
+    }?;
+    Some(format!("{spec} {time_zone}"))
+}
+
+/// One field of an `OnCalendar=` time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// `*`, any value
+    Any,
+    /// A single value
+    At(i64),
+    /// `start/step`, every `step` starting at `start`
+    Every(i64, i64),
+}
+
+impl Field {
+    fn parse(text: &str) -> Option<Self> {
+        if text == "*" {
+            return Some(Field::Any);
+        }
+        match text.split_once('/') {
+            Some((start, step)) => {
+                let start = if start == "*" { 0 } else { start.parse().ok()? };
+                let step: i64 = step.parse().ok()?;
+                (step > 0 && start < step).then_some(Field::Every(start, step))
+            }
+            None => text.parse().ok().map(Field::At),
+        }
+    }
+}
+
+/// Parses an `OnCalendar=` expression Chronomancer can run.
+///
+/// Understands the shorthands `minutely`, `hourly`, `daily` and `weekly`,
+/// single dates like `2026-10-18 14:30:00`, and daily, weekly or sub-daily
+/// repetitions like the ones [`on_calendar`] writes (`*-*-* 07:30:00`,
+/// `Mon *-*-* 09:00:00`, `*:0/15`, `*-*-* 00/6:00:00`). A trailing time zone
+/// like `UTC` or `Europe/Berlin` reads the time in that zone instead of local
+/// time.
+///
+/// # Arguments
+///
+/// - `spec` - The expression
+/// - `now` - Current Unix timestamp, recurring schedules run next after it
+///
+/// # Returns
+///
+/// The schedule, or `None` for expressions that select calendar dates (e.g.
+/// `*-*-01`), lists, ranges or several weekdays.
+#[must_use]
+pub fn parse_on_calendar(spec: &str, now: i64) -> Option<Schedule> {
+    let spec = match spec.trim() {
+        "minutely" => "*-*-* *:*:00",
+        "hourly" => "*-*-* *:00:00",
+        "daily" => "*-*-* 00:00:00",
+        "weekly" => "Mon *-*-* 00:00:00",
+        spec => spec,
+    };
+    let mut tokens: Vec<&str> = spec.split_whitespace().collect();
+    // Dates and times start with a digit or `*`, zones with a letter
+    let zone = match tokens.as_slice() {
+        [_, .., last]
+            if last.starts_with(|c: char| c.is_ascii_alphabetic())
+                && time_zone::utc_offset(last, now).is_some() =>
+        {
+            tokens.pop().map(str::to_string)
+        }
+        _ => None,
+    }
+    .unwrap_or_else(time_zone::local_zone);
+    let weekday = match tokens.first().copied() {
+        Some(token) if token.chars().all(char::is_alphabetic) => {
+            tokens.remove(0);
+            Some(Weekday::from_str(token).ok()?)
+        }
+        _ => None,
+    };
+    let (date, time) = match tokens.as_slice() {
+        [date, time] => (*date, *time),
+        [token] if token.contains(':') => ("*-*-*", *token),
+        [token] => (*token, "00:00:00"),
+        _ => return None,
+    };
+
+    let mut fields = time.split(':');
+    let hour = Field::parse(fields.next()?)?;
+    let minute = Field::parse(fields.next()?)?;
+    let second = fields.next().map_or(Some(Field::At(0)), Field::parse)?;
+    if fields.next().is_some() {
+        return None;
+    }
+
+    if date != "*-*-*" {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        let (Field::At(hour), Field::At(minute), Field::At(second)) = (hour, minute, second) else {
+            return None;
+        };
+        let time = date.and_hms_opt(
+            u32::try_from(hour).ok()?,
+            u32::try_from(minute).ok()?,
+            u32::try_from(second).ok()?,
+        )?;
+        return Some(Schedule::Once(time_zone::resolve(time, &zone)?));
+    }
+
+    // Period and offset from the start of the day (or week)
+    let (period, offset) = match (weekday, hour, minute, second) {
+        (Some(weekday), Field::At(h), Field::At(m), Field::At(s)) => (
+            WEEK,
+            (i64::from(weekday.num_days_from_monday()) * DAY)
+                .checked_add(seconds_of_day(h, m, s)?)?,
+        ),
+        (None, Field::At(h), Field::At(m), Field::At(s)) => (DAY, seconds_of_day(h, m, s)?),
+        (None, Field::Every(h, step), Field::At(m), Field::At(s)) => (
+            step.checked_mul(HOUR).filter(|period| DAY % period == 0)?,
+            seconds_of_day(h, m, s)?,
+        ),
+        (None, Field::Any, Field::At(m), Field::At(s)) => (HOUR, seconds_of_day(0, m, s)?),
+        (None, Field::Any, Field::Every(m, step), Field::At(s)) => (
+            step.checked_mul(MINUTE)
+                .filter(|period| HOUR % period == 0)?,
+            seconds_of_day(0, m, s)?,
+        ),
+        (None, Field::Any, Field::Any, Field::At(s)) => (MINUTE, s),
+        (None, Field::Any, Field::Any, Field::Every(s, step)) if MINUTE % step == 0 => (step, s),
+        _ => return None,
+    };
+
+    // Count from the start of this week (or day), then step past now
+    let today = time_zone::local_time(now, &zone)?.date();
+    let start = if period == WEEK {
+        today - chrono::Days::new(u64::from(today.weekday().num_days_from_monday()))
+    } else {
+        today
+    };
+    let anchor = time_zone::resolve(start.and_hms_opt(0, 0, 0)?, &zone)?.checked_add(offset)?;
+    let next = anchor + ((now - anchor).div_euclid(period) + 1) * period;
+    Some(Schedule::Every { period, next })
+}
+
+/// Adds up hours, minutes and seconds, or `None` if they overflow.
+fn seconds_of_day(hour: i64, minute: i64, second: i64) -> Option<i64> {
+    hour.checked_mul(HOUR)?
+        .checked_add(minute.checked_mul(MINUTE)?)?
+        .checked_add(second)
+}
+
+/// Parses a systemd time span like `90`, `15min` or `1h 30min` into seconds.
+///
+/// Bare numbers are seconds, like in systemd. Sub-second units are not supported.
+#[must_use]
+pub fn parse_timespan(span: &str) -> Option<i64> {
+    let mut total = 0;
+    let mut rest = span.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: i64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "" | "s" | "sec" | "second" | "seconds" => 1,
+            "m" | "min" | "minute" | "minutes" => MINUTE,
+            "h" | "hr" | "hour" | "hours" => HOUR,
+            "d" | "day" | "days" => DAY,
+            "w" | "week" | "weeks" => WEEK,
+            _ => return None,
+        };
+        total = value
+            .checked_mul(unit)
+            .and_then(|seconds| seconds.checked_add(total))?;
+        rest = rest[unit_len..].trim_start();
+    }
+    Some(total)
+}
+
+/// Contents of a `.timer`/`.service` pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Units {
+    /// Unit name without the suffix, e.g. `chronomancer-12`
+    pub name: String,
+    /// Contents of the `.timer` unit
+    pub timer: String,
+    /// Contents of the `.service` unit
+    pub service: String,
+}
+
+impl Units {
+    /// Builds the units that run a timer's action on its schedule.
+    ///
+    /// Power actions run through `systemctl` (`loginctl` for logging out),
+    /// reminders through `notify-send`. Calendar times are written in the zone
+    /// of a wall clock timer, and in the local zone for countdowns.
+    ///
+    /// # Arguments
+    ///
+    /// - `timer` - The timer to export
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for timers without a schedule systemd can run, see [`Schedule::of`].
+    pub fn for_timer(timer: &Timer, now: i64) -> Result<Self> {
+        let schedule = Schedule::of(timer)
+            .ok_or_else(|| anyhow!("Only running timers with a deadline can be exported"))?;
+        let timer_type = TimerType::from_str(&timer.description)?;
+        let description = single_line(timer_type.as_str());
+
+        let text = match &timer_type {
+            TimerType::UserDefined(text) => format!("X-Chronomancer-Text={}\n", single_line(text)),
+            _ => String::new(),
+        };
+
+        let mut timer_unit = format!(
+            "[Unit]\nDescription=Chronomancer: {description}\nX-Chronomancer-Kind={}\n{text}\n[Timer]\n",
+            timer_type.kind()
+        );
+        let zone = match timer.intent() {
+            TimerIntent::WallClock { time_zone, .. } => time_zone,
+            TimerIntent::Relative => time_zone::local_zone(),
+        };
+        for line in schedule.timer_lines(now, &zone) {
+            timer_unit.push_str(&line);
+            timer_unit.push('\n');
+        }
+        timer_unit.push_str("AccuracySec=1s\n\n[Install]\nWantedBy=timers.target\n");
+
+        let service = format!(
+            "[Unit]\nDescription=Chronomancer: {description}\n\n[Service]\nType=oneshot\nExecStart={}\n",
+            exec_start(&timer_type)
+        );
+        Ok(Self {
+            name: format!("{UNIT_PREFIX}{}", timer.id),
+            timer: timer_unit,
+            service,
+        })
+    }
+
+    /// Writes the units into a unit directory, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file can't be written.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        for (suffix, contents) in [("timer", &self.timer), ("service", &self.service)] {
+            let path = dir.join(format!("{}.{suffix}", self.name));
+            std::fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets the command line that runs a timer type's action.
+fn exec_start(timer_type: &TimerType) -> String {
+    match timer_type {
+        TimerType::Suspend => "systemctl suspend".to_string(),
+        TimerType::Hibernate => "systemctl hibernate".to_string(),
+        TimerType::Shutdown => "systemctl poweroff".to_string(),
+        TimerType::Reboot => "systemctl reboot".to_string(),
+        TimerType::Logout => "loginctl terminate-user %u".to_string(),
+        TimerType::UserDefined(text) => format!(
+            "notify-send --app-name=Chronomancer \"Timer Finished\" {}",
+            quote(text)
+        ),
+    }
+}
+
+/// Quotes a command line argument for `ExecStart=`, escaping specifiers and variables.
+fn quote(text: &str) -> String {
+    let escaped = single_line(text)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+/// Joins the lines of a text, since unit file values end at the line.
+fn single_line(text: &str) -> String {
+    text.lines().collect::<Vec<_>>().join(" ")
+}
+
+/// A user timer read from a unit directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedTimer {
+    /// Unit name without the `.timer` suffix
+    pub name: String,
+    /// What the timer does in Chronomancer
+    pub timer_type: TimerType,
+    /// When it runs, `None` when systemd's schedule can't be translated
+    pub schedule: Option<Schedule>,
+}
+
+impl ImportedTimer {
+    /// Reads a `.timer` unit.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - Unit name without the `.timer` suffix
+    /// - `contents` - The unit file
+    /// - `now` - Current Unix timestamp in seconds
+    #[must_use]
+    pub fn parse(name: &str, contents: &str, now: i64) -> Self {
+        let kind = unit_value(contents, "Unit", "X-Chronomancer-Kind");
+        let text = unit_value(contents, "Unit", "X-Chronomancer-Text").unwrap_or_default();
+        let timer_type = kind
+            .and_then(|kind| TimerType::from_kind(kind, text))
+            .unwrap_or_else(|| {
+                let description = unit_value(contents, "Unit", "Description")
+                    .filter(|description| !description.is_empty())
+                    .unwrap_or(name);
+                TimerType::UserDefined(description.to_string())
+            });
+
+        // One schedule line is all a Chronomancer timer can follow
+        let calendar = unit_values(contents, "Timer", "OnCalendar");
+        let monotonic = unit_values(contents, "Timer", "OnUnitActiveSec");
+        let schedule = match (calendar.as_slice(), monotonic.as_slice()) {
+            ([spec], []) => parse_on_calendar(spec, now),
+            ([], [span]) => parse_timespan(span)
+                .filter(|period| *period > 0)
+                .and_then(|period| {
+                    let first = unit_value(contents, "Timer", "OnActiveSec")
+                        .and_then(parse_timespan)
+                        .unwrap_or(period);
+                    Some(Schedule::Every {
+                        period,
+                        next: now.checked_add(first)?,
+                    })
+                }),
+            _ => None,
+        };
+
+        Self {
+            name: name.to_string(),
+            timer_type,
+            schedule,
+        }
+    }
+
+    /// Gets the timer this unit would be in Chronomancer.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` - Current Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The timer, or `None` if the schedule can't be translated or already passed.
+    #[must_use]
+    pub fn to_timer(&self, now: i64) -> Option<Timer> {
+        self.schedule?.to_timer(&self.timer_type, now)
+    }
+
+    /// Gets the id of the Chronomancer timer this unit was exported from.
+    #[must_use]
+    pub fn exported_id(&self) -> Option<i64> {
+        self.name.strip_prefix(UNIT_PREFIX)?.parse().ok()
+    }
+}
+
+/// Gets the values of a key in one section of a unit file.
+fn unit_values<'a>(contents: &'a str, section: &str, key: &str) -> Vec<&'a str> {
+    let mut current = "";
+    let mut values = vec![];
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            current = name;
+        } else if current == section
+            && let Some((name, value)) = line.split_once('=')
+            && name.trim() == key
+        {
+            let value = value.trim();
+            // An empty assignment resets the list, like in systemd
+            if value.is_empty() {
+                values.clear();
+            } else {
+                values.push(value);
+            }
+        }
+    }
+    values
+}
+
+/// Gets the last value of a key in one section of a unit file.
+fn unit_value<'a>(contents: &'a str, section: &str, key: &str) -> Option<&'a str> {
+    unit_values(contents, section, key).pop()
+}
+
+/// Reads the `.timer` units in a unit directory, sorted by name.
+///
+/// # Arguments
+///
+/// - `dir` - The unit directory, usually [`user_unit_dir`]
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Errors
+///
+/// Returns an error if the directory exists but can't be read. A missing
+/// directory has no timers.
+pub fn read_timers(dir: &Path, now: i64) -> Result<Vec<ImportedTimer>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut timers = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".timer"))
+        else {
+            continue;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => timers.push(ImportedTimer::parse(name, &contents, now)),
+            Err(e) => tracing::warn!("Failed to read {}: {e}", path.display()),
+        }
+    }
+    timers.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(timers)
+}
+
+/// Moves a timer from the database to systemd user units.
+///
+/// Writes the units into the user's unit directory and starts them, then
+/// deletes the timer, so its action doesn't run twice.
+///
+/// # Arguments
+///
+/// - `pool` - The database connection pool
+/// - `id` - Id of the timer to export
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Returns
+///
+/// The exported timer and its units.
+///
+/// # Errors
+///
+/// Returns an error if the timer doesn't exist, has a chain or no schedule
+/// systemd can run, or if the units can't be written or started.
+pub async fn export(pool: &SqlitePool, id: i64, now: i64) -> Result<(Timer, Units)> {
+    let timer = Timer::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| anyhow!("No timer with id {id}"))?;
+    if Timer::get_all_chained(pool)
+        .await?
+        .iter()
+        .any(|link| link.parent_id == id)
+    {
+        bail!("Timers with a chain can't be exported");
+    }
+
+    let units = Units::for_timer(&timer, now)?;
+    units.write(&user_unit_dir()?)?;
+    enable(&units.name).await?;
+    Timer::delete_chain(pool, id).await?;
+    Ok((timer, units))
+}
+
+/// Gets the user's systemd unit directory, `~/.config/systemd/user`.
+///
+/// # Errors
+///
+/// Returns an error if the config directory can't be determined.
+pub fn user_unit_dir() -> Result<PathBuf> {
+    let config = dirs::config_dir().ok_or_else(|| anyhow!("No config directory"))?;
+    Ok(config.join("systemd").join("user"))
+}
+
+/// Connects to the user's service manager.
+async fn manager() -> Result<Proxy<'static>> {
+    let connection = Connection::session()
+        .await
+        .context("Failed to connect to session bus")?;
+    Ok(Proxy::new(
+        &connection,
+        "org.freedesktop.systemd1",
+        "/org/freedesktop/systemd1",
+        "org.freedesktop.systemd1.Manager",
+    )
+    .await?)
+}
+
+/// Enables and starts exported units after [`Units::write`].
+///
+/// # Arguments
+///
+/// - `name` - Unit name without the suffix
+///
+/// # Errors
+///
+/// Returns an error if the user's service manager can't be reached or refuses.
+///
+/// # D-Bus API
+///
+/// ```text
+/// org.freedesktop.systemd1.Manager.Reload()
+/// org.freedesktop.systemd1.Manager.EnableUnitFiles(as files, b runtime, b force) -> (b, a(sss))
+/// org.freedesktop.systemd1.Manager.StartUnit(s name, s mode) -> o job
+/// ```
+pub async fn enable(name: &str) -> Result<()> {
+    let manager = manager().await?;
+    let timer = format!("{name}.timer");
+    manager
+        .call_method("Reload", &())
+        .await
+        .context("Failed to reload systemd units")?;
+    let _: (bool, Vec<(String, String, String)>) = manager
+        .call("EnableUnitFiles", &(vec![timer.as_str()], false, true))
+        .await
+        .with_context(|| format!("Failed to enable {timer}"))?;
+    let _: OwnedObjectPath = manager
+        .call("StartUnit", &(timer.as_str(), "replace"))
+        .await
+        .with_context(|| format!("Failed to start {timer}"))?;
+    Ok(())
+}
+
+/// Stops, disables and deletes exported units, e.g. once they were imported back.
+///
+/// # Arguments
+///
+/// - `dir` - The unit directory the units are in
+/// - `name` - Unit name without the suffix
+///
+/// # Errors
+///
+/// Returns an error if the user's service manager can't be reached, or the
+/// files can't be deleted. Stopping a timer that isn't running is fine.
+///
+/// # D-Bus API
+///
+/// ```text
+/// org.freedesktop.systemd1.Manager.StopUnit(s name, s mode) -> o job
+/// org.freedesktop.systemd1.Manager.DisableUnitFiles(as files, b runtime) -> a(sss)
+/// org.freedesktop.systemd1.Manager.Reload()
+/// ```
+pub async fn remove(dir: &Path, name: &str) -> Result<()> {
+    let manager = manager().await?;
+    let timer = format!("{name}.timer");
+    // Fails for timers that aren't loaded, which is what we want anyway
+    let _: Result<OwnedObjectPath, _> =
+        manager.call("StopUnit", &(timer.as_str(), "replace")).await;
+    let _: Vec<(String, String, String)> = manager
+        .call("DisableUnitFiles", &(vec![timer.as_str()], false))
+        .await
+        .with_context(|| format!("Failed to disable {timer}"))?;
+
+    for suffix in ["timer", "service"] {
+        let path = dir.join(format!("{name}.{suffix}"));
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => bail!("Failed to delete {}: {e}", path.display()),
+        }
+    }
+    manager
+        .call_method("Reload", &())
+        .await
+        .context("Failed to reload systemd units")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A local Monday at 10:07:30, away from DST changes
+    fn monday_morning() -> i64 {
+        let time = NaiveDate::from_ymd_opt(2026, 1, 5)
+            .and_then(|date| date.and_hms_opt(10, 7, 30))
+            .unwrap();
+        time_zone::resolve(time, &time_zone::local_zone()).unwrap()
+    }
+
+    #[test]
+    fn test_recurring_schedules_keep_their_phase() {
+        let now = monday_morning();
+        for period in [15, MINUTE, 15 * MINUTE, HOUR, 6 * HOUR, DAY, WEEK] {
+            let schedule = Schedule::Every {
+                period,
+                next: now + period.min(45),
+            };
+            for zone in [time_zone::local_zone().as_str(), "Asia/Kolkata"] {
+                let spec =
+                    on_calendar(&schedule, zone).expect("period should be a calendar repetition");
+                assert_eq!(parse_on_calendar(&spec, now), Some(schedule), "{spec}");
+            }
+        }
+
+        // 10:07:30 UTC is 11:07:30 in Berlin
+        let now = 1_767_607_650;
+        assert_eq!(
+            on_calendar(
+                &Schedule::Every {
+                    period: 15 * MINUTE,
+                    next: now + 45
+                },
+                "Europe/Berlin"
+            ),
+            Some("*-*-* *:08/15:15 Europe/Berlin".to_string())
+        );
+        assert_eq!(
+            on_calendar(
+                &Schedule::Every {
+                    period: DAY,
+                    next: now
+                },
+                "Europe/Berlin"
+            ),
+            Some("*-*-* 11:07:30 Europe/Berlin".to_string())
+        );
+        assert_eq!(
+            on_calendar(&Schedule::Once(now), "UTC"),
+            Some("2026-01-05 10:07:30 UTC".to_string())
+        );
+        // Seven minutes don't divide an hour
+        assert_eq!(
+            on_calendar(
+                &Schedule::Every {
+                    period: 7 * MINUTE,
+                    next: now
+                },
+                "UTC"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_on_calendar() {
+        let now = monday_morning();
+        assert_eq!(
+            parse_on_calendar("daily", now),
+            Some(Schedule::Every {
+                period: DAY,
+                next: now - 10 * HOUR - 7 * MINUTE - 30 + DAY
+            })
+        );
+        assert_eq!(
+            parse_on_calendar("*:0/15", now),
+            Some(Schedule::Every {
+                period: 15 * MINUTE,
+                next: now - 7 * MINUTE - 30 + 15 * MINUTE
+            })
+        );
+        assert_eq!(
+            parse_on_calendar("Wed 09:00", now),
+            Some(Schedule::Every {
+                period: WEEK,
+                next: now - 10 * HOUR - 7 * MINUTE - 30 + 2 * DAY + 9 * HOUR
+            })
+        );
+        assert_eq!(
+            parse_on_calendar("2026-01-05 12:00:00", now),
+            Some(Schedule::Once(now + HOUR + 52 * MINUTE + 30))
+        );
+        assert_eq!(
+            parse_on_calendar("2026-01-05 12:00:00 UTC", now),
+            Some(Schedule::Once(1_767_614_400))
+        );
+        assert_eq!(
+            parse_on_calendar("2026-01-05 13:00:00 Europe/Berlin", now),
+            Some(Schedule::Once(1_767_614_400))
+        );
+
+        assert_eq!(parse_on_calendar("*-*-01 00:00:00", now), None);
+        assert_eq!(parse_on_calendar("Mon..Fri 09:00", now), None);
+        assert_eq!(parse_on_calendar("*:0/7", now), None);
+        assert_eq!(
+            parse_on_calendar("*-*-* 0/9999999999999999:00:00", now),
+            None
+        );
+        assert_eq!(parse_on_calendar("*-*-* 9999999999999999:00", now), None);
+    }
+
+    #[test]
+    fn test_parse_timespan() {
+        assert_eq!(parse_timespan("90"), Some(90));
+        assert_eq!(parse_timespan("15min"), Some(900));
+        assert_eq!(parse_timespan("1h 30min"), Some(5400));
+        assert_eq!(parse_timespan("2d"), Some(2 * DAY));
+        assert_eq!(parse_timespan("500ms"), None);
+        assert_eq!(parse_timespan(""), None);
+        assert_eq!(parse_timespan("9999999999999999w"), None);
+    }
+
+    #[test]
+    fn test_units_round_trip() -> Result<()> {
+        let now = monday_morning();
+        let mut reminder = Timer {
+            id: 12,
+            ends_at: now + 300,
+            ..Timer::repeating(420, &TimerType::UserDefined("Stretch 100%".into()), now)
+        };
+        let units = Units::for_timer(&reminder, now)?;
+        assert_eq!(units.name, "chronomancer-12");
+        assert!(
+            units
+                .timer
+                .contains("OnActiveSec=300s\nOnUnitActiveSec=420s\n")
+        );
+        assert!(units.service.contains(
+            "ExecStart=notify-send --app-name=Chronomancer \"Timer Finished\" \"Stretch 100%%\""
+        ));
+
+        let imported = ImportedTimer::parse(&units.name, &units.timer, now);
+        assert_eq!(imported.exported_id(), Some(12));
+        assert_eq!(
+            imported.timer_type,
+            TimerType::UserDefined("Stretch 100%".into())
+        );
+        assert_eq!(
+            imported.schedule,
+            Some(Schedule::Every {
+                period: 7 * MINUTE,
+                next: now + 300
+            })
+        );
+
+        reminder.ends_at = Timer::NO_DEADLINE;
+        assert!(Units::for_timer(&reminder, now).is_err());
+
+        let mut reboot = Timer::new(0, false, &TimerType::Reboot, now);
+        reboot.ends_at = now + HOUR;
+        let units = Units::for_timer(&reboot, now)?;
+        assert!(units.service.contains("ExecStart=systemctl reboot\n"));
+        let imported = ImportedTimer::parse(&units.name, &units.timer, now);
+        assert_eq!(imported.timer_type, TimerType::Reboot);
+        assert_eq!(imported.schedule, Some(Schedule::Once(now + HOUR)));
+
+        // A wall clock timer stays in the zone it was set in
+        let at = NaiveDate::from_ymd_opt(2026, 1, 6)
+            .and_then(|date| date.and_hms_opt(8, 0, 0))
+            .unwrap();
+        let alarm =
+            Timer::at(at, "Europe/Berlin", &TimerType::Logout, now).expect("Berlin is in tzdata");
+        let units = Units::for_timer(&alarm, now)?;
+        assert!(
+            units
+                .timer
+                .contains("OnCalendar=2026-01-06 08:00:00 Europe/Berlin\n")
+        );
+        let imported = ImportedTimer::parse(&units.name, &units.timer, now);
+        assert_eq!(imported.schedule, Some(Schedule::Once(alarm.ends_at)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reads_foreign_timers() -> Result<()> {
+        let now = monday_morning();
+        let dir = std::env::temp_dir().join(format!("chronomancer-systemd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(
+            dir.join("backup.timer"),
+            "[Unit]\nDescription=Nightly backup\n\n[Timer]\nOnCalendar=*-*-* 02:30:00\nPersistent=true\n",
+        )?;
+        std::fs::write(dir.join("monthly.timer"), "[Timer]\nOnCalendar=monthly\n")?;
+        std::fs::write(dir.join("backup.service"), "[Service]\nExecStart=true\n")?;
+
+        let timers = read_timers(&dir, now)?;
+        std::fs::remove_dir_all(&dir)?;
+        assert_eq!(timers.len(), 2);
+        assert_eq!(timers[0].name, "backup");
+        assert_eq!(
+            timers[0].timer_type,
+            TimerType::UserDefined("Nightly backup".into())
+        );
+        assert_eq!(timers[0].exported_id(), None);
+        let timer = timers[0].to_timer(now).expect("daily timers should import");
+        assert_eq!(timer.period(), Some(DAY));
+        assert_eq!(
+            timer.ends_at,
+            now - 10 * HOUR - 7 * MINUTE - 30 + DAY + 2 * HOUR + 30 * MINUTE
+        );
+
+        assert_eq!(timers[1].name, "monthly");
+        assert_eq!(timers[1].schedule, None);
+
+        assert!(read_timers(&dir, now)?.is_empty());
+        Ok(())
+    }
+}
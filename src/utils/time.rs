@@ -36,6 +36,8 @@
 //! ```
 
 use crate::fl;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Time units supported by Chronomancer.
@@ -57,9 +59,10 @@ use std::fmt;
 /// let total_seconds = value * unit.to_seconds_multiplier();
 /// assert_eq!(total_seconds, 1800);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum TimeUnit {
     /// Seconds (1 second = 1 second)
+    #[default]
     Seconds,
 
     /// Minutes (1 minute = 60 seconds)
@@ -73,6 +76,9 @@ pub enum TimeUnit {
 }
 
 impl TimeUnit {
+    /// All units in selection order.
+    pub const ALL: [Self; 4] = [Self::Seconds, Self::Minutes, Self::Hours, Self::Days];
+
     /// Returns the number of seconds in one unit of this time unit.
     ///
     /// This multiplier is used to convert a duration value to seconds.
@@ -181,6 +187,194 @@ pub fn format_duration(seconds: i32) -> String {
     }
 }
 
+/// Formats a countdown as a clock, e.g. for a running phase.
+///
+/// # Arguments
+///
+/// - `seconds` - Seconds left, negative values are shown as zero
+///
+/// # Returns
+///
+/// `MM:SS` below an hour, `H:MM:SS` from an hour up.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::time::format_countdown;
+///
+/// assert_eq!(format_countdown(1500), "25:00");
+/// assert_eq!(format_countdown(65), "01:05");
+/// assert_eq!(format_countdown(3725), "1:02:05");
+/// assert_eq!(format_countdown(-3), "00:00");
+/// ```
+#[must_use]
+pub fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Formats time left as a single short unit, for places too narrow for a clock.
+///
+/// # Arguments
+///
+/// - `seconds` - Seconds left, negative values are shown as zero
+///
+/// # Returns
+///
+/// Whole seconds below a minute, then whole minutes, hours or days, rounded down.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::time::format_compact;
+///
+/// assert_eq!(format_compact(42), "42s");
+/// assert_eq!(format_compact(754), "12m");
+/// assert_eq!(format_compact(7200), "2h");
+/// assert_eq!(format_compact(90_000), "1d");
+/// assert_eq!(format_compact(-3), "0s");
+/// ```
+#[must_use]
+pub fn format_compact(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 86_400 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{}d", seconds / 86_400)
+    }
+}
+
+/// Parses a typed duration such as `25m`, `1h30m` or `90s` into seconds.
+///
+/// Each part is a number followed by a unit: `s`, `m`, `h` or `d` (the longer
+/// `sec`, `min`, `hr`/`hour` and `day` spellings, with or without a plural `s`,
+/// work too). A bare number counts as minutes, the unit people mean most often
+/// when typing a quick timer.
+///
+/// # Arguments
+///
+/// - `input` - The typed duration, without spaces between its parts
+///
+/// # Returns
+///
+/// The total in seconds, or `None` if the input isn't a duration or adds up to zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::time::parse_duration;
+///
+/// assert_eq!(parse_duration("25m"), Some(1500));
+/// assert_eq!(parse_duration("1h30m"), Some(5400));
+/// assert_eq!(parse_duration("90s"), Some(90));
+/// assert_eq!(parse_duration("2hours"), Some(7200));
+/// assert_eq!(parse_duration("45"), Some(2700));
+/// assert_eq!(parse_duration("standup"), None);
+/// assert_eq!(parse_duration("0m"), None);
+/// ```
+#[must_use]
+pub fn parse_duration(input: &str) -> Option<i32> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+    if let Ok(minutes) = input.parse::<i32>() {
+        return minutes.checked_mul(60).filter(|seconds| *seconds > 0);
+    }
+
+    let mut total: i32 = 0;
+    let mut rest = input.as_str();
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value: i32 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "s" | "sec" | "secs" | "second" | "seconds" => TimeUnit::Seconds,
+            "m" | "min" | "mins" | "minute" | "minutes" => TimeUnit::Minutes,
+            "h" | "hr" | "hrs" | "hour" | "hours" => TimeUnit::Hours,
+            "d" | "day" | "days" => TimeUnit::Days,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+
+        total = total.checked_add(value.checked_mul(unit.to_seconds_multiplier())?)?;
+    }
+
+    (total > 0).then_some(total)
+}
+
+/// Parses a typed time of day on the 24-hour clock, such as `8:00` or `20:30`.
+///
+/// # Arguments
+///
+/// - `input` - Hours and minutes separated by a colon
+///
+/// # Returns
+///
+/// The time, or `None` if the input isn't a valid time of day.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::NaiveTime;
+/// use chronomancer::utils::time::parse_time_of_day;
+///
+/// assert_eq!(parse_time_of_day("8:00"), NaiveTime::from_hms_opt(8, 0, 0));
+/// assert_eq!(parse_time_of_day("20:30"), NaiveTime::from_hms_opt(20, 30, 0));
+/// assert_eq!(parse_time_of_day("25:00"), None);
+/// assert_eq!(parse_time_of_day("25m"), None);
+/// ```
+#[must_use]
+pub fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    let (hours, minutes) = input.trim().split_once(':')?;
+    let digits = |part: &str, max_len| {
+        (1..=max_len).contains(&part.len()) && part.chars().all(|c| c.is_ascii_digit())
+    };
+    if !digits(hours, 2) || !digits(minutes, 2) || minutes.len() != 2 {
+        return None;
+    }
+    NaiveTime::from_hms_opt(hours.parse().ok()?, minutes.parse().ok()?, 0)
+}
+
+/// Formats a stopwatch time as a clock with tenths of a second, e.g. for a lap.
+///
+/// # Arguments
+///
+/// - `millis` - Elapsed milliseconds, negative values are shown as zero
+///
+/// # Examples
+///
+/// ```rust
+/// use chronomancer::utils::time::format_lap;
+///
+/// assert_eq!(format_lap(65_432), "01:05.4");
+/// assert_eq!(format_lap(3_725_000), "1:02:05.0");
+/// ```
+#[must_use]
+pub fn format_lap(millis: i64) -> String {
+    let millis = millis.max(0);
+    format!(
+        "{}.{}",
+        format_countdown(millis / 1000),
+        millis % 1000 / 100
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +408,38 @@ mod tests {
         assert_eq!(format_duration(30), "0 minutes");
         assert_eq!(format_duration(59), "0 minutes");
     }
+
+    #[test]
+    fn test_parse_duration_rejects_malformed_input() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("m25"), None);
+        assert_eq!(parse_duration("25x"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration("-5m"), None);
+        // Overflowing durations are rejected instead of wrapping
+        assert_eq!(parse_duration("99999999d"), None);
+        assert_eq!(parse_duration("1H30M"), Some(5400));
+    }
+
+    #[test]
+    fn test_parse_time_of_day() {
+        assert_eq!(parse_time_of_day("07:05"), NaiveTime::from_hms_opt(7, 5, 0));
+        assert_eq!(parse_time_of_day("0:00"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(
+            parse_time_of_day("23:59"),
+            NaiveTime::from_hms_opt(23, 59, 0)
+        );
+        assert_eq!(parse_time_of_day("12:60"), None);
+        assert_eq!(parse_time_of_day("8:5"), None);
+        assert_eq!(parse_time_of_day(":30"), None);
+        assert_eq!(parse_time_of_day("+8:00"), None);
+        assert_eq!(parse_time_of_day("8"), None);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(0), "00:00");
+        assert_eq!(format_countdown(59), "00:59");
+        assert_eq!(format_countdown(3600), "1:00:00");
+    }
 }
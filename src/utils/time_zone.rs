@@ -0,0 +1,279 @@
+//! Wall clock times in IANA time zones.
+//!
+//! Timers set for a time of day ("at 08:00") keep that local time and the IANA
+//! name of the zone it was set in, see [`TimerIntent`](crate::models::intent::TimerIntent).
+//! Their deadline is resolved with the zone's rules from the system's tzdata,
+//! so it is right on either side of a DST change, and resolved again when the
+//! laptop moves to another zone.
+//!
+//! Local times that don't exist because the clocks were put forward resolve to
+//! the same time after the change (02:30 on the night clocks go from 02:00 to
+//! 03:00 becomes 03:30). Local times that happen twice because the clocks were
+//! put back resolve to the first one.
+//!
+//! The next DST switch is known ahead (see [`next_offset_change`]), and a new
+//! zone is announced by `systemd-timedated` (see [`changes`]), so neither has
+//! to be polled for.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chrono::NaiveDate;
+//! use chronomancer::utils::time_zone;
+//!
+//! let morning = NaiveDate::from_ymd_opt(2026, 7, 1)
+//!     .and_then(|date| date.and_hms_opt(8, 0, 0))
+//!     .unwrap();
+//!
+//! // 08:00 in Berlin is 06:00 UTC in summer
+//! let at = time_zone::resolve(morning, "Europe/Berlin").unwrap();
+//! assert_eq!(at, morning.and_utc().timestamp() - 2 * 3600);
+//! assert_eq!(time_zone::local_time(at, "Europe/Berlin"), Some(morning));
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, NaiveTime};
+use futures_util::{StreamExt, stream::BoxStream};
+use tz::TimeZone;
+use zbus::{Connection, MatchRule, MessageStream, message::Type};
+
+const DAY: i64 = 24 * 60 * 60;
+const WEEK: i64 = 7 * DAY;
+/// How far ahead [`next_offset_change`] looks, in weeks
+const OFFSET_CHANGE_HORIZON_WEEKS: i64 = 53;
+/// Object path of `systemd-timedated`, which sets the system's time zone
+const TIMEDATED_PATH: &str = "/org/freedesktop/timedate1";
+
+/// Gets the IANA name of the system's time zone, `UTC` when it can't be told.
+#[must_use]
+pub fn local_zone() -> String {
+    iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string())
+}
+
+/// Gets a zone's offset from UTC in seconds at a given time.
+///
+/// # Arguments
+///
+/// - `zone` - IANA name of the zone, e.g. `Europe/Berlin`
+/// - `at` - Unix timestamp in seconds
+///
+/// # Returns
+///
+/// The offset, positive east of UTC, or `None` for zones missing from tzdata.
+#[must_use]
+pub fn utc_offset(zone: &str, at: i64) -> Option<i32> {
+    let zone = TimeZone::from_posix_tz(zone).ok()?;
+    zone.find_local_time_type(at)
+        .ok()
+        .map(tz::LocalTimeType::ut_offset)
+}
+
+/// Gets when a zone's offset from UTC changes next, such as the next DST switch.
+///
+/// Looks up to a year ahead. Zones change their offset at most a few times a
+/// year and never twice in a week, so the change is found week by week and
+/// then narrowed down to the second.
+///
+/// # Arguments
+///
+/// - `zone` - IANA name of the zone
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Returns
+///
+/// The first second with the new offset, or `None` for zones that keep their
+/// offset all year or are missing from tzdata.
+#[must_use]
+pub fn next_offset_change(zone: &str, now: i64) -> Option<i64> {
+    let zone = TimeZone::from_posix_tz(zone).ok()?;
+    let offset = |at: i64| {
+        zone.find_local_time_type(at)
+            .ok()
+            .map(tz::LocalTimeType::ut_offset)
+    };
+    let current = offset(now)?;
+
+    let mut after = (1..=OFFSET_CHANGE_HORIZON_WEEKS)
+        .map(|weeks| now + weeks * WEEK)
+        .find(|&at| offset(at) != Some(current))?;
+    let mut before = after - WEEK;
+    while after - before > 1 {
+        let middle = before + (after - before) / 2;
+        if offset(middle) == Some(current) {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+    Some(after)
+}
+
+/// Follows changes of the system's time zone.
+///
+/// `systemd-timedated` announces a new zone with a `PropertiesChanged`
+/// signal; the stream yields once for each, and the caller reads the zone
+/// again (see [`local_zone`]).
+///
+/// # Errors
+///
+/// Returns an error if the system bus can't be reached or the signal
+/// subscription fails.
+pub async fn changes() -> Result<BoxStream<'static, ()>> {
+    let connection = Connection::system()
+        .await
+        .context("Failed to connect to system bus")?;
+    // timedated only runs on demand, so it's matched by path rather than by its name
+    let rule = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .path(TIMEDATED_PATH)?
+        .build();
+    let signals = MessageStream::for_match_rule(rule, &connection, None).await?;
+    // The stream ends with the connection, so it holds on to it
+    Ok(signals
+        .map(move |_| {
+            let _ = &connection;
+        })
+        .boxed())
+}
+
+/// Gets the wall clock time a zone shows at a given time.
+///
+/// # Arguments
+///
+/// - `at` - Unix timestamp in seconds
+/// - `zone` - IANA name of the zone
+#[must_use]
+pub fn local_time(at: i64, zone: &str) -> Option<NaiveDateTime> {
+    let offset = utc_offset(zone, at)?;
+    DateTime::from_timestamp(at + i64::from(offset), 0).map(|time| time.naive_utc())
+}
+
+/// Gets the Unix timestamp at which a zone's clocks show a wall clock time.
+///
+/// # Arguments
+///
+/// - `local` - The wall clock time
+/// - `zone` - IANA name of the zone
+///
+/// # Returns
+///
+/// The timestamp, or `None` for zones missing from tzdata.
+#[must_use]
+pub fn resolve(local: NaiveDateTime, zone: &str) -> Option<i64> {
+    let naive = local.and_utc().timestamp();
+    // The offsets a day either side cover any change of the clocks that night
+    let before = utc_offset(zone, naive - DAY)?;
+    let after = utc_offset(zone, naive + DAY)?;
+
+    [before, after]
+        .into_iter()
+        .map(|offset| (naive - i64::from(offset), offset))
+        .filter(|&(at, offset)| utc_offset(zone, at) == Some(offset))
+        .map(|(at, _)| at)
+        .min()
+        // Skipped when the clocks went forward, count on from before the change
+        .or(Some(naive - i64::from(before)))
+}
+
+/// Gets the next time a zone's clocks show a time of day.
+///
+/// # Arguments
+///
+/// - `time` - The time of day
+/// - `zone` - IANA name of the zone
+/// - `now` - Current Unix timestamp in seconds
+///
+/// # Returns
+///
+/// The wall clock time today if it's still ahead, tomorrow otherwise, or `None`
+/// for zones missing from tzdata.
+#[must_use]
+pub fn next_occurrence(time: NaiveTime, zone: &str, now: i64) -> Option<NaiveDateTime> {
+    let today = local_time(now, zone)?.date().and_time(time);
+    if resolve(today, zone)? > now {
+        Some(today)
+    } else {
+        today.checked_add_days(chrono::Days::new(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn local(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .unwrap()
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        local(month, day, hour, minute).and_utc().timestamp()
+    }
+
+    #[test]
+    fn test_resolve_follows_dst() {
+        assert_eq!(
+            resolve(local(1, 15, 8, 0), "Europe/Berlin"),
+            Some(utc(1, 15, 7, 0))
+        );
+        assert_eq!(
+            resolve(local(7, 15, 8, 0), "Europe/Berlin"),
+            Some(utc(7, 15, 6, 0))
+        );
+        assert_eq!(
+            resolve(local(7, 15, 8, 0), "America/New_York"),
+            Some(utc(7, 15, 12, 0))
+        );
+
+        // Clocks go forward at 02:00 on March 29th, 02:30 never happens
+        assert_eq!(
+            resolve(local(3, 29, 2, 30), "Europe/Berlin"),
+            Some(utc(3, 29, 1, 30))
+        );
+        // Clocks go back at 03:00 on October 25th, 02:30 happens twice
+        assert_eq!(
+            resolve(local(10, 25, 2, 30), "Europe/Berlin"),
+            Some(utc(10, 25, 0, 30))
+        );
+
+        assert_eq!(resolve(local(1, 15, 8, 0), "Europe/Atlantis"), None);
+        assert_eq!(utc_offset("UTC", 0), Some(0));
+    }
+
+    #[test]
+    fn test_next_offset_change() {
+        // Berlin springs forward at 01:00 UTC on March 29th, and falls back at 01:00 UTC on October 25th
+        assert_eq!(
+            next_offset_change("Europe/Berlin", utc(1, 15, 0, 0)),
+            Some(utc(3, 29, 1, 0))
+        );
+        assert_eq!(
+            next_offset_change("Europe/Berlin", utc(3, 29, 1, 0)),
+            Some(utc(10, 25, 1, 0))
+        );
+        assert_eq!(next_offset_change("Asia/Tokyo", utc(1, 15, 0, 0)), None);
+        assert_eq!(next_offset_change("Europe/Atlantis", 0), None);
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let time = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        let zone = "Asia/Tokyo";
+
+        // 07:00 in Tokyo, 08:00 is still ahead
+        assert_eq!(
+            next_occurrence(time, zone, utc(5, 31, 22, 0)),
+            Some(local(6, 1, 8, 0))
+        );
+        // 08:00 in Tokyo, next one is tomorrow
+        assert_eq!(
+            next_occurrence(time, zone, utc(5, 31, 23, 0)),
+            Some(local(6, 2, 8, 0))
+        );
+        assert_eq!(local_time(utc(5, 31, 23, 0), zone), Some(local(6, 1, 8, 0)));
+    }
+}
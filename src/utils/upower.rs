@@ -0,0 +1,381 @@
+//! `UPower` D-Bus client for battery and AC power state.
+//!
+//! This module reads the system's power source from the `org.freedesktop.UPower`
+//! service so timers can be conditioned on it (e.g. "hibernate when the battery
+//! drops below 10%" or "only shut down while on AC power").
+//!
+//! # Mocking
+//!
+//! The public entry points [`get_power_state`] and [`watch_power_state`] talk to
+//! the real `UPower` daemon on the system bus. [`get_power_state_from`] and
+//! [`watch_power_state_from`] take the connection and bus name explicitly, so
+//! tests (or a developer without a laptop) can point them at a local stand-in
+//! service that exposes the same object paths and properties.
+//!
+//! # Following changes
+//!
+//! [`watch_power_state`] reads the state again whenever `UPower` announces a
+//! change of its properties, so nothing is polled while the battery level and
+//! the AC adapter stay the same.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use chronomancer::utils::upower;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let state = upower::get_power_state().await?;
+//!
+//! if state.is_battery_below(10) {
+//!     println!("Battery is almost empty!");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use zbus::{Connection, MatchRule, MessageStream, Proxy, message::Type};
+
+/// Well-known bus name of the `UPower` daemon.
+pub const UPOWER_SERVICE: &str = "org.freedesktop.UPower";
+
+/// Object path of the `UPower` manager object.
+pub const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+
+/// Object path of `UPower`'s composite "display device".
+///
+/// The display device aggregates all batteries into a single percentage, which
+/// is the same value the panel's battery indicator shows.
+pub const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+
+/// Snapshot of the system's power source.
+///
+/// # Fields
+///
+/// - `on_battery` - Whether the system is currently running on battery
+/// - `battery_percentage` - Charge of the display device, `None` when no battery is present
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerState {
+    pub on_battery: bool,
+    pub battery_percentage: Option<f64>,
+}
+
+impl PowerState {
+    /// Whether the system is discharging with a charge strictly below `percent`.
+    ///
+    /// Systems plugged into AC power or without a battery never count as below
+    /// the threshold, so a desktop will never trip a battery condition.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronomancer::utils::upower::PowerState;
+    ///
+    /// let state = PowerState { on_battery: true, battery_percentage: Some(8.0) };
+    /// assert!(state.is_battery_below(10));
+    /// assert!(!state.is_battery_below(5));
+    ///
+    /// let charging = PowerState { on_battery: false, battery_percentage: Some(8.0) };
+    /// assert!(!charging.is_battery_below(10));
+    /// ```
+    #[must_use]
+    pub fn is_battery_below(&self, percent: u8) -> bool {
+        self.on_battery
+            && self
+                .battery_percentage
+                .is_some_and(|charge| charge < f64::from(percent))
+    }
+}
+
+/// Reads the current power state from the `UPower` daemon on the system bus.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Failed to connect to the system D-Bus
+/// - `UPower` is not running or its properties cannot be read
+pub async fn get_power_state() -> Result<PowerState> {
+    let connection = Connection::system()
+        .await
+        .context("Failed to connect to system bus")?;
+
+    get_power_state_from(&connection, UPOWER_SERVICE).await
+}
+
+/// Reads the power state from a `UPower`-compatible service on the given connection.
+///
+/// This is the mockable core of [`get_power_state`]. Any service that serves the
+/// `org.freedesktop.UPower` interface at [`UPOWER_PATH`] and the
+/// `org.freedesktop.UPower.Device` interface at [`DISPLAY_DEVICE_PATH`] works.
+///
+/// # Arguments
+///
+/// - `connection` - D-Bus connection the service is reachable on
+/// - `destination` - Bus name of the service (normally [`UPOWER_SERVICE`])
+///
+/// # Errors
+///
+/// Returns an error if the service is unreachable or a property read fails.
+///
+/// # D-Bus API
+///
+/// ```text
+/// org.freedesktop.UPower.OnBattery -> bool
+/// org.freedesktop.UPower.Device.IsPresent -> bool    (DisplayDevice)
+/// org.freedesktop.UPower.Device.Percentage -> double (DisplayDevice)
+/// ```
+pub async fn get_power_state_from(
+    connection: &Connection,
+    destination: &str,
+) -> Result<PowerState> {
+    let manager = Proxy::new(
+        connection,
+        destination,
+        UPOWER_PATH,
+        "org.freedesktop.UPower",
+    )
+    .await?;
+
+    let on_battery: bool = manager
+        .get_property("OnBattery")
+        .await
+        .context("Failed to read UPower OnBattery")?;
+
+    let display_device = Proxy::new(
+        connection,
+        destination,
+        DISPLAY_DEVICE_PATH,
+        "org.freedesktop.UPower.Device",
+    )
+    .await?;
+
+    let is_present: bool = display_device
+        .get_property("IsPresent")
+        .await
+        .context("Failed to read UPower IsPresent")?;
+
+    let battery_percentage = if is_present {
+        let percentage: f64 = display_device
+            .get_property("Percentage")
+            .await
+            .context("Failed to read UPower Percentage")?;
+        Some(percentage)
+    } else {
+        None
+    };
+
+    Ok(PowerState {
+        on_battery,
+        battery_percentage,
+    })
+}
+
+/// Follows the power state of the `UPower` daemon on the system bus.
+///
+/// # Returns
+///
+/// A stream that yields the current state, then the state after each change
+/// `UPower` announces. Failed reads are yielded as errors, and the stream goes
+/// on.
+///
+/// # Errors
+///
+/// Returns an error if the system bus can't be reached.
+pub async fn watch_power_state() -> Result<BoxStream<'static, Result<PowerState>>> {
+    let connection = Connection::system()
+        .await
+        .context("Failed to connect to system bus")?;
+
+    watch_power_state_from(connection, UPOWER_SERVICE).await
+}
+
+/// Follows the power state of a `UPower`-compatible service on the given connection.
+///
+/// This is the mockable core of [`watch_power_state`], see [`get_power_state_from`]
+/// for what the service has to serve.
+///
+/// # Arguments
+///
+/// - `connection` - D-Bus connection the service is reachable on
+/// - `destination` - Bus name of the service (normally [`UPOWER_SERVICE`])
+///
+/// # Errors
+///
+/// Returns an error if the signal subscription fails.
+pub async fn watch_power_state_from(
+    connection: Connection,
+    destination: &str,
+) -> Result<BoxStream<'static, Result<PowerState>>> {
+    // The sender is left out since signals carry the unique name, not the well-known one
+    let rule = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .path_namespace(UPOWER_PATH)?
+        .build();
+    let changes = MessageStream::for_match_rule(rule, &connection, None)
+        .await?
+        .filter(|message| {
+            let relevant = message.as_ref().is_ok_and(|message| {
+                message
+                    .header()
+                    .path()
+                    .is_some_and(|path| [UPOWER_PATH, DISPLAY_DEVICE_PATH].contains(&path.as_str()))
+            });
+            async move { relevant }
+        });
+
+    let destination = destination.to_string();
+    Ok(stream::once(async {})
+        .chain(changes.map(|_| ()))
+        .then(move |()| {
+            let connection = connection.clone();
+            let destination = destination.clone();
+            async move { get_power_state_from(&connection, &destination).await }
+        })
+        .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stand-in for the `UPower` manager object.
+    struct StandInManager {
+        on_battery: bool,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.UPower")]
+    impl StandInManager {
+        #[zbus(property)]
+        fn on_battery(&self) -> bool {
+            self.on_battery
+        }
+    }
+
+    /// Stand-in for `UPower`'s display device.
+    struct StandInDevice {
+        is_present: bool,
+        percentage: f64,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.UPower.Device")]
+    impl StandInDevice {
+        #[zbus(property)]
+        fn is_present(&self) -> bool {
+            self.is_present
+        }
+
+        #[zbus(property)]
+        fn percentage(&self) -> f64 {
+            self.percentage
+        }
+    }
+
+    #[test]
+    fn test_is_battery_below() {
+        let discharging = PowerState {
+            on_battery: true,
+            battery_percentage: Some(9.5),
+        };
+        assert!(discharging.is_battery_below(10));
+        assert!(!discharging.is_battery_below(9));
+
+        let no_battery = PowerState {
+            on_battery: true,
+            battery_percentage: None,
+        };
+        assert!(!no_battery.is_battery_below(100));
+    }
+
+    // Serves a stand-in UPower on the session bus under a private name.
+    // Skipped when no session bus is available (e.g. headless CI).
+    #[tokio::test]
+    async fn test_get_power_state_from_stand_in() -> Result<()> {
+        let name = format!("io.vulpapps.Chronomancer.Test.UPower{}", std::process::id());
+
+        let Ok(builder) = zbus::connection::Builder::session() else {
+            return Ok(());
+        };
+        let Ok(_service) = builder
+            .name(name.as_str())?
+            .serve_at(UPOWER_PATH, StandInManager { on_battery: true })?
+            .serve_at(
+                DISPLAY_DEVICE_PATH,
+                StandInDevice {
+                    is_present: true,
+                    percentage: 7.0,
+                },
+            )?
+            .build()
+            .await
+        else {
+            return Ok(());
+        };
+
+        let client = Connection::session().await?;
+        let state = get_power_state_from(&client, &name).await?;
+
+        assert_eq!(
+            state,
+            PowerState {
+                on_battery: true,
+                battery_percentage: Some(7.0),
+            }
+        );
+        assert!(state.is_battery_below(10));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_power_state_follows_changes() -> Result<()> {
+        let name = format!(
+            "io.vulpapps.Chronomancer.Test.UPowerWatch{}",
+            std::process::id()
+        );
+
+        let Ok(builder) = zbus::connection::Builder::session() else {
+            return Ok(());
+        };
+        let Ok(service) = builder
+            .name(name.as_str())?
+            .serve_at(UPOWER_PATH, StandInManager { on_battery: true })?
+            .serve_at(
+                DISPLAY_DEVICE_PATH,
+                StandInDevice {
+                    is_present: true,
+                    percentage: 40.0,
+                },
+            )?
+            .build()
+            .await
+        else {
+            return Ok(());
+        };
+
+        let mut states = watch_power_state_from(Connection::session().await?, &name).await?;
+        let first = states.next().await.expect("the current state")?;
+        assert_eq!(first.battery_percentage, Some(40.0));
+
+        let device = service
+            .object_server()
+            .interface::<_, StandInDevice>(DISPLAY_DEVICE_PATH)
+            .await?;
+        device.get_mut().await.percentage = 9.0;
+        device
+            .get()
+            .await
+            .percentage_changed(device.signal_emitter())
+            .await?;
+
+        let changed = tokio::time::timeout(std::time::Duration::from_secs(5), states.next())
+            .await?
+            .expect("the changed state")?;
+        assert!(changed.is_battery_below(10));
+        Ok(())
+    }
+}
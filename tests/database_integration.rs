@@ -12,10 +12,13 @@
 // Key concept: Integration tests should use an isolated database (in-memory
 // or temporary file) so they don't pollute your actual app data.
 
+use chronomancer::models::sequence::{Phase, Sequence};
 use chronomancer::utils::database::repository::Repository;
 use chronomancer::utils::database::sqlite::SQLiteDatabase;
 use chronomancer::{Timer, TimerType};
 
+const NOW: i64 = 1_767_600_000;
+
 /// Helper to create a test database (in-memory or temp file)
 async fn setup_test_db() -> SQLiteDatabase {
     SQLiteDatabase::new_in_memory()
@@ -27,7 +30,7 @@ async fn setup_test_db() -> SQLiteDatabase {
 async fn timer_insert_and_fetch() {
     let db = setup_test_db().await;
     let timer_type = TimerType::Suspend;
-    let timer = Timer::new(120, false, &timer_type);
+    let timer = Timer::new(120, false, &timer_type, NOW);
 
     // Insert timer
     let saved = Timer::insert(db.pool(), &timer)
@@ -53,8 +56,8 @@ async fn timer_get_all_active() {
     let db = setup_test_db().await;
 
     // Create and insert multiple timers
-    let timer1 = Timer::new(60, false, &TimerType::Suspend);
-    let timer2 = Timer::new(120, false, &TimerType::Shutdown);
+    let timer1 = Timer::new(60, false, &TimerType::Suspend, NOW);
+    let timer2 = Timer::new(120, false, &TimerType::Shutdown, NOW);
 
     Timer::insert(db.pool(), &timer1)
         .await
@@ -64,7 +67,7 @@ async fn timer_get_all_active() {
         .expect("Insert failed");
 
     // All timers should be active
-    let active = Timer::get_all_active(db.pool())
+    let active = Timer::get_all_active(db.pool(), NOW)
         .await
         .expect("Failed to fetch active timers");
 
@@ -74,7 +77,7 @@ async fn timer_get_all_active() {
 #[tokio::test]
 async fn timer_delete() {
     let db = setup_test_db().await;
-    let timer = Timer::new(60, false, &TimerType::Logout);
+    let timer = Timer::new(60, false, &TimerType::Logout, NOW);
 
     let saved = Timer::insert(db.pool(), &timer)
         .await
@@ -97,15 +100,15 @@ async fn timer_expired_not_in_active_list() {
     let db = setup_test_db().await;
 
     // Create timer that's already expired
-    let mut expired_timer = Timer::new(1, false, &TimerType::UserDefined("Test".into()));
-    expired_timer.ends_at = chrono::Utc::now().timestamp() - 100;
+    let mut expired_timer = Timer::new(1, false, &TimerType::UserDefined("Test".into()), NOW);
+    expired_timer.ends_at = NOW - 100;
 
     let saved = Timer::insert(db.pool(), &expired_timer)
         .await
         .expect("Insert failed");
 
     // get_all_active should NOT include expired timers
-    let active = Timer::get_all_active(db.pool())
+    let active = Timer::get_all_active(db.pool(), NOW)
         .await
         .expect("Fetch failed");
 
@@ -114,3 +117,85 @@ async fn timer_expired_not_in_active_list() {
         "Expired timer should not appear in active list"
     );
 }
+
+#[tokio::test]
+async fn sequence_progress_survives_reload() {
+    let db = setup_test_db().await;
+    let sequence = Sequence::new(
+        "Pomodoro",
+        2,
+        vec![
+            Phase::new("Work", 1500, false),
+            Phase::new("Break", 300, false),
+        ],
+        NOW,
+    );
+
+    let mut saved = Sequence::insert(db.pool(), &sequence)
+        .await
+        .expect("Insert failed");
+    saved.advance(NOW);
+    saved.pause(NOW);
+    Sequence::update_progress(db.pool(), &saved)
+        .await
+        .expect("Update failed");
+
+    // Reloading picks up where the sequence was paused, phases in order
+    let reloaded = Sequence::get_by_id(db.pool(), &saved.id)
+        .await
+        .expect("Fetch failed")
+        .expect("Sequence should exist");
+
+    assert!(reloaded.is_paused());
+    assert_eq!(reloaded.current_step, 1);
+    assert_eq!(
+        reloaded.current_phase().map(|p| p.name.as_str()),
+        Some("Break")
+    );
+}
+
+#[tokio::test]
+async fn sequence_update_replaces_phases() {
+    let db = setup_test_db().await;
+    let sequence = Sequence::new(
+        "Pomodoro",
+        2,
+        vec![
+            Phase::new("Work", 1500, false),
+            Phase::new("Break", 300, false),
+        ],
+        NOW,
+    );
+    let mut saved = Sequence::insert(db.pool(), &sequence)
+        .await
+        .expect("Insert failed");
+    let other = Sequence::insert(db.pool(), &sequence)
+        .await
+        .expect("Insert failed");
+
+    saved.name = "Deep work".to_string();
+    saved.phases = Sequence::new("", 1, vec![Phase::new("Focus", 3000, false)], NOW).phases;
+    Sequence::update(db.pool(), &saved)
+        .await
+        .expect("Update failed");
+
+    let reloaded = Sequence::get_by_id(db.pool(), &saved.id)
+        .await
+        .expect("Fetch failed")
+        .expect("Sequence should exist");
+    assert_eq!(reloaded.name, "Deep work");
+    let phases: Vec<&str> = reloaded.phases.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(phases, ["Focus"]);
+
+    // Both sequences and their phases go at once
+    let deleted = Sequence::delete_many(db.pool(), &[saved.id, other.id])
+        .await
+        .expect("Delete failed");
+    assert_eq!(deleted, 2);
+    assert!(
+        Sequence::get_all_active(db.pool(), NOW)
+            .await
+            .expect("Fetch failed")
+            .is_empty()
+    );
+}
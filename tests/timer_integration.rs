@@ -8,19 +8,22 @@
 //! Coming from TypeScript, I prefer tests that treat modules as black boxes,
 //! focusing on public interfaces rather than implementation details.
 use chronomancer::models::timer::{Timer, TimerType};
+use chronomancer::utils::clock::{Clock, ManualClock};
 use std::str::FromStr;
 
+const NOW: i64 = 1_767_600_000;
+
 // ============================================================================
 // Basic Timer Creation and State
 // ============================================================================
 
 #[test]
 fn timer_creation_basic() {
-    let timer = Timer::new(60, false, &TimerType::Suspend);
+    let timer = Timer::new(60, false, &TimerType::Suspend, NOW);
 
     assert_eq!(timer.description, "System Suspend");
     assert!(!timer.is_recurring);
-    assert!(timer.is_active(), "Newly created timer should be active");
+    assert!(timer.is_active(NOW), "Newly created timer should be active");
 }
 
 #[test]
@@ -49,13 +52,19 @@ fn timer_type_conversions() {
 
 #[test]
 fn timer_expiry_manual() {
-    let mut timer = Timer::new(5, false, &TimerType::UserDefined("Test".into()));
-    assert!(timer.is_active());
+    let clock = ManualClock::new(NOW);
+    let timer = Timer::new(
+        5,
+        false,
+        &TimerType::UserDefined("Test".into()),
+        clock.now(),
+    );
+    assert!(timer.is_active(clock.now()));
 
-    // Force expiry by setting ends_at to the past
-    timer.ends_at = chrono::Utc::now().timestamp() - 1;
+    // Let the clock run past the end time
+    clock.advance(5);
     assert!(
-        !timer.is_active(),
+        !timer.is_active(clock.now()),
         "Timer with past end time should be inactive"
     );
 }